@@ -0,0 +1,49 @@
+mod common;
+
+use common::{assert_program_error, Harness, TICKET_PRICE};
+use event_ticketing::client;
+use event_ticketing::errors::EventTicketingError;
+use solana_sdk::clock::Clock;
+use solana_sdk::signature::Signer;
+
+#[test]
+fn canceled_events_stop_selling() {
+    let mut harness = Harness::new();
+    let event = harness.create_event(1, TICKET_PRICE, 10);
+    let buyer = harness.new_wallet();
+    harness.mint(event, &buyer).unwrap();
+
+    harness.cancel(event).unwrap();
+
+    let state = harness.event(&event);
+    assert!(state.canceled());
+    assert_eq!(
+        state.canceled_at,
+        harness.svm.get_sysvar::<Clock>().unix_timestamp
+    );
+
+    let result = harness.mint(event, &buyer);
+    assert_program_error(result, EventTicketingError::EventCanceled);
+}
+
+#[test]
+fn events_cannot_be_canceled_twice() {
+    let mut harness = Harness::new();
+    let event = harness.create_event(1, TICKET_PRICE, 10);
+
+    harness.cancel(event).unwrap();
+
+    let result = harness.cancel(event);
+    assert_program_error(result, EventTicketingError::EventCanceled);
+}
+
+#[test]
+fn only_the_event_authority_can_cancel() {
+    let mut harness = Harness::new();
+    let event = harness.create_event(1, TICKET_PRICE, 10);
+    let stranger = harness.new_wallet();
+
+    let result = harness.send(client::cancel_event(event, stranger.pubkey()), &[&stranger]);
+    assert_program_error(result, EventTicketingError::UnauthorizedEventAuthority);
+    assert!(!harness.event(&event).canceled());
+}
@@ -0,0 +1,195 @@
+//! Shared LiteSVM setup. Loads the program built by `anchor build` and seeds
+//! the global `Config` directly, since `initialize_config` needs an
+//! upgradeable deployment.
+
+#![allow(dead_code)]
+
+use anchor_lang::{AccountDeserialize, AccountSerialize, Discriminator};
+use event_ticketing::client;
+use event_ticketing::errors::EventTicketingError;
+use event_ticketing::state::{Config, Event, EventRegistry};
+use litesvm::types::TransactionResult;
+use litesvm::LiteSVM;
+use solana_sdk::account::Account;
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+use std::mem::size_of;
+
+const PROGRAM_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../target/deploy/event_ticketing.so"
+);
+
+pub const TICKET_PRICE: u64 = LAMPORTS_PER_SOL / 10;
+
+pub struct Harness {
+    pub svm: LiteSVM,
+    pub organizer: Keypair,
+}
+
+impl Harness {
+    pub fn new() -> Self {
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(event_ticketing::ID, PROGRAM_PATH)
+            .expect("run `anchor build` before the LiteSVM tests");
+
+        let mut data = Vec::with_capacity(Config::SPACE);
+        Config {
+            admin: Pubkey::new_unique(),
+            max_unverified_supply: u32::MAX,
+            organizer_bond_lamports: 0,
+        }
+        .try_serialize(&mut data)
+        .unwrap();
+        svm.set_account(
+            client::derive_config_pda().0,
+            Account {
+                lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+                data,
+                owner: event_ticketing::ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+        let mut harness = Self {
+            svm,
+            organizer: Keypair::new(),
+        };
+        harness.fund(&harness.organizer.pubkey());
+        harness
+    }
+
+    pub fn fund(&mut self, wallet: &Pubkey) {
+        self.svm.airdrop(wallet, 100 * LAMPORTS_PER_SOL).unwrap();
+    }
+
+    pub fn new_wallet(&mut self) -> Keypair {
+        let wallet = Keypair::new();
+        self.fund(&wallet.pubkey());
+        wallet
+    }
+
+    /// Sends `ix` paid for by the first signer. The blockhash is expired
+    /// afterwards so identical retries are not rejected as duplicates.
+    pub fn send(&mut self, ix: Instruction, signers: &[&Keypair]) -> TransactionResult {
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&signers[0].pubkey()),
+            signers,
+            self.svm.latest_blockhash(),
+        );
+        let result = self.svm.send_transaction(tx);
+        self.svm.expire_blockhash();
+        result
+    }
+
+    pub fn account<T: AccountDeserialize>(&self, address: &Pubkey) -> T {
+        let account = self.svm.get_account(address).expect("account not found");
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub fn balance(&self, address: &Pubkey) -> u64 {
+        self.svm.get_balance(address).unwrap_or_default()
+    }
+
+    pub fn registry_page(&self) -> u64 {
+        let registry = client::derive_registry_pda().0;
+        match self.svm.get_account(&registry) {
+            Some(account) => EventRegistry::try_deserialize(&mut account.data.as_slice())
+                .unwrap()
+                .current_page(),
+            None => 0,
+        }
+    }
+
+    pub fn create_event_as(
+        &mut self,
+        organizer: &Keypair,
+        event_id: u32,
+        price: u64,
+        supply: u32,
+    ) -> TransactionResult {
+        let ix = client::initialize_event(
+            organizer.pubkey(),
+            event_id,
+            price,
+            supply,
+            format!("Event {event_id}"),
+            "2030-01-01".to_string(),
+            true,
+            self.registry_page(),
+            false,
+        );
+        self.send(ix, &[organizer])
+    }
+
+    /// Creates an event owned by the harness organizer and returns its address.
+    pub fn create_event(&mut self, event_id: u32, price: u64, supply: u32) -> Pubkey {
+        let organizer = self.organizer.insecure_clone();
+        self.create_event_as(&organizer, event_id, price, supply)
+            .unwrap();
+        client::derive_event_pda(&organizer.pubkey(), event_id).0
+    }
+
+    /// Reads a zero-copy `Event`, which has no Borsh form to deserialize.
+    pub fn event(&self, event: &Pubkey) -> Event {
+        let account = self.svm.get_account(event).expect("account not found");
+        assert_eq!(&account.data[..8], Event::DISCRIMINATOR);
+        bytemuck::pod_read_unaligned(&account.data[8..8 + size_of::<Event>()])
+    }
+
+    /// Buys the next ticket of `event`, i.e. ticket id `event.next_ticket_id`.
+    pub fn mint(&mut self, event: Pubkey, buyer: &Keypair) -> TransactionResult {
+        let ticket_id = self.event(&event).next_ticket_id;
+        self.send(
+            client::mint_ticket(event, ticket_id, buyer.pubkey()),
+            &[buyer],
+        )
+    }
+
+    pub fn cancel(&mut self, event: Pubkey) -> TransactionResult {
+        let organizer = self.organizer.insecure_clone();
+        self.send(
+            client::cancel_event(event, organizer.pubkey()),
+            &[&organizer],
+        )
+    }
+
+    /// Ends `event` a minute ago with no payout timelock and finalizes it,
+    /// unlocking its proceeds.
+    pub fn finalize(&mut self, event: Pubkey) -> TransactionResult {
+        let organizer = self.organizer.insecure_clone();
+        let now = self.svm.get_sysvar::<Clock>().unix_timestamp;
+        self.send(
+            client::set_payout_schedule(event, organizer.pubkey(), now - 60, 0),
+            &[&organizer],
+        )?;
+        self.send(
+            client::finalize_event(event, organizer.pubkey()),
+            &[&organizer],
+        )
+    }
+
+    pub fn refund(&mut self, event: Pubkey, ticket_id: u32, owner: &Pubkey) -> TransactionResult {
+        let organizer = self.organizer.insecure_clone();
+        self.send(
+            client::refund(event, ticket_id, *owner, organizer.pubkey()),
+            &[&organizer],
+        )
+    }
+}
+
+/// Asserts the transaction failed with the given program error.
+pub fn assert_program_error(result: TransactionResult, error: EventTicketingError) {
+    let err = result.expect_err("transaction should have failed").err;
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::Custom(error.into()))
+    );
+}
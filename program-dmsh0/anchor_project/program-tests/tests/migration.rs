@@ -0,0 +1,126 @@
+mod common;
+
+use anchor_lang::AnchorSerialize;
+use common::{Harness, TICKET_PRICE};
+use event_ticketing::client;
+use event_ticketing::constants::{BORSH_EVENT_DISCRIMINATOR, EVENT_VERSION};
+use event_ticketing::state::{Event, EventMetadata};
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+/// Size every event had before names and dates moved to `EventMetadata`.
+const LEGACY_EVENT_SPACE: usize = 149;
+
+/// Writes an event on the original layout, as `initialize_event` used to.
+fn seed_legacy_event(harness: &mut Harness, event_id: u32, sold: u32) -> Pubkey {
+    let organizer = harness.organizer.pubkey();
+    let event = client::derive_event_pda(&organizer, event_id).0;
+
+    let mut data = BORSH_EVENT_DISCRIMINATOR.to_vec();
+    (
+        organizer,
+        TICKET_PRICE,
+        10u32,
+        sold,
+        false,
+        event_id,
+        "Legacy Show".to_string(),
+        "2024-06-01".to_string(),
+    )
+        .serialize(&mut data)
+        .unwrap();
+    data.resize(LEGACY_EVENT_SPACE, 0);
+    set_event_data(harness, event, data);
+    event
+}
+
+/// Writes a Borsh event from before `version` was tracked, with only its
+/// leading fields set. `migrate_event` grows the account and reads the rest
+/// as zeroes.
+fn seed_borsh_event(harness: &mut Harness, event_id: u32, sold: u32) -> Pubkey {
+    let organizer = harness.organizer.pubkey();
+    let event = client::derive_event_pda(&organizer, event_id).0;
+
+    let mut data = BORSH_EVENT_DISCRIMINATOR.to_vec();
+    (organizer, TICKET_PRICE, 10u32, sold, true, event_id)
+        .serialize(&mut data)
+        .unwrap();
+    set_event_data(harness, event, data);
+    event
+}
+
+fn set_event_data(harness: &mut Harness, event: Pubkey, data: Vec<u8>) {
+    harness
+        .svm
+        .set_account(
+            event,
+            Account {
+                lamports: harness.svm.minimum_balance_for_rent_exemption(data.len()),
+                data,
+                owner: event_ticketing::ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn legacy_events_move_their_strings_into_metadata() {
+    let mut harness = Harness::new();
+    let event = seed_legacy_event(&mut harness, 3, 4);
+    let payer = harness.new_wallet();
+
+    harness
+        .send(
+            client::migrate_legacy_event(event, payer.pubkey()),
+            &[&payer],
+        )
+        .unwrap();
+
+    let migrated = harness.event(&event);
+    assert_eq!(migrated.version, EVENT_VERSION);
+    assert_eq!(migrated.event_authority, harness.organizer.pubkey());
+    assert_eq!(migrated.price, TICKET_PRICE);
+    assert_eq!((migrated.supply, migrated.sold), (10, 4));
+    assert_eq!(migrated.next_ticket_id, 4);
+
+    let metadata: EventMetadata = harness.account(&client::derive_event_metadata_pda(&event).0);
+    assert_eq!(metadata.name, "Legacy Show");
+    assert_eq!(metadata.date, "2024-06-01");
+
+    // The event and its metadata are now on the current layout.
+    let result = harness.send(
+        client::migrate_legacy_event(event, payer.pubkey()),
+        &[&payer],
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn borsh_events_become_zero_copy() {
+    let mut harness = Harness::new();
+    let event = seed_borsh_event(&mut harness, 5, 4);
+    let payer = harness.new_wallet();
+
+    harness
+        .send(client::migrate_event(event, payer.pubkey()), &[&payer])
+        .unwrap();
+
+    let account = harness.svm.get_account(&event).unwrap();
+    assert!(account.data.len() >= Event::SPACE);
+
+    let migrated = harness.event(&event);
+    assert_eq!(migrated.version, EVENT_VERSION);
+    assert_eq!(migrated.event_authority, harness.organizer.pubkey());
+    assert_eq!(migrated.price, TICKET_PRICE);
+    assert_eq!((migrated.supply, migrated.sold), (10, 4));
+    assert!(migrated.canceled());
+    assert_eq!(migrated.next_ticket_id, 4);
+    assert_eq!(migrated.max_total_supply, 10);
+    assert_eq!(migrated.pending_authority(), None);
+
+    let result = harness.send(client::migrate_event(event, payer.pubkey()), &[&payer]);
+    assert!(result.is_err());
+}
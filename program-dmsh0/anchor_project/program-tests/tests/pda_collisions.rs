@@ -0,0 +1,88 @@
+mod common;
+
+use common::{Harness, TICKET_PRICE};
+use event_ticketing::client;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::signature::Signer;
+use solana_sdk::transaction::TransactionError;
+
+#[test]
+fn organizers_can_reuse_each_others_event_ids() {
+    let mut harness = Harness::new();
+    let other_organizer = harness.new_wallet();
+
+    let first = harness.create_event(7, TICKET_PRICE, 5);
+    harness
+        .create_event_as(&other_organizer, 7, TICKET_PRICE, 5)
+        .unwrap();
+    let second = client::derive_event_pda(&other_organizer.pubkey(), 7).0;
+
+    assert_ne!(first, second);
+    assert_eq!(
+        harness.event(&second).event_authority,
+        other_organizer.pubkey()
+    );
+    assert_ne!(
+        client::derive_vault_pda(&first).0,
+        client::derive_vault_pda(&second).0
+    );
+    assert_ne!(
+        client::derive_ticket_pda(&first, 0).0,
+        client::derive_ticket_pda(&second, 0).0
+    );
+}
+
+#[test]
+fn an_organizer_cannot_reuse_an_event_id() {
+    let mut harness = Harness::new();
+    let organizer = harness.organizer.insecure_clone();
+    harness.create_event(7, TICKET_PRICE, 5);
+
+    let err = harness
+        .create_event_as(&organizer, 7, 2 * TICKET_PRICE, 5)
+        .expect_err("event PDA is already in use")
+        .err;
+    assert!(matches!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::Custom(_))
+    ));
+    assert_eq!(
+        harness
+            .event(&client::derive_event_pda(&organizer.pubkey(), 7).0)
+            .price,
+        TICKET_PRICE
+    );
+}
+
+#[test]
+fn tickets_cannot_be_minted_into_another_ticket_id() {
+    let mut harness = Harness::new();
+    let event = harness.create_event(1, TICKET_PRICE, 5);
+    let buyer = harness.new_wallet();
+    harness.mint(event, &buyer).unwrap();
+
+    // Ticket 0 already exists; the next sale must use ticket id 1.
+    let result = harness.send(client::mint_ticket(event, 0, buyer.pubkey()), &[&buyer]);
+    assert!(result.is_err());
+    assert_eq!(harness.event(&event).sold, 1);
+    assert_eq!(harness.event(&event).next_ticket_id, 1);
+}
+
+#[test]
+fn a_prefunded_ticket_pda_does_not_block_batch_minting() {
+    let mut harness = Harness::new();
+    let event = harness.create_event(1, TICKET_PRICE, 5);
+    let buyer = harness.new_wallet();
+
+    // Anyone can send lamports to the next ticket's address before the sale.
+    let ticket = client::derive_ticket_pda(&event, 0).0;
+    harness.svm.airdrop(&ticket, 1_000).unwrap();
+
+    harness
+        .send(client::mint_tickets(event, 0, 2, buyer.pubkey()), &[&buyer])
+        .unwrap();
+
+    let minted: event_ticketing::state::Ticket = harness.account(&ticket);
+    assert_eq!(minted.owner, buyer.pubkey());
+    assert_eq!(harness.event(&event).sold, 2);
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use common::{assert_program_error, Harness, TICKET_PRICE};
+use event_ticketing::client;
+use event_ticketing::errors::EventTicketingError;
+use event_ticketing::state::{RefundPool, Ticket};
+use solana_sdk::signature::Signer;
+
+#[test]
+fn refunding_every_ticket_leaves_only_the_rent_reserve() {
+    let mut harness = Harness::new();
+    let event = harness.create_event(1, TICKET_PRICE, 5);
+    let vault = client::derive_vault_pda(&event).0;
+    let reserve = harness.balance(&vault);
+
+    let buyers: Vec<_> = (0..5).map(|_| harness.new_wallet()).collect();
+    for buyer in &buyers {
+        harness.mint(event, buyer).unwrap();
+    }
+    harness.cancel(event).unwrap();
+
+    for (ticket_id, buyer) in buyers.iter().enumerate() {
+        let before = harness.balance(&buyer.pubkey());
+        harness
+            .refund(event, ticket_id as u32, &buyer.pubkey())
+            .unwrap();
+        assert_eq!(harness.balance(&buyer.pubkey()), before + TICKET_PRICE);
+
+        let ticket: Ticket =
+            harness.account(&client::derive_ticket_pda(&event, ticket_id as u32).0);
+        assert!(ticket.refunded);
+    }
+
+    assert_eq!(harness.balance(&vault), reserve);
+    assert_eq!(harness.event(&event).refunded_count, 5);
+}
+
+#[test]
+fn canceling_moves_vault_funds_into_the_refund_pool() {
+    let mut harness = Harness::new();
+    let event = harness.create_event(1, TICKET_PRICE, 5);
+    let vault = client::derive_vault_pda(&event).0;
+    let reserve = harness.balance(&vault);
+
+    for _ in 0..3 {
+        let buyer = harness.new_wallet();
+        harness.mint(event, &buyer).unwrap();
+    }
+    harness.cancel(event).unwrap();
+
+    assert_eq!(harness.balance(&vault), reserve);
+    let pool: RefundPool = harness.account(&client::derive_refund_pool_pda(&event).0);
+    assert_eq!(pool.event, event);
+    assert_eq!(pool.deposited, 3 * TICKET_PRICE);
+    assert_eq!(pool.claimed, 0);
+}
+
+#[test]
+fn tickets_cannot_be_refunded_twice() {
+    let mut harness = Harness::new();
+    let event = harness.create_event(1, TICKET_PRICE, 5);
+    let buyer = harness.new_wallet();
+    harness.mint(event, &buyer).unwrap();
+    harness.cancel(event).unwrap();
+
+    harness.refund(event, 0, &buyer.pubkey()).unwrap();
+
+    let result = harness.refund(event, 0, &buyer.pubkey());
+    assert_program_error(result, EventTicketingError::AlreadyRefunded);
+}
+
+#[test]
+fn withdrawals_cannot_touch_the_rent_reserve() {
+    let mut harness = Harness::new();
+    let event = harness.create_event(1, TICKET_PRICE, 5);
+    let buyer = harness.new_wallet();
+    harness.mint(event, &buyer).unwrap();
+    harness.finalize(event).unwrap();
+
+    let organizer = harness.organizer.insecure_clone();
+    let result = harness.send(
+        client::withdraw_proceeds(
+            event,
+            organizer.pubkey(),
+            organizer.pubkey(),
+            TICKET_PRICE + 1,
+        ),
+        &[&organizer],
+    );
+    assert_program_error(result, EventTicketingError::InsufficientVaultFunds);
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use common::{assert_program_error, Harness, TICKET_PRICE};
+use event_ticketing::client;
+use event_ticketing::errors::EventTicketingError;
+use event_ticketing::state::{Receipt, Ticket};
+use solana_sdk::signature::Signer;
+
+#[test]
+fn sells_every_ticket_then_rejects_the_next_buyer() {
+    let mut harness = Harness::new();
+    let event = harness.create_event(1, TICKET_PRICE, 3);
+    let vault = client::derive_vault_pda(&event).0;
+    let reserve = harness.balance(&vault);
+
+    for _ in 0..3 {
+        let buyer = harness.new_wallet();
+        harness.mint(event, &buyer).unwrap();
+    }
+
+    let state = harness.event(&event);
+    assert_eq!(state.sold, 3);
+    assert_eq!(state.available(), 0);
+    assert_eq!(harness.balance(&vault), reserve + 3 * TICKET_PRICE);
+    assert_eq!(state.gross_sales, 3 * TICKET_PRICE);
+    assert_eq!(state.organizer_net, 3 * TICKET_PRICE);
+
+    let late_buyer = harness.new_wallet();
+    let result = harness.mint(event, &late_buyer);
+    assert_program_error(result, EventTicketingError::EventSoldOut);
+}
+
+#[test]
+fn records_the_buyer_and_price_on_each_ticket() {
+    let mut harness = Harness::new();
+    let event = harness.create_event(1, TICKET_PRICE, 2);
+    let buyer = harness.new_wallet();
+
+    harness.mint(event, &buyer).unwrap();
+
+    let ticket: Ticket = harness.account(&client::derive_ticket_pda(&event, 0).0);
+    assert_eq!(ticket.owner, buyer.pubkey());
+    assert_eq!(ticket.event, event);
+    assert_eq!(ticket.purchase_price, TICKET_PRICE);
+}
+
+#[test]
+fn each_sale_leaves_a_receipt_the_payer_can_close() {
+    let mut harness = Harness::new();
+    let event = harness.create_event(1, TICKET_PRICE, 2);
+    let buyer = harness.new_wallet();
+
+    harness.mint(event, &buyer).unwrap();
+
+    let ticket = client::derive_ticket_pda(&event, 0).0;
+    let receipt_address = client::derive_receipt_pda(&ticket).0;
+    let receipt: Receipt = harness.account(&receipt_address);
+    assert_eq!(receipt.ticket, ticket);
+    assert_eq!(receipt.payer, buyer.pubkey());
+    assert_eq!(receipt.amount, TICKET_PRICE);
+    assert_eq!(receipt.fees.referral_fee, 0);
+    assert_eq!(receipt.fees.protocol_fee, 0);
+    assert_eq!(receipt.fees.organizer_net, TICKET_PRICE);
+
+    let stranger = harness.new_wallet();
+    let result = harness.send(
+        client::close_receipt(receipt_address, stranger.pubkey()),
+        &[&stranger],
+    );
+    assert_program_error(result, EventTicketingError::UnauthorizedReceiptClose);
+
+    harness
+        .send(
+            client::close_receipt(receipt_address, buyer.pubkey()),
+            &[&buyer],
+        )
+        .unwrap();
+    assert_eq!(harness.balance(&receipt_address), 0);
+}
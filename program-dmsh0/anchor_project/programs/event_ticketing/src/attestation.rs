@@ -0,0 +1,56 @@
+//! Identity and age gating. An organizer can point `Event::attestor` at a
+//! KYC or age-verification provider; every ticket sold through `mint_ticket`
+//! and its variants then needs an `Attestation` that provider issued for the
+//! ticket's owner, passed as the mint's `attestation` account.
+//!
+//! Attestations are PDAs of this program at
+//! `[ATTESTATION_SEED, attestor, subject]`, created with `issue_attestation`.
+//! The attestor signs it, so the attestor can be a plain key or a PDA of the
+//! provider's own program signing through CPI.
+//!
+//! Sale paths that cannot check an attestation per owner are closed for
+//! gated events.
+
+use crate::errors::EventTicketingError;
+use crate::state::{Attestation, Event};
+use anchor_lang::prelude::*;
+
+/// Rejects attestation-gated events on sale paths that do not check it.
+pub fn require_no_attestor(event: &Event) -> Result<()> {
+    require!(
+        event.attestor().is_none(),
+        EventTicketingError::AttestationNotSupported
+    );
+    Ok(())
+}
+
+/// Checks `attestation` proves `owner` passed the event attestor's checks.
+/// Does nothing for events without an attestor.
+pub fn verify_attestation(
+    event: &Event,
+    attestation: Option<&Attestation>,
+    owner: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    let Some(attestor) = event.attestor() else {
+        return Ok(());
+    };
+    let attestation = attestation.ok_or(EventTicketingError::AttestationMissing)?;
+
+    require_keys_eq!(
+        attestation.attestor,
+        attestor,
+        EventTicketingError::InvalidAttestation
+    );
+    require_keys_eq!(
+        attestation.subject,
+        *owner,
+        EventTicketingError::InvalidAttestation
+    );
+    require!(
+        attestation.is_valid_at(now),
+        EventTicketingError::AttestationExpired
+    );
+
+    Ok(())
+}
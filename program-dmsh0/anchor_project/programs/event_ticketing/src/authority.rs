@@ -0,0 +1,45 @@
+use crate::errors::EventTicketingError;
+use crate::state::{Event, EventAuthorityConfig};
+use anchor_lang::prelude::*;
+
+/// Checks that an authority-gated action on `event` is approved.
+///
+/// Single-authority events only need `signer` to be the event authority.
+/// Multisig events need `threshold` distinct configured signers, counting
+/// `signer` plus any signing accounts passed in `remaining_accounts`.
+pub fn require_event_authority(
+    event: &Event,
+    signer: &Pubkey,
+    authority_config: Option<&EventAuthorityConfig>,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    if !event.multisig_enabled() {
+        require_keys_eq!(
+            *signer,
+            event.event_authority,
+            EventTicketingError::UnauthorizedEventAuthority
+        );
+        return Ok(());
+    }
+
+    let authority_config = authority_config.ok_or(EventTicketingError::MissingAuthorityConfig)?;
+
+    let mut approvals: Vec<Pubkey> = Vec::with_capacity(authority_config.signers.len());
+    let extra_signers = remaining_accounts
+        .iter()
+        .filter(|account| account.is_signer)
+        .map(|account| account.key);
+
+    for key in std::iter::once(signer).chain(extra_signers) {
+        if authority_config.signers.contains(key) && !approvals.contains(key) {
+            approvals.push(*key);
+        }
+    }
+
+    require!(
+        approvals.len() >= authority_config.threshold as usize,
+        EventTicketingError::InsufficientSigners
+    );
+
+    Ok(())
+}
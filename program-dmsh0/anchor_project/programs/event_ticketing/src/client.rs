@@ -0,0 +1,395 @@
+//! Instruction builders for off-chain Rust clients, enabled with the `client`
+//! feature. Each builder derives the PDAs the instruction needs and fills in
+//! the Anchor discriminator, so callers only supply the wallets and arguments.
+//! Builders target SOL events and leave out the optional accounts they do not
+//! take (referrers, loyalty, seat maps, multisig configs, SPL token accounts);
+//! build those instructions from `crate::accounts` and `crate::instruction`.
+
+use crate::{accounts, instruction};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{system_program, InstructionData};
+
+pub use crate::pda::{
+    derive_buyer_record_pda, derive_config_pda, derive_event_cpi_authority_pda,
+    derive_event_metadata_pda, derive_event_pda, derive_organizer_pda, derive_pending_transfer_pda,
+    derive_receipt_pda, derive_refund_pool_pda, derive_registry_page_pda, derive_registry_pda,
+    derive_ticket_pda, derive_vault_pda, derive_wallet_ticket_pda,
+};
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// `initialize_event` for a general admission event. `registry_page` is the
+/// registry's `current_page()`; pass `organizer_registered` when the
+/// authority has an `OrganizerRegistry`, which large events require.
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_event(
+    event_authority: Pubkey,
+    event_id: u32,
+    price: u64,
+    supply: u32,
+    name: String,
+    date: String,
+    transferable: bool,
+    registry_page: u64,
+    organizer_registered: bool,
+) -> Instruction {
+    let event = derive_event_pda(&event_authority, event_id).0;
+
+    build(
+        accounts::InitializeEvent {
+            event,
+            metadata: derive_event_metadata_pda(&event).0,
+            vault: derive_vault_pda(&event).0,
+            config: derive_config_pda().0,
+            registry: derive_registry_pda().0,
+            registry_page: derive_registry_page_pda(registry_page).0,
+            organizer_registry: organizer_registered
+                .then(|| derive_organizer_pda(&event_authority).0),
+            seat_map: None,
+            currency_mint: None,
+            event_authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeEvent {
+            event_id,
+            price,
+            supply,
+            name,
+            date,
+            transferable,
+            seat_layout: None,
+            one_per_wallet: false,
+            max_total_supply: None,
+        },
+    )
+}
+
+/// `mint_ticket` for the next ticket; `ticket_id` is the event's `next_ticket_id`.
+pub fn mint_ticket(event: Pubkey, ticket_id: u32, buyer: Pubkey) -> Instruction {
+    mint_ticket_sponsored(event, ticket_id, buyer, buyer)
+}
+
+/// `mint_ticket` with the rent of the new accounts paid by `payer`.
+pub fn mint_ticket_sponsored(
+    event: Pubkey,
+    ticket_id: u32,
+    buyer: Pubkey,
+    payer: Pubkey,
+) -> Instruction {
+    let ticket = derive_ticket_pda(&event, ticket_id).0;
+
+    build(
+        accounts::MintTicket {
+            event,
+            ticket,
+            receipt: derive_receipt_pda(&ticket).0,
+            vault: derive_vault_pda(&event).0,
+            buyer_record: derive_buyer_record_pda(&event, &buyer).0,
+            referrer: None,
+            referrer_wallet: None,
+            price_update: None,
+            loyalty: None,
+            buyer_token_account: None,
+            vault_token_account: None,
+            token_program: None,
+            instructions_sysvar: None,
+            attestation: None,
+            gate_token_account: None,
+            gate_metadata: None,
+            hook_program: None,
+            buyer,
+            payer,
+            system_program: system_program::ID,
+            sales_stats: None,
+            transaction_log: None,
+            event_cpi_authority: derive_event_cpi_authority_pda().0,
+            program: crate::ID,
+        },
+        instruction::MintTicket {},
+    )
+}
+
+/// `mint_tickets` of `count` tickets starting at `first_ticket_id`, the
+/// event's `next_ticket_id`.
+pub fn mint_tickets(event: Pubkey, first_ticket_id: u32, count: u8, buyer: Pubkey) -> Instruction {
+    let mut ix = build(
+        accounts::MintTickets {
+            event,
+            vault: derive_vault_pda(&event).0,
+            buyer_record: derive_buyer_record_pda(&event, &buyer).0,
+            price_update: None,
+            buyer,
+            system_program: system_program::ID,
+            sales_stats: None,
+            transaction_log: None,
+            event_cpi_authority: derive_event_cpi_authority_pda().0,
+            program: crate::ID,
+        },
+        instruction::MintTickets { count },
+    );
+    ix.accounts.extend((0..count as u32).map(|offset| {
+        AccountMeta::new(derive_ticket_pda(&event, first_ticket_id + offset).0, false)
+    }));
+    ix
+}
+
+/// `close_receipt` of a purchase receipt by the wallet that paid.
+pub fn close_receipt(receipt: Pubkey, payer: Pubkey) -> Instruction {
+    build(
+        accounts::CloseReceipt { receipt, payer },
+        instruction::CloseReceipt {},
+    )
+}
+
+/// `approve_transfer` of a ticket from `owner` to `recipient`.
+pub fn approve_transfer(
+    event: Pubkey,
+    ticket_id: u32,
+    owner: Pubkey,
+    recipient: Pubkey,
+) -> Instruction {
+    let ticket = derive_ticket_pda(&event, ticket_id).0;
+
+    build(
+        accounts::ApproveTransfer {
+            event,
+            ticket,
+            pending_transfer: derive_pending_transfer_pda(&ticket).0,
+            recipient,
+            owner,
+            system_program: system_program::ID,
+        },
+        instruction::ApproveTransfer {},
+    )
+}
+
+/// `accept_transfer` by `recipient` of a ticket offered by `sender`.
+pub fn accept_transfer(
+    event: Pubkey,
+    ticket_id: u32,
+    sender: Pubkey,
+    recipient: Pubkey,
+) -> Instruction {
+    let ticket = derive_ticket_pda(&event, ticket_id).0;
+
+    build(
+        accounts::AcceptTransfer {
+            event,
+            ticket,
+            pending_transfer: derive_pending_transfer_pda(&ticket).0,
+            vault: derive_vault_pda(&event).0,
+            sender,
+            recipient,
+            transaction_log: None,
+            event_cpi_authority: derive_event_cpi_authority_pda().0,
+            program: crate::ID,
+        },
+        instruction::AcceptTransfer {},
+    )
+}
+
+/// `check_in` by the event authority, or by a door worker whose `Staff`
+/// record is passed as `staff`, presenting the scanned `scan_nonce`.
+pub fn check_in(
+    event: Pubkey,
+    ticket_id: u32,
+    operator: Pubkey,
+    staff: Option<Pubkey>,
+    scan_nonce: u64,
+) -> Instruction {
+    build(
+        accounts::CheckIn {
+            event,
+            ticket: derive_ticket_pda(&event, ticket_id).0,
+            staff,
+            co_organizer: None,
+            poap_mint: None,
+            poap_authority: None,
+            attendee_poap_account: None,
+            token_program: None,
+            loyalty: None,
+            vault: None,
+            deposit_recipient: None,
+            system_program: None,
+            transaction_log: None,
+            operator,
+        },
+        instruction::CheckIn { scan_nonce },
+    )
+}
+
+/// `cancel_event` by a single-signer event authority.
+pub fn cancel_event(event: Pubkey, event_authority: Pubkey) -> Instruction {
+    build(
+        accounts::CancelEvent {
+            event,
+            authority_config: None,
+            refund_pool: derive_refund_pool_pda(&event).0,
+            vault: derive_vault_pda(&event).0,
+            event_authority,
+            system_program: system_program::ID,
+            transaction_log: None,
+            event_cpi_authority: derive_event_cpi_authority_pda().0,
+            program: crate::ID,
+        },
+        instruction::CancelEvent {},
+    )
+}
+
+/// `refund` of an unseated ticket of a canceled event to its owner, paid
+/// from the event's refund pool.
+pub fn refund(
+    event: Pubkey,
+    ticket_id: u32,
+    ticket_owner: Pubkey,
+    event_authority: Pubkey,
+) -> Instruction {
+    build(
+        accounts::Refund {
+            event,
+            authority_config: None,
+            co_organizer: None,
+            ticket: derive_ticket_pda(&event, ticket_id).0,
+            vault: derive_vault_pda(&event).0,
+            refund_pool: Some(derive_refund_pool_pda(&event).0),
+            seat_map: None,
+            seat_claim: None,
+            ticket_owner,
+            vault_token_account: None,
+            owner_token_account: None,
+            token_program: None,
+            event_authority,
+            system_program: system_program::ID,
+            sales_stats: None,
+            transaction_log: None,
+            event_cpi_authority: derive_event_cpi_authority_pda().0,
+            program: crate::ID,
+        },
+        instruction::Refund {},
+    )
+}
+
+/// `refund_batch` of unseated tickets, given as `(ticket_id, refund recipient)`
+/// pairs, by a single-signer event authority of a canceled event.
+pub fn refund_batch(
+    event: Pubkey,
+    tickets: &[(u32, Pubkey)],
+    event_authority: Pubkey,
+) -> Instruction {
+    let mut ix = build(
+        accounts::RefundBatch {
+            event,
+            authority_config: None,
+            vault: derive_vault_pda(&event).0,
+            refund_pool: Some(derive_refund_pool_pda(&event).0),
+            event_authority,
+            system_program: system_program::ID,
+            sales_stats: None,
+            transaction_log: None,
+            event_cpi_authority: derive_event_cpi_authority_pda().0,
+            program: crate::ID,
+        },
+        instruction::RefundBatch {},
+    );
+    for (ticket_id, recipient) in tickets {
+        ix.accounts.push(AccountMeta::new(
+            derive_ticket_pda(&event, *ticket_id).0,
+            false,
+        ));
+        ix.accounts.push(AccountMeta::new(*recipient, false));
+    }
+    ix
+}
+
+/// `migrate_legacy_event`, with `payer` covering the rent of the grown event
+/// and its new metadata account.
+pub fn migrate_legacy_event(event: Pubkey, payer: Pubkey) -> Instruction {
+    build(
+        accounts::MigrateLegacyEvent {
+            event,
+            metadata: derive_event_metadata_pda(&event).0,
+            payer,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateLegacyEvent {},
+    )
+}
+
+/// `migrate_event`, with `payer` covering the rent of the grown event.
+pub fn migrate_event(event: Pubkey, payer: Pubkey) -> Instruction {
+    build(
+        accounts::MigrateAccount {
+            account: event,
+            payer,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateEvent {},
+    )
+}
+
+/// `set_payout_schedule` by a single-signer event authority.
+pub fn set_payout_schedule(
+    event: Pubkey,
+    event_authority: Pubkey,
+    end_time: i64,
+    payout_timelock_secs: i64,
+) -> Instruction {
+    build(
+        accounts::SetPayoutSchedule {
+            event,
+            event_authority,
+        },
+        instruction::SetPayoutSchedule {
+            end_time,
+            payout_timelock_secs,
+        },
+    )
+}
+
+/// `finalize_event` by a single-signer event authority of an event without
+/// an organizer bond.
+pub fn finalize_event(event: Pubkey, event_authority: Pubkey) -> Instruction {
+    build(
+        accounts::FinalizeEvent {
+            event,
+            authority_config: None,
+            vault: derive_vault_pda(&event).0,
+            organizer_registry: None,
+            event_authority,
+        },
+        instruction::FinalizeEvent {},
+    )
+}
+
+/// `withdraw_proceeds` of `amount` lamports to `destination`.
+pub fn withdraw_proceeds(
+    event: Pubkey,
+    event_authority: Pubkey,
+    destination: Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        accounts::WithdrawProceeds {
+            event,
+            authority_config: None,
+            co_organizer: None,
+            vault: derive_vault_pda(&event).0,
+            destination,
+            vault_token_account: None,
+            destination_token_account: None,
+            token_program: None,
+            payout_config: None,
+            transaction_log: None,
+            event_authority,
+            system_program: system_program::ID,
+        },
+        instruction::WithdrawProceeds { amount },
+    )
+}
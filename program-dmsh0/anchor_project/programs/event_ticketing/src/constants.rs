@@ -1,6 +1,94 @@
 pub const EVENT_SEED: &[u8] = b"event";
+pub const EVENT_METADATA_SEED: &[u8] = b"event_metadata";
 pub const TICKET_SEED: &[u8] = b"ticket";
 pub const VAULT_SEED: &[u8] = b"vault";
+pub const REFUND_POOL_SEED: &[u8] = b"refund_pool";
 pub const ORGANIZER_SEED: &[u8] = b"organizer";
+pub const CONFIG_SEED: &[u8] = b"config";
+pub const AUTHORITY_CONFIG_SEED: &[u8] = b"authority_config";
+pub const STAFF_SEED: &[u8] = b"staff";
+pub const CO_ORGANIZER_SEED: &[u8] = b"co_organizer";
+pub const LISTING_SEED: &[u8] = b"listing";
+pub const PENDING_TRANSFER_SEED: &[u8] = b"pending_transfer";
+pub const OPERATOR_APPROVAL_SEED: &[u8] = b"operator_approval";
+pub const BUYER_RECORD_SEED: &[u8] = b"buyer_record";
+pub const PROMO_SEED: &[u8] = b"promo";
+pub const REFERRER_SEED: &[u8] = b"referrer";
+pub const WAITLIST_SEED: &[u8] = b"waitlist";
+pub const RESERVATION_SEED: &[u8] = b"reservation";
+pub const AUCTION_SEED: &[u8] = b"auction";
+pub const BID_SEED: &[u8] = b"bid";
+pub const RAFFLE_SEED: &[u8] = b"raffle";
+pub const RAFFLE_ENTRY_SEED: &[u8] = b"raffle_entry";
+pub const SEAT_SEED: &[u8] = b"seat";
+pub const SERIES_SEED: &[u8] = b"series";
+pub const PASS_SEED: &[u8] = b"pass";
+pub const PASS_CHECK_IN_SEED: &[u8] = b"pass_check_in";
+pub const SESSION_SEED: &[u8] = b"session";
+pub const ATTESTATION_SEED: &[u8] = b"attestation";
+pub const SESSION_CHECK_IN_SEED: &[u8] = b"session_check_in";
+pub const TIER_SEED: &[u8] = b"tier";
+pub const POAP_SEED: &[u8] = b"poap";
+pub const LOYALTY_SEED: &[u8] = b"loyalty";
+pub const REGISTRY_SEED: &[u8] = b"registry";
+pub const REGISTRY_PAGE_SEED: &[u8] = b"registry_page";
+pub const BOND_SEED: &[u8] = b"bond";
+pub const PAYOUT_CONFIG_SEED: &[u8] = b"payout_config";
+pub const RECEIPT_SEED: &[u8] = b"receipt";
+pub const TIER_CURRENCY_SEED: &[u8] = b"tier_currency";
+pub const SALES_STATS_SEED: &[u8] = b"sales_stats";
+pub const PURCHASE_QUEUE_SEED: &[u8] = b"purchase_queue";
+pub const PURCHASE_COMMITMENT_SEED: &[u8] = b"purchase_commitment";
+pub const TRANSACTION_LOG_SEED: &[u8] = b"transaction_log";
+/// Seed of the PDA signing event self-CPIs; must match Anchor's `emit_cpi!`.
+pub const EVENT_CPI_AUTHORITY_SEED: &[u8] = b"__event_authority";
 pub const MAX_NAME_LEN: usize = 50;
 pub const MAX_DATE_LEN: usize = 30;
+/// Limits for `update_event`, which can grow the metadata account past the
+/// `MAX_NAME_LEN` and `MAX_DATE_LEN` allocated at creation.
+pub const MAX_UPDATED_NAME_LEN: usize = 200;
+pub const MAX_UPDATED_DATE_LEN: usize = 100;
+pub const MAX_MULTISIG_SIGNERS: usize = 10;
+pub const SECONDS_PER_DAY: i64 = 86_400;
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const MAX_TICKETS_PER_MINT: usize = 10;
+pub const MAX_PROMO_CODE_LEN: usize = 32;
+pub const RESERVATION_SLOTS: u64 = 750;
+pub const MAX_SEATS: usize = 8192;
+pub const LOYALTY_POINTS_PER_MINT: u64 = 10;
+pub const LOYALTY_POINTS_PER_CHECK_IN: u64 = 5;
+pub const LAMPORTS_PER_LOYALTY_POINT: u64 = 100_000;
+pub const DEFAULT_PAYOUT_TIMELOCK_SECS: i64 = 3 * SECONDS_PER_DAY;
+pub const DEFAULT_DISPUTE_WINDOW_SECS: i64 = 7 * SECONDS_PER_DAY;
+/// How long after entries close a raffle can wait for its draw before
+/// anyone may cancel it and refund the entrants.
+pub const RAFFLE_DRAW_TIMEOUT_SECS: i64 = SECONDS_PER_DAY;
+/// How long a canceled event keeps its hold on the organizer bond, leaving the
+/// admin time to slash it, unless every holder is refunded sooner.
+pub const CANCELED_BOND_HOLD_SECS: i64 = DEFAULT_DISPUTE_WINDOW_SECS;
+pub const MAX_METADATA_URI_LEN: usize = 200;
+pub const MAX_PAYOUT_TRANCHES: usize = 4;
+pub const MAX_APPROVED_VENUES: usize = 4;
+pub const MAX_AUCTION_SEATS: usize = 10;
+pub const MAX_TICKET_ATTRIBUTES_LEN: usize = 64;
+/// Days of history kept by `SalesStats`; older buckets are reused.
+pub const SALES_STATS_DAYS: usize = 32;
+/// Vault movements kept by `TransactionLog`; older entries are overwritten.
+pub const TRANSACTION_LOG_LEN: usize = 32;
+/// How far a tier's listed token price may drift from its lamport price at
+/// oracle rates before purchases in that token are refused.
+pub const MAX_CURRENCY_DEVIATION_BPS: u64 = 200;
+pub const EVENTS_PER_PAGE: u64 = 100;
+/// Layout version written by `Ticket::issue`; older tickets are brought up to
+/// date with `migrate_ticket`.
+pub const TICKET_VERSION: u8 = 7;
+/// Layout version written by `Event::init`; see `migrate_event`.
+pub const EVENT_VERSION: u8 = 18;
+/// Discriminator of the zero-copy `Event`, the first 8 bytes of
+/// sha256("account:ZeroCopyEvent"). It differs from the Borsh layout's so
+/// events up to v17 fail to load until `migrate_event` rewrites them.
+pub const EVENT_DISCRIMINATOR: [u8; 8] = [175, 48, 206, 76, 40, 209, 100, 141];
+/// Discriminator Anchor gave the Borsh `Event`, sha256("account:Event").
+pub const BORSH_EVENT_DISCRIMINATOR: [u8; 8] = [125, 192, 125, 158, 9, 115, 152, 233];
+/// Layout version written by `register_organizer`; see `migrate_organizer`.
+pub const ORGANIZER_VERSION: u8 = 1;
@@ -0,0 +1,76 @@
+use crate::errors::EventTicketingError;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+// Layout of the Ed25519 program instruction data for a single signature:
+// [num_signatures: u8, padding: u8, offsets: 7 x u16, ...payload]
+const HEADER_LEN: usize = 2;
+const OFFSETS_LEN: usize = 14;
+const PUBKEY_LEN: usize = 32;
+const SIGNATURE_LEN: usize = 64;
+// Marks an offset as pointing into the Ed25519 instruction's own data.
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Verifies that the instruction immediately before the current one is an
+/// Ed25519 program instruction checking `signer`'s signature over `message`.
+///
+/// The runtime rejects the transaction if the signature itself is invalid,
+/// so only the signer and message need to be matched here.
+pub fn verify_ed25519_instruction(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, EventTicketingError::InvalidVoucher);
+
+    let ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+    require_keys_eq!(
+        ix.program_id,
+        ed25519_program::ID,
+        EventTicketingError::InvalidVoucher
+    );
+
+    let data = &ix.data;
+    require!(
+        data.len() >= HEADER_LEN + OFFSETS_LEN && data[0] == 1,
+        EventTicketingError::InvalidVoucher
+    );
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let offsets = HEADER_LEN;
+    let signature_offset = read_u16(offsets) as usize;
+    let signature_ix = read_u16(offsets + 2);
+    let pubkey_offset = read_u16(offsets + 4) as usize;
+    let pubkey_ix = read_u16(offsets + 6);
+    let message_offset = read_u16(offsets + 8) as usize;
+    let message_len = read_u16(offsets + 10) as usize;
+    let message_ix = read_u16(offsets + 12);
+
+    require!(
+        signature_ix == CURRENT_INSTRUCTION
+            && pubkey_ix == CURRENT_INSTRUCTION
+            && message_ix == CURRENT_INSTRUCTION,
+        EventTicketingError::InvalidVoucher
+    );
+    require!(
+        data.len() >= signature_offset + SIGNATURE_LEN
+            && data.len() >= pubkey_offset + PUBKEY_LEN
+            && data.len() >= message_offset + message_len,
+        EventTicketingError::InvalidVoucher
+    );
+
+    require!(
+        &data[pubkey_offset..pubkey_offset + PUBKEY_LEN] == signer.as_ref(),
+        EventTicketingError::InvalidVoucher
+    );
+    require!(
+        &data[message_offset..message_offset + message_len] == message,
+        EventTicketingError::InvalidVoucher
+    );
+
+    Ok(())
+}
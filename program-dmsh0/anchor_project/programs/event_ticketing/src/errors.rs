@@ -24,4 +24,420 @@ pub enum EventTicketingError {
     NameTooLong,
     #[msg("Event date is too long")]
     DateTooLong,
+    #[msg("Only the config admin can perform this action")]
+    UnauthorizedAdmin,
+    #[msg("Organizer must be verified to create an event of this size")]
+    OrganizerNotVerified,
+    #[msg("Only the event authority can perform this action")]
+    UnauthorizedEventAuthority,
+    #[msg("Signer is not the pending event authority")]
+    NotPendingAuthority,
+    #[msg("Multisig threshold must be between 1 and the number of signers")]
+    InvalidThreshold,
+    #[msg("Too many multisig signers")]
+    TooManySigners,
+    #[msg("Duplicate multisig signer")]
+    DuplicateSigner,
+    #[msg("Multisig authority config account is required")]
+    MissingAuthorityConfig,
+    #[msg("Not enough multisig signers approved this action")]
+    InsufficientSigners,
+    #[msg("Batch must contain at least one account")]
+    EmptyBatch,
+    #[msg("Ticket account must be writable")]
+    TicketNotWritable,
+    #[msg("Missing or invalid Ed25519 voucher signature")]
+    InvalidVoucher,
+    #[msg("Check-in is only allowed while doors are open")]
+    DoorsClosed,
+    #[msg("Doors must open before they close")]
+    InvalidDoorsWindow,
+    #[msg("Ticket has already been checked in today")]
+    AlreadyCheckedInToday,
+    #[msg("Max check-ins must be at least 1")]
+    InvalidMaxCheckIns,
+    #[msg("Tickets for this event are non-transferable")]
+    TicketNotTransferable,
+    #[msg("Ticket transfers are currently frozen for this event")]
+    TransfersFrozen,
+    #[msg("Only the ticket owner can list or delist it")]
+    UnauthorizedListing,
+    #[msg("Listing is stale: the seller no longer owns the ticket")]
+    StaleListing,
+    #[msg("Resale price exceeds the event's resale cap")]
+    ResalePriceTooHigh,
+    #[msg("Ticket count must be between 1 and the per-transaction maximum")]
+    InvalidTicketCount,
+    #[msg("Ticket account does not match the expected PDA")]
+    InvalidTicketAccount,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Purchase would exceed the per-wallet ticket limit")]
+    WalletLimitReached,
+    #[msg("Allowlist presale is active")]
+    PresaleActive,
+    #[msg("No allowlist is set for this event")]
+    AllowlistNotSet,
+    #[msg("Buyer is not on the allowlist")]
+    NotAllowlisted,
+    #[msg("Promo code is too long")]
+    PromoCodeTooLong,
+    #[msg("Discount must be between 1 and 10000 basis points")]
+    InvalidDiscount,
+    #[msg("Promo code must allow at least one use")]
+    InvalidMaxUses,
+    #[msg("Promo code has no uses left")]
+    PromoExhausted,
+    #[msg("Referral cut cannot exceed 10000 basis points")]
+    InvalidReferralBps,
+    #[msg("Buyers cannot refer themselves")]
+    SelfReferral,
+    #[msg("Referrer wallet does not match the referrer account")]
+    InvalidReferrerWallet,
+    #[msg("Referrer is not registered for this event")]
+    InvalidReferrer,
+    #[msg("Complimentary tickets cannot be refunded")]
+    CompTicketNotRefundable,
+    #[msg("Event still has tickets for sale")]
+    EventNotSoldOut,
+    #[msg("No refunded seats are available for the waitlist")]
+    NoFreedSeats,
+    #[msg("Reservation has expired")]
+    ReservationExpired,
+    #[msg("Reservation has not expired yet")]
+    ReservationNotExpired,
+    #[msg("Auction window or prices are invalid")]
+    InvalidAuction,
+    #[msg("Auction is closed for bidding")]
+    AuctionClosed,
+    #[msg("Auction has not closed yet")]
+    AuctionNotClosed,
+    #[msg("Auction was already settled")]
+    AuctionAlreadySettled,
+    #[msg("Bid is below the auction minimum")]
+    BidTooLow,
+    #[msg("Every bid must be passed exactly once to settle")]
+    InvalidBidAccounts,
+    #[msg("Randomness account is not a Switchboard randomness account")]
+    InvalidRandomnessAccount,
+    #[msg("Randomness must be committed in the previous slot")]
+    RandomnessExpired,
+    #[msg("Randomness was already revealed")]
+    RandomnessAlreadyRevealed,
+    #[msg("Randomness has not been revealed in this slot")]
+    RandomnessNotResolved,
+    #[msg("Raffle is closed for entries")]
+    RaffleClosed,
+    #[msg("Raffle has not closed yet")]
+    RaffleNotClosed,
+    #[msg("Raffle randomness was already committed")]
+    RaffleAlreadyCommitted,
+    #[msg("Raffle randomness has not been committed")]
+    RaffleNotCommitted,
+    #[msg("Raffle was already drawn")]
+    RaffleAlreadyDrawn,
+    #[msg("Raffle has not been drawn yet")]
+    RaffleNotDrawn,
+    #[msg("Price account is not a verified Pyth SOL/USD price update")]
+    InvalidPriceFeed,
+    #[msg("Pyth price update is too old")]
+    StalePrice,
+    #[msg("A Pyth price update is required for USD-priced events")]
+    MissingPriceFeed,
+    #[msg("Seat is outside the venue layout")]
+    InvalidSeat,
+    #[msg("Seat is already taken")]
+    SeatTaken,
+    #[msg("Seat map does not belong to this event")]
+    InvalidSeatMap,
+    #[msg("This event requires its seat map account")]
+    MissingSeatMap,
+    #[msg("Seat layout exceeds the maximum number of seats")]
+    SeatLayoutTooLarge,
+    #[msg("Passes are not on sale for this series")]
+    PassesNotOnSale,
+    #[msg("Event is not part of the pass's series")]
+    PassNotValidForEvent,
+    #[msg("Ticket is not in the given tier")]
+    TierMismatch,
+    #[msg("Target tier must cost more than the current tier")]
+    NotAnUpgrade,
+    #[msg("Tier is sold out")]
+    TierSoldOut,
+    #[msg("Cannot change the tier of a used or refunded ticket")]
+    TicketNotChangeable,
+    #[msg("Target tier must cost less than the current tier")]
+    NotADowngrade,
+    #[msg("Attendance collectible mint does not match the event")]
+    PoapMintMismatch,
+    #[msg("Attendance collectible accounts are required for this event")]
+    PoapAccountsMissing,
+    #[msg("Attendance collectible mint must have zero decimals and the POAP authority as mint authority")]
+    InvalidPoapMint,
+    #[msg("Not enough loyalty points")]
+    InsufficientLoyaltyPoints,
+    #[msg("Loyalty account does not belong to this organizer and attendee")]
+    InvalidLoyaltyAccount,
+    #[msg("A loyalty account is required to redeem points")]
+    LoyaltyAccountMissing,
+    #[msg("Proceeds are timelocked until after the event ends")]
+    PayoutTimelocked,
+    #[msg("Payout schedule cannot be shortened once tickets are sold")]
+    PayoutScheduleLocked,
+    #[msg("Payout timelock cannot be negative")]
+    InvalidPayoutTimelock,
+    #[msg("Refunds cannot be claimed for this event yet")]
+    RefundsNotClaimable,
+    #[msg("Dispute window cannot be extended once tickets are sold")]
+    DisputeWindowLocked,
+    #[msg("Dispute window must be positive")]
+    InvalidDisputeWindow,
+    #[msg("Only the ticket owner can claim a refund")]
+    UnauthorizedRefundClaim,
+    #[msg("Proceeds are frozen while holders can claim refunds")]
+    ProceedsFrozen,
+    #[msg("Event has been finalized")]
+    EventFinalized,
+    #[msg("Event has not ended yet")]
+    EventNotEnded,
+    #[msg("Ticket sales are paused")]
+    SalesPaused,
+    #[msg("Ticket has been revoked")]
+    TicketRevoked,
+    #[msg("Refund recipient must be the ticket owner")]
+    InvalidRefundRecipient,
+    #[msg("Event has already started")]
+    EventAlreadyStarted,
+    #[msg("Refunds require a canceled event or an open refund window")]
+    RefundNotAllowed,
+    #[msg("Refund deadline cannot be moved earlier once tickets are sold")]
+    RefundPolicyLocked,
+    #[msg("Vault does not hold enough funds above its rent-exempt reserve")]
+    InsufficientVaultFunds,
+    #[msg("Metadata URI is too long")]
+    MetadataUriTooLong,
+    #[msg("Ticket already uses the current layout")]
+    TicketAlreadyMigrated,
+    #[msg("Organizer is already registered")]
+    OrganizerAlreadyRegistered,
+    #[msg("New supply must be lower than the current supply")]
+    NotASupplyDecrease,
+    #[msg("Supply cannot drop below tickets already sold or reserved")]
+    SupplyBelowSold,
+    #[msg("Withdrawals for this event have been frozen by the admin")]
+    WithdrawalsFrozen,
+    #[msg("Bond must be zero or cover the bond account's rent-exempt reserve")]
+    InvalidBondAmount,
+    #[msg("Organizer must post the required bond")]
+    OrganizerNotBonded,
+    #[msg("Bond stays locked until all bonded events finalize")]
+    BondLocked,
+    #[msg("Organizer registry of the bonding organizer is required")]
+    BondAccountsMissing,
+    #[msg("Organizer can only be slashed while the event owes refunds")]
+    SlashNotAllowed,
+    #[msg("Event has already ended")]
+    EventEnded,
+    #[msg("Event currency does not support this operation")]
+    CurrencyNotSupported,
+    #[msg("Token accounts are required for SPL-currency events")]
+    CurrencyAccountsMissing,
+    #[msg("Vault token account does not match the event currency")]
+    InvalidVaultTokenAccount,
+    #[msg("Recipient must differ from the ticket owner")]
+    InvalidTransferRecipient,
+    #[msg("Ticket changed hands since the transfer was approved")]
+    StalePendingTransfer,
+    #[msg("Signer is not the approved operator for this ticket")]
+    UnauthorizedOperator,
+    #[msg("Ticket changed hands since the operator was approved")]
+    StaleOperatorApproval,
+    #[msg("Ticket is lent out")]
+    TicketOnLoan,
+    #[msg("Ticket is not lent out")]
+    TicketNotLent,
+    #[msg("Loan deadline must be in the future")]
+    InvalidLoanDeadline,
+    #[msg("Loan has not expired yet")]
+    LoanNotExpired,
+    #[msg("Loan has expired; reclaim the ticket first")]
+    LoanExpired,
+    #[msg("Voucher has expired")]
+    VoucherExpired,
+    #[msg("Sale hook program account is required")]
+    SaleHookMissing,
+    #[msg("Sale hook program does not match the event")]
+    InvalidSaleHook,
+    #[msg("Events with a sale hook can only be sold through mint_ticket")]
+    SaleHookNotSupported,
+    #[msg("Account is already on the current layout")]
+    AccountAlreadyMigrated,
+    #[msg("Event name cannot be empty")]
+    InvalidEventName,
+    #[msg("Session must end after it starts")]
+    InvalidSessionWindow,
+    #[msg("Session is not open for check-in")]
+    SessionNotOpen,
+    #[msg("Session does not belong to this event")]
+    InvalidSession,
+    #[msg("Session is at capacity")]
+    SessionFull,
+    #[msg("Refund batch accounts must be (ticket, recipient) pairs")]
+    UnpairedRefundAccounts,
+    #[msg("Seated tickets must be refunded individually")]
+    SeatedTicketInBatch,
+    #[msg("Refund pool does not hold enough funds")]
+    InsufficientRefundPool,
+    #[msg("Refund pool does not belong to this event")]
+    InvalidRefundPool,
+    #[msg("Not supported for one-per-wallet events")]
+    OnePerWalletNotSupported,
+    #[msg("One-per-wallet events cannot have transferable tickets")]
+    OnePerWalletTransferable,
+    #[msg("Event requires an attestation for the ticket owner")]
+    AttestationMissing,
+    #[msg("Attestation is not from the event attestor or not for this owner")]
+    InvalidAttestation,
+    #[msg("Attestation has expired")]
+    AttestationExpired,
+    #[msg("Event attestation gate is not supported by this instruction")]
+    AttestationNotSupported,
+    #[msg("Token-gated events cannot be sold this way")]
+    TokenGateNotSupported,
+    #[msg("Token-gated events require the buyer's gate token account")]
+    TokenGateProofMissing,
+    #[msg("Gate token account is not the buyer's account of the gate mint")]
+    InvalidTokenGateAccount,
+    #[msg("Gate token balance is below the required minimum")]
+    TokenGateBalanceTooLow,
+    #[msg("Invalid NFT metadata account")]
+    InvalidGateMetadata,
+    #[msg("NFT is not a verified member of the gate collection")]
+    NotInGateCollection,
+    #[msg("Balance gates need a minimum amount")]
+    InvalidTokenGate,
+    #[msg("Attendance deposits are only for free, fixed-price events")]
+    DepositRequiresFreeEvent,
+    #[msg("Attendance deposit cannot change after tickets are sold")]
+    DepositLocked,
+    #[msg("Attendance deposits are held until the event is finalized")]
+    DepositsHeld,
+    #[msg("Attendance deposit accounts missing")]
+    DepositAccountsMissing,
+    #[msg("Deposit recipient must be the ticket holder")]
+    InvalidDepositRecipient,
+    #[msg("Events with attendance deposits cannot be checked in this way")]
+    DepositNotSupported,
+    #[msg("Pay-what-you-want is not enabled for this event")]
+    PayWhatYouWantDisabled,
+    #[msg("Amount is below the ticket price")]
+    AmountBelowFloor,
+    #[msg("Payout tranches must be 1-4 shares adding up to 100%")]
+    InvalidPayoutTranches,
+    #[msg("Withdrawal exceeds the unlocked payout tranches")]
+    PayoutTrancheLocked,
+    #[msg("Event payout config missing or invalid")]
+    InvalidPayoutConfig,
+    #[msg("Only the payer can close a receipt")]
+    UnauthorizedReceiptClose,
+    #[msg("Tier does not accept this currency")]
+    TierCurrencyNotAccepted,
+    #[msg("Token price is not equivalent to the tier price at oracle rates")]
+    PriceNotEquivalent,
+    #[msg("Too many approved resale venues")]
+    TooManyVenues,
+    #[msg("Resale must go through an approved venue")]
+    VenueNotApproved,
+    #[msg("Co-organizer permissions must be a non-empty set of known bits")]
+    InvalidPermissions,
+    #[msg("Ticket attributes too long")]
+    AttributesTooLong,
+    #[msg("Scan nonce already used for this ticket")]
+    ScanReplayed,
+    #[msg("One scan nonce is needed per ticket")]
+    ScanNonceCountMismatch,
+    #[msg("Event sales stats account missing or invalid")]
+    InvalidSalesStats,
+    #[msg("Purchase queue commit window is closed")]
+    PurchaseCommitsClosed,
+    #[msg("Purchase queue commit window is still open")]
+    PurchaseCommitsOpen,
+    #[msg("Purchase queue was already closed")]
+    PurchaseQueueAlreadyClosed,
+    #[msg("Purchase queue has not been closed yet")]
+    PurchaseQueueNotClosed,
+    #[msg("Purchase queue reveal window is closed")]
+    PurchaseRevealClosed,
+    #[msg("Purchase queue reveal window is still open")]
+    PurchaseRevealOpen,
+    #[msg("Reveal does not match the purchase commitment")]
+    InvalidPurchaseReveal,
+    #[msg("Purchase queue windows must close in the future, commits before reveals")]
+    InvalidPurchaseWindows,
+    #[msg("Waitlist is still open for this event")]
+    WaitlistNotClosed,
+    #[msg("Account is not an expired reservation or waitlist entry of this event")]
+    InvalidPurgeAccount,
+    #[msg("Waitlist entry must be followed by its wallet")]
+    MissingWaitlistWallet,
+    #[msg("Ticket cancellation is closed for this event")]
+    CancellationClosed,
+    #[msg("Cancellation fee must be at most 100%")]
+    InvalidCancellationFee,
+    #[msg("Resale split must add up to 100%")]
+    InvalidResaleSplit,
+    #[msg("Protocol treasury missing or not the config admin")]
+    InvalidProtocolTreasury,
+    #[msg("Event transaction log account missing or invalid")]
+    InvalidTransactionLog,
+    #[msg("New supply must be higher than the current supply")]
+    NotASupplyIncrease,
+    #[msg("Supply cannot exceed the event's max total supply")]
+    MaxTotalSupplyExceeded,
+    #[msg("Max total supply cannot be below the initial supply")]
+    InvalidMaxTotalSupply,
+    #[msg("Account is not an event on the original layout")]
+    NotALegacyEvent,
+    #[msg("Listing price is above the buyer's maximum")]
+    ListingPriceAboveMax,
+    #[msg("Waitlist entry is still waiting")]
+    WaitlistEntryActive,
+    #[msg("No one is waiting on the waitlist")]
+    WaitlistEmpty,
+    #[msg("Reservation must be followed by its buyer")]
+    MissingReservationBuyer,
+    #[msg("Bid reveal does not match its commitment")]
+    InvalidBidReveal,
+    #[msg("Bid exceeds its escrowed deposit")]
+    BidExceedsDeposit,
+    #[msg("Bid was already revealed")]
+    BidAlreadyRevealed,
+    #[msg("Auction reveals are closed")]
+    AuctionRevealClosed,
+    #[msg("Auction reveals are still open")]
+    AuctionRevealOpen,
+    #[msg("Auction has not been settled yet")]
+    AuctionNotSettled,
+    #[msg("Winning bids must pass their ticket account")]
+    MissingTicketAccount,
+    #[msg("Randomness was re-committed after the raffle committed to it")]
+    RandomnessReseeded,
+    #[msg("Raffle was canceled")]
+    RaffleCanceled,
+    #[msg("Raffle can't be canceled before its draw deadline")]
+    RaffleDrawPending,
+    #[msg("Seated tickets must pass their seat claim")]
+    MissingSeatClaim,
+    #[msg("Slash amount must be non-zero")]
+    SlashAmountZero,
+    #[msg("Canceled event still holds the bond until its holders are refunded or the hold lapses")]
+    BondHoldActive,
+    #[msg("Ticket was paid in tokens; refund it instead")]
+    TokenPaidTicket,
+    #[msg("Ticket was already paid in another token")]
+    TicketCurrencyMismatch,
+    #[msg("Ticket has no token payment to refund")]
+    NoTokenPayment,
+    #[msg("Ticket must be refunded before its token payment")]
+    TicketNotRefunded,
 }
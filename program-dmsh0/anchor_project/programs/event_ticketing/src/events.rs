@@ -0,0 +1,133 @@
+//! Events are recorded with a self-CPI signed by the event CPI authority,
+//! the same wire format `emit_cpi!` produces, so Anchor clients decode them
+//! from inner instructions. Unlike `emit!` logs they are never truncated.
+//! `#[event_cpi]` cannot be used because `event_authority` already names the
+//! organizer in many instructions; structs add the accounts themselves:
+//!
+//! ```ignore
+//! /// CHECK: Signs the event self-CPI. Verified by seeds.
+//! #[account(seeds = [EVENT_CPI_AUTHORITY_SEED], bump)]
+//! pub event_cpi_authority: AccountInfo<'info>,
+//!
+//! /// CHECK: This program, invoked by the event self-CPI.
+//! #[account(address = crate::ID)]
+//! pub program: AccountInfo<'info>,
+//! ```
+
+use crate::constants::EVENT_CPI_AUTHORITY_SEED;
+use crate::state::SaleFees;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+/// Records `event` through a self-CPI signed by `authority`, the event CPI
+/// authority PDA.
+pub fn emit_cpi_event<T: anchor_lang::Event>(authority: &AccountInfo, event: &T) -> Result<()> {
+    let (_, bump) = Pubkey::find_program_address(&[EVENT_CPI_AUTHORITY_SEED], &crate::ID);
+
+    let mut data = anchor_lang::event::EVENT_IX_TAG_LE.to_vec();
+    data.extend_from_slice(&event.data());
+
+    invoke_signed(
+        &Instruction {
+            program_id: crate::ID,
+            accounts: vec![AccountMeta::new_readonly(authority.key(), true)],
+            data,
+        },
+        std::slice::from_ref(authority),
+        &[&[EVENT_CPI_AUTHORITY_SEED, &[bump]]],
+    )?;
+
+    Ok(())
+}
+
+#[event]
+pub struct EventCanceled {
+    pub event: Pubkey,
+    pub event_id: u32,
+    pub canceled_by: Pubkey,
+    pub canceled_at: i64,
+}
+
+#[event]
+pub struct OrganizerRegistered {
+    pub organizer: Pubkey,
+    pub registered_at: i64,
+}
+
+/// Capacity shrank; `available` is what is left for sale and the waitlist.
+#[event]
+pub struct SupplyDecreased {
+    pub event: Pubkey,
+    pub event_id: u32,
+    pub old_supply: u32,
+    pub new_supply: u32,
+    pub available: u32,
+}
+
+#[event]
+pub struct SupplyIncreased {
+    pub event: Pubkey,
+    pub event_id: u32,
+    pub old_supply: u32,
+    pub new_supply: u32,
+    pub available: u32,
+}
+
+#[event]
+pub struct TicketMinted {
+    pub event: Pubkey,
+    pub ticket: Pubkey,
+    pub ticket_id: u32,
+    pub owner: Pubkey,
+    pub price: u64,
+    pub fees: SaleFees,
+}
+
+/// A ticket changed hands through `accept_transfer` or `operator_transfer`;
+/// `transfer_fee` lamports went to the event vault.
+#[event]
+pub struct TicketTransferred {
+    pub event: Pubkey,
+    pub ticket: Pubkey,
+    pub ticket_id: u32,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub transfer_fee: u64,
+}
+
+/// A listed ticket sold for `price` lamports paid to the seller, plus
+/// `transfer_fee` lamports to the event vault.
+#[event]
+pub struct TicketResold {
+    pub event: Pubkey,
+    pub ticket: Pubkey,
+    pub ticket_id: u32,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    pub transfer_fee: u64,
+    /// Shares of `price` paid to the vault and to the protocol; the seller
+    /// got the rest.
+    pub organizer_fee: u64,
+    pub protocol_fee: u64,
+}
+
+/// The organizer set a ticket's `attributes`, at issuance or later.
+#[event]
+pub struct TicketAttributesSet {
+    pub event: Pubkey,
+    pub ticket: Pubkey,
+    pub ticket_id: u32,
+    pub attributes: Vec<u8>,
+}
+
+/// `recipient` got `amount` back; zero for comps.
+#[event]
+pub struct TicketRefunded {
+    pub event: Pubkey,
+    pub ticket: Pubkey,
+    pub ticket_id: u32,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
@@ -0,0 +1,76 @@
+use crate::constants::ATTESTATION_SEED;
+use crate::errors::EventTicketingError;
+use crate::state::Attestation;
+use anchor_lang::prelude::*;
+
+/// Records that `subject` passed the attestor's checks, until `expires_at`
+/// (zero for no expiry). Issuing again for the same subject renews it.
+pub fn issue_attestation(
+    ctx: Context<IssueAttestation>,
+    subject: Pubkey,
+    expires_at: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        expires_at == 0 || expires_at > now,
+        EventTicketingError::AttestationExpired
+    );
+
+    let attestation = &mut ctx.accounts.attestation;
+
+    attestation.attestor = ctx.accounts.attestor.key();
+    attestation.subject = subject;
+    attestation.issued_at = now;
+    attestation.expires_at = expires_at;
+
+    msg!(
+        "Attestor {} attested {} until {}",
+        attestation.attestor,
+        subject,
+        expires_at
+    );
+
+    Ok(())
+}
+
+/// Withdraws an attestation, returning its rent to the attestor.
+pub fn revoke_attestation(ctx: Context<RevokeAttestation>) -> Result<()> {
+    msg!(
+        "Attestor {} revoked attestation of {}",
+        ctx.accounts.attestor.key(),
+        ctx.accounts.attestation.subject
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(subject: Pubkey)]
+pub struct IssueAttestation<'info> {
+    #[account(
+        init_if_needed,
+        payer = attestor,
+        space = Attestation::SPACE,
+        seeds = [ATTESTATION_SEED, attestor.key().as_ref(), subject.as_ref()],
+        bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(mut)]
+    pub attestor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeAttestation<'info> {
+    #[account(
+        mut,
+        close = attestor,
+        has_one = attestor @ EventTicketingError::InvalidAttestation
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(mut)]
+    pub attestor: Signer<'info>,
+}
@@ -0,0 +1,451 @@
+use super::mint_tickets::create_ticket_account;
+use crate::attestation::require_no_attestor;
+use crate::constants::*;
+use crate::errors::EventTicketingError;
+use crate::events::{emit_cpi_event, TicketMinted};
+use crate::payment::require_sol_currency;
+use crate::sale_hook::require_no_sale_hook;
+use crate::sales_stats::record_daily_sales;
+use crate::state::{Auction, Bid, Event, SalesStats, TransactionLog, VaultActivity};
+use crate::token_gate::require_no_token_gate;
+use crate::transaction_log::record_vault_activity;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Sets aside `seats` tickets to be sold by sealed-bid auction. Sealed bids
+/// are accepted until `close_at` and revealed until `reveal_close_at`; the
+/// seats are held out of general sale in the meantime.
+pub fn create_auction(
+    ctx: Context<CreateAuction>,
+    seats: u32,
+    min_bid: u64,
+    close_at: i64,
+    reveal_close_at: i64,
+) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    require!(!event.canceled(), EventTicketingError::EventCanceled);
+    require!(!event.is_finalized(), EventTicketingError::EventFinalized);
+    require_sol_currency(event)?;
+    require_no_sale_hook(event)?;
+    require_no_attestor(event)?;
+    require_no_token_gate(event)?;
+    event.require_numbered_tickets()?;
+    require!(
+        seats > 0 && seats as usize <= MAX_AUCTION_SEATS && event.available() >= seats,
+        EventTicketingError::InvalidTicketCount
+    );
+    require!(
+        close_at > Clock::get()?.unix_timestamp,
+        EventTicketingError::AuctionClosed
+    );
+    require!(
+        reveal_close_at > close_at,
+        EventTicketingError::InvalidAuction
+    );
+
+    let auction = &mut ctx.accounts.auction;
+
+    auction.event = event_key;
+    auction.seats = seats;
+    auction.min_bid = min_bid;
+    auction.close_at = close_at;
+    auction.reveal_close_at = reveal_close_at;
+    auction.bid_count = 0;
+    auction.settled = false;
+    auction.leaders = Vec::new();
+
+    event.reserved += seats;
+
+    msg!(
+        "Auction for {} seats of event {}: bids until {}, reveals until {}",
+        seats,
+        event.event_id,
+        close_at,
+        reveal_close_at
+    );
+
+    Ok(())
+}
+
+/// Places a sealed bid (see `Bid::hash`), escrowing `deposit` lamports in the
+/// bidder's Bid PDA. The deposit is public, so bidders can pad it above
+/// their real bid.
+pub fn place_bid(ctx: Context<PlaceBid>, commitment: [u8; 32], deposit: u64) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        !ctx.accounts.event.load()?.canceled(),
+        EventTicketingError::EventCanceled
+    );
+    require!(now < auction.close_at, EventTicketingError::AuctionClosed);
+    require!(
+        deposit > 0 && deposit >= auction.min_bid,
+        EventTicketingError::BidTooLow
+    );
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.bidder.to_account_info(),
+                to: ctx.accounts.bid.to_account_info(),
+            },
+        ),
+        deposit,
+    )?;
+
+    let bid = &mut ctx.accounts.bid;
+
+    bid.auction = auction.key();
+    bid.bidder = ctx.accounts.bidder.key();
+    bid.commitment = commitment;
+    bid.deposit = deposit;
+    bid.amount = 0;
+    bid.placed_at = now;
+    bid.revealed = false;
+
+    auction.bid_count += 1;
+
+    msg!(
+        "Sealed bid with a {} lamport deposit placed by {}",
+        deposit,
+        bid.bidder
+    );
+
+    Ok(())
+}
+
+/// Withdraws a bid while bidding is still open, returning the deposit.
+pub fn withdraw_bid(ctx: Context<WithdrawBid>) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+
+    require!(
+        Clock::get()?.unix_timestamp < auction.close_at,
+        EventTicketingError::AuctionClosed
+    );
+
+    auction.bid_count -= 1;
+
+    msg!("Bid withdrawn by {}", ctx.accounts.bidder.key());
+
+    Ok(())
+}
+
+/// Opens a sealed bid once bidding has closed and ranks it against the other
+/// revealed bids.
+pub fn reveal_bid(ctx: Context<RevealBid>, amount: u64, salt: [u8; 32]) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    let bid = &mut ctx.accounts.bid;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        now >= auction.close_at,
+        EventTicketingError::AuctionNotClosed
+    );
+    require!(
+        now < auction.reveal_close_at,
+        EventTicketingError::AuctionRevealClosed
+    );
+    require!(!bid.revealed, EventTicketingError::BidAlreadyRevealed);
+    require!(
+        bid.matches(amount, &salt),
+        EventTicketingError::InvalidBidReveal
+    );
+    require!(amount >= auction.min_bid, EventTicketingError::BidTooLow);
+    require!(
+        amount <= bid.deposit,
+        EventTicketingError::BidExceedsDeposit
+    );
+
+    bid.amount = amount;
+    bid.revealed = true;
+    auction.record_reveal(bid.key(), amount, bid.placed_at);
+
+    msg!("Bid of {} lamports revealed by {}", amount, bid.bidder);
+
+    Ok(())
+}
+
+/// Fixes the winning bids once reveals have closed and releases the seats
+/// nobody won. If the event is canceled the auction closes straight away
+/// with no winners. Anyone can crank this.
+pub fn close_auction(ctx: Context<CloseAuction>) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+    let auction = &mut ctx.accounts.auction;
+
+    require!(
+        event.canceled() || Clock::get()?.unix_timestamp >= auction.reveal_close_at,
+        EventTicketingError::AuctionRevealOpen
+    );
+    require!(!auction.settled, EventTicketingError::AuctionAlreadySettled);
+
+    if event.canceled() {
+        auction.leaders.clear();
+    }
+    event.reserved -= auction.seats - auction.leaders.len() as u32;
+    auction.settled = true;
+
+    msg!(
+        "Auction for event {} closed: {} of {} seats won",
+        event.event_id,
+        auction.leaders.len(),
+        auction.seats
+    );
+
+    Ok(())
+}
+
+/// Settles one bid after the auction has closed. A winning bid pays its
+/// revealed amount into the vault for the next ticket; everything else in
+/// the Bid PDA, or all of it for losing, unrevealed and canceled bids, goes
+/// back to the bidder. Anyone can crank this.
+pub fn settle_bid(ctx: Context<SettleBid>) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+    let auction = &ctx.accounts.auction;
+    let bid = &ctx.accounts.bid;
+
+    require!(auction.settled, EventTicketingError::AuctionNotSettled);
+
+    if !auction.is_winner(&bid.key()) {
+        msg!(
+            "Bid by {} refunded for event {}",
+            bid.bidder,
+            event.event_id
+        );
+        return Ok(());
+    }
+
+    event.reserved -= 1;
+
+    if event.canceled() {
+        msg!(
+            "Winning bid by {} refunded for canceled event {}",
+            bid.bidder,
+            event.event_id
+        );
+        return Ok(());
+    }
+
+    let ticket = ctx
+        .accounts
+        .ticket
+        .as_ref()
+        .ok_or(EventTicketingError::MissingTicketAccount)?;
+
+    bid.sub_lamports(bid.amount)?;
+    ctx.accounts.vault.add_lamports(bid.amount)?;
+
+    let ticket_id = event.take_ticket_id()?;
+    create_ticket_account(
+        ticket,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        event_key,
+        ticket_id,
+        bid.bidder,
+        bid.amount,
+    )?;
+    let fees = event.record_sale(bid.amount, 0)?;
+    record_daily_sales(
+        event,
+        ctx.accounts.sales_stats.as_deref_mut(),
+        1,
+        bid.amount,
+        Clock::get()?.unix_timestamp,
+    )?;
+    record_vault_activity(
+        event,
+        ctx.accounts.transaction_log.as_ref(),
+        VaultActivity::Mint,
+        bid.bidder,
+        bid.amount,
+        Clock::get()?.unix_timestamp,
+    )?;
+    emit_cpi_event(
+        &ctx.accounts.event_cpi_authority,
+        &TicketMinted {
+            event: event_key,
+            ticket: ticket.key(),
+            ticket_id,
+            owner: bid.bidder,
+            price: bid.amount,
+            fees,
+        },
+    )?;
+
+    event.sold += 1;
+
+    msg!(
+        "Winning bid of {} lamports by {} issued ticket #{} for event {}",
+        bid.amount,
+        bid.bidder,
+        ticket_id,
+        event.event_id
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateAuction<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        init,
+        payer = event_authority,
+        space = Auction::SPACE,
+        seeds = [AUCTION_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub auction: Account<'info, Auction>,
+
+    #[account(mut)]
+    pub event_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceBid<'info> {
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        init,
+        payer = bidder,
+        space = Bid::SPACE,
+        seeds = [BID_SEED, auction.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid: Account<'info, Bid>,
+
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawBid<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [BID_SEED, auction.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid: Account<'info, Bid>,
+
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevealBid<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        mut,
+        seeds = [BID_SEED, auction.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid: Account<'info, Bid>,
+
+    pub bidder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAuction<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub auction: Account<'info, Auction>,
+}
+
+#[derive(Accounts)]
+pub struct SettleBid<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        seeds = [AUCTION_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [BID_SEED, auction.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid: Account<'info, Bid>,
+
+    /// CHECK: Bidder receiving the refund and rent; verified by the bid seeds.
+    #[account(mut)]
+    pub bidder: AccountInfo<'info>,
+
+    /// CHECK: Ticket PDA for `event.next_ticket_id`; required for winning bids.
+    #[account(mut)]
+    pub ticket: Option<AccountInfo<'info>>,
+
+    /// CHECK: This is the vault PDA that holds event funds. Verified by seeds.
+    #[account(
+        mut,
+        seeds = [
+            VAULT_SEED,
+            event.key().as_ref()
+        ],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Daily sales buckets; required when the event keeps them.
+    #[account(
+        mut,
+        constraint = event.load()?.sales_stats() == Some(sales_stats.key()) @ EventTicketingError::InvalidSalesStats
+    )]
+    pub sales_stats: Option<Account<'info, SalesStats>>,
+
+    /// Vault activity log; required when the event keeps one.
+    #[account(
+        mut,
+        constraint = event.load()?.transaction_log() == Some(transaction_log.key()) @ EventTicketingError::InvalidTransactionLog
+    )]
+    pub transaction_log: Option<AccountLoader<'info, TransactionLog>>,
+
+    /// CHECK: Signs the event self-CPI. Verified by seeds.
+    #[account(seeds = [EVENT_CPI_AUTHORITY_SEED], bump)]
+    pub event_cpi_authority: AccountInfo<'info>,
+
+    /// CHECK: This program, invoked by the event self-CPI.
+    #[account(address = crate::ID)]
+    pub program: AccountInfo<'info>,
+}
@@ -0,0 +1,229 @@
+use crate::constants::{BOND_SEED, CANCELED_BOND_HOLD_SECS, CONFIG_SEED, ORGANIZER_SEED};
+use crate::errors::EventTicketingError;
+use crate::state::{Config, Event, OrganizerRegistry};
+use crate::vault::vault_reserve;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Sets the bond new organizers lock at registration. A non-zero bond must
+/// cover the bond PDA's rent-exempt reserve.
+pub fn set_organizer_bond(ctx: Context<SetOrganizerBond>, bond_lamports: u64) -> Result<()> {
+    require!(
+        bond_lamports == 0 || bond_lamports >= vault_reserve()?,
+        EventTicketingError::InvalidBondAmount
+    );
+
+    ctx.accounts.config.organizer_bond_lamports = bond_lamports;
+
+    msg!("Organizer bond set to {} lamports", bond_lamports);
+
+    Ok(())
+}
+
+/// Tops the organizer's bond up to the configured amount, e.g. for organizers
+/// registered before bonding was enabled or after the bond was raised.
+pub fn post_bond(ctx: Context<PostBond>) -> Result<()> {
+    let required = ctx.accounts.config.organizer_bond_lamports;
+    let organizer_registry = &mut ctx.accounts.organizer_registry;
+    let shortfall = required.saturating_sub(organizer_registry.bond);
+
+    deposit_bond(
+        &ctx.accounts.organizer,
+        &ctx.accounts.bond,
+        &ctx.accounts.system_program,
+        shortfall,
+    )?;
+    organizer_registry.bond += shortfall;
+
+    msg!(
+        "Organizer {} bond is now {} lamports",
+        organizer_registry.organizer,
+        organizer_registry.bond
+    );
+
+    Ok(())
+}
+
+/// Returns the bond once every bonded event of the organizer has finalized.
+pub fn release_bond(ctx: Context<ReleaseBond>) -> Result<()> {
+    let organizer_registry = &mut ctx.accounts.organizer_registry;
+
+    require!(
+        organizer_registry.open_events == 0,
+        EventTicketingError::BondLocked
+    );
+
+    let amount = organizer_registry.bond;
+    let organizer_key = organizer_registry.organizer;
+    let seeds = &[BOND_SEED, organizer_key.as_ref(), &[ctx.bumps.bond]];
+    let signer_seeds = &[&seeds[..]];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.bond.to_account_info(),
+                to: ctx.accounts.organizer.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+    organizer_registry.bond = 0;
+
+    msg!(
+        "Released {} lamports bond to organizer {}",
+        amount,
+        organizer_key
+    );
+
+    Ok(())
+}
+
+/// Drops a canceled event's hold on its organizer's bond once every holder
+/// has been refunded, or `CANCELED_BOND_HOLD_SECS` after cancellation if the
+/// admin has not slashed it by then. Anyone can crank this.
+pub fn release_event_bond(ctx: Context<ReleaseEventBond>) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+    let organizer_registry = &mut ctx.accounts.organizer_registry;
+
+    require!(event.canceled(), EventTicketingError::BondLocked);
+    require!(
+        event.refunded_count >= event.sold
+            || Clock::get()?.unix_timestamp
+                >= event.canceled_at.saturating_add(CANCELED_BOND_HOLD_SECS),
+        EventTicketingError::BondHoldActive
+    );
+
+    organizer_registry.open_events = organizer_registry.open_events.saturating_sub(1);
+    event.bond_organizer = Pubkey::default();
+
+    msg!(
+        "Canceled event {} released its hold on organizer {} bond",
+        event.event_id,
+        organizer_registry.organizer
+    );
+
+    Ok(())
+}
+
+pub(crate) fn deposit_bond<'info>(
+    organizer: &Signer<'info>,
+    bond: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    system_program::transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            system_program::Transfer {
+                from: organizer.to_account_info(),
+                to: bond.clone(),
+            },
+        ),
+        amount,
+    )
+}
+
+/// When bonding is enabled, checks the organizer's bond covers the config
+/// amount and counts a new open event against it. Returns the organizer to
+/// record on the event.
+pub(crate) fn lock_organizer_bond(
+    config: &Config,
+    organizer_registry: Option<&mut OrganizerRegistry>,
+) -> Result<Option<Pubkey>> {
+    if config.organizer_bond_lamports == 0 {
+        return Ok(None);
+    }
+
+    let organizer_registry = organizer_registry.ok_or(EventTicketingError::OrganizerNotBonded)?;
+    require!(
+        organizer_registry.bond >= config.organizer_bond_lamports,
+        EventTicketingError::OrganizerNotBonded
+    );
+    organizer_registry.open_events += 1;
+
+    Ok(Some(organizer_registry.organizer))
+}
+
+#[derive(Accounts)]
+pub struct SetOrganizerBond<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump,
+        has_one = admin @ EventTicketingError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PostBond<'info> {
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [ORGANIZER_SEED, organizer.key().as_ref()],
+        bump
+    )]
+    pub organizer_registry: Account<'info, OrganizerRegistry>,
+
+    /// CHECK: Lamport-only PDA holding the organizer's bond. Verified by seeds.
+    #[account(
+        mut,
+        seeds = [BOND_SEED, organizer.key().as_ref()],
+        bump
+    )]
+    pub bond: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub organizer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseBond<'info> {
+    #[account(
+        mut,
+        seeds = [ORGANIZER_SEED, organizer.key().as_ref()],
+        bump
+    )]
+    pub organizer_registry: Account<'info, OrganizerRegistry>,
+
+    /// CHECK: Lamport-only PDA holding the organizer's bond. Verified by seeds.
+    #[account(
+        mut,
+        seeds = [BOND_SEED, organizer.key().as_ref()],
+        bump
+    )]
+    pub bond: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub organizer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseEventBond<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.bond_organizer() == Some(organizer_registry.organizer) @ EventTicketingError::BondAccountsMissing
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [ORGANIZER_SEED, organizer_registry.organizer.as_ref()],
+        bump
+    )]
+    pub organizer_registry: Account<'info, OrganizerRegistry>,
+}
@@ -0,0 +1,185 @@
+use crate::constants::{CONFIG_SEED, EVENT_CPI_AUTHORITY_SEED, LISTING_SEED, VAULT_SEED};
+use crate::errors::EventTicketingError;
+use crate::events::{emit_cpi_event, TicketResold};
+use crate::state::{Config, Event, Listing, Ticket, TransactionLog, VaultActivity};
+use crate::transaction_log::record_vault_activity;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Buys a listed ticket at the listing's price. `max_price` guards the buyer
+/// against the seller relisting at a higher price while the purchase is in
+/// flight.
+pub fn buy_listed_ticket(ctx: Context<BuyListedTicket>, max_price: u64) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+    let listing = &ctx.accounts.listing;
+    let ticket = &mut ctx.accounts.ticket;
+
+    require!(
+        listing.price <= max_price,
+        EventTicketingError::ListingPriceAboveMax
+    );
+
+    require!(
+        event.transferable(),
+        EventTicketingError::TicketNotTransferable
+    );
+    require!(
+        !event.transfers_frozen(),
+        EventTicketingError::TransfersFrozen
+    );
+    require!(!ticket.is_used(), EventTicketingError::TicketAlreadyUsed);
+    require!(!ticket.revoked, EventTicketingError::TicketRevoked);
+    require!(!ticket.refunded, EventTicketingError::AlreadyRefunded);
+    event.require_approved_venue(ctx.accounts.venue.as_ref().map(|venue| venue.key()))?;
+    // The organizer may have lowered the resale cap since the ticket was
+    // listed.
+    if let Some(max_price) = event.max_resale_price() {
+        require!(
+            listing.price <= max_price,
+            EventTicketingError::ResalePriceTooHigh
+        );
+    }
+
+    let split = event.record_resale_split(listing)?;
+    let buyer = ctx.accounts.buyer.to_account_info();
+    let pay = |to, amount: u64| {
+        if amount == 0 {
+            return Ok(());
+        }
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: buyer.clone(),
+                    to,
+                },
+            ),
+            amount,
+        )
+    };
+
+    pay(ctx.accounts.seller.to_account_info(), split.seller)?;
+    let vault_fees = split.organizer + event.transfer_fee_lamports;
+    pay(ctx.accounts.vault.to_account_info(), vault_fees)?;
+    if vault_fees > 0 {
+        record_vault_activity(
+            event,
+            ctx.accounts.transaction_log.as_ref(),
+            VaultActivity::Fee,
+            ctx.accounts.buyer.key(),
+            vault_fees,
+            Clock::get()?.unix_timestamp,
+        )?;
+    }
+    if split.protocol > 0 {
+        let treasury = ctx
+            .accounts
+            .protocol_treasury
+            .as_ref()
+            .ok_or(EventTicketingError::InvalidProtocolTreasury)?;
+        pay(treasury.to_account_info(), split.protocol)?;
+    }
+
+    ticket.owner = ctx.accounts.buyer.key();
+    let transfer_fee = event.transfer_fee_lamports;
+    event.record_transfer(transfer_fee, listing.price)?;
+
+    emit_cpi_event(
+        &ctx.accounts.event_cpi_authority,
+        &TicketResold {
+            event: event_key,
+            ticket: ticket.key(),
+            ticket_id: ticket.ticket_id,
+            seller: listing.seller,
+            buyer: ctx.accounts.buyer.key(),
+            price: listing.price,
+            transfer_fee,
+            organizer_fee: split.organizer,
+            protocol_fee: split.protocol,
+        },
+    )?;
+
+    msg!(
+        "Ticket #{} for event {} sold to {} for {} lamports",
+        ticket.ticket_id,
+        event.event_id,
+        ctx.accounts.buyer.key(),
+        listing.price
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct BuyListedTicket<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        constraint = ticket.event == event.key(),
+        constraint = ticket.owner == listing.seller @ EventTicketingError::StaleListing
+    )]
+    pub ticket: Account<'info, Ticket>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [LISTING_SEED, ticket.key().as_ref()],
+        bump
+    )]
+    pub listing: Account<'info, Listing>,
+
+    /// CHECK: This is the vault PDA that collects transfer fees. Verified by seeds.
+    #[account(
+        mut,
+        seeds = [
+            VAULT_SEED,
+            event.key().as_ref()
+        ],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: The seller recorded on the listing; receives their share of the
+    /// sale price and the listing rent.
+    #[account(mut, address = listing.seller)]
+    pub seller: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// Marketplace routing the purchase; required when the event restricts
+    /// resale venues.
+    pub venue: Option<Signer<'info>>,
+
+    /// Program config naming the protocol treasury; required when the
+    /// event's resale split has a protocol share.
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Option<Account<'info, Config>>,
+
+    /// CHECK: The config admin, receiving the protocol share of the price.
+    #[account(
+        mut,
+        constraint = config.as_ref().is_some_and(|config| config.admin == protocol_treasury.key()) @ EventTicketingError::InvalidProtocolTreasury
+    )]
+    pub protocol_treasury: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Vault activity log; required when the event keeps one.
+    #[account(
+        mut,
+        constraint = event.load()?.transaction_log() == Some(transaction_log.key()) @ EventTicketingError::InvalidTransactionLog
+    )]
+    pub transaction_log: Option<AccountLoader<'info, TransactionLog>>,
+
+    /// CHECK: Signs the event self-CPI. Verified by seeds.
+    #[account(seeds = [EVENT_CPI_AUTHORITY_SEED], bump)]
+    pub event_cpi_authority: AccountInfo<'info>,
+
+    /// CHECK: This program, invoked by the event self-CPI.
+    #[account(address = crate::ID)]
+    pub program: AccountInfo<'info>,
+}
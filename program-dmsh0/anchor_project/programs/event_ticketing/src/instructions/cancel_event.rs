@@ -1,16 +1,80 @@
-use crate::state::Event;
+use crate::constants::{EVENT_CPI_AUTHORITY_SEED, REFUND_POOL_SEED, VAULT_SEED};
+use crate::errors::EventTicketingError;
+use crate::events::{emit_cpi_event, EventCanceled};
+use crate::rbac::{has_permission, Delegations, Permission};
+use crate::state::{Event, EventAuthorityConfig, RefundPool, TransactionLog, VaultActivity};
+use crate::transaction_log::record_vault_activity;
+use crate::vault::sweep_to_refund_pool;
 use anchor_lang::prelude::*;
 
+/// Cancels the event and moves the vault's funds into its RefundPool, from
+/// which holders claim their refunds without the organizer's involvement.
+/// SPL-currency proceeds stay in the vault token account.
 pub fn cancel_event(ctx: Context<CancelEvent>) -> Result<()> {
-    let event = &mut ctx.accounts.event;
+    has_permission(
+        &*ctx.accounts.event.load()?,
+        &ctx.accounts.event_authority.key(),
+        Permission::Cancel,
+        &Delegations {
+            authority_config: ctx.accounts.authority_config.as_deref(),
+            cosigners: ctx.remaining_accounts,
+            ..Default::default()
+        },
+    )?;
 
-    event.canceled = true;
+    let now = Clock::get()?.unix_timestamp;
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    require!(!event.canceled(), EventTicketingError::EventCanceled);
+    require!(!event.is_finalized(), EventTicketingError::EventFinalized);
+    require!(
+        !event.has_started(now),
+        EventTicketingError::EventAlreadyStarted
+    );
+
+    event.canceled = true.into();
+    event.canceled_at = now;
+
+    let refund_pool = &mut ctx.accounts.refund_pool;
+    refund_pool.event = event_key;
+
+    let pooled = if event.currency().is_none() {
+        sweep_to_refund_pool(
+            &ctx.accounts.vault,
+            ctx.bumps.vault,
+            refund_pool,
+            &ctx.accounts.system_program.to_account_info(),
+        )?
+    } else {
+        0
+    };
+    if pooled > 0 {
+        record_vault_activity(
+            event,
+            ctx.accounts.transaction_log.as_ref(),
+            VaultActivity::Sweep,
+            refund_pool.key(),
+            pooled,
+            now,
+        )?;
+    }
+
+    emit_cpi_event(
+        &ctx.accounts.event_cpi_authority,
+        &EventCanceled {
+            event: event_key,
+            event_id: event.event_id,
+            canceled_by: ctx.accounts.event_authority.key(),
+            canceled_at: now,
+        },
+    )?;
 
     msg!(
-        "Event '{}' (ID: {}) has been canceled by {}",
-        event.name,
+        "Event {} has been canceled by {}, {} lamports moved to the refund pool",
         event.event_id,
-        ctx.accounts.event_authority.key()
+        ctx.accounts.event_authority.key(),
+        pooled
     );
 
     Ok(())
@@ -18,11 +82,49 @@ pub fn cancel_event(ctx: Context<CancelEvent>) -> Result<()> {
 
 #[derive(Accounts)]
 pub struct CancelEvent<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(constraint = authority_config.event == event.key())]
+    pub authority_config: Option<Account<'info, EventAuthorityConfig>>,
+
+    #[account(
+        init,
+        payer = event_authority,
+        space = RefundPool::SPACE,
+        seeds = [REFUND_POOL_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub refund_pool: Account<'info, RefundPool>,
+
+    /// CHECK: This is the vault PDA that holds event funds. Verified by seeds.
     #[account(
         mut,
-        constraint = event.event_authority == event_authority.key()
+        seeds = [
+            VAULT_SEED,
+            event.key().as_ref()
+        ],
+        bump
     )]
-    pub event: Account<'info, Event>,
+    pub vault: AccountInfo<'info>,
 
+    #[account(mut)]
     pub event_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Vault activity log; required when the event keeps one.
+    #[account(
+        mut,
+        constraint = event.load()?.transaction_log() == Some(transaction_log.key()) @ EventTicketingError::InvalidTransactionLog
+    )]
+    pub transaction_log: Option<AccountLoader<'info, TransactionLog>>,
+
+    /// CHECK: Signs the event self-CPI. Verified by seeds.
+    #[account(seeds = [EVENT_CPI_AUTHORITY_SEED], bump)]
+    pub event_cpi_authority: AccountInfo<'info>,
+
+    /// CHECK: This program, invoked by the event self-CPI.
+    #[account(address = crate::ID)]
+    pub program: AccountInfo<'info>,
 }
@@ -0,0 +1,173 @@
+use super::mint_ticket_with_seat::release_seat;
+use crate::constants::{EVENT_CPI_AUTHORITY_SEED, VAULT_SEED};
+use crate::errors::EventTicketingError;
+use crate::events::{emit_cpi_event, TicketRefunded};
+use crate::payment::require_sol_currency;
+use crate::sales_stats::record_daily_refunds;
+use crate::state::{Event, SalesStats, SeatClaim, SeatMap, Ticket, TransactionLog, VaultActivity};
+use crate::transaction_log::record_vault_activity;
+use crate::vault::pay_refund;
+use anchor_lang::prelude::*;
+
+/// Holder returns an unused ticket before `Event::cancellation_cutoff`,
+/// getting back what they paid less the event's cancellation fee, which
+/// stays in the vault. The seat goes to the waitlist when anyone is
+/// waiting, and back on sale otherwise.
+pub fn cancel_ticket(ctx: Context<CancelTicket>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+    let ticket = &mut ctx.accounts.ticket;
+
+    require!(
+        event.cancellation_cutoff != 0 && now <= event.cancellation_cutoff,
+        EventTicketingError::CancellationClosed
+    );
+    require!(!event.canceled(), EventTicketingError::EventCanceled);
+    require!(!event.is_finalized(), EventTicketingError::EventFinalized);
+    require!(
+        !ticket.is_used(),
+        EventTicketingError::CannotRefundUsedTicket
+    );
+    require!(!ticket.refunded, EventTicketingError::AlreadyRefunded);
+    require!(!ticket.comped, EventTicketingError::CompTicketNotRefundable);
+    require!(!ticket.is_lent(), EventTicketingError::TicketOnLoan);
+    require!(
+        ticket.token_payment.is_none(),
+        EventTicketingError::TokenPaidTicket
+    );
+    require_sol_currency(event)?;
+
+    let fee = event.cancellation_fee(ticket.purchase_price);
+    let refund_amount = ticket.purchase_price - fee;
+    pay_refund(
+        &ctx.accounts.vault,
+        ctx.bumps.vault,
+        event_key,
+        None,
+        &ctx.accounts.holder.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        refund_amount,
+    )?;
+
+    ticket.refunded = true;
+    event.refunded_count += 1;
+    record_daily_refunds(
+        event,
+        ctx.accounts.sales_stats.as_deref_mut(),
+        1,
+        refund_amount,
+        now,
+    )?;
+    record_vault_activity(
+        event,
+        ctx.accounts.transaction_log.as_ref(),
+        VaultActivity::Refund,
+        ctx.accounts.holder.key(),
+        refund_amount,
+        now,
+    )?;
+
+    // Like `claim_from_waitlist`, a seat put back on sale is issued as a new
+    // ticket at the end of the sequence, without growing `supply`.
+    if event.waitlist_next < event.waitlist_len {
+        event.freed_seats += 1;
+    } else {
+        event.reissued_seats += 1;
+    }
+
+    release_seat(
+        event,
+        ticket,
+        ctx.accounts.seat_map.as_ref(),
+        ctx.accounts.seat_claim.as_ref(),
+    )?;
+
+    emit_cpi_event(
+        &ctx.accounts.event_cpi_authority,
+        &TicketRefunded {
+            event: event_key,
+            ticket: ticket.key(),
+            ticket_id: ticket.ticket_id,
+            recipient: ctx.accounts.holder.key(),
+            amount: refund_amount,
+        },
+    )?;
+
+    msg!(
+        "Ticket #{} canceled by {}: {} lamports refunded, {} kept as fee",
+        ticket.ticket_id,
+        ctx.accounts.holder.key(),
+        refund_amount,
+        fee
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelTicket<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        constraint = ticket.event == event.key(),
+        constraint = ticket.owner == holder.key() @ EventTicketingError::UnauthorizedRefundClaim
+    )]
+    pub ticket: Account<'info, Ticket>,
+
+    /// CHECK: This is the vault PDA that holds event funds. Verified by seeds.
+    #[account(
+        mut,
+        seeds = [
+            VAULT_SEED,
+            event.key().as_ref()
+        ],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    /// Required to release the seat of a seated ticket.
+    #[account(
+        mut,
+        constraint = event.load()?.seat_map() == Some(seat_map.key()) @ EventTicketingError::InvalidSeatMap
+    )]
+    pub seat_map: Option<AccountLoader<'info, SeatMap>>,
+
+    /// Claim on the canceled ticket's seat, required when it has one; closing it
+    /// lets the seat be sold again.
+    #[account(
+        mut,
+        close = holder,
+        constraint = seat_claim.ticket == ticket.key() @ EventTicketingError::InvalidSeat
+    )]
+    pub seat_claim: Option<Account<'info, SeatClaim>>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Daily sales buckets; required when the event keeps them.
+    #[account(
+        mut,
+        constraint = event.load()?.sales_stats() == Some(sales_stats.key()) @ EventTicketingError::InvalidSalesStats
+    )]
+    pub sales_stats: Option<Account<'info, SalesStats>>,
+
+    /// Vault activity log; required when the event keeps one.
+    #[account(
+        mut,
+        constraint = event.load()?.transaction_log() == Some(transaction_log.key()) @ EventTicketingError::InvalidTransactionLog
+    )]
+    pub transaction_log: Option<AccountLoader<'info, TransactionLog>>,
+
+    /// CHECK: Signs the event self-CPI. Verified by seeds.
+    #[account(seeds = [EVENT_CPI_AUTHORITY_SEED], bump)]
+    pub event_cpi_authority: AccountInfo<'info>,
+
+    /// CHECK: This program, invoked by the event self-CPI.
+    #[account(address = crate::ID)]
+    pub program: AccountInfo<'info>,
+}
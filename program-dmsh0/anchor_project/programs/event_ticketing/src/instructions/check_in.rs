@@ -1,20 +1,151 @@
+use crate::constants::{LOYALTY_POINTS_PER_CHECK_IN, POAP_SEED, VAULT_SEED};
 use crate::errors::EventTicketingError;
-use crate::state::{Event, Ticket};
+use crate::rbac::{has_permission, Delegations, Permission};
+use crate::state::{
+    CoOrganizer, Event, LoyaltyAccount, Staff, Ticket, TransactionLog, VaultActivity,
+};
+use crate::transaction_log::record_vault_activity;
+use crate::vault::pay_refund;
 use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
 
-pub fn check_in(ctx: Context<CheckIn>) -> Result<()> {
+/// Admits the ticket's holder. `scan_nonce` comes from the scanned QR code
+/// or NFC read and must exceed the last one accepted for the ticket.
+pub fn check_in(ctx: Context<CheckIn>, scan_nonce: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
     let ticket = &mut ctx.accounts.ticket;
 
-    require!(!ticket.is_used, EventTicketingError::AlreadyCheckedIn);
-    require!(!ticket.refunded, EventTicketingError::AlreadyRefunded);
+    // `return_attendance_deposit` loads the event again, so the borrow ends
+    // with this block.
+    let (mints_collectible, returns_deposit) = {
+        let event = &mut ctx.accounts.event.load_mut()?;
 
-    ticket.is_used = true;
+        has_permission(
+            event,
+            &ctx.accounts.operator.key(),
+            Permission::CheckIn,
+            &Delegations {
+                staff: ctx.accounts.staff.as_deref(),
+                co_organizer: ctx.accounts.co_organizer.as_deref(),
+                ..Default::default()
+            },
+        )?;
+        require!(event.doors_are_open(now), EventTicketingError::DoorsClosed);
+
+        ticket.record_check_in(event, ctx.accounts.operator.key(), scan_nonce, now)?;
+
+        msg!(
+            "Ticket #{} for event {} checked in by {}",
+            ticket.ticket_id,
+            event.event_id,
+            ctx.accounts.operator.key()
+        );
+
+        let first_entry = ticket.checked_in_count == 1;
+        (
+            first_entry && event.poap_mint().is_some(),
+            first_entry && event.attendance_deposit > 0,
+        )
+    };
+
+    if let Some(loyalty) = &mut ctx.accounts.loyalty {
+        loyalty.accrue(LOYALTY_POINTS_PER_CHECK_IN);
+    }
+
+    if mints_collectible {
+        mint_attendance_collectible(&ctx)?;
+    }
+
+    if returns_deposit {
+        return_attendance_deposit(&ctx)?;
+    }
+
+    Ok(())
+}
+
+/// Pays the ticket's attendance deposit back to its holder (the lender, while
+/// lent out) on their first check-in.
+fn return_attendance_deposit(ctx: &Context<CheckIn>) -> Result<()> {
+    let (Some(vault), Some(deposit_recipient), Some(system_program), Some(vault_bump)) = (
+        &ctx.accounts.vault,
+        &ctx.accounts.deposit_recipient,
+        &ctx.accounts.system_program,
+        ctx.bumps.vault,
+    ) else {
+        return err!(EventTicketingError::DepositAccountsMissing);
+    };
+
+    let event = &ctx.accounts.event.load()?;
+    let ticket = &ctx.accounts.ticket;
+    require_keys_eq!(
+        deposit_recipient.key(),
+        ticket.lender.unwrap_or(ticket.owner),
+        EventTicketingError::InvalidDepositRecipient
+    );
+
+    let amount = ticket.purchase_price.min(event.attendance_deposit);
+    pay_refund(
+        vault,
+        vault_bump,
+        ctx.accounts.event.key(),
+        None,
+        deposit_recipient,
+        &system_program.to_account_info(),
+        amount,
+    )?;
+    record_vault_activity(
+        event,
+        ctx.accounts.transaction_log.as_ref(),
+        VaultActivity::Refund,
+        deposit_recipient.key(),
+        amount,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    msg!(
+        "Attendance deposit of {} lamports returned to {}",
+        amount,
+        deposit_recipient.key()
+    );
+
+    Ok(())
+}
+
+/// Mints one attendance collectible to the ticket owner on their first check-in.
+fn mint_attendance_collectible(ctx: &Context<CheckIn>) -> Result<()> {
+    let (Some(poap_mint), Some(poap_authority), Some(attendee_poap_account), Some(token_program)) = (
+        &ctx.accounts.poap_mint,
+        &ctx.accounts.poap_authority,
+        &ctx.accounts.attendee_poap_account,
+        &ctx.accounts.token_program,
+    ) else {
+        return err!(EventTicketingError::PoapAccountsMissing);
+    };
+
+    let event_key = ctx.accounts.event.key();
+    let bump = ctx
+        .bumps
+        .poap_authority
+        .ok_or(EventTicketingError::PoapAccountsMissing)?;
+    let seeds = &[POAP_SEED, event_key.as_ref(), &[bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    token::mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                mint: poap_mint.to_account_info(),
+                to: attendee_poap_account.to_account_info(),
+                authority: poap_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        1,
+    )?;
 
     msg!(
-        "Ticket #{} for event {} checked in by {}",
-        ticket.ticket_id,
-        ctx.accounts.event.event_id,
-        ticket.owner
+        "Attendance collectible minted to {}",
+        ctx.accounts.ticket.owner
     );
 
     Ok(())
@@ -22,7 +153,8 @@ pub fn check_in(ctx: Context<CheckIn>) -> Result<()> {
 
 #[derive(Accounts)]
 pub struct CheckIn<'info> {
-    pub event: Account<'info, Event>,
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
 
     #[account(
         mut,
@@ -30,8 +162,61 @@ pub struct CheckIn<'info> {
     )]
     pub ticket: Account<'info, Ticket>,
 
+    /// Staff delegation record; required when `operator` is not the event authority.
+    #[account(constraint = staff.event == event.key() @ EventTicketingError::UnauthorizedCheckIn)]
+    pub staff: Option<Account<'info, Staff>>,
+
+    /// Co-organizer record; an alternative to `staff` for teammates allowed
+    /// to check in.
+    #[account(constraint = co_organizer.event == event.key() @ EventTicketingError::UnauthorizedCheckIn)]
+    pub co_organizer: Option<Account<'info, CoOrganizer>>,
+
+    /// Attendance collectible mint; required when the event has one configured.
     #[account(
-        constraint = event_authority.key() == event.event_authority @ EventTicketingError::UnauthorizedCheckIn
+        mut,
+        constraint = event.load()?.poap_mint() == Some(poap_mint.key()) @ EventTicketingError::PoapMintMismatch
     )]
-    pub event_authority: Signer<'info>,
+    pub poap_mint: Option<Account<'info, Mint>>,
+
+    /// CHECK: PDA that signs attendance collectible mints. Verified by seeds.
+    #[account(seeds = [POAP_SEED, event.key().as_ref()], bump)]
+    pub poap_authority: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        constraint = event.load()?.poap_mint() == Some(attendee_poap_account.mint) @ EventTicketingError::PoapMintMismatch,
+        constraint = attendee_poap_account.owner == ticket.owner @ EventTicketingError::PoapMintMismatch
+    )]
+    pub attendee_poap_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// Ticket owner's loyalty account with the organizer; accrues points on check-in.
+    #[account(
+        mut,
+        constraint = loyalty.organizer == event.load()?.event_authority
+            && loyalty.attendee == ticket.owner @ EventTicketingError::InvalidLoyaltyAccount
+    )]
+    pub loyalty: Option<Account<'info, LoyaltyAccount>>,
+
+    /// CHECK: Event vault holding attendance deposits; required when the
+    /// event has one. Verified by seeds.
+    #[account(mut, seeds = [VAULT_SEED, event.key().as_ref()], bump)]
+    pub vault: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Receives the attendance deposit; must be the ticket holder.
+    #[account(mut)]
+    pub deposit_recipient: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Option<Program<'info, System>>,
+
+    /// Vault activity log; required when the event keeps one and returns an
+    /// attendance deposit.
+    #[account(
+        mut,
+        constraint = event.load()?.transaction_log() == Some(transaction_log.key()) @ EventTicketingError::InvalidTransactionLog
+    )]
+    pub transaction_log: Option<AccountLoader<'info, TransactionLog>>,
+
+    pub operator: Signer<'info>,
 }
@@ -0,0 +1,80 @@
+use crate::errors::EventTicketingError;
+use crate::rbac::{has_permission, Delegations, Permission};
+use crate::state::{CoOrganizer, Event, Staff, Ticket};
+use anchor_lang::prelude::*;
+
+/// Checks in every Ticket passed (writable) in `remaining_accounts`, with
+/// `scan_nonces` holding each ticket's scan nonce in the same order. The
+/// whole batch fails if any ticket is invalid, already used or replayed.
+pub fn check_in_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CheckInBatch<'info>>,
+    scan_nonces: Vec<u64>,
+) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    has_permission(
+        event,
+        &ctx.accounts.operator.key(),
+        Permission::CheckIn,
+        &Delegations {
+            staff: ctx.accounts.staff.as_deref(),
+            co_organizer: ctx.accounts.co_organizer.as_deref(),
+            ..Default::default()
+        },
+    )?;
+    event.require_no_attendance_deposit()?;
+    let now = Clock::get()?.unix_timestamp;
+    require!(event.doors_are_open(now), EventTicketingError::DoorsClosed);
+    require!(
+        !ctx.remaining_accounts.is_empty(),
+        EventTicketingError::EmptyBatch
+    );
+    require!(
+        scan_nonces.len() == ctx.remaining_accounts.len(),
+        EventTicketingError::ScanNonceCountMismatch
+    );
+
+    for (ticket_info, scan_nonce) in ctx.remaining_accounts.iter().zip(scan_nonces) {
+        require!(
+            ticket_info.is_writable,
+            EventTicketingError::TicketNotWritable
+        );
+
+        let mut ticket = Account::<Ticket>::try_from(ticket_info)?;
+
+        require_keys_eq!(
+            ticket.event,
+            event_key,
+            EventTicketingError::UnauthorizedCheckIn
+        );
+        ticket.record_check_in(event, ctx.accounts.operator.key(), scan_nonce, now)?;
+        ticket.exit(&crate::ID)?;
+    }
+
+    msg!(
+        "{} tickets for event {} checked in by {}",
+        ctx.remaining_accounts.len(),
+        event.event_id,
+        ctx.accounts.operator.key()
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CheckInBatch<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    /// Staff delegation record; required when `operator` is not the event authority.
+    #[account(constraint = staff.event == event.key() @ EventTicketingError::UnauthorizedCheckIn)]
+    pub staff: Option<Account<'info, Staff>>,
+
+    /// Co-organizer record; an alternative to `staff` for teammates allowed
+    /// to check in.
+    #[account(constraint = co_organizer.event == event.key() @ EventTicketingError::UnauthorizedCheckIn)]
+    pub co_organizer: Option<Account<'info, CoOrganizer>>,
+
+    pub operator: Signer<'info>,
+}
@@ -0,0 +1,77 @@
+use crate::ed25519::verify_ed25519_instruction;
+use crate::errors::EventTicketingError;
+use crate::rbac::{has_permission, Delegations, Permission};
+use crate::state::{CoOrganizer, Event, Staff, Ticket};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+
+/// Checks in a ticket using a voucher signed by the ticket owner, e.g. from a
+/// QR code. The transaction must include an Ed25519 program instruction right
+/// before this one verifying the owner's signature over
+/// `event || ticket_id (u32 LE) || nonce (u64 LE)`. The nonce doubles as the
+/// scan nonce, so each voucher admits at most once.
+pub fn check_in_with_voucher(ctx: Context<CheckInWithVoucher>, nonce: u64) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    has_permission(
+        event,
+        &ctx.accounts.operator.key(),
+        Permission::CheckIn,
+        &Delegations {
+            staff: ctx.accounts.staff.as_deref(),
+            co_organizer: ctx.accounts.co_organizer.as_deref(),
+            ..Default::default()
+        },
+    )?;
+    event.require_no_attendance_deposit()?;
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(event.doors_are_open(now), EventTicketingError::DoorsClosed);
+
+    let ticket = &mut ctx.accounts.ticket;
+
+    let mut message = Vec::with_capacity(32 + 4 + 8);
+    message.extend_from_slice(ctx.accounts.event.key().as_ref());
+    message.extend_from_slice(&ticket.ticket_id.to_le_bytes());
+    message.extend_from_slice(&nonce.to_le_bytes());
+
+    verify_ed25519_instruction(&ctx.accounts.instructions_sysvar, &ticket.owner, &message)?;
+
+    ticket.record_check_in(event, ctx.accounts.operator.key(), nonce, now)?;
+
+    msg!(
+        "Ticket #{} for event {} checked in by voucher (nonce {})",
+        ticket.ticket_id,
+        event.event_id,
+        nonce
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CheckInWithVoucher<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        constraint = ticket.event == event.key() @ EventTicketingError::UnauthorizedCheckIn
+    )]
+    pub ticket: Account<'info, Ticket>,
+
+    /// Staff delegation record; required when `operator` is not the event authority.
+    #[account(constraint = staff.event == event.key() @ EventTicketingError::UnauthorizedCheckIn)]
+    pub staff: Option<Account<'info, Staff>>,
+
+    /// Co-organizer record; an alternative to `staff` for teammates allowed
+    /// to check in.
+    #[account(constraint = co_organizer.event == event.key() @ EventTicketingError::UnauthorizedCheckIn)]
+    pub co_organizer: Option<Account<'info, CoOrganizer>>,
+
+    pub operator: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, verified by address.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+}
@@ -0,0 +1,201 @@
+use super::mint_ticket_with_seat::release_seat;
+use crate::constants::{EVENT_CPI_AUTHORITY_SEED, VAULT_SEED};
+use crate::errors::EventTicketingError;
+use crate::events::{emit_cpi_event, TicketRefunded};
+use crate::payment::{pay_out_tokens, require_vault_token_account};
+use crate::sales_stats::record_daily_refunds;
+use crate::state::{
+    Event, RefundPool, SalesStats, SeatClaim, SeatMap, Ticket, TransactionLog, VaultActivity,
+};
+use crate::transaction_log::record_vault_activity;
+use crate::vault::pay_refund;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+/// Holder-initiated refund, available once `Event::refunds_claimable`: the
+/// event was canceled, or its organizer let the dispute window lapse.
+/// Canceled events pay out of their refund pool when it is passed; SPL-currency
+/// events pay out of the vault's token account.
+pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+    let ticket = &mut ctx.accounts.ticket;
+
+    require!(
+        event.refunds_claimable(now),
+        EventTicketingError::RefundsNotClaimable
+    );
+    require!(
+        !ticket.is_used(),
+        EventTicketingError::CannotRefundUsedTicket
+    );
+    require!(!ticket.refunded, EventTicketingError::AlreadyRefunded);
+    require!(!ticket.comped, EventTicketingError::CompTicketNotRefundable);
+
+    let refund_amount = ticket.purchase_price;
+
+    if event.currency().is_some() {
+        let (Some(vault_token_account), Some(holder_token_account), Some(token_program)) = (
+            &ctx.accounts.vault_token_account,
+            &ctx.accounts.holder_token_account,
+            &ctx.accounts.token_program,
+        ) else {
+            return err!(EventTicketingError::CurrencyAccountsMissing);
+        };
+        require_vault_token_account(event, &ctx.accounts.vault.key(), vault_token_account)?;
+        pay_out_tokens(
+            vault_token_account,
+            holder_token_account,
+            &ctx.accounts.vault,
+            ctx.bumps.vault,
+            event_key,
+            token_program,
+            refund_amount,
+        )?;
+    } else {
+        pay_refund(
+            &ctx.accounts.vault,
+            ctx.bumps.vault,
+            event_key,
+            ctx.accounts.refund_pool.as_mut(),
+            &ctx.accounts.holder.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            refund_amount,
+        )?;
+    }
+
+    ticket.refunded = true;
+    event.freed_seats += 1;
+    event.refunded_count += 1;
+    record_daily_refunds(
+        event,
+        ctx.accounts.sales_stats.as_deref_mut(),
+        1,
+        refund_amount,
+        now,
+    )?;
+    record_vault_activity(
+        event,
+        ctx.accounts.transaction_log.as_ref(),
+        VaultActivity::Refund,
+        ctx.accounts.holder.key(),
+        refund_amount,
+        now,
+    )?;
+
+    release_seat(
+        event,
+        ticket,
+        ctx.accounts.seat_map.as_ref(),
+        ctx.accounts.seat_claim.as_ref(),
+    )?;
+
+    emit_cpi_event(
+        &ctx.accounts.event_cpi_authority,
+        &TicketRefunded {
+            event: event_key,
+            ticket: ticket.key(),
+            ticket_id: ticket.ticket_id,
+            recipient: ctx.accounts.holder.key(),
+            amount: refund_amount,
+        },
+    )?;
+
+    msg!(
+        "Ticket #{} refund of {} claimed by {}",
+        ticket.ticket_id,
+        refund_amount,
+        ctx.accounts.holder.key()
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        constraint = ticket.event == event.key(),
+        constraint = ticket.refund_recipient() == holder.key() @ EventTicketingError::UnauthorizedRefundClaim
+    )]
+    pub ticket: Account<'info, Ticket>,
+
+    /// CHECK: This is the vault PDA that holds event funds. Verified by seeds.
+    #[account(
+        mut,
+        seeds = [
+            VAULT_SEED,
+            event.key().as_ref()
+        ],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    /// Event's refund pool; pays the refund instead of the vault once the
+    /// event is canceled.
+    #[account(
+        mut,
+        constraint = refund_pool.event == event.key() @ EventTicketingError::InvalidRefundPool
+    )]
+    pub refund_pool: Option<Account<'info, RefundPool>>,
+
+    /// Required to release the seat of a seated ticket.
+    #[account(
+        mut,
+        constraint = event.load()?.seat_map() == Some(seat_map.key()) @ EventTicketingError::InvalidSeatMap
+    )]
+    pub seat_map: Option<AccountLoader<'info, SeatMap>>,
+
+    /// Claim on the refunded ticket's seat, required when it has one; closing it
+    /// lets the seat be sold again.
+    #[account(
+        mut,
+        close = holder,
+        constraint = seat_claim.ticket == ticket.key() @ EventTicketingError::InvalidSeat
+    )]
+    pub seat_claim: Option<Account<'info, SeatClaim>>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    /// Vault's token account for SPL-currency events.
+    #[account(mut)]
+    pub vault_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Holder's token account receiving an SPL-currency refund.
+    #[account(
+        mut,
+        constraint = holder_token_account.owner == holder.key() @ EventTicketingError::InvalidRefundRecipient
+    )]
+    pub holder_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Daily sales buckets; required when the event keeps them.
+    #[account(
+        mut,
+        constraint = event.load()?.sales_stats() == Some(sales_stats.key()) @ EventTicketingError::InvalidSalesStats
+    )]
+    pub sales_stats: Option<Account<'info, SalesStats>>,
+
+    /// Vault activity log; required when the event keeps one.
+    #[account(
+        mut,
+        constraint = event.load()?.transaction_log() == Some(transaction_log.key()) @ EventTicketingError::InvalidTransactionLog
+    )]
+    pub transaction_log: Option<AccountLoader<'info, TransactionLog>>,
+
+    /// CHECK: Signs the event self-CPI. Verified by seeds.
+    #[account(seeds = [EVENT_CPI_AUTHORITY_SEED], bump)]
+    pub event_cpi_authority: AccountInfo<'info>,
+
+    /// CHECK: This program, invoked by the event self-CPI.
+    #[account(address = crate::ID)]
+    pub program: AccountInfo<'info>,
+}
@@ -0,0 +1,28 @@
+use crate::errors::EventTicketingError;
+use crate::state::Receipt;
+use anchor_lang::prelude::*;
+
+/// Closes a purchase receipt the payer no longer needs, returning its rent.
+/// The ticket itself is unaffected.
+pub fn close_receipt(ctx: Context<CloseReceipt>) -> Result<()> {
+    msg!(
+        "Receipt for ticket {} closed by {}",
+        ctx.accounts.receipt.ticket,
+        ctx.accounts.payer.key()
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseReceipt<'info> {
+    #[account(
+        mut,
+        close = payer,
+        has_one = payer @ EventTicketingError::UnauthorizedReceiptClose
+    )]
+    pub receipt: Account<'info, Receipt>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
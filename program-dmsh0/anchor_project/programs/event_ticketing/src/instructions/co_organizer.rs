@@ -0,0 +1,105 @@
+use crate::authority::require_event_authority;
+use crate::constants::CO_ORGANIZER_SEED;
+use crate::errors::EventTicketingError;
+use crate::state::{CoOrganizer, Event, EventAuthorityConfig};
+use anchor_lang::prelude::*;
+
+/// Grants `member` the actions in `permissions` (a mask of the
+/// `CoOrganizer::CAN_*` bits, such as a `rbac::Role` preset) on this event.
+/// Granting again replaces the previous permissions. Needs the same approval
+/// as other authority actions, so a multisig event's signers decide who may
+/// act alone.
+pub fn add_co_organizer(
+    ctx: Context<AddCoOrganizer>,
+    member: Pubkey,
+    permissions: u8,
+) -> Result<()> {
+    let event = &ctx.accounts.event.load()?;
+
+    require_event_authority(
+        event,
+        &ctx.accounts.event_authority.key(),
+        ctx.accounts.authority_config.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+    require!(
+        permissions != 0 && permissions & !CoOrganizer::ALL_PERMISSIONS == 0,
+        EventTicketingError::InvalidPermissions
+    );
+
+    let co_organizer = &mut ctx.accounts.co_organizer;
+    co_organizer.event = ctx.accounts.event.key();
+    co_organizer.member = member;
+    co_organizer.permissions = permissions;
+    co_organizer.added_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "Co-organizer {} added to event {} with permissions {:#06b}",
+        member,
+        event.event_id,
+        permissions
+    );
+
+    Ok(())
+}
+
+pub fn remove_co_organizer(ctx: Context<RemoveCoOrganizer>) -> Result<()> {
+    let event = &ctx.accounts.event.load()?;
+
+    require_event_authority(
+        event,
+        &ctx.accounts.event_authority.key(),
+        ctx.accounts.authority_config.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    msg!(
+        "Co-organizer {} removed from event {}",
+        ctx.accounts.co_organizer.member,
+        event.event_id
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(member: Pubkey)]
+pub struct AddCoOrganizer<'info> {
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(constraint = authority_config.event == event.key())]
+    pub authority_config: Option<Account<'info, EventAuthorityConfig>>,
+
+    #[account(
+        init_if_needed,
+        payer = event_authority,
+        space = CoOrganizer::SPACE,
+        seeds = [CO_ORGANIZER_SEED, event.key().as_ref(), member.as_ref()],
+        bump
+    )]
+    pub co_organizer: Account<'info, CoOrganizer>,
+
+    #[account(mut)]
+    pub event_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveCoOrganizer<'info> {
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(constraint = authority_config.event == event.key())]
+    pub authority_config: Option<Account<'info, EventAuthorityConfig>>,
+
+    #[account(
+        mut,
+        close = event_authority,
+        seeds = [CO_ORGANIZER_SEED, event.key().as_ref(), co_organizer.member.as_ref()],
+        bump
+    )]
+    pub co_organizer: Account<'info, CoOrganizer>,
+
+    #[account(mut)]
+    pub event_authority: Signer<'info>,
+}
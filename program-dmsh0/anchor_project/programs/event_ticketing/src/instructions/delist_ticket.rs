@@ -0,0 +1,38 @@
+use crate::constants::LISTING_SEED;
+use crate::errors::EventTicketingError;
+use crate::state::{Listing, Ticket};
+use anchor_lang::prelude::*;
+
+/// Closes a listing. The seller can always delist; the ticket's current owner
+/// can also clear a stale listing left behind after a direct transfer.
+pub fn delist_ticket(ctx: Context<DelistTicket>) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+
+    require!(
+        authority == ctx.accounts.listing.seller || authority == ctx.accounts.ticket.owner,
+        EventTicketingError::UnauthorizedListing
+    );
+
+    msg!("Ticket #{} delisted", ctx.accounts.ticket.ticket_id);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DelistTicket<'info> {
+    pub ticket: Account<'info, Ticket>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [LISTING_SEED, ticket.key().as_ref()],
+        bump
+    )]
+    pub listing: Account<'info, Listing>,
+
+    /// CHECK: The seller recorded on the listing; receives the listing rent back.
+    #[account(mut, address = listing.seller)]
+    pub seller: AccountInfo<'info>,
+
+    pub authority: Signer<'info>,
+}
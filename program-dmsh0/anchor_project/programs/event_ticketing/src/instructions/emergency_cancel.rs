@@ -0,0 +1,117 @@
+use crate::constants::{CONFIG_SEED, EVENT_CPI_AUTHORITY_SEED, REFUND_POOL_SEED, VAULT_SEED};
+use crate::errors::EventTicketingError;
+use crate::events::{emit_cpi_event, EventCanceled};
+use crate::state::{Config, Event, RefundPool, TransactionLog, VaultActivity};
+use crate::transaction_log::record_vault_activity;
+use crate::vault::sweep_to_refund_pool;
+use anchor_lang::prelude::*;
+
+/// Config admin's kill switch for fraudulent events. Cancels the event even
+/// after doors open, which lets holders claim refunds, and freezes organizer
+/// withdrawals for good. Vault funds are swept into the refund pool, also
+/// when the organizer had already canceled.
+pub fn emergency_cancel(ctx: Context<EmergencyCancel>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    require!(!event.is_finalized(), EventTicketingError::EventFinalized);
+
+    if !event.canceled() {
+        event.canceled = true.into();
+        event.canceled_at = now;
+
+        emit_cpi_event(
+            &ctx.accounts.event_cpi_authority,
+            &EventCanceled {
+                event: event_key,
+                event_id: event.event_id,
+                canceled_by: ctx.accounts.admin.key(),
+                canceled_at: now,
+            },
+        )?;
+    }
+    event.withdrawals_frozen = true.into();
+
+    let refund_pool = &mut ctx.accounts.refund_pool;
+    refund_pool.event = event_key;
+    if event.currency().is_none() {
+        let pooled = sweep_to_refund_pool(
+            &ctx.accounts.vault,
+            ctx.bumps.vault,
+            refund_pool,
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        if pooled > 0 {
+            record_vault_activity(
+                event,
+                ctx.accounts.transaction_log.as_ref(),
+                VaultActivity::Sweep,
+                refund_pool.key(),
+                pooled,
+                now,
+            )?;
+        }
+    }
+
+    msg!(
+        "Event {} emergency canceled by admin {}",
+        event.event_id,
+        ctx.accounts.admin.key()
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct EmergencyCancel<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump,
+        has_one = admin @ EventTicketingError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = RefundPool::SPACE,
+        seeds = [REFUND_POOL_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub refund_pool: Account<'info, RefundPool>,
+
+    /// CHECK: This is the vault PDA that holds event funds. Verified by seeds.
+    #[account(
+        mut,
+        seeds = [
+            VAULT_SEED,
+            event.key().as_ref()
+        ],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Vault activity log; required when the event keeps one.
+    #[account(
+        mut,
+        constraint = event.load()?.transaction_log() == Some(transaction_log.key()) @ EventTicketingError::InvalidTransactionLog
+    )]
+    pub transaction_log: Option<AccountLoader<'info, TransactionLog>>,
+
+    /// CHECK: Signs the event self-CPI. Verified by seeds.
+    #[account(seeds = [EVENT_CPI_AUTHORITY_SEED], bump)]
+    pub event_cpi_authority: AccountInfo<'info>,
+
+    /// CHECK: This program, invoked by the event self-CPI.
+    #[account(address = crate::ID)]
+    pub program: AccountInfo<'info>,
+}
@@ -0,0 +1,42 @@
+use crate::constants::SALES_STATS_SEED;
+use crate::errors::EventTicketingError;
+use crate::state::{Event, SalesStats};
+use anchor_lang::prelude::*;
+
+/// Starts keeping daily sales buckets for the event. Only sales and refunds
+/// from now on are counted; the event's running totals cover earlier ones.
+pub fn enable_sales_stats(ctx: Context<EnableSalesStats>) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+    let sales_stats = &mut ctx.accounts.sales_stats;
+
+    sales_stats.event = event_key;
+    event.sales_stats = sales_stats.key();
+
+    msg!("Event {} keeps daily sales stats", event.event_id);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct EnableSalesStats<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        init,
+        payer = event_authority,
+        space = SalesStats::SPACE,
+        seeds = [SALES_STATS_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub sales_stats: Account<'info, SalesStats>,
+
+    #[account(mut)]
+    pub event_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
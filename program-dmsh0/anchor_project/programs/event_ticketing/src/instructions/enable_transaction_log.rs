@@ -0,0 +1,42 @@
+use crate::constants::TRANSACTION_LOG_SEED;
+use crate::errors::EventTicketingError;
+use crate::state::{Event, TransactionLog};
+use anchor_lang::prelude::*;
+
+/// Starts logging the event's vault movements. Only mints, refunds and
+/// withdrawals from now on are recorded.
+pub fn enable_transaction_log(ctx: Context<EnableTransactionLog>) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+    let mut transaction_log = ctx.accounts.transaction_log.load_init()?;
+
+    transaction_log.event = event_key;
+    event.transaction_log = ctx.accounts.transaction_log.key();
+
+    msg!("Event {} logs its vault activity", event.event_id);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct EnableTransactionLog<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        init,
+        payer = event_authority,
+        space = TransactionLog::SPACE,
+        seeds = [TRANSACTION_LOG_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub transaction_log: AccountLoader<'info, TransactionLog>,
+
+    #[account(mut)]
+    pub event_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,84 @@
+use crate::authority::require_event_authority;
+use crate::constants::VAULT_SEED;
+use crate::errors::EventTicketingError;
+use crate::state::{Event, EventAuthorityConfig, OrganizerRegistry};
+use crate::vault::available_funds;
+use anchor_lang::prelude::*;
+
+/// Moves an ended event into its terminal state: sales, refunds and
+/// check-ins stop, final attendance and revenue are recorded, and proceeds
+/// unlock once the payout timelock has passed.
+pub fn finalize_event(ctx: Context<FinalizeEvent>) -> Result<()> {
+    require_event_authority(
+        &*ctx.accounts.event.load()?,
+        &ctx.accounts.event_authority.key(),
+        ctx.accounts.authority_config.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    require!(!event.canceled(), EventTicketingError::EventCanceled);
+    require!(!event.is_finalized(), EventTicketingError::EventFinalized);
+    // Events without an end time never count as ended, so an organizer
+    // cannot settle before the event has happened.
+    require!(event.has_ended(now), EventTicketingError::EventNotEnded);
+    // Once holders may claim refunds the organizer can no longer settle.
+    require!(
+        !event.refunds_claimable(now),
+        EventTicketingError::ProceedsFrozen
+    );
+
+    event.finalized_at = now;
+    event.final_revenue = available_funds(&ctx.accounts.vault)?;
+    event.no_show_count = event.no_shows();
+
+    if event.bond_organizer().is_some() {
+        let organizer_registry = ctx
+            .accounts
+            .organizer_registry
+            .as_mut()
+            .ok_or(EventTicketingError::BondAccountsMissing)?;
+        organizer_registry.open_events = organizer_registry.open_events.saturating_sub(1);
+    }
+
+    msg!(
+        "Event {} finalized: {} of {} tickets checked in, {} no-shows, {} lamports in vault",
+        event.event_id,
+        event.checked_in_count,
+        event.sold,
+        event.no_show_count,
+        event.final_revenue
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinalizeEvent<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(constraint = authority_config.event == event.key())]
+    pub authority_config: Option<Account<'info, EventAuthorityConfig>>,
+
+    /// CHECK: This is the vault PDA that holds event funds. Verified by seeds.
+    #[account(
+        seeds = [
+            VAULT_SEED,
+            event.key().as_ref()
+        ],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    /// Bonding organizer's registry; required when the event holds a bond open.
+    #[account(
+        mut,
+        constraint = event.load()?.bond_organizer() == Some(organizer_registry.organizer) @ EventTicketingError::BondAccountsMissing
+    )]
+    pub organizer_registry: Option<Account<'info, OrganizerRegistry>>,
+
+    pub event_authority: Signer<'info>,
+}
@@ -0,0 +1,34 @@
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+
+pub fn freeze_transfers(ctx: Context<FreezeTransfers>) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    event.transfers_frozen = true.into();
+
+    msg!("Ticket transfers frozen for event {}", event.event_id);
+
+    Ok(())
+}
+
+pub fn unfreeze_transfers(ctx: Context<FreezeTransfers>) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    event.transfers_frozen = false.into();
+
+    msg!("Ticket transfers unfrozen for event {}", event.event_id);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FreezeTransfers<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub event_authority: Signer<'info>,
+}
@@ -0,0 +1,44 @@
+use crate::constants::CONFIG_SEED;
+use crate::program::EventTicketing;
+use crate::state::Config;
+use anchor_lang::prelude::*;
+
+pub fn initialize_config(ctx: Context<InitializeConfig>, max_unverified_supply: u32) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.admin = ctx.accounts.admin.key();
+    config.max_unverified_supply = max_unverified_supply;
+    config.organizer_bond_lamports = 0;
+
+    msg!(
+        "Config initialized with admin {} (max unverified supply: {})",
+        config.admin,
+        max_unverified_supply
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = Config::SPACE,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, EventTicketing>,
+
+    /// Only the program's upgrade authority may become the config admin.
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()))]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,8 +1,15 @@
+use super::bond::lock_organizer_bond;
 use crate::constants::*;
 use crate::errors::EventTicketingError;
-use crate::state::Event;
+use crate::state::{
+    Config, Event, EventMetadata, EventRegistry, EventRegistryPage, OrganizerRegistry, SeatLayout,
+    SeatMap,
+};
+use crate::vault::fund_vault_reserve;
 use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
 
+#[allow(clippy::too_many_arguments)]
 pub fn initialize_event(
     ctx: Context<InitializeEvent>,
     event_id: u32,
@@ -10,33 +17,120 @@ pub fn initialize_event(
     supply: u32,
     name: String,
     date: String,
+    transferable: bool,
+    seat_layout: Option<SeatLayout>,
+    one_per_wallet: bool,
+    max_total_supply: Option<u32>,
 ) -> Result<()> {
     require!(name.len() <= MAX_NAME_LEN, EventTicketingError::NameTooLong);
     require!(date.len() <= MAX_DATE_LEN, EventTicketingError::DateTooLong);
+    // A transfer would let a wallet end up holding a second ticket.
+    require!(
+        !(one_per_wallet && transferable),
+        EventTicketingError::OnePerWalletTransferable
+    );
 
-    let event = &mut ctx.accounts.event;
+    // Without an explicit ceiling the event can never grow.
+    let max_total_supply = max_total_supply.unwrap_or(supply);
+    require!(
+        max_total_supply >= supply,
+        EventTicketingError::InvalidMaxTotalSupply
+    );
 
-    event.event_authority = ctx.accounts.event_authority.key();
+    // The ceiling is what the event may grow to, so it is what the
+    // unverified limit applies to.
+    require_supply_allowed(
+        &ctx.accounts.config,
+        ctx.accounts.organizer_registry.as_deref(),
+        max_total_supply,
+    )?;
+    let bond_organizer = lock_organizer_bond(
+        &ctx.accounts.config,
+        ctx.accounts.organizer_registry.as_deref_mut(),
+    )?;
+
+    fund_vault_reserve(
+        &ctx.accounts.event_authority,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+    )?;
+
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_init()?;
+
+    event.init(ctx.accounts.event_authority.key(), event_id);
     event.price = price;
     event.supply = supply;
-    event.sold = 0;
-    event.canceled = false;
-    event.event_id = event_id;
-    event.name = name;
-    event.date = date;
+    event.max_total_supply = max_total_supply;
+    event.transferable = transferable.into();
+    event.one_per_wallet = one_per_wallet.into();
+    event.bond_organizer = bond_organizer.unwrap_or_default();
+    event.currency = ctx
+        .accounts
+        .currency_mint
+        .as_ref()
+        .map(|mint| mint.key())
+        .unwrap_or_default();
+
+    if let Some(layout) = seat_layout {
+        let total_seats =
+            layout.sections as usize * layout.rows as usize * layout.seats_per_row as usize;
+        require!(
+            total_seats <= MAX_SEATS,
+            EventTicketingError::SeatLayoutTooLarge
+        );
+
+        let seat_map_loader = ctx
+            .accounts
+            .seat_map
+            .as_ref()
+            .ok_or(EventTicketingError::MissingSeatMap)?;
+        let mut seat_map = seat_map_loader.load_init()?;
+
+        seat_map.event = event_key;
+        seat_map.sections = layout.sections;
+        seat_map.rows = layout.rows;
+        seat_map.seats_per_row = layout.seats_per_row;
+
+        event.seat_map = seat_map_loader.key();
+    }
+
+    ctx.accounts
+        .registry
+        .record(&mut ctx.accounts.registry_page, event_key);
+
+    let metadata = &mut ctx.accounts.metadata;
+
+    metadata.event = event_key;
+    metadata.name = name;
+    metadata.date = date;
+    metadata.metadata_uri = String::new();
 
     msg!("Event initialized with ID: {}", event_id);
 
     Ok(())
 }
 
+/// Events above the config's unverified supply limit need a verified organizer.
+pub(crate) fn require_supply_allowed(
+    config: &Config,
+    organizer_registry: Option<&OrganizerRegistry>,
+    supply: u32,
+) -> Result<()> {
+    if supply > config.max_unverified_supply {
+        let verified = organizer_registry.is_some_and(|registry| registry.verified);
+        require!(verified, EventTicketingError::OrganizerNotVerified);
+    }
+    Ok(())
+}
+
 #[derive(Accounts)]
 #[instruction(event_id: u32)]
 pub struct InitializeEvent<'info> {
     #[account(
         init,
         payer = event_authority,
-        space = Event::space(MAX_NAME_LEN, MAX_DATE_LEN),
+        space = Event::SPACE,
         seeds = [
             EVENT_SEED,
             event_authority.key().as_ref(),
@@ -44,7 +138,61 @@ pub struct InitializeEvent<'info> {
         ],
         bump
     )]
-    pub event: Account<'info, Event>,
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        init,
+        payer = event_authority,
+        space = EventMetadata::SPACE,
+        seeds = [EVENT_METADATA_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub metadata: Account<'info, EventMetadata>,
+
+    /// CHECK: This is the vault PDA that will hold event funds. Verified by seeds.
+    #[account(
+        mut,
+        seeds = [
+            VAULT_SEED,
+            event.key().as_ref()
+        ],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = event_authority,
+        space = EventRegistry::SPACE,
+        seeds = [REGISTRY_SEED],
+        bump
+    )]
+    pub registry: Account<'info, EventRegistry>,
+
+    #[account(
+        init_if_needed,
+        payer = event_authority,
+        space = EventRegistryPage::SPACE,
+        seeds = [REGISTRY_PAGE_SEED, &registry.current_page().to_le_bytes()],
+        bump
+    )]
+    pub registry_page: Account<'info, EventRegistryPage>,
+
+    /// Required when `max_total_supply` exceeds the config's unverified limit or when
+    /// organizer bonds are enabled.
+    #[account(mut, constraint = organizer_registry.organizer == event_authority.key())]
+    pub organizer_registry: Option<Account<'info, OrganizerRegistry>>,
+
+    /// Pre-allocated (zeroed, program-owned) account for seated venues; required
+    /// when `seat_layout` is given.
+    #[account(zero)]
+    pub seat_map: Option<AccountLoader<'info, SeatMap>>,
+
+    /// SPL mint tickets are priced in; omitted for SOL events.
+    pub currency_mint: Option<Account<'info, Mint>>,
 
     #[account(mut)]
     pub event_authority: Signer<'info>,
@@ -0,0 +1,121 @@
+use crate::constants::*;
+use crate::errors::EventTicketingError;
+use crate::events::{emit_cpi_event, TicketAttributesSet, TicketMinted};
+use crate::sales_stats::record_daily_sales;
+use crate::state::{Event, SalesStats, Ticket};
+use anchor_lang::prelude::*;
+
+/// Issues a free ticket to `recipient`, optionally with `attributes` (see
+/// `set_ticket_attributes`). Comped tickets count against supply but never
+/// pass through the vault, so they can't be refunded.
+pub fn issue_comp_ticket(
+    ctx: Context<IssueCompTicket>,
+    recipient: Pubkey,
+    attributes: Vec<u8>,
+) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+    let ticket = &mut ctx.accounts.ticket;
+
+    require!(!event.canceled(), EventTicketingError::EventCanceled);
+    require!(!event.is_finalized(), EventTicketingError::EventFinalized);
+    require!(event.available() > 0, EventTicketingError::EventSoldOut);
+    event.require_numbered_tickets()?;
+
+    let ticket_id = event.take_ticket_id()?;
+
+    ticket.issue(
+        recipient,
+        event_key,
+        ticket_id,
+        0,
+        Clock::get()?.unix_timestamp,
+    );
+    ticket.comped = true;
+    ticket.set_attributes(attributes)?;
+
+    event.sold += 1;
+    let fees = event.record_sale(0, 0)?;
+    record_daily_sales(
+        event,
+        ctx.accounts.sales_stats.as_deref_mut(),
+        1,
+        0,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    emit_cpi_event(
+        &ctx.accounts.event_cpi_authority,
+        &TicketMinted {
+            event: event_key,
+            ticket: ticket.key(),
+            ticket_id,
+            owner: recipient,
+            price: 0,
+            fees,
+        },
+    )?;
+
+    if !ticket.attributes.is_empty() {
+        emit_cpi_event(
+            &ctx.accounts.event_cpi_authority,
+            &TicketAttributesSet {
+                event: event_key,
+                ticket: ticket.key(),
+                ticket_id,
+                attributes: ticket.attributes.clone(),
+            },
+        )?;
+    }
+
+    msg!(
+        "Comp ticket #{} issued for event {} to {}",
+        ticket_id,
+        event.event_id,
+        recipient
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct IssueCompTicket<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        init,
+        payer = event_authority,
+        space = Ticket::SPACE,
+        seeds = [
+            TICKET_SEED,
+            event.key().as_ref(),
+            &event.load()?.next_ticket_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub ticket: Account<'info, Ticket>,
+
+    #[account(mut)]
+    pub event_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Daily sales buckets; required when the event keeps them.
+    #[account(
+        mut,
+        constraint = event.load()?.sales_stats() == Some(sales_stats.key()) @ EventTicketingError::InvalidSalesStats
+    )]
+    pub sales_stats: Option<Account<'info, SalesStats>>,
+
+    /// CHECK: Signs the event self-CPI. Verified by seeds.
+    #[account(seeds = [EVENT_CPI_AUTHORITY_SEED], bump)]
+    pub event_cpi_authority: AccountInfo<'info>,
+
+    /// CHECK: This program, invoked by the event self-CPI.
+    #[account(address = crate::ID)]
+    pub program: AccountInfo<'info>,
+}
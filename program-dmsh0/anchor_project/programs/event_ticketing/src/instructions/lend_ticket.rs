@@ -0,0 +1,81 @@
+use super::transfer_ticket::require_transferable;
+use crate::errors::EventTicketingError;
+use crate::state::{Event, Ticket};
+use anchor_lang::prelude::*;
+
+/// Lends the ticket to `borrower` until `until`. The borrower becomes the
+/// owner for check-in purposes but cannot transfer, list or upgrade it, and
+/// refunds still go to the lender. `reclaim_ticket` hands it back.
+pub fn lend_ticket(ctx: Context<LendTicket>, until: i64) -> Result<()> {
+    let ticket = &mut ctx.accounts.ticket;
+    let borrower = ctx.accounts.borrower.key();
+
+    require_transferable(&*ctx.accounts.event.load()?, ticket)?;
+    require!(
+        borrower != ticket.owner,
+        EventTicketingError::InvalidTransferRecipient
+    );
+    require!(
+        until > Clock::get()?.unix_timestamp,
+        EventTicketingError::InvalidLoanDeadline
+    );
+
+    ticket.lender = Some(ticket.owner);
+    ticket.lent_until = until;
+    ticket.owner = borrower;
+
+    msg!(
+        "Ticket #{} lent to {} until {}",
+        ticket.ticket_id,
+        borrower,
+        until
+    );
+
+    Ok(())
+}
+
+/// Returns a lent ticket to its lender. Anyone can reclaim once the loan has
+/// expired; the borrower can also return it early.
+pub fn reclaim_ticket(ctx: Context<ReclaimTicket>) -> Result<()> {
+    let ticket = &mut ctx.accounts.ticket;
+    let lender = ticket.lender.ok_or(EventTicketingError::TicketNotLent)?;
+
+    require!(
+        Clock::get()?.unix_timestamp >= ticket.lent_until
+            || ctx.accounts.authority.key() == ticket.owner,
+        EventTicketingError::LoanNotExpired
+    );
+
+    ticket.owner = lender;
+    ticket.lender = None;
+    ticket.lent_until = 0;
+
+    msg!("Ticket #{} returned to {}", ticket.ticket_id, lender);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct LendTicket<'info> {
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        constraint = ticket.owner == owner.key() @ EventTicketingError::UnauthorizedTransfer,
+        constraint = ticket.event == event.key()
+    )]
+    pub ticket: Account<'info, Ticket>,
+
+    /// CHECK: The wallet allowed to use the ticket until the deadline. Can be any account.
+    pub borrower: AccountInfo<'info>,
+
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimTicket<'info> {
+    #[account(mut)]
+    pub ticket: Account<'info, Ticket>,
+
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,75 @@
+use crate::constants::LISTING_SEED;
+use crate::errors::EventTicketingError;
+use crate::state::{Event, Listing, Ticket};
+use anchor_lang::prelude::*;
+
+pub fn list_ticket(ctx: Context<ListTicket>, price: u64) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+    let event = &ctx.accounts.event.load()?;
+    let ticket = &ctx.accounts.ticket;
+
+    require!(
+        event.transferable(),
+        EventTicketingError::TicketNotTransferable
+    );
+    require!(
+        !event.transfers_frozen(),
+        EventTicketingError::TransfersFrozen
+    );
+    require!(!ticket.is_used(), EventTicketingError::TicketAlreadyUsed);
+    require!(!ticket.revoked, EventTicketingError::TicketRevoked);
+    require!(!ticket.refunded, EventTicketingError::AlreadyRefunded);
+    require!(!ticket.is_lent(), EventTicketingError::TicketOnLoan);
+    event.require_approved_venue(ctx.accounts.venue.as_ref().map(|venue| venue.key()))?;
+    if let Some(max_price) = event.max_resale_price() {
+        require!(price <= max_price, EventTicketingError::ResalePriceTooHigh);
+    }
+
+    let listing = &mut ctx.accounts.listing;
+
+    listing.ticket = ticket.key();
+    listing.event = event_key;
+    listing.seller = ctx.accounts.seller.key();
+    listing.price = price;
+    listing.listed_at = Clock::get()?.unix_timestamp;
+    listing.organizer_bps = event.resale_organizer_bps;
+    listing.protocol_bps = event.resale_protocol_bps;
+
+    msg!(
+        "Ticket #{} for event {} listed for {} lamports",
+        ticket.ticket_id,
+        event.event_id,
+        price
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ListTicket<'info> {
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        constraint = ticket.owner == seller.key() @ EventTicketingError::UnauthorizedListing,
+        constraint = ticket.event == event.key()
+    )]
+    pub ticket: Account<'info, Ticket>,
+
+    #[account(
+        init,
+        payer = seller,
+        space = Listing::SPACE,
+        seeds = [LISTING_SEED, ticket.key().as_ref()],
+        bump
+    )]
+    pub listing: Account<'info, Listing>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// Marketplace routing the listing; required when the event restricts
+    /// resale venues.
+    pub venue: Option<Signer<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,40 @@
+use crate::constants::LOYALTY_SEED;
+use crate::state::LoyaltyAccount;
+use anchor_lang::prelude::*;
+
+/// Opens the attendee's loyalty account with `organizer`. Points accrue when
+/// the account is passed to `mint_ticket` and `check_in`.
+pub fn open_loyalty_account(ctx: Context<OpenLoyaltyAccount>, organizer: Pubkey) -> Result<()> {
+    let loyalty = &mut ctx.accounts.loyalty;
+
+    loyalty.organizer = organizer;
+    loyalty.attendee = ctx.accounts.attendee.key();
+    loyalty.points = 0;
+    loyalty.lifetime_points = 0;
+
+    msg!(
+        "Loyalty account opened for {} with organizer {}",
+        loyalty.attendee,
+        organizer
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(organizer: Pubkey)]
+pub struct OpenLoyaltyAccount<'info> {
+    #[account(
+        init,
+        payer = attendee,
+        space = LoyaltyAccount::SPACE,
+        seeds = [LOYALTY_SEED, organizer.as_ref(), attendee.key().as_ref()],
+        bump
+    )]
+    pub loyalty: Account<'info, LoyaltyAccount>,
+
+    #[account(mut)]
+    pub attendee: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,575 @@
+use crate::constants::{
+    BORSH_EVENT_DISCRIMINATOR, BPS_DENOMINATOR, EVENT_DISCRIMINATOR, EVENT_METADATA_SEED,
+    EVENT_VERSION, MAX_APPROVED_VENUES, MAX_DATE_LEN, MAX_NAME_LEN, ORGANIZER_VERSION,
+};
+use crate::errors::EventTicketingError;
+use crate::state::{Event, EventMetadata, OrganizerRegistry};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use std::mem::size_of;
+
+/// Grows `account` to `space` bytes if it is smaller, with `payer` covering
+/// the extra rent. The new bytes are zeroed, which is the default of every
+/// field appended to a layout.
+pub(crate) fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    space: usize,
+) -> Result<()> {
+    if account.data_len() >= space {
+        return Ok(());
+    }
+
+    let required = Rent::get()?.minimum_balance(space);
+    let shortfall = required.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    // `resize` is not available on every runtime this crate is built for.
+    #[allow(deprecated)]
+    account.realloc(space, false)?;
+
+    Ok(())
+}
+
+/// Zeroes an event account's `data` and stamps it with the zero-copy `Event`
+/// discriminator, returning the event to fill in. The account must already
+/// be at least `Event::SPACE` bytes.
+fn reset_to_zero_copy(data: &mut [u8]) -> &mut Event {
+    data.fill(0);
+    data[..8].copy_from_slice(&EVENT_DISCRIMINATOR);
+    bytemuck::from_bytes_mut(&mut data[8..8 + size_of::<Event>()])
+}
+
+/// The original `Event` layout, with the name and date stored inline. Those
+/// accounts predate `version` and cannot be read as `Event`; they go through
+/// `migrate_legacy_event` instead of `migrate_event`.
+#[derive(AnchorDeserialize)]
+struct LegacyEvent {
+    event_authority: Pubkey,
+    price: u64,
+    supply: u32,
+    sold: u32,
+    canceled: bool,
+    event_id: u32,
+    name: String,
+    date: String,
+}
+
+impl LegacyEvent {
+    /// Every legacy event was allocated at exactly this size.
+    const SPACE: usize = 8 + 32 + 8 + 4 + 4 + 1 + 4 + 4 + MAX_NAME_LEN + 4 + MAX_DATE_LEN;
+}
+
+/// Upgrades an event created before names and dates moved to
+/// `EventMetadata`: the strings go to a new metadata account and the event
+/// is rewritten on the current layout with the defaults of a new event,
+/// keeping its authority, id, price, supply, sales and cancellation.
+pub fn migrate_legacy_event(ctx: Context<MigrateLegacyEvent>) -> Result<()> {
+    let info = ctx.accounts.event.to_account_info();
+
+    let legacy = {
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() == LegacyEvent::SPACE && data.starts_with(&BORSH_EVENT_DISCRIMINATOR),
+            EventTicketingError::NotALegacyEvent
+        );
+        LegacyEvent::deserialize(&mut &data[8..])?
+    };
+
+    let metadata = &mut ctx.accounts.metadata;
+    metadata.event = info.key();
+    metadata.name = legacy.name;
+    metadata.date = legacy.date;
+    metadata.metadata_uri = String::new();
+
+    grow_account(
+        &info,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        Event::SPACE,
+    )?;
+
+    // Drop the inline strings and fill the event in like `initialize_event`.
+    let mut data = info.try_borrow_mut_data()?;
+    let event = reset_to_zero_copy(&mut data);
+    event.init(legacy.event_authority, legacy.event_id);
+    event.price = legacy.price;
+    event.supply = legacy.supply;
+    event.max_total_supply = legacy.supply;
+    event.sold = legacy.sold;
+    event.next_ticket_id = legacy.sold;
+    event.canceled = legacy.canceled.into();
+
+    msg!(
+        "Legacy event {} migrated to layout v{}",
+        event.event_id,
+        EVENT_VERSION
+    );
+
+    Ok(())
+}
+
+/// The Borsh `Event` layout used up to v17, before events became zero-copy.
+/// `migrate_event` reads it and rewrites the account on the current layout.
+#[derive(AnchorDeserialize)]
+struct BorshEvent {
+    event_authority: Pubkey,
+    price: u64,
+    supply: u32,
+    sold: u32,
+    canceled: bool,
+    event_id: u32,
+    pending_authority: Option<Pubkey>,
+    multisig_enabled: bool,
+    doors_open: i64,
+    doors_close: i64,
+    max_check_ins: u16,
+    transferable: bool,
+    transfers_frozen: bool,
+    transfer_fee_lamports: u64,
+    max_resale_multiplier_bps: u16,
+    max_per_wallet: u32,
+    allowlist_root: [u8; 32],
+    referral_bps: u16,
+    waitlist_len: u32,
+    waitlist_next: u32,
+    freed_seats: u32,
+    reserved: u32,
+    auction_start_price: u64,
+    auction_floor_price: u64,
+    auction_start: i64,
+    auction_end: i64,
+    curve_step_lamports: u64,
+    curve_step_size: u32,
+    usd_price_cents: u64,
+    seat_map: Option<Pubkey>,
+    series: Option<Pubkey>,
+    poap_mint: Option<Pubkey>,
+    end_time: i64,
+    payout_timelock_secs: i64,
+    finalized_at: i64,
+    dispute_window_secs: i64,
+    checked_in_count: u32,
+    final_revenue: u64,
+    sales_paused: bool,
+    canceled_at: i64,
+    refunded_count: u32,
+    refund_deadline: i64,
+    withdrawals_frozen: bool,
+    bond_organizer: Option<Pubkey>,
+    no_show_count: u32,
+    currency: Option<Pubkey>,
+    hook_program: Option<Pubkey>,
+    version: u8,
+    next_ticket_id: u32,
+    one_per_wallet: bool,
+    attestor: Option<Pubkey>,
+    gate_mint: Option<Pubkey>,
+    gate_min_amount: u64,
+    gate_collection: bool,
+    attendance_deposit: u64,
+    pay_what_you_want: bool,
+    donations: u64,
+    payout_config: Option<Pubkey>,
+    gross_sales: u64,
+    referral_fees: u64,
+    protocol_fees: u64,
+    organizer_net: u64,
+    transfer_fees: u64,
+    resale_volume: u64,
+    approved_venues: [Pubkey; MAX_APPROVED_VENUES],
+    approved_venue_count: u8,
+    sales_stats: Option<Pubkey>,
+    cancellation_cutoff: i64,
+    cancellation_fee_bps: u16,
+    resale_seller_bps: u16,
+    resale_organizer_bps: u16,
+    resale_protocol_bps: u16,
+    resale_organizer_fees: u64,
+    resale_protocol_fees: u64,
+    transaction_log: Option<Pubkey>,
+    max_total_supply: u32,
+    reissued_seats: u32,
+}
+
+impl BorshEvent {
+    const SPACE: usize = 8
+        + 32
+        + 8
+        + 4
+        + 4
+        + 1
+        + 4
+        + (1 + 32)
+        + 1
+        + 8
+        + 8
+        + 2
+        + 1
+        + 1
+        + 8
+        + 2
+        + 4
+        + 32
+        + 2
+        + 4
+        + 4
+        + 4
+        + 4
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 4
+        + 8
+        + (1 + 32)
+        + (1 + 32)
+        + (1 + 32)
+        + 8
+        + 8
+        + 8
+        + 8
+        + 4
+        + 8
+        + 1
+        + 8
+        + 4
+        + 8
+        + 1
+        + (1 + 32)
+        + 4
+        + (1 + 32)
+        + (1 + 32)
+        + 1
+        + 4
+        + 1
+        + (1 + 32)
+        + (1 + 32)
+        + 8
+        + 1
+        + 8
+        + 1
+        + 8
+        + (1 + 32)
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 32 * MAX_APPROVED_VENUES
+        + 1
+        + (1 + 32)
+        + 8
+        + 2
+        + 2
+        + 2
+        + 2
+        + 8
+        + 8
+        + (1 + 32)
+        + 4
+        + 4;
+
+    /// Copies every field into `event`, converting flags to `u8` and unset
+    /// keys to `Pubkey::default()`.
+    fn write_to(self, event: &mut Event) {
+        event.event_authority = self.event_authority;
+        event.price = self.price;
+        event.supply = self.supply;
+        event.sold = self.sold;
+        event.canceled = self.canceled.into();
+        event.event_id = self.event_id;
+        event.pending_authority = self.pending_authority.unwrap_or_default();
+        event.multisig_enabled = self.multisig_enabled.into();
+        event.doors_open = self.doors_open;
+        event.doors_close = self.doors_close;
+        event.max_check_ins = self.max_check_ins;
+        event.transferable = self.transferable.into();
+        event.transfers_frozen = self.transfers_frozen.into();
+        event.transfer_fee_lamports = self.transfer_fee_lamports;
+        event.max_resale_multiplier_bps = self.max_resale_multiplier_bps;
+        event.max_per_wallet = self.max_per_wallet;
+        event.allowlist_root = self.allowlist_root;
+        event.referral_bps = self.referral_bps;
+        event.waitlist_len = self.waitlist_len;
+        event.waitlist_next = self.waitlist_next;
+        event.freed_seats = self.freed_seats;
+        event.reserved = self.reserved;
+        event.auction_start_price = self.auction_start_price;
+        event.auction_floor_price = self.auction_floor_price;
+        event.auction_start = self.auction_start;
+        event.auction_end = self.auction_end;
+        event.curve_step_lamports = self.curve_step_lamports;
+        event.curve_step_size = self.curve_step_size;
+        event.usd_price_cents = self.usd_price_cents;
+        event.seat_map = self.seat_map.unwrap_or_default();
+        event.series = self.series.unwrap_or_default();
+        event.poap_mint = self.poap_mint.unwrap_or_default();
+        event.end_time = self.end_time;
+        event.payout_timelock_secs = self.payout_timelock_secs;
+        event.finalized_at = self.finalized_at;
+        event.dispute_window_secs = self.dispute_window_secs;
+        event.checked_in_count = self.checked_in_count;
+        event.final_revenue = self.final_revenue;
+        event.sales_paused = self.sales_paused.into();
+        event.canceled_at = self.canceled_at;
+        event.refunded_count = self.refunded_count;
+        event.refund_deadline = self.refund_deadline;
+        event.withdrawals_frozen = self.withdrawals_frozen.into();
+        event.bond_organizer = self.bond_organizer.unwrap_or_default();
+        event.no_show_count = self.no_show_count;
+        event.currency = self.currency.unwrap_or_default();
+        event.hook_program = self.hook_program.unwrap_or_default();
+        event.version = self.version;
+        event.next_ticket_id = self.next_ticket_id;
+        event.one_per_wallet = self.one_per_wallet.into();
+        event.attestor = self.attestor.unwrap_or_default();
+        event.gate_mint = self.gate_mint.unwrap_or_default();
+        event.gate_min_amount = self.gate_min_amount;
+        event.gate_collection = self.gate_collection.into();
+        event.attendance_deposit = self.attendance_deposit;
+        event.pay_what_you_want = self.pay_what_you_want.into();
+        event.donations = self.donations;
+        event.payout_config = self.payout_config.unwrap_or_default();
+        event.gross_sales = self.gross_sales;
+        event.referral_fees = self.referral_fees;
+        event.protocol_fees = self.protocol_fees;
+        event.organizer_net = self.organizer_net;
+        event.transfer_fees = self.transfer_fees;
+        event.resale_volume = self.resale_volume;
+        event.approved_venues = self.approved_venues;
+        event.approved_venue_count = self.approved_venue_count;
+        event.sales_stats = self.sales_stats.unwrap_or_default();
+        event.cancellation_cutoff = self.cancellation_cutoff;
+        event.cancellation_fee_bps = self.cancellation_fee_bps;
+        event.resale_seller_bps = self.resale_seller_bps;
+        event.resale_organizer_bps = self.resale_organizer_bps;
+        event.resale_protocol_bps = self.resale_protocol_bps;
+        event.resale_organizer_fees = self.resale_organizer_fees;
+        event.resale_protocol_fees = self.resale_protocol_fees;
+        event.transaction_log = self.transaction_log.unwrap_or_default();
+        event.max_total_supply = self.max_total_supply;
+        event.reissued_seats = self.reissued_seats;
+    }
+}
+
+/// Types of the `BorshEvent` fields ahead of `approved_venues`, grouped in tuples
+/// as Borsh reads them, used to find where the venues start.
+type PreVenueFields = (
+    (
+        Pubkey,
+        u64,
+        u32,
+        u32,
+        bool,
+        u32,
+        Option<Pubkey>,
+        bool,
+        i64,
+        i64,
+    ),
+    (u16, bool, bool, u64, u16, u32, [u8; 32], u16, u32, u32),
+    (u32, u32, u64, u64, i64, i64, u64, u32, u64, Option<Pubkey>),
+    (
+        Option<Pubkey>,
+        Option<Pubkey>,
+        i64,
+        i64,
+        i64,
+        i64,
+        u32,
+        u64,
+        bool,
+        i64,
+    ),
+    (
+        u32,
+        i64,
+        bool,
+        Option<Pubkey>,
+        u32,
+        Option<Pubkey>,
+        Option<Pubkey>,
+    ),
+    (
+        u8,
+        u32,
+        bool,
+        Option<Pubkey>,
+        Option<Pubkey>,
+        u64,
+        bool,
+        u64,
+        bool,
+        u64,
+    ),
+    (Option<Pubkey>, u64, u64, u64, u64, u64, u64),
+);
+
+/// Layouts 10 to 16 stored `approved_venues` as a length-prefixed list of
+/// only the keys set. Rewrites those bytes as the fixed array and count in
+/// place, shifting the fields after them.
+fn rewrite_venue_list(info: &AccountInfo) -> Result<()> {
+    let mut data = info.try_borrow_mut_data()?;
+
+    let mut rest = &data[8..];
+    let (_, _, _, _, _, (version, ..), _) = PreVenueFields::deserialize(&mut rest)?;
+    if !(10..=16).contains(&version) {
+        return Ok(());
+    }
+    let offset = data.len() - rest.len();
+    let venues = Vec::<Pubkey>::deserialize(&mut rest)?;
+    let tail = rest.to_vec();
+
+    let mut fixed = [Pubkey::default(); MAX_APPROVED_VENUES];
+    fixed[..venues.len()].copy_from_slice(&venues);
+    let mut rewritten: Vec<u8> = fixed.iter().flat_map(|key| key.to_bytes()).collect();
+    rewritten.push(venues.len() as u8);
+    rewritten.extend(tail);
+    // The list reserved room for every venue, so whatever no longer fits is
+    // the zeroed slack at the end of the account.
+    let room = data.len() - offset;
+    rewritten.truncate(room);
+    data[offset..offset + rewritten.len()].copy_from_slice(&rewritten);
+
+    Ok(())
+}
+
+/// Upgrades an event account to the current layout (`EVENT_VERSION`),
+/// rewriting Borsh events as zero-copy ones. Fields added since the event
+/// was created start out zeroed, except `next_ticket_id`, which continues
+/// from `sold`, the resale split, which leaves the whole price to the
+/// seller, and `max_total_supply`, which stays at `supply`. Events that
+/// still store their name and date inline need `migrate_legacy_event`.
+pub fn migrate_event(ctx: Context<MigrateAccount>) -> Result<()> {
+    let info = ctx.accounts.account.to_account_info();
+    {
+        let data = info.try_borrow_data()?;
+        require!(
+            !data.starts_with(&EVENT_DISCRIMINATOR),
+            EventTicketingError::AccountAlreadyMigrated
+        );
+        require!(
+            data.starts_with(&BORSH_EVENT_DISCRIMINATOR),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+    }
+
+    grow_account(
+        &info,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        BorshEvent::SPACE.max(Event::SPACE),
+    )?;
+    rewrite_venue_list(&info)?;
+
+    let mut borsh_event = {
+        let data = info.try_borrow_data()?;
+        BorshEvent::deserialize(&mut &data[8..])?
+    };
+    if borsh_event.version < 2 {
+        borsh_event.next_ticket_id = borsh_event.sold;
+    }
+    if borsh_event.version < 13 {
+        borsh_event.resale_seller_bps = BPS_DENOMINATOR as u16;
+    }
+    if borsh_event.version < 15 {
+        borsh_event.max_total_supply = borsh_event.supply;
+    }
+
+    let mut data = info.try_borrow_mut_data()?;
+    let event = reset_to_zero_copy(&mut data);
+    borsh_event.write_to(event);
+    event.version = EVENT_VERSION;
+
+    msg!(
+        "Event {} migrated to layout v{}",
+        event.event_id,
+        EVENT_VERSION
+    );
+
+    Ok(())
+}
+
+/// Upgrades an organizer registry to the current layout (`ORGANIZER_VERSION`).
+/// Fields added since registration start out zeroed.
+pub fn migrate_organizer(ctx: Context<MigrateAccount>) -> Result<()> {
+    let info = ctx.accounts.account.to_account_info();
+    grow_account(
+        &info,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        OrganizerRegistry::SPACE,
+    )?;
+
+    let mut registry = {
+        let data = info.try_borrow_data()?;
+        OrganizerRegistry::try_deserialize(&mut &data[..])?
+    };
+    require!(
+        registry.version < ORGANIZER_VERSION,
+        EventTicketingError::AccountAlreadyMigrated
+    );
+    registry.version = ORGANIZER_VERSION;
+
+    let mut data = info.try_borrow_mut_data()?;
+    registry.try_serialize(&mut &mut data[..])?;
+
+    msg!(
+        "Organizer {} migrated to layout v{}",
+        registry.organizer,
+        ORGANIZER_VERSION
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateLegacyEvent<'info> {
+    /// CHECK: Still on the legacy layout, so it cannot be loaded as an
+    /// `Event`. Ownership is checked here; the discriminator and
+    /// size are checked by the handler.
+    #[account(mut, owner = crate::ID)]
+    pub event: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = EventMetadata::SPACE,
+        seeds = [EVENT_METADATA_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub metadata: Account<'info, EventMetadata>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: May still have an older, smaller layout, so it cannot be loaded
+    /// as a typed account until it has been resized. Ownership is checked
+    /// here; the discriminator is checked when the handler deserializes it.
+    #[account(mut, owner = crate::ID)]
+    pub account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,73 @@
+use super::migrate::grow_account;
+use crate::constants::TICKET_VERSION;
+use crate::errors::EventTicketingError;
+use crate::state::{Event, Ticket};
+use anchor_lang::prelude::*;
+
+/// Upgrades a ticket issued on an older layout. The account is grown to
+/// `Ticket::SPACE` (the payer covers the extra rent). Pre-v2 tickets get their
+/// purchase fields backfilled: the event's current price, or zero for comps.
+/// The original purchase time was never recorded, so `purchased_at` stays
+/// zero. Pre-v3 tickets were never lent, so the zeroed loan fields are valid,
+/// pre-v4 tickets carry no donation, pre-v5 tickets no attributes,
+/// pre-v6 tickets start accepting any scan nonce, and pre-v7 tickets were
+/// paid in lamports only.
+pub fn migrate_ticket(ctx: Context<MigrateTicket>) -> Result<()> {
+    let ticket_info = ctx.accounts.ticket.to_account_info();
+    grow_account(
+        &ticket_info,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        Ticket::SPACE,
+    )?;
+
+    let mut ticket = {
+        let data = ticket_info.try_borrow_data()?;
+        Ticket::try_deserialize(&mut &data[..])?
+    };
+    require!(
+        ticket.event == ctx.accounts.event.key(),
+        EventTicketingError::InvalidTicketAccount
+    );
+    require!(
+        ticket.version < TICKET_VERSION,
+        EventTicketingError::TicketAlreadyMigrated
+    );
+
+    if ticket.version < 2 {
+        ticket.purchased_at = 0;
+        ticket.purchase_price = if ticket.comped {
+            0
+        } else {
+            ctx.accounts.event.load()?.price
+        };
+    }
+    ticket.version = TICKET_VERSION;
+
+    let mut data = ticket_info.try_borrow_mut_data()?;
+    ticket.try_serialize(&mut &mut data[..])?;
+
+    msg!(
+        "Ticket {} migrated to layout v{}",
+        ticket.ticket_id,
+        TICKET_VERSION
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateTicket<'info> {
+    pub event: AccountLoader<'info, Event>,
+
+    /// CHECK: May still have the pre-v2 size, so it cannot be loaded as
+    /// `Account<Ticket>` until it has been resized. Ownership is checked here;
+    /// the discriminator and event are checked by the handler.
+    #[account(mut, owner = crate::ID)]
+    pub ticket: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,18 @@
+use super::mint_tickets::{sell_tickets, MintTickets};
+use crate::errors::EventTicketingError;
+use anchor_lang::prelude::*;
+
+/// Buys one ticket for each member of a group. `remaining_accounts` holds
+/// `(recipient, ticket PDA)` pairs; the ticket PDAs follow
+/// `next_ticket_id` order.
+pub fn mint_for_group<'info>(ctx: Context<'_, '_, 'info, 'info, MintTickets<'info>>) -> Result<()> {
+    let pairs = ctx.remaining_accounts.chunks_exact(2);
+    require!(
+        pairs.remainder().is_empty(),
+        EventTicketingError::InvalidTicketCount
+    );
+
+    let tickets: Vec<_> = pairs.map(|pair| (&pair[1], pair[0].key())).collect();
+
+    sell_tickets(ctx.accounts, &tickets)
+}
@@ -1,37 +1,390 @@
+use crate::attestation::verify_attestation;
 use crate::constants::*;
+use crate::ed25519::verify_ed25519_instruction;
 use crate::errors::EventTicketingError;
-use crate::state::{Event, Ticket};
+use crate::events::{emit_cpi_event, TicketMinted};
+use crate::merkle;
+use crate::payment::{collect_tokens, require_vault_token_account};
+use crate::pyth;
+use crate::sale_hook::{require_no_sale_hook, run_sale_hook};
+use crate::sales_stats::record_daily_sales;
+use crate::state::{
+    Attestation, BuyerRecord, Event, LoyaltyAccount, Receipt, Referrer, SalesStats, Ticket,
+    TokenPayment, TransactionLog, VaultActivity,
+};
+use crate::token_gate::verify_token_gate;
+use crate::transaction_log::record_vault_activity;
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
 use anchor_lang::system_program;
+use anchor_spl::token::{Token, TokenAccount};
 
-pub fn mint_ticket(ctx: Context<MintTicket>) -> Result<()> {
-    let event = &mut ctx.accounts.event;
-    let ticket = &mut ctx.accounts.ticket;
+/// Buys the next ticket. For events with a sale hook, `remaining_accounts`
+/// are passed through to the hook program.
+pub fn mint_ticket<'info>(ctx: Context<'_, '_, 'info, 'info, MintTicket<'info>>) -> Result<()> {
+    let buyer = ctx.accounts.buyer.key();
+    sell_hooked_ticket(ctx, buyer)
+}
 
-    require!(!event.canceled, EventTicketingError::EventCanceled);
-    require!(event.sold < event.supply, EventTicketingError::EventSoldOut);
+/// Same as `mint_ticket`, but the buyer pays for a ticket owned by `recipient`.
+pub fn mint_ticket_to<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MintTicket<'info>>,
+    recipient: Pubkey,
+) -> Result<()> {
+    sell_hooked_ticket(ctx, recipient)
+}
 
-    let cpi_context = CpiContext::new(
-        ctx.accounts.system_program.to_account_info(),
-        system_program::Transfer {
-            from: ctx.accounts.buyer.to_account_info(),
-            to: ctx.accounts.vault.to_account_info(),
-        },
+/// Pay-what-you-want mint: the buyer pays `amount`, at least the current
+/// price, and the excess is recorded as a donation on the ticket and event.
+pub fn mint_ticket_with_amount<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MintTicket<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require!(
+        ctx.accounts.event.load()?.pay_what_you_want(),
+        EventTicketingError::PayWhatYouWantDisabled
+    );
+    require!(
+        !ctx.accounts.event.load()?.is_presale(),
+        EventTicketingError::PresaleActive
+    );
+    let floor = ctx.accounts.sale_price()?;
+    require!(amount >= floor, EventTicketingError::AmountBelowFloor);
+
+    let buyer = ctx.accounts.buyer.key();
+    run_sale_hook(
+        &ctx.accounts.event,
+        ctx.accounts.hook_program.as_ref(),
+        &ctx.accounts.buyer,
+        ctx.remaining_accounts,
+        buyer,
+        amount,
+    )?;
+    complete_sale(ctx.accounts, buyer, amount, None)?;
+
+    let donation = amount - floor;
+    ctx.accounts.ticket.donation = donation;
+    ctx.accounts.receipt.donation = donation;
+    let event = &mut ctx.accounts.event.load_mut()?;
+    event.donations = event
+        .donations
+        .checked_add(donation)
+        .ok_or(EventTicketingError::Overflow)?;
+
+    msg!("Donation of {} included", donation);
+
+    Ok(())
+}
+
+fn sell_hooked_ticket<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MintTicket<'info>>,
+    owner: Pubkey,
+) -> Result<()> {
+    require!(
+        !ctx.accounts.event.load()?.is_presale(),
+        EventTicketingError::PresaleActive
     );
+    let price = ctx.accounts.sale_price()?;
+    run_sale_hook(
+        &ctx.accounts.event,
+        ctx.accounts.hook_program.as_ref(),
+        &ctx.accounts.buyer,
+        ctx.remaining_accounts,
+        owner,
+        price,
+    )?;
+    complete_sale(ctx.accounts, owner, price, None)
+}
+
+/// Presale mint: `proof` must show the buyer's pubkey is a leaf of the
+/// event's `allowlist_root`.
+pub fn mint_allowlisted(ctx: Context<MintTicket>, proof: Vec<[u8; 32]>) -> Result<()> {
+    let allowlist_root = {
+        let event = ctx.accounts.event.load()?;
+        require!(event.is_presale(), EventTicketingError::AllowlistNotSet);
+        event.allowlist_root
+    };
+
+    let buyer = ctx.accounts.buyer.key();
+    let leaf = merkle::allowlist_leaf(&buyer.to_bytes());
+    require!(
+        merkle::verify_proof(&proof, allowlist_root, leaf),
+        EventTicketingError::NotAllowlisted
+    );
+
+    let price = ctx.accounts.sale_price()?;
+    sell_ticket(ctx.accounts, buyer, price)
+}
 
-    system_program::transfer(cpi_context, event.price)?;
+/// Mints a ticket for the buyer, spending `points` from their loyalty
+/// account with the event's organizer as a discount on the price.
+pub fn redeem_points(ctx: Context<MintTicket>, points: u64) -> Result<()> {
+    require!(
+        !ctx.accounts.event.load()?.is_presale(),
+        EventTicketingError::PresaleActive
+    );
+    let buyer = ctx.accounts.buyer.key();
+    let price = ctx.accounts.sale_price()?;
+    let loyalty = ctx
+        .accounts
+        .loyalty
+        .as_mut()
+        .ok_or(EventTicketingError::LoyaltyAccountMissing)?;
+    let price = loyalty.redeem(points, price)?;
+
+    msg!(
+        "{} loyalty points redeemed, {} remaining",
+        points,
+        loyalty.points
+    );
 
-    let ticket_id = event.sold;
+    sell_ticket(ctx.accounts, buyer, price)
+}
+
+/// Mints a ticket at a price personalized by the organizer. The transaction
+/// must include an Ed25519 program instruction right before this one
+/// verifying the event authority's signature over
+/// `event || buyer || price (u64 LE) || expires_at (i64 LE) || purchased (u32 LE)`,
+/// where `purchased` is the buyer's purchase count for the event. Each
+/// purchase bumps that count, so a voucher can only be redeemed once.
+pub fn mint_with_voucher(ctx: Context<MintTicket>, price: u64, expires_at: i64) -> Result<()> {
+    require!(
+        Clock::get()?.unix_timestamp < expires_at,
+        EventTicketingError::VoucherExpired
+    );
 
-    ticket.owner = ctx.accounts.buyer.key();
-    ticket.event = event.key();
-    ticket.ticket_id = ticket_id;
-    ticket.is_used = false;
-    ticket.refunded = false;
+    let accounts = &ctx.accounts;
+    let instructions_sysvar = accounts
+        .instructions_sysvar
+        .as_ref()
+        .ok_or(EventTicketingError::InvalidVoucher)?;
+    let buyer = accounts.buyer.key();
+
+    let mut message = Vec::with_capacity(32 + 32 + 8 + 8 + 4);
+    message.extend_from_slice(accounts.event.key().as_ref());
+    message.extend_from_slice(buyer.as_ref());
+    message.extend_from_slice(&price.to_le_bytes());
+    message.extend_from_slice(&expires_at.to_le_bytes());
+    message.extend_from_slice(&accounts.buyer_record.purchased.to_le_bytes());
+
+    verify_ed25519_instruction(
+        instructions_sysvar,
+        &accounts.event.load()?.event_authority,
+        &message,
+    )?;
+
+    sell_ticket(ctx.accounts, buyer, price)
+}
+
+/// Charges `price` and issues the next ticket of the event to `owner`.
+/// Events with a sale hook can only be sold through `mint_ticket(_to)`.
+pub(crate) fn sell_ticket(accounts: &mut MintTicket, owner: Pubkey, price: u64) -> Result<()> {
+    require_no_sale_hook(&*accounts.event.load()?)?;
+    complete_sale(accounts, owner, price, None)
+}
+
+/// Sells a ticket worth `price` lamports for `payment` in one of the
+/// organizer's listed SPL currencies instead.
+pub(crate) fn sell_ticket_for_tokens(
+    accounts: &mut MintTicket,
+    owner: Pubkey,
+    price: u64,
+    payment: TokenPayment,
+) -> Result<()> {
+    require_no_sale_hook(&*accounts.event.load()?)?;
+    complete_sale(accounts, owner, price, Some(payment))
+}
+
+fn complete_sale(
+    accounts: &mut MintTicket,
+    owner: Pubkey,
+    price: u64,
+    token_payment: Option<TokenPayment>,
+) -> Result<()> {
+    let event_key = accounts.event.key();
+    let event = &mut accounts.event.load_mut()?;
+    let ticket = &mut accounts.ticket;
+
+    require!(!event.canceled(), EventTicketingError::EventCanceled);
+    require!(!event.is_finalized(), EventTicketingError::EventFinalized);
+    require!(!event.sales_paused(), EventTicketingError::SalesPaused);
+    require!(event.available() > 0, EventTicketingError::EventSoldOut);
+    if owner != accounts.buyer.key() {
+        event.require_numbered_tickets()?;
+    }
+    verify_attestation(
+        event,
+        accounts.attestation.as_deref(),
+        &owner,
+        Clock::get()?.unix_timestamp,
+    )?;
+    verify_token_gate(
+        event,
+        accounts.gate_token_account.as_deref(),
+        accounts.gate_metadata.as_deref(),
+        &accounts.buyer.key(),
+    )?;
+
+    accounts
+        .buyer_record
+        .record_purchase(event, event_key, accounts.buyer.key(), 1)?;
+
+    let mut referral_fee = 0;
+    if let Some(payment) = token_payment {
+        require!(
+            accounts.referrer.is_none(),
+            EventTicketingError::CurrencyNotSupported
+        );
+        let (Some(buyer_token_account), Some(vault_token_account), Some(token_program)) = (
+            &accounts.buyer_token_account,
+            &accounts.vault_token_account,
+            &accounts.token_program,
+        ) else {
+            return err!(EventTicketingError::CurrencyAccountsMissing);
+        };
+        require!(
+            vault_token_account.mint == payment.mint
+                && vault_token_account.owner == accounts.vault.key(),
+            EventTicketingError::InvalidVaultTokenAccount
+        );
+        collect_tokens(
+            buyer_token_account,
+            vault_token_account,
+            &accounts.buyer.to_account_info(),
+            token_program,
+            payment.amount,
+        )?;
+    } else if event.currency().is_some() {
+        // Referral cuts are paid in lamports, so SPL-currency sales skip them.
+        require!(
+            accounts.referrer.is_none(),
+            EventTicketingError::CurrencyNotSupported
+        );
+        let (Some(buyer_token_account), Some(vault_token_account), Some(token_program)) = (
+            &accounts.buyer_token_account,
+            &accounts.vault_token_account,
+            &accounts.token_program,
+        ) else {
+            return err!(EventTicketingError::CurrencyAccountsMissing);
+        };
+        require_vault_token_account(event, &accounts.vault.key(), vault_token_account)?;
+        collect_tokens(
+            buyer_token_account,
+            vault_token_account,
+            &accounts.buyer.to_account_info(),
+            token_program,
+            price,
+        )?;
+    } else {
+        let mut proceeds = price;
+
+        if let (Some(referrer), Some(referrer_wallet)) =
+            (&mut accounts.referrer, &accounts.referrer_wallet)
+        {
+            require_keys_eq!(
+                referrer_wallet.key(),
+                referrer.referrer,
+                EventTicketingError::InvalidReferrerWallet
+            );
+            require_keys_neq!(
+                referrer.referrer,
+                accounts.buyer.key(),
+                EventTicketingError::SelfReferral
+            );
+
+            let cut = event.referral_cut(price);
+            proceeds -= cut;
+            referral_fee = cut;
+
+            let cpi_context = CpiContext::new(
+                accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: accounts.buyer.to_account_info(),
+                    to: referrer_wallet.to_account_info(),
+                },
+            );
+
+            system_program::transfer(cpi_context, cut)?;
+
+            referrer.referrals = referrer
+                .referrals
+                .checked_add(1)
+                .ok_or(EventTicketingError::Overflow)?;
+            referrer.earned = referrer
+                .earned
+                .checked_add(cut)
+                .ok_or(EventTicketingError::Overflow)?;
+        }
+
+        let cpi_context = CpiContext::new(
+            accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: accounts.buyer.to_account_info(),
+                to: accounts.vault.to_account_info(),
+            },
+        );
+
+        system_program::transfer(cpi_context, proceeds)?;
+    }
+
+    let ticket_id = event.take_ticket_id()?;
+    let now = Clock::get()?.unix_timestamp;
+
+    ticket.issue(owner, event_key, ticket_id, price, now);
+    if let Some(payment) = token_payment {
+        ticket.purchase_price = 0;
+        ticket.record_token_payment(payment.mint, payment.amount)?;
+    }
 
     event.sold += 1;
+    let fees = event.record_sale(price, referral_fee)?;
+    record_daily_sales(event, accounts.sales_stats.as_deref_mut(), 1, price, now)?;
+    let (activity, amount) = match token_payment {
+        Some(payment) => (VaultActivity::TierCurrencyPayment, payment.amount),
+        None => (VaultActivity::Mint, fees.organizer_net),
+    };
+    record_vault_activity(
+        event,
+        accounts.transaction_log.as_ref(),
+        activity,
+        accounts.buyer.key(),
+        amount,
+        now,
+    )?;
+
+    let receipt = &mut accounts.receipt;
+    receipt.ticket = ticket.key();
+    receipt.event = event_key;
+    receipt.payer = accounts.buyer.key();
+    receipt.owner = owner;
+    receipt.amount = token_payment.map_or(price, |payment| payment.amount);
+    receipt.fees = fees;
+    receipt.donation = 0;
+    receipt.currency = token_payment.map_or(event.currency(), |payment| Some(payment.mint));
+    receipt.issued_at = now;
+
+    emit_cpi_event(
+        &accounts.event_cpi_authority,
+        &TicketMinted {
+            event: event_key,
+            ticket: ticket.key(),
+            ticket_id,
+            owner,
+            price,
+            fees,
+        },
+    )?;
+
+    if let Some(loyalty) = &mut accounts.loyalty {
+        loyalty.accrue(LOYALTY_POINTS_PER_MINT);
+    }
 
-    msg!("Ticket #{} minted for event {}", ticket_id, event.event_id);
+    msg!(
+        "Ticket #{} minted for event {} to {}",
+        ticket_id,
+        event.event_id,
+        owner
+    );
 
     Ok(())
 }
@@ -39,21 +392,31 @@ pub fn mint_ticket(ctx: Context<MintTicket>) -> Result<()> {
 #[derive(Accounts)]
 pub struct MintTicket<'info> {
     #[account(mut)]
-    pub event: Account<'info, Event>,
+    pub event: AccountLoader<'info, Event>,
 
     #[account(
         init,
-        payer = buyer,
+        payer = payer,
         space = Ticket::SPACE,
         seeds = [
             TICKET_SEED,
             event.key().as_ref(),
-            &event.sold.to_le_bytes()
+            &event.load()?.ticket_seed(&buyer.key())
         ],
         bump
     )]
     pub ticket: Account<'info, Ticket>,
 
+    /// Purchase receipt; see `Receipt`.
+    #[account(
+        init,
+        payer = payer,
+        space = Receipt::SPACE,
+        seeds = [RECEIPT_SEED, ticket.key().as_ref()],
+        bump
+    )]
+    pub receipt: Account<'info, Receipt>,
+
     /// CHECK: This is the vault PDA that holds event funds. It's derived with correct seeds.
     #[account(
         mut,
@@ -65,8 +428,107 @@ pub struct MintTicket<'info> {
     )]
     pub vault: AccountInfo<'info>,
 
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = BuyerRecord::SPACE,
+        seeds = [BUYER_RECORD_SEED, event.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub buyer_record: Account<'info, BuyerRecord>,
+
+    /// Referrer credited with this sale; earns `event.referral_bps` of the price.
+    #[account(
+        mut,
+        constraint = referrer.event == event.key() @ EventTicketingError::InvalidReferrer
+    )]
+    pub referrer: Option<Account<'info, Referrer>>,
+
+    /// CHECK: Receives the referral cut; must match `referrer.referrer`.
+    #[account(mut)]
+    pub referrer_wallet: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Pyth SOL/USD `PriceUpdateV2`; required for USD-priced events and
+    /// verified in `pyth::sol_usd_price`.
+    pub price_update: Option<UncheckedAccount<'info>>,
+
+    /// Buyer's loyalty account with the organizer; accrues points on every mint.
+    #[account(
+        mut,
+        constraint = loyalty.organizer == event.load()?.event_authority
+            && loyalty.attendee == buyer.key() @ EventTicketingError::InvalidLoyaltyAccount
+    )]
+    pub loyalty: Option<Account<'info, LoyaltyAccount>>,
+
+    /// Buyer's token account paying for SPL-currency events.
+    #[account(mut)]
+    pub buyer_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Vault's token account for SPL-currency events.
+    #[account(mut)]
+    pub vault_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// CHECK: Instructions sysvar, verified by address; required by `mint_with_voucher`.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// Attestation of the ticket owner; required when `event.attestor` is set.
+    pub attestation: Option<Account<'info, Attestation>>,
+
+    /// Buyer's token account proving the gate holding; required when
+    /// `event.gate_mint` is set.
+    pub gate_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Metaplex metadata of the gate NFT, for collection gates;
+    /// verified in `token_gate::verify_token_gate`.
+    pub gate_metadata: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The event's sale hook program; must match `event.hook_program`.
+    pub hook_program: Option<UncheckedAccount<'info>>,
+
     #[account(mut)]
     pub buyer: Signer<'info>,
 
+    /// Pays rent for the accounts created by the sale, letting organizers or
+    /// dapps sponsor it; the buyer still pays the ticket price. Pass the
+    /// buyer again for unsponsored sales.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     pub system_program: Program<'info, System>,
+
+    /// Daily sales buckets; required when the event keeps them.
+    #[account(
+        mut,
+        constraint = event.load()?.sales_stats() == Some(sales_stats.key()) @ EventTicketingError::InvalidSalesStats
+    )]
+    pub sales_stats: Option<Account<'info, SalesStats>>,
+
+    /// Vault activity log; required when the event keeps one.
+    #[account(
+        mut,
+        constraint = event.load()?.transaction_log() == Some(transaction_log.key()) @ EventTicketingError::InvalidTransactionLog
+    )]
+    pub transaction_log: Option<AccountLoader<'info, TransactionLog>>,
+
+    /// CHECK: Signs the event self-CPI. Verified by seeds.
+    #[account(seeds = [EVENT_CPI_AUTHORITY_SEED], bump)]
+    pub event_cpi_authority: AccountInfo<'info>,
+
+    /// CHECK: This program, invoked by the event self-CPI.
+    #[account(address = crate::ID)]
+    pub program: AccountInfo<'info>,
+}
+
+impl MintTicket<'_> {
+    /// Lamport price of the next ticket; see `pyth::sale_price`.
+    pub fn sale_price(&self) -> Result<u64> {
+        pyth::sale_price(
+            &*self.event.load()?,
+            self.price_update.as_deref(),
+            Clock::get()?.unix_timestamp,
+        )
+    }
 }
@@ -0,0 +1,109 @@
+use crate::constants::*;
+use crate::errors::EventTicketingError;
+use crate::instructions::mint_ticket::*;
+use crate::state::{Event, Seat, SeatClaim, SeatMap, Ticket};
+use anchor_lang::prelude::*;
+
+/// Buys a ticket for a specific seat. The SeatClaim PDA is derived from the
+/// seat coordinates, so each seat can only be sold once.
+pub fn mint_ticket_with_seat(
+    ctx: Context<MintTicketWithSeat>,
+    section: u16,
+    row: u16,
+    number: u16,
+) -> Result<()> {
+    require!(
+        !ctx.accounts.sale.event.load()?.is_presale(),
+        EventTicketingError::PresaleActive
+    );
+
+    let seat = Seat {
+        section,
+        row,
+        number,
+    };
+
+    if ctx.accounts.sale.event.load()?.seat_map().is_some() {
+        let seat_map = ctx
+            .accounts
+            .seat_map
+            .as_ref()
+            .ok_or(EventTicketingError::MissingSeatMap)?;
+        seat_map.load_mut()?.take(&seat)?;
+    }
+
+    let buyer = ctx.accounts.sale.buyer.key();
+    let price = ctx.accounts.sale.sale_price()?;
+
+    sell_ticket(&mut ctx.accounts.sale, buyer, price)?;
+
+    ctx.accounts.sale.ticket.seat = Some(seat);
+
+    let seat_claim = &mut ctx.accounts.seat_claim;
+    seat_claim.event = ctx.accounts.sale.event.key();
+    seat_claim.ticket = ctx.accounts.sale.ticket.key();
+    seat_claim.seat = seat;
+
+    msg!(
+        "Seat {}/{}/{} assigned to ticket #{}",
+        section,
+        row,
+        number,
+        ctx.accounts.sale.ticket.ticket_id
+    );
+
+    Ok(())
+}
+
+/// Frees the seat of a seated ticket that is leaving circulation. The seat
+/// claim must be passed so the caller's `close` releases the seat for sale
+/// again; the seat map bit is cleared here when the event keeps one.
+pub(crate) fn release_seat(
+    event: &Event,
+    ticket: &Ticket,
+    seat_map: Option<&AccountLoader<SeatMap>>,
+    seat_claim: Option<&Account<SeatClaim>>,
+) -> Result<()> {
+    let Some(seat) = ticket.seat else {
+        return Ok(());
+    };
+
+    require!(seat_claim.is_some(), EventTicketingError::MissingSeatClaim);
+
+    if event.seat_map().is_some() {
+        let seat_map = seat_map.ok_or(EventTicketingError::MissingSeatMap)?;
+        seat_map.load_mut()?.release(&seat)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(section: u16, row: u16, number: u16)]
+pub struct MintTicketWithSeat<'info> {
+    pub sale: MintTicket<'info>,
+
+    #[account(
+        init,
+        payer = sale.payer,
+        space = SeatClaim::SPACE,
+        seeds = [
+            SEAT_SEED,
+            sale.event.key().as_ref(),
+            &section.to_le_bytes(),
+            &row.to_le_bytes(),
+            &number.to_le_bytes()
+        ],
+        bump
+    )]
+    pub seat_claim: Account<'info, SeatClaim>,
+
+    /// Required when the event was created with a seat layout.
+    #[account(
+        mut,
+        constraint = sale.event.load()?.seat_map() == Some(seat_map.key()) @ EventTicketingError::InvalidSeatMap
+    )]
+    pub seat_map: Option<AccountLoader<'info, SeatMap>>,
+
+    pub system_program: Program<'info, System>,
+}
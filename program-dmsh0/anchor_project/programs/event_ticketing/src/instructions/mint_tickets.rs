@@ -0,0 +1,308 @@
+use crate::attestation::require_no_attestor;
+use crate::constants::*;
+use crate::errors::EventTicketingError;
+use crate::events::{emit_cpi_event, TicketMinted};
+use crate::payment::require_sol_currency;
+use crate::pda::derive_ticket_pda;
+use crate::pyth;
+use crate::sale_hook::require_no_sale_hook;
+use crate::sales_stats::record_daily_sales;
+use crate::state::{BuyerRecord, Event, SalesStats, Ticket, TransactionLog, VaultActivity};
+use crate::token_gate::require_no_token_gate;
+use crate::transaction_log::record_vault_activity;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Buys `count` tickets at once. The Ticket PDAs for ids
+/// `next_ticket_id..next_ticket_id + count` are passed (writable,
+/// uninitialized) in `remaining_accounts`, in order.
+pub fn mint_tickets<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MintTickets<'info>>,
+    count: u8,
+) -> Result<()> {
+    require!(
+        ctx.remaining_accounts.len() == count as usize,
+        EventTicketingError::InvalidTicketCount
+    );
+
+    let buyer = ctx.accounts.buyer.key();
+    let tickets: Vec<_> = ctx
+        .remaining_accounts
+        .iter()
+        .map(|ticket_info| (ticket_info, buyer))
+        .collect();
+
+    sell_tickets(ctx.accounts, &tickets)
+}
+
+/// Charges the buyer for every ticket in `tickets` and creates each Ticket
+/// PDA (in `next_ticket_id` order) for its paired owner.
+pub(crate) fn sell_tickets<'info>(
+    accounts: &mut MintTickets<'info>,
+    tickets: &[(&'info AccountInfo<'info>, Pubkey)],
+) -> Result<()> {
+    let event_key = accounts.event.key();
+    let count = tickets.len();
+
+    require!(
+        (1..=MAX_TICKETS_PER_MINT).contains(&count),
+        EventTicketingError::InvalidTicketCount
+    );
+    {
+        let event = &accounts.event.load()?;
+        require!(!event.canceled(), EventTicketingError::EventCanceled);
+        require!(!event.is_finalized(), EventTicketingError::EventFinalized);
+        require!(!event.sales_paused(), EventTicketingError::SalesPaused);
+        require!(!event.is_presale(), EventTicketingError::PresaleActive);
+        require_sol_currency(event)?;
+        require_no_sale_hook(event)?;
+        require_no_attestor(event)?;
+        require_no_token_gate(event)?;
+        event.require_numbered_tickets()?;
+        require!(
+            event.available() >= count as u32,
+            EventTicketingError::EventSoldOut
+        );
+
+        accounts.buyer_record.record_purchase(
+            event,
+            event_key,
+            accounts.buyer.key(),
+            count as u32,
+        )?;
+    }
+
+    let prices = accounts.ticket_prices(count as u32)?;
+    let total_price = prices
+        .iter()
+        .try_fold(0u64, |total, price| total.checked_add(*price))
+        .ok_or(EventTicketingError::Overflow)?;
+
+    system_program::transfer(
+        CpiContext::new(
+            accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: accounts.buyer.to_account_info(),
+                to: accounts.vault.to_account_info(),
+            },
+        ),
+        total_price,
+    )?;
+
+    let event = &mut accounts.event.load_mut()?;
+    for ((ticket_info, owner), price) in tickets.iter().zip(prices) {
+        let ticket_id = event.take_ticket_id()?;
+        create_ticket_account(
+            ticket_info,
+            &accounts.buyer,
+            &accounts.system_program,
+            event_key,
+            ticket_id,
+            *owner,
+            price,
+        )?;
+        let fees = event.record_sale(price, 0)?;
+        emit_cpi_event(
+            &accounts.event_cpi_authority,
+            &TicketMinted {
+                event: event_key,
+                ticket: ticket_info.key(),
+                ticket_id,
+                owner: *owner,
+                price,
+                fees,
+            },
+        )?;
+        event.sold += 1;
+    }
+    record_daily_sales(
+        event,
+        accounts.sales_stats.as_deref_mut(),
+        count as u32,
+        total_price,
+        Clock::get()?.unix_timestamp,
+    )?;
+    record_vault_activity(
+        event,
+        accounts.transaction_log.as_ref(),
+        VaultActivity::Mint,
+        accounts.buyer.key(),
+        total_price,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    msg!(
+        "{} tickets minted for event {} ({} lamports)",
+        count,
+        event.event_id,
+        total_price
+    );
+
+    Ok(())
+}
+
+/// Creates and initializes the Ticket PDA for `ticket_id` at `ticket_info`,
+/// funded by `payer`. Used by instructions that mint several tickets through
+/// `remaining_accounts`, where Anchor's `init` constraint is unavailable.
+pub(crate) fn create_ticket_account<'info>(
+    ticket_info: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    event: Pubkey,
+    ticket_id: u32,
+    owner: Pubkey,
+    purchase_price: u64,
+) -> Result<()> {
+    let ticket_id_bytes = ticket_id.to_le_bytes();
+    let (expected, bump) = derive_ticket_pda(&event, ticket_id);
+    require_keys_eq!(
+        ticket_info.key(),
+        expected,
+        EventTicketingError::InvalidTicketAccount
+    );
+
+    let seeds = &[TICKET_SEED, event.as_ref(), &ticket_id_bytes, &[bump]];
+    let signer_seeds = &[&seeds[..]];
+    let rent = Rent::get()?.minimum_balance(Ticket::SPACE);
+    let current_lamports = ticket_info.lamports();
+    if current_lamports == 0 {
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: payer.to_account_info(),
+                    to: ticket_info.clone(),
+                },
+                signer_seeds,
+            ),
+            rent,
+            Ticket::SPACE as u64,
+            &crate::ID,
+        )?;
+    } else {
+        // Anyone can send lamports to the PDA ahead of time, which makes
+        // `create_account` fail. Top it up to rent exemption and claim it
+        // the way Anchor's `init` does.
+        let shortfall = rent.saturating_sub(current_lamports);
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: payer.to_account_info(),
+                        to: ticket_info.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                system_program::Allocate {
+                    account_to_allocate: ticket_info.clone(),
+                },
+                signer_seeds,
+            ),
+            Ticket::SPACE as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                system_program::Assign {
+                    account_to_assign: ticket_info.clone(),
+                },
+                signer_seeds,
+            ),
+            &crate::ID,
+        )?;
+    }
+
+    // The freshly created account is zeroed, which deserializes as a blank Ticket.
+    let mut data = ticket_info.try_borrow_mut_data()?;
+    let mut ticket = Ticket::try_deserialize_unchecked(&mut &data[..])?;
+    ticket.issue(
+        owner,
+        event,
+        ticket_id,
+        purchase_price,
+        Clock::get()?.unix_timestamp,
+    );
+    ticket.try_serialize(&mut &mut data[..])
+}
+
+#[derive(Accounts)]
+pub struct MintTickets<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    /// CHECK: This is the vault PDA that holds event funds. It's derived with correct seeds.
+    #[account(
+        mut,
+        seeds = [
+            VAULT_SEED,
+            event.key().as_ref()
+        ],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = BuyerRecord::SPACE,
+        seeds = [BUYER_RECORD_SEED, event.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub buyer_record: Account<'info, BuyerRecord>,
+
+    /// CHECK: Pyth SOL/USD `PriceUpdateV2`; required for USD-priced events and
+    /// verified in `pyth::sol_usd_price`.
+    pub price_update: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Daily sales buckets; required when the event keeps them.
+    #[account(
+        mut,
+        constraint = event.load()?.sales_stats() == Some(sales_stats.key()) @ EventTicketingError::InvalidSalesStats
+    )]
+    pub sales_stats: Option<Account<'info, SalesStats>>,
+
+    /// Vault activity log; required when the event keeps one.
+    #[account(
+        mut,
+        constraint = event.load()?.transaction_log() == Some(transaction_log.key()) @ EventTicketingError::InvalidTransactionLog
+    )]
+    pub transaction_log: Option<AccountLoader<'info, TransactionLog>>,
+
+    /// CHECK: Signs the event self-CPI. Verified by seeds.
+    #[account(seeds = [EVENT_CPI_AUTHORITY_SEED], bump)]
+    pub event_cpi_authority: AccountInfo<'info>,
+
+    /// CHECK: This program, invoked by the event self-CPI.
+    #[account(address = crate::ID)]
+    pub program: AccountInfo<'info>,
+}
+
+impl MintTickets<'_> {
+    /// Lamport price of each of the next `count` tickets, following the same pricing
+    /// rules as `MintTicket::sale_price`.
+    pub fn ticket_prices(&self, count: u32) -> Result<Vec<u64>> {
+        let event = &self.event.load()?;
+        let now = Clock::get()?.unix_timestamp;
+
+        if event.usd_price_cents > 0 {
+            let unit =
+                pyth::cents_in_lamports(event.usd_price_cents, self.price_update.as_deref(), now)?;
+            return Ok(vec![unit; count as usize]);
+        }
+
+        Ok((event.sold..event.sold + count)
+            .map(|sold| event.price_at(now, sold))
+            .collect())
+    }
+}
@@ -1,15 +1,163 @@
+pub mod attestation;
+pub mod auction;
+pub mod bond;
+pub mod buy_listed_ticket;
 pub mod cancel_event;
+pub mod cancel_ticket;
 pub mod check_in;
+pub mod check_in_batch;
+pub mod check_in_with_voucher;
+pub mod claim_refund;
+pub mod close_receipt;
+pub mod co_organizer;
+pub mod delist_ticket;
+pub mod emergency_cancel;
+pub mod enable_sales_stats;
+pub mod enable_transaction_log;
+pub mod finalize_event;
+pub mod freeze_transfers;
+pub mod initialize_config;
 pub mod initialize_event;
+pub mod issue_comp_ticket;
+pub mod lend_ticket;
+pub mod list_ticket;
+pub mod loyalty;
+pub mod migrate;
+pub mod migrate_ticket;
+pub mod mint_for_group;
 pub mod mint_ticket;
+pub mod mint_ticket_with_seat;
+pub mod mint_tickets;
+pub mod operator;
+pub mod pass;
+pub mod pause_sales;
+pub mod promo;
+pub mod purchase_queue;
+pub mod purge_expired;
+pub mod raffle;
 pub mod refund;
+pub mod refund_batch;
 pub mod register_organizer;
+pub mod register_referrer;
+pub mod reservation;
+pub mod revoke_ticket;
+pub mod series;
+pub mod session;
+pub mod set_allowlist_root;
+pub mod set_approved_venues;
+pub mod set_attendance_deposit;
+pub mod set_attestor;
+pub mod set_cancellation_policy;
+pub mod set_dispute_window;
+pub mod set_doors_window;
+pub mod set_dutch_auction;
+pub mod set_event_authority;
+pub mod set_event_multisig;
+pub mod set_max_check_ins;
+pub mod set_max_per_wallet;
+pub mod set_max_resale_multiplier;
+pub mod set_metadata_uri;
+pub mod set_pay_what_you_want;
+pub mod set_payout_schedule;
+pub mod set_payout_tranches;
+pub mod set_poap_mint;
+pub mod set_price_curve;
+pub mod set_referral_bps;
+pub mod set_refund_policy;
+pub mod set_resale_split;
+pub mod set_sale_hook;
+pub mod set_ticket_attributes;
+pub mod set_token_gate;
+pub mod set_transfer_fee;
+pub mod set_usd_price;
+pub mod slash_organizer;
+pub mod staff;
+pub mod supply;
+pub mod tier;
 pub mod transfer_ticket;
+pub mod update_event;
+pub mod verify_organizer;
+pub mod waitlist;
+pub mod withdraw_proceeds;
 
+pub use attestation::*;
+pub use auction::*;
+pub use bond::*;
+pub use buy_listed_ticket::*;
 pub use cancel_event::*;
+pub use cancel_ticket::*;
 pub use check_in::*;
+pub use check_in_batch::*;
+pub use check_in_with_voucher::*;
+pub use claim_refund::*;
+pub use close_receipt::*;
+pub use co_organizer::*;
+pub use delist_ticket::*;
+pub use emergency_cancel::*;
+pub use enable_sales_stats::*;
+pub use enable_transaction_log::*;
+pub use finalize_event::*;
+pub use freeze_transfers::*;
+pub use initialize_config::*;
 pub use initialize_event::*;
+pub use issue_comp_ticket::*;
+pub use lend_ticket::*;
+pub use list_ticket::*;
+pub use loyalty::*;
+pub use migrate::*;
+pub use migrate_ticket::*;
+pub use mint_for_group::*;
 pub use mint_ticket::*;
+pub use mint_ticket_with_seat::*;
+pub use mint_tickets::*;
+pub use operator::*;
+pub use pass::*;
+pub use pause_sales::*;
+pub use promo::*;
+pub use purchase_queue::*;
+pub use purge_expired::*;
+pub use raffle::*;
 pub use refund::*;
+pub use refund_batch::*;
 pub use register_organizer::*;
+pub use register_referrer::*;
+pub use reservation::*;
+pub use revoke_ticket::*;
+pub use series::*;
+pub use session::*;
+pub use set_allowlist_root::*;
+pub use set_approved_venues::*;
+pub use set_attendance_deposit::*;
+pub use set_attestor::*;
+pub use set_cancellation_policy::*;
+pub use set_dispute_window::*;
+pub use set_doors_window::*;
+pub use set_dutch_auction::*;
+pub use set_event_authority::*;
+pub use set_event_multisig::*;
+pub use set_max_check_ins::*;
+pub use set_max_per_wallet::*;
+pub use set_max_resale_multiplier::*;
+pub use set_metadata_uri::*;
+pub use set_pay_what_you_want::*;
+pub use set_payout_schedule::*;
+pub use set_payout_tranches::*;
+pub use set_poap_mint::*;
+pub use set_price_curve::*;
+pub use set_referral_bps::*;
+pub use set_refund_policy::*;
+pub use set_resale_split::*;
+pub use set_sale_hook::*;
+pub use set_ticket_attributes::*;
+pub use set_token_gate::*;
+pub use set_transfer_fee::*;
+pub use set_usd_price::*;
+pub use slash_organizer::*;
+pub use staff::*;
+pub use supply::*;
+pub use tier::*;
 pub use transfer_ticket::*;
+pub use update_event::*;
+pub use verify_organizer::*;
+pub use waitlist::*;
+pub use withdraw_proceeds::*;
@@ -0,0 +1,218 @@
+use super::transfer_ticket::require_transferable;
+use crate::constants::{EVENT_CPI_AUTHORITY_SEED, OPERATOR_APPROVAL_SEED, VAULT_SEED};
+use crate::errors::EventTicketingError;
+use crate::events::{emit_cpi_event, TicketTransferred};
+use crate::state::{Event, OperatorApproval, Ticket, TransactionLog, VaultActivity};
+use crate::transaction_log::record_vault_activity;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Lets `operator` (a marketplace wallet or program PDA) transfer this ticket
+/// once on the owner's behalf. Re-approving replaces the previous operator.
+pub fn approve_operator(ctx: Context<ApproveOperator>) -> Result<()> {
+    let ticket = &ctx.accounts.ticket;
+    let operator = ctx.accounts.operator.key();
+
+    require_transferable(&*ctx.accounts.event.load()?, ticket)?;
+
+    let approval = &mut ctx.accounts.approval;
+    approval.ticket = ticket.key();
+    approval.owner = ticket.owner;
+    approval.operator = operator;
+    approval.approved_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "Operator {} approved for ticket #{}",
+        operator,
+        ticket.ticket_id
+    );
+
+    Ok(())
+}
+
+/// Withdraws an operator approval. The approving owner or the ticket's
+/// current owner (clearing a stale approval) may revoke.
+pub fn revoke_operator(ctx: Context<RevokeOperator>) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+
+    require!(
+        authority == ctx.accounts.approval.owner || authority == ctx.accounts.ticket.owner,
+        EventTicketingError::UnauthorizedTransfer
+    );
+
+    msg!(
+        "Operator approval for ticket #{} revoked",
+        ctx.accounts.ticket.ticket_id
+    );
+
+    Ok(())
+}
+
+/// Transfers the ticket to `new_owner` on the owner's behalf and consumes
+/// the approval. The operator pays the event's transfer fee.
+pub fn operator_transfer(ctx: Context<OperatorTransfer>) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+    let ticket = &mut ctx.accounts.ticket;
+
+    // Approvals do not follow the ticket to a new owner.
+    require!(
+        ticket.owner == ctx.accounts.approval.owner,
+        EventTicketingError::StaleOperatorApproval
+    );
+    require_transferable(event, ticket)?;
+
+    if event.transfer_fee_lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.operator.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            event.transfer_fee_lamports,
+        )?;
+        record_vault_activity(
+            event,
+            ctx.accounts.transaction_log.as_ref(),
+            VaultActivity::Fee,
+            ctx.accounts.operator.key(),
+            event.transfer_fee_lamports,
+            Clock::get()?.unix_timestamp,
+        )?;
+    }
+
+    let from = ticket.owner;
+    ticket.owner = ctx.accounts.new_owner.key();
+    let transfer_fee = event.transfer_fee_lamports;
+    event.record_transfer(transfer_fee, 0)?;
+
+    emit_cpi_event(
+        &ctx.accounts.event_cpi_authority,
+        &TicketTransferred {
+            event: event_key,
+            ticket: ticket.key(),
+            ticket_id: ticket.ticket_id,
+            from,
+            to: ticket.owner,
+            transfer_fee,
+        },
+    )?;
+
+    msg!(
+        "Ticket #{} transferred to {} by operator {} (fee: {} lamports)",
+        ticket.ticket_id,
+        ctx.accounts.new_owner.key(),
+        ctx.accounts.operator.key(),
+        event.transfer_fee_lamports
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ApproveOperator<'info> {
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        constraint = ticket.owner == owner.key() @ EventTicketingError::UnauthorizedTransfer,
+        constraint = ticket.event == event.key()
+    )]
+    pub ticket: Account<'info, Ticket>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = OperatorApproval::SPACE,
+        seeds = [OPERATOR_APPROVAL_SEED, ticket.key().as_ref()],
+        bump
+    )]
+    pub approval: Account<'info, OperatorApproval>,
+
+    /// CHECK: The wallet or program PDA being approved. Can be any account.
+    pub operator: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeOperator<'info> {
+    pub ticket: Account<'info, Ticket>,
+
+    #[account(
+        mut,
+        close = approver,
+        seeds = [OPERATOR_APPROVAL_SEED, ticket.key().as_ref()],
+        bump
+    )]
+    pub approval: Account<'info, OperatorApproval>,
+
+    /// CHECK: The owner who granted the approval; receives its rent back.
+    #[account(mut, address = approval.owner)]
+    pub approver: AccountInfo<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct OperatorTransfer<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        constraint = ticket.event == event.key()
+    )]
+    pub ticket: Account<'info, Ticket>,
+
+    #[account(
+        mut,
+        close = approver,
+        seeds = [OPERATOR_APPROVAL_SEED, ticket.key().as_ref()],
+        bump,
+        has_one = operator @ EventTicketingError::UnauthorizedOperator
+    )]
+    pub approval: Account<'info, OperatorApproval>,
+
+    /// CHECK: This is the vault PDA that collects transfer fees. Verified by seeds.
+    #[account(
+        mut,
+        seeds = [
+            VAULT_SEED,
+            event.key().as_ref()
+        ],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: The owner who granted the approval; receives its rent back.
+    #[account(mut, address = approval.owner)]
+    pub approver: AccountInfo<'info>,
+
+    /// CHECK: This is the recipient of the ticket. Can be any valid account.
+    pub new_owner: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Vault activity log; required when the event keeps one.
+    #[account(
+        mut,
+        constraint = event.load()?.transaction_log() == Some(transaction_log.key()) @ EventTicketingError::InvalidTransactionLog
+    )]
+    pub transaction_log: Option<AccountLoader<'info, TransactionLog>>,
+
+    /// CHECK: Signs the event self-CPI. Verified by seeds.
+    #[account(seeds = [EVENT_CPI_AUTHORITY_SEED], bump)]
+    pub event_cpi_authority: AccountInfo<'info>,
+
+    /// CHECK: This program, invoked by the event self-CPI.
+    #[account(address = crate::ID)]
+    pub program: AccountInfo<'info>,
+}
@@ -0,0 +1,161 @@
+use crate::constants::*;
+use crate::errors::EventTicketingError;
+use crate::rbac::{has_permission, Delegations, Permission};
+use crate::state::{CoOrganizer, Event, EventSeries, Pass, PassCheckIn, Staff};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Puts season passes for a series on sale. Zero takes them off sale.
+pub fn set_pass_price(ctx: Context<SetPassPrice>, pass_price: u64) -> Result<()> {
+    let series = &mut ctx.accounts.series;
+
+    series.pass_price = pass_price;
+
+    msg!(
+        "Series {} pass price set to {} lamports",
+        series.series_id,
+        pass_price
+    );
+
+    Ok(())
+}
+
+/// Buys the holder's season pass for a series, paid to the organizer. Each
+/// wallet can hold one pass per series.
+pub fn buy_pass(ctx: Context<BuyPass>) -> Result<()> {
+    let series = &ctx.accounts.series;
+
+    require!(series.pass_price > 0, EventTicketingError::PassesNotOnSale);
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.holder.to_account_info(),
+                to: ctx.accounts.organizer.to_account_info(),
+            },
+        ),
+        series.pass_price,
+    )?;
+
+    let pass = &mut ctx.accounts.pass;
+
+    pass.series = series.key();
+    pass.holder = ctx.accounts.holder.key();
+    pass.purchased_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "Season pass for series {} bought by {}",
+        series.series_id,
+        pass.holder
+    );
+
+    Ok(())
+}
+
+/// Admits a pass holder to one event of the series. The PassCheckIn record
+/// is derived per (pass, event), so each event admits a pass only once.
+pub fn check_in_with_pass(ctx: Context<CheckInWithPass>) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    has_permission(
+        event,
+        &ctx.accounts.operator.key(),
+        Permission::CheckIn,
+        &Delegations {
+            staff: ctx.accounts.staff.as_deref(),
+            co_organizer: ctx.accounts.co_organizer.as_deref(),
+            ..Default::default()
+        },
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(event.doors_are_open(now), EventTicketingError::DoorsClosed);
+    require!(!event.canceled(), EventTicketingError::EventCanceled);
+    require!(!event.is_finalized(), EventTicketingError::EventFinalized);
+
+    event.checked_in_count += 1;
+
+    let record = &mut ctx.accounts.pass_check_in;
+
+    record.pass = ctx.accounts.pass.key();
+    record.event = event_key;
+    record.checked_in_at = now;
+    record.checked_in_by = ctx.accounts.operator.key();
+
+    msg!(
+        "Pass of {} checked in to event {} by {}",
+        ctx.accounts.pass.holder,
+        event.event_id,
+        record.checked_in_by
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPassPrice<'info> {
+    #[account(mut, has_one = organizer)]
+    pub series: Account<'info, EventSeries>,
+
+    pub organizer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BuyPass<'info> {
+    #[account(has_one = organizer)]
+    pub series: Account<'info, EventSeries>,
+
+    #[account(
+        init,
+        payer = holder,
+        space = Pass::SPACE,
+        seeds = [PASS_SEED, series.key().as_ref(), holder.key().as_ref()],
+        bump
+    )]
+    pub pass: Account<'info, Pass>,
+
+    /// CHECK: Series organizer receiving the pass payment; verified by `has_one`.
+    #[account(mut)]
+    pub organizer: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CheckInWithPass<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.series() == Some(pass.series) @ EventTicketingError::PassNotValidForEvent
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub pass: Account<'info, Pass>,
+
+    #[account(
+        init,
+        payer = operator,
+        space = PassCheckIn::SPACE,
+        seeds = [PASS_CHECK_IN_SEED, pass.key().as_ref(), event.key().as_ref()],
+        bump
+    )]
+    pub pass_check_in: Account<'info, PassCheckIn>,
+
+    /// Staff delegation record; required when `operator` is not the event authority.
+    #[account(constraint = staff.event == event.key() @ EventTicketingError::UnauthorizedCheckIn)]
+    pub staff: Option<Account<'info, Staff>>,
+
+    /// Co-organizer record; an alternative to `staff` for teammates allowed
+    /// to check in.
+    #[account(constraint = co_organizer.event == event.key() @ EventTicketingError::UnauthorizedCheckIn)]
+    pub co_organizer: Option<Account<'info, CoOrganizer>>,
+
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
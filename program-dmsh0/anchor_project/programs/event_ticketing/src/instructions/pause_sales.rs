@@ -0,0 +1,36 @@
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+
+/// Temporarily stops ticket sales without canceling the event, e.g. for a
+/// box-office hold. Check-ins, transfers and refunds are unaffected.
+pub fn pause_sales(ctx: Context<SetSalesPaused>) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    event.sales_paused = true.into();
+
+    msg!("Sales paused for event {}", event.event_id);
+
+    Ok(())
+}
+
+pub fn resume_sales(ctx: Context<SetSalesPaused>) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    event.sales_paused = false.into();
+
+    msg!("Sales resumed for event {}", event.event_id);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetSalesPaused<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub event_authority: Signer<'info>,
+}
@@ -0,0 +1,96 @@
+use crate::constants::*;
+use crate::errors::EventTicketingError;
+use crate::instructions::mint_ticket::*;
+use crate::state::{Event, Promo};
+use anchor_lang::prelude::*;
+
+pub fn create_promo(
+    ctx: Context<CreatePromo>,
+    code: String,
+    discount_bps: u16,
+    max_uses: u32,
+) -> Result<()> {
+    require!(
+        code.len() <= MAX_PROMO_CODE_LEN,
+        EventTicketingError::PromoCodeTooLong
+    );
+    require!(
+        discount_bps > 0 && discount_bps as u64 <= BPS_DENOMINATOR,
+        EventTicketingError::InvalidDiscount
+    );
+    require!(max_uses > 0, EventTicketingError::InvalidMaxUses);
+
+    let promo = &mut ctx.accounts.promo;
+
+    promo.event = ctx.accounts.event.key();
+    promo.code = code;
+    promo.discount_bps = discount_bps;
+    promo.max_uses = max_uses;
+    promo.uses = 0;
+
+    msg!(
+        "Promo {} created for event {}: {} bps off, {} uses",
+        promo.code,
+        ctx.accounts.event.load()?.event_id,
+        discount_bps,
+        max_uses
+    );
+
+    Ok(())
+}
+
+pub fn mint_with_promo(ctx: Context<MintWithPromo>, _code: String) -> Result<()> {
+    require!(
+        !ctx.accounts.sale.event.load()?.is_presale(),
+        EventTicketingError::PresaleActive
+    );
+
+    let price = ctx.accounts.sale.sale_price()?;
+    let price = ctx.accounts.promo.redeem(price)?;
+    let buyer = ctx.accounts.sale.buyer.key();
+
+    msg!(
+        "Promo {} redeemed ({}/{} uses)",
+        ctx.accounts.promo.code,
+        ctx.accounts.promo.uses,
+        ctx.accounts.promo.max_uses
+    );
+
+    sell_ticket(&mut ctx.accounts.sale, buyer, price)
+}
+
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct CreatePromo<'info> {
+    #[account(
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        init,
+        payer = event_authority,
+        space = Promo::SPACE,
+        seeds = [PROMO_SEED, event.key().as_ref(), code.as_bytes()],
+        bump
+    )]
+    pub promo: Account<'info, Promo>,
+
+    #[account(mut)]
+    pub event_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct MintWithPromo<'info> {
+    pub sale: MintTicket<'info>,
+
+    #[account(
+        mut,
+        seeds = [PROMO_SEED, sale.event.key().as_ref(), code.as_bytes()],
+        bump
+    )]
+    pub promo: Account<'info, Promo>,
+}
@@ -0,0 +1,405 @@
+use super::mint_tickets::create_ticket_account;
+use crate::attestation::require_no_attestor;
+use crate::constants::*;
+use crate::errors::EventTicketingError;
+use crate::events::{emit_cpi_event, TicketMinted};
+use crate::payment::require_sol_currency;
+use crate::pyth;
+use crate::sale_hook::require_no_sale_hook;
+use crate::sales_stats::record_daily_sales;
+use crate::state::{
+    Event, PurchaseCommitment, PurchaseQueue, SalesStats, TransactionLog, VaultActivity,
+};
+use crate::token_gate::require_no_token_gate;
+use crate::transaction_log::record_vault_activity;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Sets aside `seats` tickets for a commit–reveal on-sale. Buyers commit
+/// until `commit_close_at` and reveal until `reveal_close_at`; the seats are
+/// held out of general sale in the meantime.
+pub fn create_purchase_queue(
+    ctx: Context<CreatePurchaseQueue>,
+    seats: u32,
+    commit_close_at: i64,
+    reveal_close_at: i64,
+) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    require!(!event.canceled(), EventTicketingError::EventCanceled);
+    require!(!event.is_finalized(), EventTicketingError::EventFinalized);
+    require!(
+        seats > 0 && event.available() >= seats,
+        EventTicketingError::InvalidTicketCount
+    );
+    require!(
+        commit_close_at > Clock::get()?.unix_timestamp && reveal_close_at > commit_close_at,
+        EventTicketingError::InvalidPurchaseWindows
+    );
+
+    let queue = &mut ctx.accounts.queue;
+
+    queue.event = event_key;
+    queue.seats = seats;
+    queue.commit_close_at = commit_close_at;
+    queue.reveal_close_at = reveal_close_at;
+    queue.commit_count = 0;
+    queue.closed = false;
+
+    event.reserved += seats;
+
+    msg!(
+        "Purchase queue for {} seats of event {}: commits until {}, reveals until {}",
+        seats,
+        event.event_id,
+        commit_close_at,
+        reveal_close_at
+    );
+
+    Ok(())
+}
+
+/// Takes the next place in the queue with `commitment` (see
+/// `PurchaseCommitment::hash`), escrowing the current ticket price (see
+/// `pyth::sale_price`) in the commitment account. A winning reveal pays
+/// exactly that deposit.
+pub fn commit_purchase(ctx: Context<CommitPurchase>, commitment: [u8; 32]) -> Result<()> {
+    let event = &ctx.accounts.event.load()?;
+    let queue = &mut ctx.accounts.queue;
+    let price = pyth::sale_price(
+        event,
+        ctx.accounts.price_update.as_deref(),
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    require!(!event.sales_paused(), EventTicketingError::SalesPaused);
+    require_sol_currency(event)?;
+    require_no_sale_hook(event)?;
+    require_no_attestor(event)?;
+    require_no_token_gate(event)?;
+    event.require_numbered_tickets()?;
+    require!(
+        Clock::get()?.unix_timestamp < queue.commit_close_at,
+        EventTicketingError::PurchaseCommitsClosed
+    );
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.commitment.to_account_info(),
+            },
+        ),
+        price,
+    )?;
+
+    let entry = &mut ctx.accounts.commitment;
+
+    entry.queue = queue.key();
+    entry.buyer = ctx.accounts.buyer.key();
+    entry.commitment = commitment;
+    entry.position = queue.commit_count;
+    entry.deposit = price;
+
+    queue.commit_count += 1;
+
+    msg!(
+        "{} committed to purchase for event {} at position {}",
+        entry.buyer,
+        event.event_id,
+        entry.position
+    );
+
+    Ok(())
+}
+
+/// Ends the commit phase and releases the seats left over when the queue is
+/// undersubscribed. Anyone can crank this.
+pub fn close_purchase_queue(ctx: Context<ClosePurchaseQueue>) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+    let queue = &mut ctx.accounts.queue;
+
+    require!(
+        Clock::get()?.unix_timestamp >= queue.commit_close_at,
+        EventTicketingError::PurchaseCommitsOpen
+    );
+    require!(
+        !queue.closed,
+        EventTicketingError::PurchaseQueueAlreadyClosed
+    );
+
+    queue.closed = true;
+    event.reserved -= queue.seats - queue.winners();
+
+    msg!(
+        "Purchase queue for event {} closed: {} winners from {} commitments",
+        event.event_id,
+        queue.winners(),
+        queue.commit_count
+    );
+
+    Ok(())
+}
+
+/// Reveals the buyer's `nonce`. Commitments within the first `seats`
+/// positions get the next ticket and their deposit moves to the vault; the
+/// rest are refunded. The commitment is closed either way.
+pub fn reveal_purchase(ctx: Context<RevealPurchase>, nonce: [u8; 32]) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+    let queue = &ctx.accounts.queue;
+    let entry = &ctx.accounts.commitment;
+
+    require!(queue.closed, EventTicketingError::PurchaseQueueNotClosed);
+    require!(
+        Clock::get()?.unix_timestamp < queue.reveal_close_at,
+        EventTicketingError::PurchaseRevealClosed
+    );
+    require!(
+        entry.matches(&nonce),
+        EventTicketingError::InvalidPurchaseReveal
+    );
+
+    if queue.is_winner(entry.position) {
+        require!(!event.canceled(), EventTicketingError::EventCanceled);
+
+        entry.sub_lamports(entry.deposit)?;
+        ctx.accounts.vault.add_lamports(entry.deposit)?;
+
+        let ticket_id = event.take_ticket_id()?;
+        create_ticket_account(
+            &ctx.accounts.ticket,
+            &ctx.accounts.buyer,
+            &ctx.accounts.system_program,
+            event_key,
+            ticket_id,
+            entry.buyer,
+            entry.deposit,
+        )?;
+        let fees = event.record_sale(entry.deposit, 0)?;
+        record_daily_sales(
+            event,
+            ctx.accounts.sales_stats.as_deref_mut(),
+            1,
+            entry.deposit,
+            Clock::get()?.unix_timestamp,
+        )?;
+        record_vault_activity(
+            event,
+            ctx.accounts.transaction_log.as_ref(),
+            VaultActivity::Mint,
+            entry.buyer,
+            entry.deposit,
+            Clock::get()?.unix_timestamp,
+        )?;
+        emit_cpi_event(
+            &ctx.accounts.event_cpi_authority,
+            &TicketMinted {
+                event: event_key,
+                ticket: ctx.accounts.ticket.key(),
+                ticket_id,
+                owner: entry.buyer,
+                price: entry.deposit,
+                fees,
+            },
+        )?;
+
+        event.reserved -= 1;
+        event.sold += 1;
+
+        msg!(
+            "Queue position {} issued ticket #{} for event {}",
+            entry.position,
+            ticket_id,
+            event.event_id
+        );
+    } else {
+        msg!(
+            "Queue position {} refunded for event {}",
+            entry.position,
+            event.event_id
+        );
+    }
+
+    Ok(())
+}
+
+/// Refunds a commitment that was never revealed once the reveal window has
+/// passed, releasing its seat if it held one. Anyone can crank this.
+pub fn reclaim_purchase(ctx: Context<ReclaimPurchase>) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+    let queue = &ctx.accounts.queue;
+    let entry = &ctx.accounts.commitment;
+
+    require!(queue.closed, EventTicketingError::PurchaseQueueNotClosed);
+    require!(
+        Clock::get()?.unix_timestamp >= queue.reveal_close_at,
+        EventTicketingError::PurchaseRevealOpen
+    );
+
+    if queue.is_winner(entry.position) {
+        event.reserved -= 1;
+    }
+
+    msg!(
+        "Unrevealed queue position {} refunded for event {}",
+        entry.position,
+        event.event_id
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreatePurchaseQueue<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        init,
+        payer = event_authority,
+        space = PurchaseQueue::SPACE,
+        seeds = [PURCHASE_QUEUE_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub queue: Account<'info, PurchaseQueue>,
+
+    #[account(mut)]
+    pub event_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CommitPurchase<'info> {
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [PURCHASE_QUEUE_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub queue: Account<'info, PurchaseQueue>,
+
+    #[account(
+        init,
+        payer = buyer,
+        space = PurchaseCommitment::SPACE,
+        seeds = [PURCHASE_COMMITMENT_SEED, queue.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub commitment: Account<'info, PurchaseCommitment>,
+
+    /// CHECK: Pyth SOL/USD `PriceUpdateV2`; required for USD-priced events and
+    /// verified in `pyth::sol_usd_price`.
+    pub price_update: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClosePurchaseQueue<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [PURCHASE_QUEUE_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub queue: Account<'info, PurchaseQueue>,
+}
+
+#[derive(Accounts)]
+pub struct RevealPurchase<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        seeds = [PURCHASE_QUEUE_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub queue: Account<'info, PurchaseQueue>,
+
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [PURCHASE_COMMITMENT_SEED, queue.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub commitment: Account<'info, PurchaseCommitment>,
+
+    /// CHECK: Ticket PDA for `event.next_ticket_id`; only created for winning commitments.
+    #[account(mut)]
+    pub ticket: AccountInfo<'info>,
+
+    /// CHECK: This is the vault PDA that holds event funds. Verified by seeds.
+    #[account(
+        mut,
+        seeds = [
+            VAULT_SEED,
+            event.key().as_ref()
+        ],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Daily sales buckets; required when the event keeps them.
+    #[account(
+        mut,
+        constraint = event.load()?.sales_stats() == Some(sales_stats.key()) @ EventTicketingError::InvalidSalesStats
+    )]
+    pub sales_stats: Option<Account<'info, SalesStats>>,
+
+    /// Vault activity log; required when the event keeps one.
+    #[account(
+        mut,
+        constraint = event.load()?.transaction_log() == Some(transaction_log.key()) @ EventTicketingError::InvalidTransactionLog
+    )]
+    pub transaction_log: Option<AccountLoader<'info, TransactionLog>>,
+
+    /// CHECK: Signs the event self-CPI. Verified by seeds.
+    #[account(seeds = [EVENT_CPI_AUTHORITY_SEED], bump)]
+    pub event_cpi_authority: AccountInfo<'info>,
+
+    /// CHECK: This program, invoked by the event self-CPI.
+    #[account(address = crate::ID)]
+    pub program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimPurchase<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        seeds = [PURCHASE_QUEUE_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub queue: Account<'info, PurchaseQueue>,
+
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [PURCHASE_COMMITMENT_SEED, queue.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub commitment: Account<'info, PurchaseCommitment>,
+
+    /// CHECK: Buyer receiving the deposit and rent; verified by the commitment seeds.
+    #[account(mut)]
+    pub buyer: AccountInfo<'info>,
+}
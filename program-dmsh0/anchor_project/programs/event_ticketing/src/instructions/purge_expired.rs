@@ -0,0 +1,97 @@
+use super::reservation::release_expired_reservation;
+use crate::errors::EventTicketingError;
+use crate::state::{Event, Reservation, WaitlistEntry};
+use anchor_lang::prelude::*;
+
+/// Closes the event's expired reservations and, once the event is canceled
+/// or finalized, its unclaimed waitlist entries, passed as
+/// `remaining_accounts`. Each reservation must be followed by its buyer and
+/// each waitlist entry by its wallet, which get the escrowed deposit back.
+/// Anyone can crank this and keeps the rent of the closed accounts.
+pub fn purge_expired<'info>(ctx: Context<'_, '_, 'info, 'info, PurgeExpired<'info>>) -> Result<()> {
+    require!(
+        !ctx.remaining_accounts.is_empty(),
+        EventTicketingError::EmptyBatch
+    );
+
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+    let cranker = ctx.accounts.cranker.to_account_info();
+    let (mut reservations, mut entries) = (0u32, 0u32);
+
+    let mut accounts = ctx.remaining_accounts.iter();
+    while let Some(info) = accounts.next() {
+        require!(info.is_writable, EventTicketingError::InvalidPurgeAccount);
+
+        if info
+            .try_borrow_data()?
+            .starts_with(Reservation::DISCRIMINATOR)
+        {
+            let reservation = Account::<Reservation>::try_from(info)?;
+            require_keys_eq!(
+                reservation.event,
+                event_key,
+                EventTicketingError::InvalidPurgeAccount
+            );
+            let buyer = accounts
+                .next()
+                .ok_or(EventTicketingError::MissingReservationBuyer)?;
+            require_keys_eq!(
+                buyer.key(),
+                reservation.buyer,
+                EventTicketingError::MissingReservationBuyer
+            );
+
+            release_expired_reservation(event, &reservation)?;
+            reservation.sub_lamports(reservation.deposit)?;
+            buyer.add_lamports(reservation.deposit)?;
+            reservation.close(cranker.clone())?;
+            reservations += 1;
+        } else {
+            let entry = Account::<WaitlistEntry>::try_from(info)
+                .map_err(|_| error!(EventTicketingError::InvalidPurgeAccount))?;
+            require_keys_eq!(
+                entry.event,
+                event_key,
+                EventTicketingError::InvalidPurgeAccount
+            );
+            require!(
+                event.waitlist_closed(),
+                EventTicketingError::WaitlistNotClosed
+            );
+            let wallet = accounts
+                .next()
+                .ok_or(EventTicketingError::MissingWaitlistWallet)?;
+            require_keys_eq!(
+                wallet.key(),
+                entry.wallet,
+                EventTicketingError::MissingWaitlistWallet
+            );
+
+            entry.sub_lamports(entry.deposit)?;
+            wallet.add_lamports(entry.deposit)?;
+            entry.close(cranker.clone())?;
+            entries += 1;
+        }
+    }
+
+    msg!(
+        "Purged {} reservations and {} waitlist entries of event {} for {}",
+        reservations,
+        entries,
+        event.event_id,
+        cranker.key()
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PurgeExpired<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    /// Receives the rent of every purged account.
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+}
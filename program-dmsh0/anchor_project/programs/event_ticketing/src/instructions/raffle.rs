@@ -0,0 +1,438 @@
+use super::mint_tickets::create_ticket_account;
+use crate::attestation::require_no_attestor;
+use crate::constants::*;
+use crate::errors::EventTicketingError;
+use crate::events::{emit_cpi_event, TicketMinted};
+use crate::payment::require_sol_currency;
+use crate::randomness;
+use crate::sale_hook::require_no_sale_hook;
+use crate::sales_stats::record_daily_sales;
+use crate::state::{Event, Raffle, RaffleEntry, SalesStats, TransactionLog, VaultActivity};
+use crate::token_gate::require_no_token_gate;
+use crate::transaction_log::record_vault_activity;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Sets aside `seats` tickets to be allocated by raffle. Entries are accepted
+/// until `close_at`; the seats are held out of general sale in the meantime.
+pub fn create_raffle(ctx: Context<CreateRaffle>, seats: u32, close_at: i64) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    require!(!event.canceled(), EventTicketingError::EventCanceled);
+    require!(!event.is_finalized(), EventTicketingError::EventFinalized);
+    require!(
+        seats > 0 && event.available() >= seats,
+        EventTicketingError::InvalidTicketCount
+    );
+    require!(
+        close_at > Clock::get()?.unix_timestamp,
+        EventTicketingError::RaffleClosed
+    );
+
+    let raffle = &mut ctx.accounts.raffle;
+
+    raffle.event = event_key;
+    raffle.seats = seats;
+    raffle.close_at = close_at;
+    raffle.entry_count = 0;
+    raffle.randomness = None;
+    raffle.seed_slot = 0;
+    raffle.drawn = false;
+    raffle.seed = [0; 32];
+    raffle.canceled = false;
+
+    event.reserved += seats;
+
+    msg!(
+        "Raffle for {} seats of event {} open until {}",
+        seats,
+        event.event_id,
+        close_at
+    );
+
+    Ok(())
+}
+
+/// Enters the raffle, escrowing the ticket price in the entry account.
+pub fn enter_raffle(ctx: Context<EnterRaffle>) -> Result<()> {
+    let event = &ctx.accounts.event.load()?;
+    let raffle = &mut ctx.accounts.raffle;
+    let price = event.price;
+
+    require!(!event.canceled(), EventTicketingError::EventCanceled);
+    require!(!event.is_finalized(), EventTicketingError::EventFinalized);
+    require!(!event.sales_paused(), EventTicketingError::SalesPaused);
+    require_sol_currency(event)?;
+    require_no_sale_hook(event)?;
+    require_no_attestor(event)?;
+    require_no_token_gate(event)?;
+    event.require_numbered_tickets()?;
+    require!(
+        Clock::get()?.unix_timestamp < raffle.close_at,
+        EventTicketingError::RaffleClosed
+    );
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.entrant.to_account_info(),
+                to: ctx.accounts.entry.to_account_info(),
+            },
+        ),
+        price,
+    )?;
+
+    let entry = &mut ctx.accounts.entry;
+
+    entry.raffle = raffle.key();
+    entry.entrant = ctx.accounts.entrant.key();
+    entry.position = raffle.entry_count;
+    entry.deposit = price;
+
+    raffle.entry_count += 1;
+
+    msg!(
+        "{} entered raffle for event {} at position {}",
+        entry.entrant,
+        event.event_id,
+        entry.position
+    );
+
+    Ok(())
+}
+
+/// Binds a freshly committed Switchboard randomness account to the raffle
+/// once entries have closed. Its value is revealed later in `draw_raffle`.
+pub fn commit_raffle(ctx: Context<CommitRaffle>) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp >= raffle.close_at,
+        EventTicketingError::RaffleNotClosed
+    );
+    require!(!raffle.canceled, EventTicketingError::RaffleCanceled);
+    require!(
+        raffle.randomness.is_none(),
+        EventTicketingError::RaffleAlreadyCommitted
+    );
+
+    raffle.seed_slot = randomness::require_fresh_commit(&ctx.accounts.randomness, &clock)?;
+    raffle.randomness = Some(ctx.accounts.randomness.key());
+
+    msg!(
+        "Raffle for event {} committed to randomness {}",
+        ctx.accounts.event.load()?.event_id,
+        ctx.accounts.randomness.key()
+    );
+
+    Ok(())
+}
+
+/// Draws the winners from the revealed randomness and releases any seats
+/// left over when the raffle is undersubscribed. Anyone can crank this.
+pub fn draw_raffle(ctx: Context<DrawRaffle>) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+    let raffle = &mut ctx.accounts.raffle;
+
+    require!(!raffle.canceled, EventTicketingError::RaffleCanceled);
+    require!(!raffle.drawn, EventTicketingError::RaffleAlreadyDrawn);
+    require!(
+        raffle.randomness == Some(ctx.accounts.randomness.key()),
+        EventTicketingError::RaffleNotCommitted
+    );
+
+    raffle.seed =
+        randomness::revealed_value(&ctx.accounts.randomness, &Clock::get()?, raffle.seed_slot)?;
+    raffle.drawn = true;
+
+    event.reserved -= raffle.seats - raffle.winners();
+
+    msg!(
+        "Raffle for event {} drawn: {} winners from {} entries",
+        event.event_id,
+        raffle.winners(),
+        raffle.entry_count
+    );
+
+    Ok(())
+}
+
+/// Calls off a raffle that was never drawn, releasing its seats so every
+/// entry can be refunded through `settle_raffle_entry`. Anyone can crank
+/// this once the event is canceled or the draw is
+/// `RAFFLE_DRAW_TIMEOUT_SECS` overdue.
+pub fn cancel_raffle(ctx: Context<CancelRaffle>) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+    let raffle = &mut ctx.accounts.raffle;
+
+    require!(!raffle.drawn, EventTicketingError::RaffleAlreadyDrawn);
+    require!(!raffle.canceled, EventTicketingError::RaffleCanceled);
+    require!(
+        event.canceled()
+            || Clock::get()?.unix_timestamp >= raffle.close_at + RAFFLE_DRAW_TIMEOUT_SECS,
+        EventTicketingError::RaffleDrawPending
+    );
+
+    raffle.canceled = true;
+    event.reserved -= raffle.seats;
+
+    msg!(
+        "Raffle for event {} canceled with {} entries",
+        event.event_id,
+        raffle.entry_count
+    );
+
+    Ok(())
+}
+
+/// Settles one raffle entry after the draw: winners get their ticket and
+/// their deposit moves to the vault, everyone else is refunded. Entries in a
+/// canceled raffle, and winners of a canceled event, are refunded too. The
+/// entry is closed either way. Anyone can crank this.
+pub fn settle_raffle_entry(ctx: Context<SettleRaffleEntry>) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+    let raffle = &ctx.accounts.raffle;
+    let entry = &ctx.accounts.entry;
+
+    require!(
+        raffle.drawn || raffle.canceled,
+        EventTicketingError::RaffleNotDrawn
+    );
+
+    if raffle.drawn && raffle.is_winner(entry.position) && event.canceled() {
+        event.reserved -= 1;
+
+        msg!(
+            "Raffle winner {} refunded for canceled event {}",
+            entry.entrant,
+            event.event_id
+        );
+    } else if raffle.drawn && raffle.is_winner(entry.position) {
+        entry.sub_lamports(entry.deposit)?;
+        ctx.accounts.vault.add_lamports(entry.deposit)?;
+
+        let ticket_id = event.take_ticket_id()?;
+        create_ticket_account(
+            &ctx.accounts.ticket,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            event_key,
+            ticket_id,
+            entry.entrant,
+            entry.deposit,
+        )?;
+        let fees = event.record_sale(entry.deposit, 0)?;
+        record_daily_sales(
+            event,
+            ctx.accounts.sales_stats.as_deref_mut(),
+            1,
+            entry.deposit,
+            Clock::get()?.unix_timestamp,
+        )?;
+        record_vault_activity(
+            event,
+            ctx.accounts.transaction_log.as_ref(),
+            VaultActivity::Mint,
+            entry.entrant,
+            entry.deposit,
+            Clock::get()?.unix_timestamp,
+        )?;
+        emit_cpi_event(
+            &ctx.accounts.event_cpi_authority,
+            &TicketMinted {
+                event: event_key,
+                ticket: ctx.accounts.ticket.key(),
+                ticket_id,
+                owner: entry.entrant,
+                price: entry.deposit,
+                fees,
+            },
+        )?;
+
+        event.reserved -= 1;
+        event.sold += 1;
+
+        msg!(
+            "Raffle winner {} issued a ticket for event {}",
+            entry.entrant,
+            event.event_id
+        );
+    } else {
+        msg!(
+            "Raffle entry of {} refunded for event {}",
+            entry.entrant,
+            event.event_id
+        );
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateRaffle<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        init,
+        payer = event_authority,
+        space = Raffle::SPACE,
+        seeds = [RAFFLE_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(mut)]
+    pub event_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EnterRaffle<'info> {
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [RAFFLE_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        init,
+        payer = entrant,
+        space = RaffleEntry::SPACE,
+        seeds = [RAFFLE_ENTRY_SEED, raffle.key().as_ref(), entrant.key().as_ref()],
+        bump
+    )]
+    pub entry: Account<'info, RaffleEntry>,
+
+    #[account(mut)]
+    pub entrant: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CommitRaffle<'info> {
+    #[account(
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [RAFFLE_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// CHECK: Switchboard randomness account; owner and layout are verified
+    /// in `randomness::require_fresh_commit`.
+    pub randomness: AccountInfo<'info>,
+
+    pub event_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DrawRaffle<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [RAFFLE_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// CHECK: Must be the randomness account committed in `commit_raffle`.
+    pub randomness: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelRaffle<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [RAFFLE_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub raffle: Account<'info, Raffle>,
+}
+
+#[derive(Accounts)]
+pub struct SettleRaffleEntry<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        seeds = [RAFFLE_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        mut,
+        close = entrant,
+        seeds = [RAFFLE_ENTRY_SEED, raffle.key().as_ref(), entrant.key().as_ref()],
+        bump
+    )]
+    pub entry: Account<'info, RaffleEntry>,
+
+    /// CHECK: Entrant receiving the refund or entry rent; verified by the entry seeds.
+    #[account(mut)]
+    pub entrant: AccountInfo<'info>,
+
+    /// CHECK: Ticket PDA for `event.next_ticket_id`; only created for winning entries.
+    #[account(mut)]
+    pub ticket: AccountInfo<'info>,
+
+    /// CHECK: This is the vault PDA that holds event funds. Verified by seeds.
+    #[account(
+        mut,
+        seeds = [
+            VAULT_SEED,
+            event.key().as_ref()
+        ],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Daily sales buckets; required when the event keeps them.
+    #[account(
+        mut,
+        constraint = event.load()?.sales_stats() == Some(sales_stats.key()) @ EventTicketingError::InvalidSalesStats
+    )]
+    pub sales_stats: Option<Account<'info, SalesStats>>,
+
+    /// Vault activity log; required when the event keeps one.
+    #[account(
+        mut,
+        constraint = event.load()?.transaction_log() == Some(transaction_log.key()) @ EventTicketingError::InvalidTransactionLog
+    )]
+    pub transaction_log: Option<AccountLoader<'info, TransactionLog>>,
+
+    /// CHECK: Signs the event self-CPI. Verified by seeds.
+    #[account(seeds = [EVENT_CPI_AUTHORITY_SEED], bump)]
+    pub event_cpi_authority: AccountInfo<'info>,
+
+    /// CHECK: This program, invoked by the event self-CPI.
+    #[account(address = crate::ID)]
+    pub program: AccountInfo<'info>,
+}
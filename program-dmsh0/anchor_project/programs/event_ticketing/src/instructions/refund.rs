@@ -1,34 +1,106 @@
+use super::mint_ticket_with_seat::release_seat;
+use crate::constants::EVENT_CPI_AUTHORITY_SEED;
 use crate::errors::EventTicketingError;
-use crate::state::{Event, Ticket};
+use crate::events::{emit_cpi_event, TicketRefunded};
+use crate::payment::{pay_out_tokens, require_vault_token_account};
+use crate::rbac::{has_permission, Delegations, Permission};
+use crate::sales_stats::record_daily_refunds;
+use crate::state::{
+    CoOrganizer, Event, EventAuthorityConfig, RefundPool, SalesStats, SeatClaim, SeatMap, Ticket,
+    TransactionLog, VaultActivity,
+};
+use crate::transaction_log::record_vault_activity;
+use crate::vault::pay_refund;
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
+use anchor_spl::token::{Token, TokenAccount};
 
 pub fn refund(ctx: Context<Refund>) -> Result<()> {
-    let event = &ctx.accounts.event;
+    has_permission(
+        &*ctx.accounts.event.load()?,
+        &ctx.accounts.event_authority.key(),
+        Permission::Refund,
+        &Delegations {
+            authority_config: ctx.accounts.authority_config.as_deref(),
+            cosigners: ctx.remaining_accounts,
+            co_organizer: ctx.accounts.co_organizer.as_deref(),
+            ..Default::default()
+        },
+    )?;
+
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
     let ticket = &mut ctx.accounts.ticket;
 
-    require!(!ticket.is_used, EventTicketingError::CannotRefundUsedTicket);
-    require!(!ticket.refunded, EventTicketingError::AlreadyRefunded);
+    require_refundable(event, ticket, Clock::get()?.unix_timestamp)?;
+
+    let refund_amount = ticket.purchase_price;
 
-    let refund_amount = event.price;
-
-    let event_key = event.key();
-    let seeds = &[b"vault".as_ref(), event_key.as_ref(), &[ctx.bumps.vault]];
-    let signer_seeds = &[&seeds[..]];
-
-    system_program::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.vault.to_account_info(),
-                to: ctx.accounts.ticket_owner.to_account_info(),
-            },
-            signer_seeds,
-        ),
+    if event.currency().is_some() {
+        let (Some(vault_token_account), Some(owner_token_account), Some(token_program)) = (
+            &ctx.accounts.vault_token_account,
+            &ctx.accounts.owner_token_account,
+            &ctx.accounts.token_program,
+        ) else {
+            return err!(EventTicketingError::CurrencyAccountsMissing);
+        };
+        require_vault_token_account(event, &ctx.accounts.vault.key(), vault_token_account)?;
+        pay_out_tokens(
+            vault_token_account,
+            owner_token_account,
+            &ctx.accounts.vault,
+            ctx.bumps.vault,
+            event_key,
+            token_program,
+            refund_amount,
+        )?;
+    } else {
+        pay_refund(
+            &ctx.accounts.vault,
+            ctx.bumps.vault,
+            event_key,
+            ctx.accounts.refund_pool.as_mut(),
+            &ctx.accounts.ticket_owner,
+            &ctx.accounts.system_program.to_account_info(),
+            refund_amount,
+        )?;
+    }
+
+    ticket.refunded = true;
+    event.freed_seats += 1;
+    event.refunded_count += 1;
+    record_daily_refunds(
+        event,
+        ctx.accounts.sales_stats.as_deref_mut(),
+        1,
         refund_amount,
+        Clock::get()?.unix_timestamp,
+    )?;
+    record_vault_activity(
+        event,
+        ctx.accounts.transaction_log.as_ref(),
+        VaultActivity::Refund,
+        ctx.accounts.ticket_owner.key(),
+        refund_amount,
+        Clock::get()?.unix_timestamp,
     )?;
 
-    ticket.refunded = true;
+    release_seat(
+        event,
+        ticket,
+        ctx.accounts.seat_map.as_ref(),
+        ctx.accounts.seat_claim.as_ref(),
+    )?;
+
+    emit_cpi_event(
+        &ctx.accounts.event_cpi_authority,
+        &TicketRefunded {
+            event: event_key,
+            ticket: ticket.key(),
+            ticket_id: ticket.ticket_id,
+            recipient: ctx.accounts.ticket_owner.key(),
+            amount: refund_amount,
+        },
+    )?;
 
     msg!(
         "Ticket #{} refunded {} lamports to {} by event authority {}",
@@ -41,12 +113,42 @@ pub fn refund(ctx: Context<Refund>) -> Result<()> {
     Ok(())
 }
 
+/// Checks that the event authority may refund `ticket` at `now`.
+pub(crate) fn require_refundable(event: &Event, ticket: &Ticket, now: i64) -> Result<()> {
+    require!(
+        !ticket.is_used(),
+        EventTicketingError::CannotRefundUsedTicket
+    );
+    require!(!ticket.refunded, EventTicketingError::AlreadyRefunded);
+    require!(!ticket.comped, EventTicketingError::CompTicketNotRefundable);
+    require!(!event.is_finalized(), EventTicketingError::EventFinalized);
+
+    // Past `end_time` only cancellation refunds remain; holders of a lapsed
+    // event use `claim_refund` instead.
+    require!(
+        event.canceled() || !event.has_ended(now),
+        EventTicketingError::EventEnded
+    );
+    require!(
+        event.canceled() || event.refund_window_open(now),
+        EventTicketingError::RefundNotAllowed
+    );
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct Refund<'info> {
-    #[account(
-        constraint = event.event_authority == event_authority.key()
-    )]
-    pub event: Account<'info, Event>,
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(constraint = authority_config.event == event.key())]
+    pub authority_config: Option<Account<'info, EventAuthorityConfig>>,
+
+    /// Co-organizer record when `event_authority` is a teammate allowed to
+    /// refund.
+    #[account(constraint = co_organizer.event == event.key() @ EventTicketingError::UnauthorizedEventAuthority)]
+    pub co_organizer: Option<Account<'info, CoOrganizer>>,
 
     #[account(
         mut,
@@ -65,11 +167,74 @@ pub struct Refund<'info> {
     )]
     pub vault: AccountInfo<'info>,
 
+    /// Event's refund pool; pays the refund instead of the vault once the
+    /// event is canceled.
+    #[account(
+        mut,
+        constraint = refund_pool.event == event.key() @ EventTicketingError::InvalidRefundPool
+    )]
+    pub refund_pool: Option<Account<'info, RefundPool>>,
+
+    /// Required to release the seat of a seated ticket.
+    #[account(
+        mut,
+        constraint = event.load()?.seat_map() == Some(seat_map.key()) @ EventTicketingError::InvalidSeatMap
+    )]
+    pub seat_map: Option<AccountLoader<'info, SeatMap>>,
+
+    /// Claim on the refunded ticket's seat, required when it has one; closing it
+    /// lets the seat be sold again.
+    #[account(
+        mut,
+        close = ticket_owner,
+        constraint = seat_claim.ticket == ticket.key() @ EventTicketingError::InvalidSeat
+    )]
+    pub seat_claim: Option<Account<'info, SeatClaim>>,
+
     /// CHECK: This is the ticket owner who will receive the refund. No signature required.
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = ticket_owner.key() == ticket.refund_recipient() @ EventTicketingError::InvalidRefundRecipient
+    )]
     pub ticket_owner: AccountInfo<'info>,
 
+    /// Vault's token account for SPL-currency events.
+    #[account(mut)]
+    pub vault_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Ticket owner's token account receiving an SPL-currency refund.
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == ticket.refund_recipient() @ EventTicketingError::InvalidRefundRecipient
+    )]
+    pub owner_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// The event authority, or a co-organizer allowed to refund.
     pub event_authority: Signer<'info>,
 
     pub system_program: Program<'info, System>,
+
+    /// Daily sales buckets; required when the event keeps them.
+    #[account(
+        mut,
+        constraint = event.load()?.sales_stats() == Some(sales_stats.key()) @ EventTicketingError::InvalidSalesStats
+    )]
+    pub sales_stats: Option<Account<'info, SalesStats>>,
+
+    /// Vault activity log; required when the event keeps one.
+    #[account(
+        mut,
+        constraint = event.load()?.transaction_log() == Some(transaction_log.key()) @ EventTicketingError::InvalidTransactionLog
+    )]
+    pub transaction_log: Option<AccountLoader<'info, TransactionLog>>,
+
+    /// CHECK: Signs the event self-CPI. Verified by seeds.
+    #[account(seeds = [EVENT_CPI_AUTHORITY_SEED], bump)]
+    pub event_cpi_authority: AccountInfo<'info>,
+
+    /// CHECK: This program, invoked by the event self-CPI.
+    #[account(address = crate::ID)]
+    pub program: AccountInfo<'info>,
 }
@@ -0,0 +1,185 @@
+use super::refund::require_refundable;
+use crate::authority::require_event_authority;
+use crate::constants::EVENT_CPI_AUTHORITY_SEED;
+use crate::errors::EventTicketingError;
+use crate::events::{emit_cpi_event, TicketRefunded};
+use crate::sales_stats::record_daily_refunds;
+use crate::state::{
+    Event, EventAuthorityConfig, RefundPool, SalesStats, Ticket, TransactionLog, VaultActivity,
+};
+use crate::transaction_log::record_vault_activity;
+use crate::vault::pay_refund;
+use anchor_lang::prelude::*;
+
+/// Refunds every ticket passed in `remaining_accounts` as writable
+/// `(ticket, refund recipient)` pairs. On multisig events the extra
+/// approving signers come first, ahead of the pairs. The whole batch fails
+/// if any ticket is not refundable.
+///
+/// Only lamport-priced events are supported. Seated tickets must go through
+/// `refund`, which also closes their SeatClaim so the seat can be resold.
+pub fn refund_batch<'info>(ctx: Context<'_, '_, 'info, 'info, RefundBatch<'info>>) -> Result<()> {
+    let cosigners = ctx
+        .remaining_accounts
+        .iter()
+        .take_while(|account| account.is_signer)
+        .count();
+    let (signers, pairs) = ctx.remaining_accounts.split_at(cosigners);
+
+    require_event_authority(
+        &*ctx.accounts.event.load()?,
+        &ctx.accounts.event_authority.key(),
+        ctx.accounts.authority_config.as_deref(),
+        signers,
+    )?;
+
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    require!(
+        event.currency().is_none(),
+        EventTicketingError::CurrencyNotSupported
+    );
+    require!(!pairs.is_empty(), EventTicketingError::EmptyBatch);
+    require!(
+        pairs.len() % 2 == 0,
+        EventTicketingError::UnpairedRefundAccounts
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let mut total: u64 = 0;
+
+    for pair in pairs.chunks_exact(2) {
+        let (ticket_info, recipient) = (&pair[0], &pair[1]);
+        require!(
+            ticket_info.is_writable,
+            EventTicketingError::TicketNotWritable
+        );
+
+        let mut ticket = Account::<Ticket>::try_from(ticket_info)?;
+
+        require_keys_eq!(
+            ticket.event,
+            event_key,
+            EventTicketingError::InvalidTicketAccount
+        );
+        require_keys_eq!(
+            recipient.key(),
+            ticket.refund_recipient(),
+            EventTicketingError::InvalidRefundRecipient
+        );
+        require_refundable(event, &ticket, now)?;
+        require!(
+            ticket.seat.is_none(),
+            EventTicketingError::SeatedTicketInBatch
+        );
+
+        let refund_amount = ticket.purchase_price;
+        pay_refund(
+            &ctx.accounts.vault,
+            ctx.bumps.vault,
+            event_key,
+            ctx.accounts.refund_pool.as_mut(),
+            recipient,
+            &ctx.accounts.system_program.to_account_info(),
+            refund_amount,
+        )?;
+
+        ticket.refunded = true;
+        event.freed_seats += 1;
+        event.refunded_count += 1;
+        record_vault_activity(
+            event,
+            ctx.accounts.transaction_log.as_ref(),
+            VaultActivity::Refund,
+            recipient.key(),
+            refund_amount,
+            now,
+        )?;
+
+        emit_cpi_event(
+            &ctx.accounts.event_cpi_authority,
+            &TicketRefunded {
+                event: event_key,
+                ticket: ticket.key(),
+                ticket_id: ticket.ticket_id,
+                recipient: recipient.key(),
+                amount: refund_amount,
+            },
+        )?;
+
+        ticket.exit(&crate::ID)?;
+        total += refund_amount;
+    }
+    record_daily_refunds(
+        event,
+        ctx.accounts.sales_stats.as_deref_mut(),
+        (pairs.len() / 2) as u32,
+        total,
+        now,
+    )?;
+
+    msg!(
+        "{} tickets for event {} refunded ({} lamports) by event authority {}",
+        pairs.len() / 2,
+        event.event_id,
+        total,
+        ctx.accounts.event_authority.key()
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RefundBatch<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(constraint = authority_config.event == event.key())]
+    pub authority_config: Option<Account<'info, EventAuthorityConfig>>,
+
+    /// CHECK: This is the vault PDA that holds event funds. Verified by seeds.
+    #[account(
+        mut,
+        seeds = [
+            b"vault",
+            event.key().as_ref()
+        ],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    /// Event's refund pool; pays the refund instead of the vault once the
+    /// event is canceled.
+    #[account(
+        mut,
+        constraint = refund_pool.event == event.key() @ EventTicketingError::InvalidRefundPool
+    )]
+    pub refund_pool: Option<Account<'info, RefundPool>>,
+
+    pub event_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Daily sales buckets; required when the event keeps them.
+    #[account(
+        mut,
+        constraint = event.load()?.sales_stats() == Some(sales_stats.key()) @ EventTicketingError::InvalidSalesStats
+    )]
+    pub sales_stats: Option<Account<'info, SalesStats>>,
+
+    /// Vault activity log; required when the event keeps one.
+    #[account(
+        mut,
+        constraint = event.load()?.transaction_log() == Some(transaction_log.key()) @ EventTicketingError::InvalidTransactionLog
+    )]
+    pub transaction_log: Option<AccountLoader<'info, TransactionLog>>,
+
+    /// CHECK: Signs the event self-CPI. Verified by seeds.
+    #[account(seeds = [EVENT_CPI_AUTHORITY_SEED], bump)]
+    pub event_cpi_authority: AccountInfo<'info>,
+
+    /// CHECK: This program, invoked by the event self-CPI.
+    #[account(address = crate::ID)]
+    pub program: AccountInfo<'info>,
+}
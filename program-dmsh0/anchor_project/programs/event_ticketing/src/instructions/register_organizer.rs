@@ -1,11 +1,18 @@
-use crate::constants::ORGANIZER_SEED;
-use crate::state::OrganizerRegistry;
+use super::bond::deposit_bond;
+use crate::constants::{
+    BOND_SEED, CONFIG_SEED, EVENT_CPI_AUTHORITY_SEED, ORGANIZER_SEED, ORGANIZER_VERSION,
+};
+use crate::errors::EventTicketingError;
+use crate::events::{emit_cpi_event, OrganizerRegistered};
+use crate::state::{Config, OrganizerRegistry};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
 pub struct RegisterOrganizer<'info> {
+    /// `init_if_needed` so a repeat registration reaches the handler and fails
+    /// with `OrganizerAlreadyRegistered` instead of a System Program error.
     #[account(
-        init,
+        init_if_needed,
         payer = organizer,
         space = OrganizerRegistry::SPACE,
         seeds = [ORGANIZER_SEED, organizer.key().as_ref()],
@@ -13,18 +20,61 @@ pub struct RegisterOrganizer<'info> {
     )]
     pub organizer_registry: Account<'info, OrganizerRegistry>,
 
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Lamport-only PDA receiving the organizer's bond. Verified by seeds.
+    #[account(
+        mut,
+        seeds = [BOND_SEED, organizer.key().as_ref()],
+        bump
+    )]
+    pub bond: AccountInfo<'info>,
+
     #[account(mut)]
     pub organizer: Signer<'info>,
 
     pub system_program: Program<'info, System>,
+
+    /// CHECK: Signs the event self-CPI. Verified by seeds.
+    #[account(seeds = [EVENT_CPI_AUTHORITY_SEED], bump)]
+    pub event_cpi_authority: AccountInfo<'info>,
+
+    /// CHECK: This program, invoked by the event self-CPI.
+    #[account(address = crate::ID)]
+    pub program: AccountInfo<'info>,
 }
 
 pub fn register_organizer(ctx: Context<RegisterOrganizer>) -> Result<()> {
     let organizer_registry = &mut ctx.accounts.organizer_registry;
     let clock = Clock::get()?;
 
+    require!(
+        organizer_registry.organizer == Pubkey::default(),
+        EventTicketingError::OrganizerAlreadyRegistered
+    );
+
     organizer_registry.organizer = ctx.accounts.organizer.key();
     organizer_registry.registered_at = clock.unix_timestamp;
+    organizer_registry.verified = false;
+    organizer_registry.bond = ctx.accounts.config.organizer_bond_lamports;
+    organizer_registry.open_events = 0;
+    organizer_registry.version = ORGANIZER_VERSION;
+
+    deposit_bond(
+        &ctx.accounts.organizer,
+        &ctx.accounts.bond,
+        &ctx.accounts.system_program,
+        organizer_registry.bond,
+    )?;
+
+    emit_cpi_event(
+        &ctx.accounts.event_cpi_authority,
+        &OrganizerRegistered {
+            organizer: organizer_registry.organizer,
+            registered_at: organizer_registry.registered_at,
+        },
+    )?;
 
     msg!("Organizer registered: {}", ctx.accounts.organizer.key());
 
@@ -0,0 +1,39 @@
+use crate::constants::REFERRER_SEED;
+use crate::state::{Event, Referrer};
+use anchor_lang::prelude::*;
+
+pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+    let referrer = &mut ctx.accounts.referrer;
+
+    referrer.event = ctx.accounts.event.key();
+    referrer.referrer = ctx.accounts.wallet.key();
+    referrer.referrals = 0;
+    referrer.earned = 0;
+
+    msg!(
+        "Referrer {} registered for event {}",
+        referrer.referrer,
+        ctx.accounts.event.load()?.event_id
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        init,
+        payer = wallet,
+        space = Referrer::SPACE,
+        seeds = [REFERRER_SEED, event.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub referrer: Account<'info, Referrer>,
+
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,164 @@
+use crate::constants::*;
+use crate::errors::EventTicketingError;
+use crate::instructions::mint_ticket::*;
+use crate::pyth;
+use crate::state::{Event, Reservation};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Holds a seat for the buyer for `RESERVATION_SLOTS` slots while their
+/// payment is being confirmed. The current ticket price is escrowed in the
+/// reservation so holds cost as much capital as purchases.
+pub fn reserve_ticket(ctx: Context<ReserveTicket>) -> Result<()> {
+    let deposit = pyth::sale_price(
+        &*ctx.accounts.event.load()?,
+        ctx.accounts.price_update.as_deref(),
+        Clock::get()?.unix_timestamp,
+    )?;
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    require!(!event.canceled(), EventTicketingError::EventCanceled);
+    require!(!event.is_finalized(), EventTicketingError::EventFinalized);
+    require!(!event.sales_paused(), EventTicketingError::SalesPaused);
+    require!(!event.is_presale(), EventTicketingError::PresaleActive);
+    require!(event.available() > 0, EventTicketingError::EventSoldOut);
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.reservation.to_account_info(),
+            },
+        ),
+        deposit,
+    )?;
+
+    let reservation = &mut ctx.accounts.reservation;
+
+    reservation.event = event_key;
+    reservation.buyer = ctx.accounts.buyer.key();
+    reservation.expires_at_slot = Clock::get()?.slot + RESERVATION_SLOTS;
+    reservation.deposit = deposit;
+
+    event.reserved += 1;
+
+    msg!(
+        "Seat reserved for event {} by {} until slot {}",
+        event.event_id,
+        reservation.buyer,
+        reservation.expires_at_slot
+    );
+
+    Ok(())
+}
+
+/// Pays for a held seat and releases the hold, returning its deposit.
+pub fn finalize_reservation(ctx: Context<FinalizeReservation>) -> Result<()> {
+    require!(
+        Clock::get()?.slot <= ctx.accounts.reservation.expires_at_slot,
+        EventTicketingError::ReservationExpired
+    );
+
+    let sale = &mut ctx.accounts.sale;
+
+    ctx.accounts
+        .reservation
+        .close(sale.buyer.to_account_info())?;
+    sale.event.load_mut()?.reserved -= 1;
+
+    let buyer = sale.buyer.key();
+    let price = sale.sale_price()?;
+    sell_ticket(sale, buyer, price)
+}
+
+/// Releases an expired hold back to general sale, returning its deposit and
+/// rent to the buyer. Anyone can crank this.
+pub fn purge_reservation(ctx: Context<PurgeReservation>) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    release_expired_reservation(event, &ctx.accounts.reservation)?;
+
+    msg!(
+        "Expired reservation by {} purged from event {}",
+        ctx.accounts.buyer.key(),
+        event.event_id
+    );
+
+    Ok(())
+}
+
+/// Gives an expired hold's seat back to general sale. The caller returns the
+/// deposit to the buyer and closes the reservation.
+pub(crate) fn release_expired_reservation(
+    event: &mut Event,
+    reservation: &Reservation,
+) -> Result<()> {
+    require!(
+        Clock::get()?.slot > reservation.expires_at_slot,
+        EventTicketingError::ReservationNotExpired
+    );
+    event.reserved -= 1;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReserveTicket<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        init,
+        payer = buyer,
+        space = Reservation::SPACE,
+        seeds = [RESERVATION_SEED, event.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub reservation: Account<'info, Reservation>,
+
+    /// CHECK: Pyth SOL/USD `PriceUpdateV2`; required for USD-priced events and
+    /// verified in `pyth::sol_usd_price`.
+    pub price_update: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeReservation<'info> {
+    pub sale: MintTicket<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            RESERVATION_SEED,
+            sale.event.key().as_ref(),
+            sale.buyer.key().as_ref()
+        ],
+        bump
+    )]
+    pub reservation: Account<'info, Reservation>,
+}
+
+#[derive(Accounts)]
+pub struct PurgeReservation<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [RESERVATION_SEED, event.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub reservation: Account<'info, Reservation>,
+
+    /// CHECK: Buyer who made the reservation; receives its deposit and rent
+    /// back.
+    #[account(mut)]
+    pub buyer: AccountInfo<'info>,
+}
@@ -0,0 +1,191 @@
+use super::bond::lock_organizer_bond;
+use super::initialize_event::require_supply_allowed;
+use crate::constants::*;
+use crate::errors::EventTicketingError;
+use crate::state::{
+    Config, Event, EventMetadata, EventRegistry, EventRegistryPage, EventSeries, OrganizerRegistry,
+};
+use crate::vault::fund_vault_reserve;
+use anchor_lang::prelude::*;
+
+/// Creates a series holding the name and pricing defaults shared by every
+/// occurrence of a recurring event.
+pub fn create_series(
+    ctx: Context<CreateSeries>,
+    series_id: u32,
+    name: String,
+    price: u64,
+    supply: u32,
+    transferable: bool,
+) -> Result<()> {
+    require!(name.len() <= MAX_NAME_LEN, EventTicketingError::NameTooLong);
+
+    let series = &mut ctx.accounts.series;
+
+    series.organizer = ctx.accounts.organizer.key();
+    series.series_id = series_id;
+    series.name = name;
+    series.price = price;
+    series.supply = supply;
+    series.transferable = transferable;
+    series.occurrences = 0;
+    series.pass_price = 0;
+
+    msg!("Series {} created: {}", series_id, series.name);
+
+    Ok(())
+}
+
+/// Creates one occurrence of a series as its own Event (with its own vault),
+/// copying the series defaults.
+pub fn create_series_event(
+    ctx: Context<CreateSeriesEvent>,
+    event_id: u32,
+    date: String,
+) -> Result<()> {
+    require!(date.len() <= MAX_DATE_LEN, EventTicketingError::DateTooLong);
+
+    let series = &mut ctx.accounts.series;
+
+    require_supply_allowed(
+        &ctx.accounts.config,
+        ctx.accounts.organizer_registry.as_deref(),
+        series.supply,
+    )?;
+    let bond_organizer = lock_organizer_bond(
+        &ctx.accounts.config,
+        ctx.accounts.organizer_registry.as_deref_mut(),
+    )?;
+
+    fund_vault_reserve(
+        &ctx.accounts.organizer,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+    )?;
+
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_init()?;
+
+    event.init(ctx.accounts.organizer.key(), event_id);
+    event.price = series.price;
+    event.supply = series.supply;
+    event.max_total_supply = series.supply;
+    event.transferable = series.transferable.into();
+    event.series = series.key();
+    event.bond_organizer = bond_organizer.unwrap_or_default();
+
+    series.occurrences += 1;
+
+    let metadata = &mut ctx.accounts.metadata;
+
+    metadata.event = event_key;
+    metadata.name = series.name.clone();
+    metadata.date = date;
+    metadata.metadata_uri = String::new();
+
+    ctx.accounts
+        .registry
+        .record(&mut ctx.accounts.registry_page, event_key);
+
+    msg!(
+        "Event {} created as occurrence #{} of series {}",
+        event_id,
+        series.occurrences,
+        series.series_id
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(series_id: u32)]
+pub struct CreateSeries<'info> {
+    #[account(
+        init,
+        payer = organizer,
+        space = EventSeries::space(MAX_NAME_LEN),
+        seeds = [
+            SERIES_SEED,
+            organizer.key().as_ref(),
+            &series_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub series: Account<'info, EventSeries>,
+
+    #[account(mut)]
+    pub organizer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(event_id: u32)]
+pub struct CreateSeriesEvent<'info> {
+    #[account(mut, has_one = organizer)]
+    pub series: Account<'info, EventSeries>,
+
+    #[account(
+        init,
+        payer = organizer,
+        space = Event::SPACE,
+        seeds = [
+            EVENT_SEED,
+            organizer.key().as_ref(),
+            &event_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        init,
+        payer = organizer,
+        space = EventMetadata::SPACE,
+        seeds = [EVENT_METADATA_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub metadata: Account<'info, EventMetadata>,
+
+    /// CHECK: This is the vault PDA that will hold event funds. Verified by seeds.
+    #[account(
+        mut,
+        seeds = [
+            VAULT_SEED,
+            event.key().as_ref()
+        ],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = organizer,
+        space = EventRegistry::SPACE,
+        seeds = [REGISTRY_SEED],
+        bump
+    )]
+    pub registry: Account<'info, EventRegistry>,
+
+    #[account(
+        init_if_needed,
+        payer = organizer,
+        space = EventRegistryPage::SPACE,
+        seeds = [REGISTRY_PAGE_SEED, &registry.current_page().to_le_bytes()],
+        bump
+    )]
+    pub registry_page: Account<'info, EventRegistryPage>,
+
+    /// Required when the series supply exceeds the config's unverified limit
+    /// or when organizer bonds are enabled.
+    #[account(mut, constraint = organizer_registry.organizer == organizer.key())]
+    pub organizer_registry: Option<Account<'info, OrganizerRegistry>>,
+
+    #[account(mut)]
+    pub organizer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
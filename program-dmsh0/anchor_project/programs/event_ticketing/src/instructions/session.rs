@@ -0,0 +1,163 @@
+use crate::constants::{SESSION_CHECK_IN_SEED, SESSION_SEED};
+use crate::errors::EventTicketingError;
+use crate::rbac::{has_permission, Delegations, Permission};
+use crate::state::{CoOrganizer, Event, Session, SessionCheckIn, Staff, Ticket};
+use anchor_lang::prelude::*;
+
+/// Adds a session (talk, track slot, workshop) to the event, open for
+/// check-ins between `start_time` and `end_time`. `capacity` caps how many
+/// tickets can attend it; zero means the room is unlimited.
+pub fn create_session(
+    ctx: Context<CreateSession>,
+    session_id: u16,
+    start_time: i64,
+    end_time: i64,
+    capacity: u32,
+) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+    let event = &ctx.accounts.event.load()?;
+
+    require!(!event.canceled(), EventTicketingError::EventCanceled);
+    require!(
+        start_time < end_time,
+        EventTicketingError::InvalidSessionWindow
+    );
+
+    let session = &mut ctx.accounts.session;
+
+    session.event = event_key;
+    session.session_id = session_id;
+    session.start_time = start_time;
+    session.end_time = end_time;
+    session.capacity = capacity;
+    session.checked_in = 0;
+
+    msg!(
+        "Session {} added to event {} ({} - {}, capacity {})",
+        session_id,
+        event.event_id,
+        start_time,
+        end_time,
+        capacity
+    );
+
+    Ok(())
+}
+
+/// Admits a ticket to a session. The SessionCheckIn record is derived per
+/// (session, ticket), so each ticket enters a session at most once; door
+/// check-ins to the event itself are tracked separately on the ticket.
+pub fn check_in_session(ctx: Context<CheckInSession>) -> Result<()> {
+    let event = &ctx.accounts.event.load()?;
+    let ticket = &ctx.accounts.ticket;
+    let session = &mut ctx.accounts.session;
+
+    has_permission(
+        event,
+        &ctx.accounts.operator.key(),
+        Permission::CheckIn,
+        &Delegations {
+            staff: ctx.accounts.staff.as_deref(),
+            co_organizer: ctx.accounts.co_organizer.as_deref(),
+            ..Default::default()
+        },
+    )?;
+    require!(!event.canceled(), EventTicketingError::EventCanceled);
+    require!(!event.is_finalized(), EventTicketingError::EventFinalized);
+    require!(!ticket.revoked, EventTicketingError::TicketRevoked);
+    require!(!ticket.refunded, EventTicketingError::AlreadyRefunded);
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        session.start_time <= now && now < session.end_time,
+        EventTicketingError::SessionNotOpen
+    );
+    // Room capacity is independent of event supply: a sold-out event can
+    // still have more ticket holders than seats in a given session.
+    require!(!session.is_full(), EventTicketingError::SessionFull);
+
+    session.checked_in = session
+        .checked_in
+        .checked_add(1)
+        .ok_or(EventTicketingError::Overflow)?;
+
+    let record = &mut ctx.accounts.session_check_in;
+
+    record.session = session.key();
+    record.ticket = ticket.key();
+    record.checked_in_at = now;
+    record.checked_in_by = ctx.accounts.operator.key();
+
+    msg!(
+        "Ticket #{} checked in to session {} of event {} ({}/{})",
+        ticket.ticket_id,
+        session.session_id,
+        event.event_id,
+        session.checked_in,
+        session.capacity
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(session_id: u16)]
+pub struct CreateSession<'info> {
+    #[account(
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        init,
+        payer = event_authority,
+        space = Session::SPACE,
+        seeds = [SESSION_SEED, event.key().as_ref(), &session_id.to_le_bytes()],
+        bump
+    )]
+    pub session: Account<'info, Session>,
+
+    #[account(mut)]
+    pub event_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CheckInSession<'info> {
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        constraint = ticket.event == event.key() @ EventTicketingError::UnauthorizedCheckIn
+    )]
+    pub ticket: Account<'info, Ticket>,
+
+    #[account(
+        mut,
+        constraint = session.event == event.key() @ EventTicketingError::InvalidSession
+    )]
+    pub session: Account<'info, Session>,
+
+    #[account(
+        init,
+        payer = operator,
+        space = SessionCheckIn::SPACE,
+        seeds = [SESSION_CHECK_IN_SEED, session.key().as_ref(), ticket.key().as_ref()],
+        bump
+    )]
+    pub session_check_in: Account<'info, SessionCheckIn>,
+
+    /// Staff delegation record; required when `operator` is not the event authority.
+    #[account(constraint = staff.event == event.key() @ EventTicketingError::UnauthorizedCheckIn)]
+    pub staff: Option<Account<'info, Staff>>,
+
+    /// Co-organizer record; an alternative to `staff` for teammates allowed
+    /// to check in.
+    #[account(constraint = co_organizer.event == event.key() @ EventTicketingError::UnauthorizedCheckIn)]
+    pub co_organizer: Option<Account<'info, CoOrganizer>>,
+
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
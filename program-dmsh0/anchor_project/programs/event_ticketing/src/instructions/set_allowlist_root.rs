@@ -0,0 +1,29 @@
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+
+/// Publishes the allowlist merkle root; an all-zero root ends the presale.
+pub fn set_allowlist_root(ctx: Context<SetAllowlistRoot>, allowlist_root: [u8; 32]) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    event.allowlist_root = allowlist_root;
+
+    if event.is_presale() {
+        msg!("Event {} allowlist presale enabled", event.event_id);
+    } else {
+        msg!("Event {} opened to public sale", event.event_id);
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetAllowlistRoot<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub event_authority: Signer<'info>,
+}
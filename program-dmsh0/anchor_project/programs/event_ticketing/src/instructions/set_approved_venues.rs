@@ -0,0 +1,46 @@
+use crate::authority::require_event_authority;
+use crate::constants::MAX_APPROVED_VENUES;
+use crate::errors::EventTicketingError;
+use crate::state::{Event, EventAuthorityConfig};
+use anchor_lang::prelude::*;
+
+/// Restricts resale to the marketplaces in `venues`: `list_ticket` and
+/// `buy_listed_ticket` then need one of them to co-sign. An empty list
+/// allows resale anywhere again.
+pub fn set_approved_venues(ctx: Context<SetApprovedVenues>, venues: Vec<Pubkey>) -> Result<()> {
+    require_event_authority(
+        &*ctx.accounts.event.load()?,
+        &ctx.accounts.event_authority.key(),
+        ctx.accounts.authority_config.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    require!(
+        venues.len() <= MAX_APPROVED_VENUES,
+        EventTicketingError::TooManyVenues
+    );
+
+    let event = &mut ctx.accounts.event.load_mut()?;
+    event.approved_venues = [Pubkey::default(); MAX_APPROVED_VENUES];
+    event.approved_venues[..venues.len()].copy_from_slice(&venues);
+    event.approved_venue_count = venues.len() as u8;
+
+    msg!(
+        "Event {} resale limited to {} venues",
+        event.event_id,
+        event.approved_venue_count
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetApprovedVenues<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(constraint = authority_config.event == event.key())]
+    pub authority_config: Option<Account<'info, EventAuthorityConfig>>,
+
+    pub event_authority: Signer<'info>,
+}
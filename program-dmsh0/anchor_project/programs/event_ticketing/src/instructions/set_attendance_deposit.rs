@@ -0,0 +1,45 @@
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+
+/// Makes a free event charge a refundable `deposit` (lamports) per ticket,
+/// returned at the ticket's first check-in and kept by the organizer for
+/// no-shows once the event is finalized. Zero turns deposits off. Can only
+/// change before the first sale, so every ticket carries the same deposit.
+pub fn set_attendance_deposit(ctx: Context<SetAttendanceDeposit>, deposit: u64) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    require!(
+        event.price == 0
+            && event.usd_price_cents == 0
+            && event.auction_end == 0
+            && event.curve_step_size == 0,
+        EventTicketingError::DepositRequiresFreeEvent
+    );
+    require!(
+        event.currency().is_none(),
+        EventTicketingError::CurrencyNotSupported
+    );
+    require!(event.sold == 0, EventTicketingError::DepositLocked);
+
+    event.attendance_deposit = deposit;
+
+    msg!(
+        "Event {} attendance deposit set to {} lamports",
+        event.event_id,
+        deposit
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetAttendanceDeposit<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub event_authority: Signer<'info>,
+}
@@ -0,0 +1,29 @@
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+
+/// Sets (or clears, with `None`) the attestor whose `Attestation` each ticket
+/// owner needs before buying. See `attestation`.
+pub fn set_attestor(ctx: Context<SetAttestor>, attestor: Option<Pubkey>) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    event.attestor = attestor.unwrap_or_default();
+
+    match attestor {
+        Some(attestor) => msg!("Event {} attestor set to {}", event.event_id, attestor),
+        None => msg!("Event {} attestor cleared", event.event_id),
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetAttestor<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub event_authority: Signer<'info>,
+}
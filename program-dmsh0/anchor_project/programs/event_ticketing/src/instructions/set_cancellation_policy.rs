@@ -0,0 +1,42 @@
+use crate::constants::BPS_DENOMINATOR;
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+
+/// Lets holders return unused tickets with `cancel_ticket` until `cutoff`,
+/// keeping `fee_bps` of what they paid. A zero `cutoff` turns it off.
+pub fn set_cancellation_policy(
+    ctx: Context<SetCancellationPolicy>,
+    cutoff: i64,
+    fee_bps: u16,
+) -> Result<()> {
+    require!(
+        fee_bps as u64 <= BPS_DENOMINATOR,
+        EventTicketingError::InvalidCancellationFee
+    );
+
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    event.cancellation_cutoff = cutoff;
+    event.cancellation_fee_bps = fee_bps;
+
+    msg!(
+        "Event {} tickets can be canceled until {} for a {} bps fee",
+        event.event_id,
+        cutoff,
+        fee_bps
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetCancellationPolicy<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub event_authority: Signer<'info>,
+}
@@ -0,0 +1,42 @@
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+
+/// Sets how long after the event ends the organizer has to finalize it before
+/// holders can claim refunds. Once tickets are sold it can only be shortened.
+pub fn set_dispute_window(ctx: Context<SetDisputeWindow>, dispute_window_secs: i64) -> Result<()> {
+    require!(
+        dispute_window_secs > 0,
+        EventTicketingError::InvalidDisputeWindow
+    );
+
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    if event.sold > 0 {
+        require!(
+            dispute_window_secs <= event.dispute_window_secs,
+            EventTicketingError::DisputeWindowLocked
+        );
+    }
+
+    event.dispute_window_secs = dispute_window_secs;
+
+    msg!(
+        "Event {} dispute window set to {}s",
+        event.event_id,
+        dispute_window_secs
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetDisputeWindow<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub event_authority: Signer<'info>,
+}
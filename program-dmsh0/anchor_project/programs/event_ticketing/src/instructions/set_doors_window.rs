@@ -0,0 +1,39 @@
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+
+pub fn set_doors_window(
+    ctx: Context<SetDoorsWindow>,
+    doors_open: i64,
+    doors_close: i64,
+) -> Result<()> {
+    require!(
+        doors_close == 0 || doors_open < doors_close,
+        EventTicketingError::InvalidDoorsWindow
+    );
+
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    event.doors_open = doors_open;
+    event.doors_close = doors_close;
+
+    msg!(
+        "Event {} doors window set: {} - {}",
+        event.event_id,
+        doors_open,
+        doors_close
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetDoorsWindow<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub event_authority: Signer<'info>,
+}
@@ -0,0 +1,47 @@
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+
+/// Switches the event to dutch auction pricing between `auction_start` and
+/// `auction_end`. Passing `auction_end == 0` returns to the fixed `price`.
+pub fn set_dutch_auction(
+    ctx: Context<SetDutchAuction>,
+    start_price: u64,
+    floor_price: u64,
+    auction_start: i64,
+    auction_end: i64,
+) -> Result<()> {
+    require!(
+        auction_end == 0 || (auction_start < auction_end && floor_price <= start_price),
+        EventTicketingError::InvalidAuction
+    );
+
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    event.auction_start_price = start_price;
+    event.auction_floor_price = floor_price;
+    event.auction_start = auction_start;
+    event.auction_end = auction_end;
+
+    msg!(
+        "Event {} dutch auction set: {} -> {} lamports between {} and {}",
+        event.event_id,
+        start_price,
+        floor_price,
+        auction_start,
+        auction_end
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetDutchAuction<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub event_authority: Signer<'info>,
+}
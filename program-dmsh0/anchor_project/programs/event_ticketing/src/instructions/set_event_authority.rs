@@ -0,0 +1,57 @@
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+
+pub fn set_event_authority(ctx: Context<SetEventAuthority>, new_authority: Pubkey) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    event.pending_authority = new_authority;
+
+    msg!(
+        "Event {} authority transfer proposed: {} -> {}",
+        event.event_id,
+        event.event_authority,
+        new_authority
+    );
+
+    Ok(())
+}
+
+pub fn accept_event_authority(ctx: Context<AcceptEventAuthority>) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+    let previous_authority = event.event_authority;
+
+    event.event_authority = ctx.accounts.new_authority.key();
+    event.pending_authority = Pubkey::default();
+
+    msg!(
+        "Event {} authority transferred: {} -> {}",
+        event.event_id,
+        previous_authority,
+        event.event_authority
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetEventAuthority<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub event_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptEventAuthority<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.pending_authority() == Some(new_authority.key()) @ EventTicketingError::NotPendingAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub new_authority: Signer<'info>,
+}
@@ -0,0 +1,67 @@
+use crate::constants::*;
+use crate::errors::EventTicketingError;
+use crate::state::{Event, EventAuthorityConfig};
+use anchor_lang::prelude::*;
+
+pub fn set_event_multisig(
+    ctx: Context<SetEventMultisig>,
+    signers: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    require!(
+        signers.len() <= MAX_MULTISIG_SIGNERS,
+        EventTicketingError::TooManySigners
+    );
+    require!(
+        threshold >= 1 && threshold as usize <= signers.len(),
+        EventTicketingError::InvalidThreshold
+    );
+    for (i, signer) in signers.iter().enumerate() {
+        require!(
+            !signers[..i].contains(signer),
+            EventTicketingError::DuplicateSigner
+        );
+    }
+
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+    let authority_config = &mut ctx.accounts.authority_config;
+
+    authority_config.event = event_key;
+    authority_config.signers = signers;
+    authority_config.threshold = threshold;
+
+    event.multisig_enabled = true.into();
+
+    msg!(
+        "Event {} now requires {} of {} signers",
+        event.event_id,
+        threshold,
+        authority_config.signers.len()
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetEventMultisig<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        init,
+        payer = event_authority,
+        space = EventAuthorityConfig::SPACE,
+        seeds = [AUTHORITY_CONFIG_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub authority_config: Account<'info, EventAuthorityConfig>,
+
+    #[account(mut)]
+    pub event_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,30 @@
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+
+pub fn set_max_check_ins(ctx: Context<SetMaxCheckIns>, max_check_ins: u16) -> Result<()> {
+    require!(max_check_ins >= 1, EventTicketingError::InvalidMaxCheckIns);
+
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    event.max_check_ins = max_check_ins;
+
+    msg!(
+        "Event {} tickets now allow {} check-ins",
+        event.event_id,
+        max_check_ins
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMaxCheckIns<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub event_authority: Signer<'info>,
+}
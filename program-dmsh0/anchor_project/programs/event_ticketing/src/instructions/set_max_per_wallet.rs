@@ -0,0 +1,28 @@
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+
+pub fn set_max_per_wallet(ctx: Context<SetMaxPerWallet>, max_per_wallet: u32) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    event.max_per_wallet = max_per_wallet;
+
+    msg!(
+        "Event {} per-wallet limit set to {}",
+        event.event_id,
+        max_per_wallet
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMaxPerWallet<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub event_authority: Signer<'info>,
+}
@@ -0,0 +1,31 @@
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+
+pub fn set_max_resale_multiplier(
+    ctx: Context<SetMaxResaleMultiplier>,
+    max_resale_multiplier_bps: u16,
+) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    event.max_resale_multiplier_bps = max_resale_multiplier_bps;
+
+    msg!(
+        "Event {} resale cap set to {} bps of face value",
+        event.event_id,
+        max_resale_multiplier_bps
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMaxResaleMultiplier<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub event_authority: Signer<'info>,
+}
@@ -0,0 +1,42 @@
+use crate::constants::{EVENT_METADATA_SEED, MAX_METADATA_URI_LEN};
+use crate::errors::EventTicketingError;
+use crate::state::{Event, EventMetadata};
+use anchor_lang::prelude::*;
+
+/// Points the event at off-chain metadata (description, venue, image), e.g.
+/// an IPFS or Arweave URI. An empty URI clears it.
+pub fn set_metadata_uri(ctx: Context<SetMetadataUri>, metadata_uri: String) -> Result<()> {
+    require!(
+        metadata_uri.len() <= MAX_METADATA_URI_LEN,
+        EventTicketingError::MetadataUriTooLong
+    );
+
+    let metadata = &mut ctx.accounts.metadata;
+
+    metadata.metadata_uri = metadata_uri;
+
+    msg!(
+        "Event {} metadata URI set to {}",
+        ctx.accounts.event.load()?.event_id,
+        metadata.metadata_uri
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMetadataUri<'info> {
+    #[account(
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [EVENT_METADATA_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub metadata: Account<'info, EventMetadata>,
+
+    pub event_authority: Signer<'info>,
+}
@@ -0,0 +1,30 @@
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+
+/// Lets buyers pay any amount at or above the ticket price through
+/// `mint_ticket_with_amount`, the excess being recorded as a donation.
+pub fn set_pay_what_you_want(ctx: Context<SetPayWhatYouWant>, enabled: bool) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    event.pay_what_you_want = enabled.into();
+
+    msg!(
+        "Event {} pay-what-you-want {}",
+        event.event_id,
+        if enabled { "enabled" } else { "disabled" }
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPayWhatYouWant<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub event_authority: Signer<'info>,
+}
@@ -0,0 +1,49 @@
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+
+/// Sets when the event ends and how long proceeds stay locked afterwards.
+/// Once tickets are sold the schedule can only be pushed back, never shortened.
+pub fn set_payout_schedule(
+    ctx: Context<SetPayoutSchedule>,
+    end_time: i64,
+    payout_timelock_secs: i64,
+) -> Result<()> {
+    require!(
+        payout_timelock_secs >= 0,
+        EventTicketingError::InvalidPayoutTimelock
+    );
+
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    if event.sold > 0 {
+        require!(
+            (event.end_time == 0 || end_time >= event.end_time)
+                && payout_timelock_secs >= event.payout_timelock_secs,
+            EventTicketingError::PayoutScheduleLocked
+        );
+    }
+
+    event.end_time = end_time;
+    event.payout_timelock_secs = payout_timelock_secs;
+
+    msg!(
+        "Event {} ends at {}, proceeds unlock {}s later",
+        event.event_id,
+        end_time,
+        payout_timelock_secs
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPayoutSchedule<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub event_authority: Signer<'info>,
+}
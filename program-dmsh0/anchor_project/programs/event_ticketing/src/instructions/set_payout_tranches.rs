@@ -0,0 +1,70 @@
+use crate::authority::require_event_authority;
+use crate::constants::{BPS_DENOMINATOR, MAX_PAYOUT_TRANCHES, PAYOUT_CONFIG_SEED};
+use crate::errors::EventTicketingError;
+use crate::state::{Event, EventAuthorityConfig, PayoutConfig, PayoutTranche};
+use anchor_lang::prelude::*;
+
+/// Releases proceeds in installments instead of all at once after the payout
+/// timelock. Tranches release earlier than the timelock would, so buyers
+/// must know the schedule before paying: it can only be set before the
+/// first sale.
+pub fn set_payout_tranches(
+    ctx: Context<SetPayoutTranches>,
+    tranches: Vec<PayoutTranche>,
+) -> Result<()> {
+    require_event_authority(
+        &*ctx.accounts.event.load()?,
+        &ctx.accounts.event_authority.key(),
+        ctx.accounts.authority_config.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    let total_bps: u64 = tranches.iter().map(|tranche| tranche.bps as u64).sum();
+    require!(
+        !tranches.is_empty()
+            && tranches.len() <= MAX_PAYOUT_TRANCHES
+            && total_bps == BPS_DENOMINATOR,
+        EventTicketingError::InvalidPayoutTranches
+    );
+
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+    require!(event.sold == 0, EventTicketingError::PayoutScheduleLocked);
+
+    let payout_config = &mut ctx.accounts.payout_config;
+    payout_config.event = event_key;
+    payout_config.tranches = tranches;
+    payout_config.withdrawn = 0;
+    event.payout_config = payout_config.key();
+
+    msg!(
+        "Event {} pays out in {} tranches",
+        event.event_id,
+        payout_config.tranches.len()
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPayoutTranches<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(constraint = authority_config.event == event.key())]
+    pub authority_config: Option<Account<'info, EventAuthorityConfig>>,
+
+    #[account(
+        init_if_needed,
+        payer = event_authority,
+        space = PayoutConfig::SPACE,
+        seeds = [PAYOUT_CONFIG_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub payout_config: Account<'info, PayoutConfig>,
+
+    #[account(mut)]
+    pub event_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
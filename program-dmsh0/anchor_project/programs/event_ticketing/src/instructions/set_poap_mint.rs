@@ -0,0 +1,53 @@
+use crate::constants::POAP_SEED;
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::Mint;
+
+/// Configures the attendance collectible minted on first check-in. The mint
+/// must be owned by the event's POAP authority PDA; passing no mint disables it.
+pub fn set_poap_mint(ctx: Context<SetPoapMint>) -> Result<()> {
+    let poap_mint = match &ctx.accounts.poap_mint {
+        Some(mint) => {
+            require!(
+                mint.decimals == 0
+                    && mint.mint_authority == COption::Some(ctx.accounts.poap_authority.key()),
+                EventTicketingError::InvalidPoapMint
+            );
+            Some(mint.key())
+        }
+        None => None,
+    };
+
+    let event = &mut ctx.accounts.event.load_mut()?;
+    event.poap_mint = poap_mint.unwrap_or_default();
+
+    match poap_mint {
+        Some(mint) => msg!(
+            "Event {} attendance collectible set to {}",
+            event.event_id,
+            mint
+        ),
+        None => msg!("Event {} attendance collectible disabled", event.event_id),
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPoapMint<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub poap_mint: Option<Account<'info, Mint>>,
+
+    /// CHECK: PDA that signs attendance collectible mints. Verified by seeds.
+    #[account(seeds = [POAP_SEED, event.key().as_ref()], bump)]
+    pub poap_authority: UncheckedAccount<'info>,
+
+    pub event_authority: Signer<'info>,
+}
@@ -0,0 +1,36 @@
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+
+/// Raises the ticket price by `step_lamports` every `step_size` tickets sold.
+/// A zero `step_size` returns to flat pricing.
+pub fn set_price_curve(
+    ctx: Context<SetPriceCurve>,
+    step_lamports: u64,
+    step_size: u32,
+) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    event.curve_step_lamports = step_lamports;
+    event.curve_step_size = step_size;
+
+    msg!(
+        "Event {} price curve set: +{} lamports every {} tickets",
+        event.event_id,
+        step_lamports,
+        step_size
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPriceCurve<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub event_authority: Signer<'info>,
+}
@@ -0,0 +1,34 @@
+use crate::constants::BPS_DENOMINATOR;
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+
+pub fn set_referral_bps(ctx: Context<SetReferralBps>, referral_bps: u16) -> Result<()> {
+    require!(
+        referral_bps as u64 <= BPS_DENOMINATOR,
+        EventTicketingError::InvalidReferralBps
+    );
+
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    event.referral_bps = referral_bps;
+
+    msg!(
+        "Event {} referral cut set to {} bps",
+        event.event_id,
+        referral_bps
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetReferralBps<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub event_authority: Signer<'info>,
+}
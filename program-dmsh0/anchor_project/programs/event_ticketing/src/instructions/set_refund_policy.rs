@@ -0,0 +1,38 @@
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+
+/// Opens an organizer refund window until `refund_deadline`. Outside it,
+/// `refund` only works for canceled events. Once tickets are sold the
+/// deadline can only be extended.
+pub fn set_refund_policy(ctx: Context<SetRefundPolicy>, refund_deadline: i64) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    if event.sold > 0 {
+        require!(
+            refund_deadline >= event.refund_deadline,
+            EventTicketingError::RefundPolicyLocked
+        );
+    }
+
+    event.refund_deadline = refund_deadline;
+
+    msg!(
+        "Event {} refunds open until {}",
+        event.event_id,
+        refund_deadline
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetRefundPolicy<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub event_authority: Signer<'info>,
+}
@@ -0,0 +1,54 @@
+use crate::authority::require_event_authority;
+use crate::constants::BPS_DENOMINATOR;
+use crate::errors::EventTicketingError;
+use crate::state::{Event, EventAuthorityConfig};
+use anchor_lang::prelude::*;
+
+/// Sets how resale prices are split between seller, organizer and protocol,
+/// in basis points adding up to 100%. New events leave everything to the
+/// seller. Open listings keep the split they were made under.
+pub fn set_resale_split(
+    ctx: Context<SetResaleSplit>,
+    seller_bps: u16,
+    organizer_bps: u16,
+    protocol_bps: u16,
+) -> Result<()> {
+    require_event_authority(
+        &*ctx.accounts.event.load()?,
+        &ctx.accounts.event_authority.key(),
+        ctx.accounts.authority_config.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    require!(
+        seller_bps as u64 + organizer_bps as u64 + protocol_bps as u64 == BPS_DENOMINATOR,
+        EventTicketingError::InvalidResaleSplit
+    );
+
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    event.resale_seller_bps = seller_bps;
+    event.resale_organizer_bps = organizer_bps;
+    event.resale_protocol_bps = protocol_bps;
+
+    msg!(
+        "Event {} resale split: seller {} bps, organizer {} bps, protocol {} bps",
+        event.event_id,
+        seller_bps,
+        organizer_bps,
+        protocol_bps
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetResaleSplit<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(constraint = authority_config.event == event.key())]
+    pub authority_config: Option<Account<'info, EventAuthorityConfig>>,
+
+    pub event_authority: Signer<'info>,
+}
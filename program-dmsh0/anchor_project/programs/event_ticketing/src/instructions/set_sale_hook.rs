@@ -0,0 +1,34 @@
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+
+/// Sets (or clears, with `None`) the program `mint_ticket` asks before each
+/// sale. See `sale_hook` for the interface it has to implement.
+pub fn set_sale_hook(ctx: Context<SetSaleHook>, hook_program: Option<Pubkey>) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    require!(
+        hook_program != Some(crate::ID),
+        EventTicketingError::InvalidSaleHook
+    );
+
+    event.hook_program = hook_program.unwrap_or_default();
+
+    match hook_program {
+        Some(hook) => msg!("Event {} sale hook set to {}", event.event_id, hook),
+        None => msg!("Event {} sale hook cleared", event.event_id),
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetSaleHook<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub event_authority: Signer<'info>,
+}
@@ -0,0 +1,72 @@
+use crate::constants::EVENT_CPI_AUTHORITY_SEED;
+use crate::errors::EventTicketingError;
+use crate::events::{emit_cpi_event, TicketAttributesSet};
+use crate::rbac::{has_permission, Delegations, Permission};
+use crate::state::{CoOrganizer, Event, Ticket};
+use anchor_lang::prelude::*;
+
+/// Writes organizer-defined `attributes` (seat notes, a meal preference
+/// code, ...) onto a ticket, replacing any previous ones. The program never
+/// interprets them. Fixed once the ticket has been checked in. Co-organizers
+/// allowed to update the event may set them too.
+pub fn set_ticket_attributes(ctx: Context<SetTicketAttributes>, attributes: Vec<u8>) -> Result<()> {
+    has_permission(
+        &*ctx.accounts.event.load()?,
+        &ctx.accounts.event_authority.key(),
+        Permission::Update,
+        &Delegations {
+            co_organizer: ctx.accounts.co_organizer.as_deref(),
+            ..Default::default()
+        },
+    )?;
+
+    let ticket = &mut ctx.accounts.ticket;
+
+    require!(!ticket.is_used(), EventTicketingError::TicketAlreadyUsed);
+    ticket.set_attributes(attributes)?;
+
+    emit_cpi_event(
+        &ctx.accounts.event_cpi_authority,
+        &TicketAttributesSet {
+            event: ctx.accounts.event.key(),
+            ticket: ticket.key(),
+            ticket_id: ticket.ticket_id,
+            attributes: ticket.attributes.clone(),
+        },
+    )?;
+
+    msg!(
+        "Ticket #{} attributes set ({} bytes)",
+        ticket.ticket_id,
+        ticket.attributes.len()
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetTicketAttributes<'info> {
+    pub event: AccountLoader<'info, Event>,
+
+    /// Co-organizer record when `event_authority` is a teammate allowed to
+    /// update the event.
+    #[account(constraint = co_organizer.event == event.key() @ EventTicketingError::UnauthorizedEventAuthority)]
+    pub co_organizer: Option<Account<'info, CoOrganizer>>,
+
+    #[account(
+        mut,
+        constraint = ticket.event == event.key() @ EventTicketingError::InvalidTicketAccount
+    )]
+    pub ticket: Account<'info, Ticket>,
+
+    /// The event authority, or a co-organizer allowed to update the event.
+    pub event_authority: Signer<'info>,
+
+    /// CHECK: Signs the event self-CPI. Verified by seeds.
+    #[account(seeds = [EVENT_CPI_AUTHORITY_SEED], bump)]
+    pub event_cpi_authority: AccountInfo<'info>,
+
+    /// CHECK: This program, invoked by the event self-CPI.
+    #[account(address = crate::ID)]
+    pub program: AccountInfo<'info>,
+}
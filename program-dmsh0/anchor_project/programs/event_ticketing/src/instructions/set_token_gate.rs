@@ -0,0 +1,53 @@
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+
+/// Sets (or clears, with `None`) the token buyers must hold: at least
+/// `min_amount` of the `gate_mint` token, or with `collection`, any NFT of
+/// the `gate_mint` collection. See `token_gate`.
+pub fn set_token_gate(
+    ctx: Context<SetTokenGate>,
+    gate_mint: Option<Pubkey>,
+    min_amount: u64,
+    collection: bool,
+) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    match gate_mint {
+        Some(gate_mint) => {
+            require!(
+                collection || min_amount > 0,
+                EventTicketingError::InvalidTokenGate
+            );
+            event.gate_mint = gate_mint;
+            // A collection proof is a single NFT.
+            event.gate_min_amount = if collection { 1 } else { min_amount };
+            event.gate_collection = collection.into();
+            msg!(
+                "Event {} gated on {} {}",
+                event.event_id,
+                if collection { "collection" } else { "mint" },
+                gate_mint
+            );
+        }
+        None => {
+            event.gate_mint = Pubkey::default();
+            event.gate_min_amount = 0;
+            event.gate_collection = false.into();
+            msg!("Event {} token gate cleared", event.event_id);
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetTokenGate<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub event_authority: Signer<'info>,
+}
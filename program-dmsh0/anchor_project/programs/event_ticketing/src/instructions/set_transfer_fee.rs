@@ -0,0 +1,35 @@
+use crate::authority::require_event_authority;
+use crate::state::{Event, EventAuthorityConfig};
+use anchor_lang::prelude::*;
+
+pub fn set_transfer_fee(ctx: Context<SetTransferFee>, transfer_fee_lamports: u64) -> Result<()> {
+    require_event_authority(
+        &*ctx.accounts.event.load()?,
+        &ctx.accounts.event_authority.key(),
+        ctx.accounts.authority_config.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    event.transfer_fee_lamports = transfer_fee_lamports;
+
+    msg!(
+        "Event {} transfer fee set to {} lamports",
+        event.event_id,
+        transfer_fee_lamports
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetTransferFee<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(constraint = authority_config.event == event.key())]
+    pub authority_config: Option<Account<'info, EventAuthorityConfig>>,
+
+    pub event_authority: Signer<'info>,
+}
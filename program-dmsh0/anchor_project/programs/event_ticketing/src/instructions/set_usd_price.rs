@@ -0,0 +1,33 @@
+use crate::errors::EventTicketingError;
+use crate::payment::require_sol_currency;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+
+/// Prices tickets in USD cents, converted to lamports through the Pyth
+/// SOL/USD feed at purchase time. Zero returns to lamport pricing.
+pub fn set_usd_price(ctx: Context<SetUsdPrice>, usd_price_cents: u64) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    // The Pyth conversion yields lamports, which only SOL events charge.
+    require_sol_currency(event)?;
+    event.usd_price_cents = usd_price_cents;
+
+    msg!(
+        "Event {} USD price set to {} cents",
+        event.event_id,
+        usd_price_cents
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetUsdPrice<'info> {
+    #[account(
+        mut,
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    pub event_authority: Signer<'info>,
+}
@@ -0,0 +1,125 @@
+use crate::constants::{BOND_SEED, CONFIG_SEED, ORGANIZER_SEED, VAULT_SEED};
+use crate::errors::EventTicketingError;
+use crate::state::{Config, Event, OrganizerRegistry, TransactionLog, VaultActivity};
+use crate::transaction_log::record_vault_activity;
+use crate::vault::vault_reserve;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Moves part of the bonding organizer's bond into the event vault, where it
+/// funds holder refunds. The event must owe refunds (canceled, or its dispute
+/// window lapsed without finalization). The config admin can slash any such
+/// event by up to `amount`; anyone can slash a lapsed one, but always by the
+/// configured bond amount and `amount` is ignored. An event is slashed at
+/// most once, which also releases its hold on the rest of the bond.
+pub fn slash_organizer(ctx: Context<SlashOrganizer>, amount: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let event = &mut ctx.accounts.event.load_mut()?;
+    let organizer_registry = &mut ctx.accounts.organizer_registry;
+
+    require!(
+        event.refunds_claimable(now),
+        EventTicketingError::SlashNotAllowed
+    );
+    let is_admin = ctx.accounts.authority.key() == ctx.accounts.config.admin;
+    require!(
+        is_admin || !event.canceled(),
+        EventTicketingError::UnauthorizedAdmin
+    );
+    let requested = if is_admin {
+        amount
+    } else {
+        ctx.accounts.config.organizer_bond_lamports
+    };
+
+    // A partial slash must leave the bond PDA rent-exempt; otherwise take it all.
+    let mut slashed = requested.min(organizer_registry.bond);
+    if organizer_registry.bond - slashed < vault_reserve()? {
+        slashed = organizer_registry.bond;
+    }
+    require!(slashed > 0, EventTicketingError::SlashAmountZero);
+
+    let organizer_key = organizer_registry.organizer;
+    let seeds = &[BOND_SEED, organizer_key.as_ref(), &[ctx.bumps.bond]];
+    let signer_seeds = &[&seeds[..]];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.bond.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        slashed,
+    )?;
+
+    record_vault_activity(
+        event,
+        ctx.accounts.transaction_log.as_ref(),
+        VaultActivity::Slash,
+        organizer_key,
+        slashed,
+        now,
+    )?;
+
+    organizer_registry.bond -= slashed;
+    organizer_registry.open_events = organizer_registry.open_events.saturating_sub(1);
+    event.bond_organizer = Pubkey::default();
+
+    msg!(
+        "Slashed {} lamports from organizer {} into event {} vault",
+        slashed,
+        organizer_registry.organizer,
+        event.event_id
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SlashOrganizer<'info> {
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        constraint = event.load()?.bond_organizer() == Some(organizer_registry.organizer) @ EventTicketingError::BondAccountsMissing
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [ORGANIZER_SEED, organizer_registry.organizer.as_ref()],
+        bump
+    )]
+    pub organizer_registry: Account<'info, OrganizerRegistry>,
+
+    /// CHECK: Lamport-only PDA holding the organizer's bond. Verified by seeds.
+    #[account(
+        mut,
+        seeds = [BOND_SEED, organizer_registry.organizer.as_ref()],
+        bump
+    )]
+    pub bond: AccountInfo<'info>,
+
+    /// CHECK: This is the vault PDA that holds event funds. Verified by seeds.
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Vault activity log; required when the event keeps one.
+    #[account(
+        mut,
+        constraint = event.load()?.transaction_log() == Some(transaction_log.key()) @ EventTicketingError::InvalidTransactionLog
+    )]
+    pub transaction_log: Option<AccountLoader<'info, TransactionLog>>,
+}
@@ -0,0 +1,86 @@
+use crate::authority::require_event_authority;
+use crate::constants::STAFF_SEED;
+use crate::state::{Event, EventAuthorityConfig, Staff};
+use anchor_lang::prelude::*;
+
+pub fn add_staff(ctx: Context<AddStaff>, staff: Pubkey) -> Result<()> {
+    let event = &ctx.accounts.event.load()?;
+
+    require_event_authority(
+        event,
+        &ctx.accounts.event_authority.key(),
+        ctx.accounts.authority_config.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    let staff_account = &mut ctx.accounts.staff_account;
+
+    staff_account.event = ctx.accounts.event.key();
+    staff_account.staff = staff;
+    staff_account.added_at = Clock::get()?.unix_timestamp;
+
+    msg!("Staff {} added to event {}", staff, event.event_id);
+
+    Ok(())
+}
+
+pub fn remove_staff(ctx: Context<RemoveStaff>) -> Result<()> {
+    let event = &ctx.accounts.event.load()?;
+
+    require_event_authority(
+        event,
+        &ctx.accounts.event_authority.key(),
+        ctx.accounts.authority_config.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    msg!(
+        "Staff {} removed from event {}",
+        ctx.accounts.staff_account.staff,
+        event.event_id
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(staff: Pubkey)]
+pub struct AddStaff<'info> {
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(constraint = authority_config.event == event.key())]
+    pub authority_config: Option<Account<'info, EventAuthorityConfig>>,
+
+    #[account(
+        init,
+        payer = event_authority,
+        space = Staff::SPACE,
+        seeds = [STAFF_SEED, event.key().as_ref(), staff.as_ref()],
+        bump
+    )]
+    pub staff_account: Account<'info, Staff>,
+
+    #[account(mut)]
+    pub event_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveStaff<'info> {
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(constraint = authority_config.event == event.key())]
+    pub authority_config: Option<Account<'info, EventAuthorityConfig>>,
+
+    #[account(
+        mut,
+        close = event_authority,
+        seeds = [STAFF_SEED, event.key().as_ref(), staff_account.staff.as_ref()],
+        bump
+    )]
+    pub staff_account: Account<'info, Staff>,
+
+    #[account(mut)]
+    pub event_authority: Signer<'info>,
+}
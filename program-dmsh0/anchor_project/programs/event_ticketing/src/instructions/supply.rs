@@ -0,0 +1,125 @@
+use crate::authority::require_event_authority;
+use crate::constants::EVENT_CPI_AUTHORITY_SEED;
+use crate::errors::EventTicketingError;
+use crate::events::{emit_cpi_event, SupplyDecreased, SupplyIncreased};
+use crate::state::{Event, EventAuthorityConfig};
+use anchor_lang::prelude::*;
+
+/// Shrinks the event's capacity, e.g. when the venue is downsized. Supply can
+/// never drop below the tickets already sold or held by reservations, less
+/// the reissued seats those include.
+pub fn decrease_supply(ctx: Context<ChangeSupply>, new_supply: u32) -> Result<()> {
+    require_event_authority(
+        &*ctx.accounts.event.load()?,
+        &ctx.accounts.event_authority.key(),
+        ctx.accounts.authority_config.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    require!(!event.canceled(), EventTicketingError::EventCanceled);
+    require!(!event.is_finalized(), EventTicketingError::EventFinalized);
+    require!(
+        new_supply < event.supply,
+        EventTicketingError::NotASupplyDecrease
+    );
+    let capacity = new_supply
+        .checked_add(event.reissued_seats)
+        .ok_or(EventTicketingError::Overflow)?;
+    require!(
+        capacity >= event.sold + event.reserved,
+        EventTicketingError::SupplyBelowSold
+    );
+
+    let old_supply = event.supply;
+    event.supply = new_supply;
+
+    emit_cpi_event(
+        &ctx.accounts.event_cpi_authority,
+        &SupplyDecreased {
+            event: event_key,
+            event_id: event.event_id,
+            old_supply,
+            new_supply,
+            available: event.available(),
+        },
+    )?;
+
+    msg!(
+        "Event {} supply decreased from {} to {}",
+        event.event_id,
+        old_supply,
+        new_supply
+    );
+
+    Ok(())
+}
+
+/// Grows the event's capacity, e.g. when more of the venue opens up. Supply
+/// can never rise past the `max_total_supply` fixed at creation. Refunded
+/// seats reissued by the waitlist or cancellations are tracked in
+/// `reissued_seats` and don't count towards it.
+pub fn increase_supply(ctx: Context<ChangeSupply>, new_supply: u32) -> Result<()> {
+    require_event_authority(
+        &*ctx.accounts.event.load()?,
+        &ctx.accounts.event_authority.key(),
+        ctx.accounts.authority_config.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    require!(!event.canceled(), EventTicketingError::EventCanceled);
+    require!(!event.is_finalized(), EventTicketingError::EventFinalized);
+    require!(
+        new_supply > event.supply,
+        EventTicketingError::NotASupplyIncrease
+    );
+    require!(
+        new_supply <= event.max_total_supply,
+        EventTicketingError::MaxTotalSupplyExceeded
+    );
+
+    let old_supply = event.supply;
+    event.supply = new_supply;
+
+    emit_cpi_event(
+        &ctx.accounts.event_cpi_authority,
+        &SupplyIncreased {
+            event: event_key,
+            event_id: event.event_id,
+            old_supply,
+            new_supply,
+            available: event.available(),
+        },
+    )?;
+
+    msg!(
+        "Event {} supply increased from {} to {}",
+        event.event_id,
+        old_supply,
+        new_supply
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ChangeSupply<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(constraint = authority_config.event == event.key())]
+    pub authority_config: Option<Account<'info, EventAuthorityConfig>>,
+
+    pub event_authority: Signer<'info>,
+
+    /// CHECK: Signs the event self-CPI. Verified by seeds.
+    #[account(seeds = [EVENT_CPI_AUTHORITY_SEED], bump)]
+    pub event_cpi_authority: AccountInfo<'info>,
+
+    /// CHECK: This program, invoked by the event self-CPI.
+    #[account(address = crate::ID)]
+    pub program: AccountInfo<'info>,
+}
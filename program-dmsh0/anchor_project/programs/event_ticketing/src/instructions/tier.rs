@@ -0,0 +1,698 @@
+use crate::authority::require_event_authority;
+use crate::constants::*;
+use crate::errors::EventTicketingError;
+use crate::instructions::mint_ticket::*;
+use crate::payment::{collect_tokens, pay_out_tokens, require_sol_currency};
+use crate::pyth;
+use crate::state::{
+    Event, EventAuthorityConfig, Ticket, Tier, TierCurrency, TokenPayment, TransactionLog,
+    VaultActivity,
+};
+use crate::transaction_log::record_vault_activity;
+use crate::vault::require_vault_funds;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+const LAMPORT_DECIMALS: u8 = 9;
+
+pub fn create_tier(ctx: Context<CreateTier>, tier_id: u8, price: u64, supply: u32) -> Result<()> {
+    let tier = &mut ctx.accounts.tier;
+
+    tier.event = ctx.accounts.event.key();
+    tier.tier_id = tier_id;
+    tier.price = price;
+    tier.supply = supply;
+    tier.sold = 0;
+
+    msg!(
+        "Tier {} created for event {}: {} seats at {} lamports",
+        tier_id,
+        ctx.accounts.event.load()?.event_id,
+        supply,
+        price
+    );
+
+    Ok(())
+}
+
+/// Moves a ticket into a pricier tier, charging the owner the difference.
+pub fn upgrade_ticket(ctx: Context<ChangeTier>) -> Result<()> {
+    let accounts = ctx.accounts;
+    let current_price = accounts.current_tier_price()?;
+    let target_price = accounts.target_tier_price()?;
+
+    require!(
+        target_price > current_price,
+        EventTicketingError::NotAnUpgrade
+    );
+
+    let difference = target_price - current_price;
+
+    system_program::transfer(
+        CpiContext::new(
+            accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: accounts.owner.to_account_info(),
+                to: accounts.vault.to_account_info(),
+            },
+        ),
+        difference,
+    )?;
+
+    accounts.move_ticket()?;
+    accounts.ticket.purchase_price = accounts
+        .ticket
+        .purchase_price
+        .checked_add(difference)
+        .ok_or(EventTicketingError::Overflow)?;
+    record_vault_activity(
+        &*accounts.event.load()?,
+        accounts.transaction_log.as_ref(),
+        VaultActivity::Upgrade,
+        accounts.owner.key(),
+        difference,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    msg!(
+        "Ticket #{} upgraded to tier {:?} for {} lamports",
+        accounts.ticket.ticket_id,
+        accounts.ticket.tier,
+        difference
+    );
+
+    Ok(())
+}
+
+/// Lists `mint` as a currency `tier` accepts at `price` (in the mint's base
+/// units), with `feed_id` the Pyth USD feed for the mint. A zero price stops
+/// accepting it.
+pub fn set_tier_currency(
+    ctx: Context<SetTierCurrency>,
+    price: u64,
+    feed_id: [u8; 32],
+) -> Result<()> {
+    require_sol_currency(&*ctx.accounts.event.load()?)?;
+
+    let tier_currency = &mut ctx.accounts.tier_currency;
+    tier_currency.tier = ctx.accounts.tier.key();
+    tier_currency.mint = ctx.accounts.mint.key();
+    tier_currency.decimals = ctx.accounts.mint.decimals;
+    tier_currency.price = price;
+    tier_currency.feed_id = feed_id;
+
+    msg!(
+        "Tier {} of event {} priced at {} of mint {}",
+        ctx.accounts.tier.tier_id,
+        ctx.accounts.event.load()?.event_id,
+        price,
+        tier_currency.mint
+    );
+
+    Ok(())
+}
+
+/// Buys the next ticket straight into `tier`, paying its listed price in
+/// one of the tier's SPL currencies. The ticket records the token payment
+/// and no lamport purchase price.
+pub fn mint_ticket_with_token(ctx: Context<MintTicketWithToken>) -> Result<()> {
+    let accounts = ctx.accounts;
+    let tier = &mut accounts.tier;
+    let tier_currency = &accounts.tier_currency;
+
+    require!(
+        !accounts.sale.event.load()?.is_presale(),
+        EventTicketingError::PresaleActive
+    );
+    require_sol_currency(&*accounts.sale.event.load()?)?;
+    require_listed_price(
+        tier_currency,
+        tier.price,
+        &accounts.sol_price_update,
+        &accounts.token_price_update,
+    )?;
+    require!(tier.sold < tier.supply, EventTicketingError::TierSoldOut);
+    tier.sold += 1;
+
+    let buyer = accounts.sale.buyer.key();
+    sell_ticket_for_tokens(
+        &mut accounts.sale,
+        buyer,
+        tier.price,
+        TokenPayment {
+            mint: tier_currency.mint,
+            amount: tier_currency.price,
+        },
+    )?;
+    accounts.sale.ticket.tier = Some(tier.tier_id);
+
+    msg!(
+        "Ticket #{} bought in tier {} for {} of mint {}",
+        accounts.sale.ticket.ticket_id,
+        tier.tier_id,
+        tier_currency.price,
+        tier_currency.mint
+    );
+
+    Ok(())
+}
+
+/// Like `upgrade_ticket`, but pays in one of the target tier's listed SPL
+/// currencies. The owner pays the same share of the listed token price as
+/// the lamport difference is of the tier price. The amount is recorded as the
+/// ticket's token payment and refunded in that token.
+pub fn upgrade_ticket_with_token(ctx: Context<ChangeTierWithToken>) -> Result<()> {
+    let accounts = ctx.accounts;
+    let current_price = accounts.change.current_tier_price()?;
+    let target_price = accounts.change.target_tier_price()?;
+
+    require!(
+        target_price > current_price,
+        EventTicketingError::NotAnUpgrade
+    );
+
+    let tier_currency = &accounts.tier_currency;
+    require_listed_price(
+        tier_currency,
+        target_price,
+        &accounts.sol_price_update,
+        &accounts.token_price_update,
+    )?;
+
+    let difference = target_price - current_price;
+    let amount = u64::try_from(
+        (tier_currency.price as u128 * difference as u128).div_ceil(target_price as u128),
+    )
+    .map_err(|_| error!(EventTicketingError::Overflow))?;
+
+    collect_tokens(
+        &accounts.owner_token_account,
+        &accounts.vault_token_account,
+        &accounts.change.owner.to_account_info(),
+        &accounts.token_program,
+        amount,
+    )?;
+
+    let change = &mut accounts.change;
+    change.move_ticket()?;
+    change
+        .ticket
+        .record_token_payment(tier_currency.mint, amount)?;
+    record_vault_activity(
+        &*change.event.load()?,
+        change.transaction_log.as_ref(),
+        VaultActivity::TierCurrencyPayment,
+        change.owner.key(),
+        amount,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    msg!(
+        "Ticket #{} upgraded to tier {:?} for {} of mint {}",
+        change.ticket.ticket_id,
+        change.ticket.tier,
+        amount,
+        tier_currency.mint
+    );
+
+    Ok(())
+}
+
+/// Moves a ticket into a cheaper tier (or back to general admission),
+/// refunding the difference from the vault.
+pub fn downgrade_ticket(ctx: Context<ChangeTier>) -> Result<()> {
+    let accounts = ctx.accounts;
+    let current_price = accounts.current_tier_price()?;
+    let target_price = accounts.target_tier_price()?;
+
+    require!(
+        target_price < current_price,
+        EventTicketingError::NotADowngrade
+    );
+    // Part of the price was paid in tokens, so a lamport difference would
+    // refund the wrong currency.
+    require!(
+        accounts.ticket.token_payment.is_none(),
+        EventTicketingError::TokenPaidTicket
+    );
+
+    let difference = current_price - target_price;
+    require_vault_funds(&accounts.vault, difference)?;
+
+    let event_key = accounts.event.key();
+    let seeds = &[VAULT_SEED, event_key.as_ref(), &[ctx.bumps.vault]];
+    let signer_seeds = &[&seeds[..]];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: accounts.vault.to_account_info(),
+                to: accounts.owner.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        difference,
+    )?;
+
+    accounts.move_ticket()?;
+    accounts.ticket.purchase_price = accounts.ticket.purchase_price.saturating_sub(difference);
+    record_vault_activity(
+        &*accounts.event.load()?,
+        accounts.transaction_log.as_ref(),
+        VaultActivity::Downgrade,
+        accounts.owner.key(),
+        difference,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    msg!(
+        "Ticket #{} downgraded to tier {:?}, refunded {} lamports",
+        accounts.ticket.ticket_id,
+        accounts.ticket.tier,
+        difference
+    );
+
+    Ok(())
+}
+
+/// Returns a refunded ticket's token payment to its refund recipient from
+/// the vault's token account. Anyone can crank this.
+pub fn refund_token_payment(ctx: Context<RefundTokenPayment>) -> Result<()> {
+    let ticket = &mut ctx.accounts.ticket;
+
+    require!(ticket.refunded, EventTicketingError::TicketNotRefunded);
+    let payment = ticket
+        .token_payment
+        .ok_or(EventTicketingError::NoTokenPayment)?;
+    require!(
+        ctx.accounts.vault_token_account.mint == payment.mint,
+        EventTicketingError::InvalidVaultTokenAccount
+    );
+
+    pay_out_tokens(
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.recipient_token_account,
+        &ctx.accounts.vault,
+        ctx.bumps.vault,
+        ctx.accounts.event.key(),
+        &ctx.accounts.token_program,
+        payment.amount,
+    )?;
+    ticket.token_payment = None;
+    record_vault_activity(
+        &*ctx.accounts.event.load()?,
+        ctx.accounts.transaction_log.as_ref(),
+        VaultActivity::TierCurrencyPayout,
+        ctx.accounts.recipient_token_account.owner,
+        payment.amount,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    msg!(
+        "Ticket #{} token payment of {} of mint {} refunded to {}",
+        ticket.ticket_id,
+        payment.amount,
+        payment.mint,
+        ctx.accounts.recipient_token_account.owner
+    );
+
+    Ok(())
+}
+
+/// Checks `tier_currency` is accepted and its price is worth `lamports` at
+/// current oracle rates.
+fn require_listed_price(
+    tier_currency: &TierCurrency,
+    lamports: u64,
+    sol_price_update: &AccountInfo,
+    token_price_update: &AccountInfo,
+) -> Result<()> {
+    require!(
+        tier_currency.price > 0,
+        EventTicketingError::TierCurrencyNotAccepted
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let (sol_price, sol_exponent) = pyth::sol_usd_price(sol_price_update, now)?;
+    let (token_price, token_exponent) =
+        pyth::feed_price(token_price_update, &tier_currency.feed_id, now)?;
+    pyth::require_equivalent(
+        (
+            tier_currency.price,
+            tier_currency.decimals,
+            token_price,
+            token_exponent,
+        ),
+        (lamports, LAMPORT_DECIMALS, sol_price, sol_exponent),
+        MAX_CURRENCY_DEVIATION_BPS,
+    )
+}
+
+#[derive(Accounts)]
+#[instruction(tier_id: u8)]
+pub struct CreateTier<'info> {
+    #[account(
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        init,
+        payer = event_authority,
+        space = Tier::SPACE,
+        seeds = [TIER_SEED, event.key().as_ref(), &[tier_id]],
+        bump
+    )]
+    pub tier: Account<'info, Tier>,
+
+    #[account(mut)]
+    pub event_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Pays tier-currency proceeds out of the vault's token account. Released
+/// under the same conditions as `withdraw_proceeds` without a tranche schedule.
+pub fn withdraw_tier_currency(ctx: Context<WithdrawTierCurrency>, amount: u64) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+    let event = &ctx.accounts.event.load()?;
+
+    require_event_authority(
+        event,
+        &ctx.accounts.event_authority.key(),
+        ctx.accounts.authority_config.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+    require_sol_currency(event)?;
+
+    require!(
+        !event.withdrawals_frozen(),
+        EventTicketingError::WithdrawalsFrozen
+    );
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        event.payouts_unlocked(now),
+        EventTicketingError::PayoutTimelocked
+    );
+    require!(
+        !event.refunds_claimable(now),
+        EventTicketingError::ProceedsFrozen
+    );
+
+    pay_out_tokens(
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.destination_token_account,
+        &ctx.accounts.vault,
+        ctx.bumps.vault,
+        event_key,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+    record_vault_activity(
+        event,
+        ctx.accounts.transaction_log.as_ref(),
+        VaultActivity::TierCurrencyPayout,
+        ctx.accounts.destination_token_account.owner,
+        amount,
+        now,
+    )?;
+
+    msg!(
+        "Withdrew {} of mint {} from event {} vault",
+        amount,
+        ctx.accounts.vault_token_account.mint,
+        event.event_id
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetTierCurrency<'info> {
+    #[account(
+        constraint = event.load()?.event_authority == event_authority.key() @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(constraint = tier.event == event.key() @ EventTicketingError::TierMismatch)]
+    pub tier: Account<'info, Tier>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = event_authority,
+        space = TierCurrency::SPACE,
+        seeds = [TIER_CURRENCY_SEED, tier.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub tier_currency: Account<'info, TierCurrency>,
+
+    #[account(mut)]
+    pub event_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ChangeTier<'info> {
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        constraint = ticket.event == event.key() @ EventTicketingError::InvalidTicketAccount,
+        constraint = ticket.owner == owner.key() @ EventTicketingError::UnauthorizedTransfer
+    )]
+    pub ticket: Account<'info, Ticket>,
+
+    /// Tier the ticket is currently in; omitted for general admission tickets.
+    #[account(
+        mut,
+        constraint = from_tier.event == event.key() @ EventTicketingError::TierMismatch
+    )]
+    pub from_tier: Option<Account<'info, Tier>>,
+
+    /// Tier to move the ticket into; omitted to move back to general admission.
+    #[account(
+        mut,
+        constraint = to_tier.event == event.key() @ EventTicketingError::TierMismatch
+    )]
+    pub to_tier: Option<Account<'info, Tier>>,
+
+    /// CHECK: This is the vault PDA that holds event funds. Verified by seeds.
+    #[account(
+        mut,
+        seeds = [
+            VAULT_SEED,
+            event.key().as_ref()
+        ],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Vault activity log; required when the event keeps one.
+    #[account(
+        mut,
+        constraint = event.load()?.transaction_log() == Some(transaction_log.key()) @ EventTicketingError::InvalidTransactionLog
+    )]
+    pub transaction_log: Option<AccountLoader<'info, TransactionLog>>,
+}
+
+#[derive(Accounts)]
+pub struct ChangeTierWithToken<'info> {
+    pub change: ChangeTier<'info>,
+
+    /// Currency listing of the target tier being paid in.
+    #[account(
+        constraint = change.to_tier.as_ref().is_some_and(|tier| tier.key() == tier_currency.tier) @ EventTicketingError::TierCurrencyNotAccepted
+    )]
+    pub tier_currency: Account<'info, TierCurrency>,
+
+    /// CHECK: Pyth SOL/USD `PriceUpdateV2`, verified in `pyth::sol_usd_price`.
+    pub sol_price_update: UncheckedAccount<'info>,
+
+    /// CHECK: Pyth `PriceUpdateV2` for the listed mint, verified in
+    /// `pyth::feed_price` against the listing's feed id.
+    pub token_price_update: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    /// Vault's token account for the listed mint.
+    #[account(
+        mut,
+        constraint = vault_token_account.mint == tier_currency.mint
+            && vault_token_account.owner == change.vault.key() @ EventTicketingError::InvalidVaultTokenAccount
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct MintTicketWithToken<'info> {
+    pub sale: MintTicket<'info>,
+
+    #[account(
+        mut,
+        constraint = tier.event == sale.event.key() @ EventTicketingError::TierMismatch
+    )]
+    pub tier: Account<'info, Tier>,
+
+    /// Currency listing of `tier` being paid in.
+    #[account(
+        constraint = tier_currency.tier == tier.key() @ EventTicketingError::TierCurrencyNotAccepted
+    )]
+    pub tier_currency: Account<'info, TierCurrency>,
+
+    /// CHECK: Pyth SOL/USD `PriceUpdateV2`, verified in `pyth::sol_usd_price`.
+    pub sol_price_update: UncheckedAccount<'info>,
+
+    /// CHECK: Pyth `PriceUpdateV2` for the listed mint, verified in
+    /// `pyth::feed_price` against the listing's feed id.
+    pub token_price_update: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RefundTokenPayment<'info> {
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        constraint = ticket.event == event.key() @ EventTicketingError::InvalidTicketAccount
+    )]
+    pub ticket: Account<'info, Ticket>,
+
+    /// CHECK: This is the vault PDA that holds event funds. Verified by seeds.
+    #[account(
+        seeds = [
+            VAULT_SEED,
+            event.key().as_ref()
+        ],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = vault_token_account.owner == vault.key() @ EventTicketingError::InvalidVaultTokenAccount
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// Refund recipient's token account for the paid mint.
+    #[account(
+        mut,
+        constraint = recipient_token_account.owner == ticket.refund_recipient() @ EventTicketingError::InvalidRefundRecipient
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// Vault activity log; required when the event keeps one.
+    #[account(
+        mut,
+        constraint = event.load()?.transaction_log() == Some(transaction_log.key()) @ EventTicketingError::InvalidTransactionLog
+    )]
+    pub transaction_log: Option<AccountLoader<'info, TransactionLog>>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTierCurrency<'info> {
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(constraint = authority_config.event == event.key())]
+    pub authority_config: Option<Account<'info, EventAuthorityConfig>>,
+
+    /// CHECK: This is the vault PDA that holds event funds. Verified by seeds.
+    #[account(
+        seeds = [
+            VAULT_SEED,
+            event.key().as_ref()
+        ],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = vault_token_account.owner == vault.key() @ EventTicketingError::InvalidVaultTokenAccount
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// Event authority's token account receiving the proceeds.
+    #[account(
+        mut,
+        constraint = destination_token_account.owner == event.load()?.event_authority @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub destination_token_account: Account<'info, TokenAccount>,
+
+    pub event_authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// Vault activity log; required when the event keeps one.
+    #[account(
+        mut,
+        constraint = event.load()?.transaction_log() == Some(transaction_log.key()) @ EventTicketingError::InvalidTransactionLog
+    )]
+    pub transaction_log: Option<AccountLoader<'info, TransactionLog>>,
+}
+
+impl ChangeTier<'_> {
+    /// Price the ticket was bought at: its tier's price, or the event price
+    /// for general admission.
+    fn current_tier_price(&self) -> Result<u64> {
+        let event = &self.event.load()?;
+        require!(!event.canceled(), EventTicketingError::EventCanceled);
+        require!(!event.is_finalized(), EventTicketingError::EventFinalized);
+        require_sol_currency(event)?;
+        require!(
+            !self.ticket.is_used() && !self.ticket.refunded,
+            EventTicketingError::TicketNotChangeable
+        );
+        require!(!self.ticket.is_lent(), EventTicketingError::TicketOnLoan);
+        require!(
+            self.ticket.tier != self.to_tier.as_ref().map(|tier| tier.tier_id),
+            EventTicketingError::TierMismatch
+        );
+
+        match (self.ticket.tier, &self.from_tier) {
+            (None, None) => Ok(event.price),
+            (Some(tier_id), Some(from_tier)) if from_tier.tier_id == tier_id => Ok(from_tier.price),
+            _ => err!(EventTicketingError::TierMismatch),
+        }
+    }
+
+    fn target_tier_price(&self) -> Result<u64> {
+        let event_price = self.event.load()?.price;
+        Ok(self.to_tier.as_ref().map_or(event_price, |tier| tier.price))
+    }
+
+    /// Updates the tier counters and moves the ticket into `to_tier`.
+    fn move_ticket(&mut self) -> Result<()> {
+        if let Some(from_tier) = &mut self.from_tier {
+            from_tier.sold -= 1;
+        }
+
+        self.ticket.tier = match &mut self.to_tier {
+            Some(to_tier) => {
+                require!(
+                    to_tier.sold < to_tier.supply,
+                    EventTicketingError::TierSoldOut
+                );
+                to_tier.sold += 1;
+                Some(to_tier.tier_id)
+            }
+            None => None,
+        };
+
+        Ok(())
+    }
+}
@@ -1,34 +1,246 @@
+use crate::constants::{EVENT_CPI_AUTHORITY_SEED, PENDING_TRANSFER_SEED, VAULT_SEED};
 use crate::errors::EventTicketingError;
-use crate::state::Ticket;
+use crate::events::{emit_cpi_event, TicketTransferred};
+use crate::state::{Event, PendingTransfer, Ticket, TransactionLog, VaultActivity};
+use crate::transaction_log::record_vault_activity;
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 
-pub fn transfer_ticket(ctx: Context<TransferTicket>) -> Result<()> {
+pub(crate) fn require_transferable(event: &Event, ticket: &Ticket) -> Result<()> {
+    require!(
+        event.transferable(),
+        EventTicketingError::TicketNotTransferable
+    );
+    require!(
+        !event.transfers_frozen(),
+        EventTicketingError::TransfersFrozen
+    );
+    require!(!ticket.is_used(), EventTicketingError::TicketAlreadyUsed);
+    require!(!ticket.revoked, EventTicketingError::TicketRevoked);
+    require!(!ticket.refunded, EventTicketingError::AlreadyRefunded);
+    require!(!ticket.is_lent(), EventTicketingError::TicketOnLoan);
+    Ok(())
+}
+
+/// Offers the ticket to `recipient`, who has to accept it before ownership
+/// changes. The sender escrows the transfer fee in the pending transfer; it
+/// goes to the vault on acceptance and back to the sender on cancellation.
+pub fn approve_transfer(ctx: Context<ApproveTransfer>) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+    let event = &ctx.accounts.event.load()?;
+    let ticket = &ctx.accounts.ticket;
+    let recipient = ctx.accounts.recipient.key();
+
+    require_transferable(event, ticket)?;
+    require!(
+        recipient != ticket.owner,
+        EventTicketingError::InvalidTransferRecipient
+    );
+
+    let fee = event.transfer_fee_lamports;
+    if fee > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.pending_transfer.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+    }
+
+    let pending_transfer = &mut ctx.accounts.pending_transfer;
+    pending_transfer.ticket = ticket.key();
+    pending_transfer.event = event_key;
+    pending_transfer.from = ticket.owner;
+    pending_transfer.to = recipient;
+    pending_transfer.fee = fee;
+    pending_transfer.created_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "Ticket #{} offered to {} (fee: {} lamports)",
+        ticket.ticket_id,
+        recipient,
+        fee
+    );
+
+    Ok(())
+}
+
+/// Completes a pending transfer. Signed by the recipient; the pending
+/// transfer is closed and its rent returned to the sender.
+pub fn accept_transfer(ctx: Context<AcceptTransfer>) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
     let ticket = &mut ctx.accounts.ticket;
+    let pending_transfer = &ctx.accounts.pending_transfer;
 
-    require!(!ticket.is_used, EventTicketingError::TicketAlreadyUsed);
-    require!(!ticket.refunded, EventTicketingError::AlreadyRefunded);
+    // The ticket may have been resold or offered elsewhere since approval.
+    require!(
+        ticket.owner == pending_transfer.from,
+        EventTicketingError::StalePendingTransfer
+    );
+    require_transferable(event, ticket)?;
+
+    if pending_transfer.fee > 0 {
+        pending_transfer.sub_lamports(pending_transfer.fee)?;
+        ctx.accounts.vault.add_lamports(pending_transfer.fee)?;
+        record_vault_activity(
+            event,
+            ctx.accounts.transaction_log.as_ref(),
+            VaultActivity::Fee,
+            pending_transfer.from,
+            pending_transfer.fee,
+            Clock::get()?.unix_timestamp,
+        )?;
+    }
 
-    ticket.owner = ctx.accounts.new_owner.key();
+    ticket.owner = pending_transfer.to;
+    event.record_transfer(pending_transfer.fee, 0)?;
+
+    emit_cpi_event(
+        &ctx.accounts.event_cpi_authority,
+        &TicketTransferred {
+            event: event_key,
+            ticket: ticket.key(),
+            ticket_id: ticket.ticket_id,
+            from: pending_transfer.from,
+            to: pending_transfer.to,
+            transfer_fee: pending_transfer.fee,
+        },
+    )?;
 
     msg!(
-        "Ticket #{} transferred to {}",
+        "Ticket #{} transferred to {} (fee: {} lamports)",
         ticket.ticket_id,
-        ctx.accounts.new_owner.key()
+        pending_transfer.to,
+        pending_transfer.fee
+    );
+
+    Ok(())
+}
+
+/// Withdraws or declines a pending transfer. The sender, the recipient or the
+/// ticket's current owner (clearing a stale offer) may cancel; the escrowed
+/// fee and rent go back to the sender.
+pub fn cancel_transfer(ctx: Context<CancelTransfer>) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let pending_transfer = &ctx.accounts.pending_transfer;
+
+    require!(
+        authority == pending_transfer.from
+            || authority == pending_transfer.to
+            || authority == ctx.accounts.ticket.owner,
+        EventTicketingError::UnauthorizedTransfer
+    );
+
+    msg!(
+        "Pending transfer of ticket #{} canceled",
+        ctx.accounts.ticket.ticket_id
     );
 
     Ok(())
 }
 
 #[derive(Accounts)]
-pub struct TransferTicket<'info> {
+pub struct ApproveTransfer<'info> {
+    pub event: AccountLoader<'info, Event>,
+
     #[account(
-        mut,
-        constraint = ticket.owner == current_owner.key() @ EventTicketingError::UnauthorizedTransfer
+        constraint = ticket.owner == owner.key() @ EventTicketingError::UnauthorizedTransfer,
+        constraint = ticket.event == event.key()
     )]
     pub ticket: Account<'info, Ticket>,
 
-    pub current_owner: Signer<'info>,
+    #[account(
+        init,
+        payer = owner,
+        space = PendingTransfer::SPACE,
+        seeds = [PENDING_TRANSFER_SEED, ticket.key().as_ref()],
+        bump
+    )]
+    pub pending_transfer: Account<'info, PendingTransfer>,
 
     /// CHECK: This is the recipient of the ticket. Can be any valid account.
-    pub new_owner: AccountInfo<'info>,
+    pub recipient: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptTransfer<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        constraint = ticket.event == event.key()
+    )]
+    pub ticket: Account<'info, Ticket>,
+
+    #[account(
+        mut,
+        close = sender,
+        seeds = [PENDING_TRANSFER_SEED, ticket.key().as_ref()],
+        bump,
+        constraint = pending_transfer.to == recipient.key() @ EventTicketingError::UnauthorizedTransfer
+    )]
+    pub pending_transfer: Account<'info, PendingTransfer>,
+
+    /// CHECK: This is the vault PDA that collects transfer fees. Verified by seeds.
+    #[account(
+        mut,
+        seeds = [
+            VAULT_SEED,
+            event.key().as_ref()
+        ],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: The sender recorded on the pending transfer; receives its rent back.
+    #[account(mut, address = pending_transfer.from)]
+    pub sender: AccountInfo<'info>,
+
+    pub recipient: Signer<'info>,
+
+    /// Vault activity log; required when the event keeps one.
+    #[account(
+        mut,
+        constraint = event.load()?.transaction_log() == Some(transaction_log.key()) @ EventTicketingError::InvalidTransactionLog
+    )]
+    pub transaction_log: Option<AccountLoader<'info, TransactionLog>>,
+
+    /// CHECK: Signs the event self-CPI. Verified by seeds.
+    #[account(seeds = [EVENT_CPI_AUTHORITY_SEED], bump)]
+    pub event_cpi_authority: AccountInfo<'info>,
+
+    /// CHECK: This program, invoked by the event self-CPI.
+    #[account(address = crate::ID)]
+    pub program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelTransfer<'info> {
+    pub ticket: Account<'info, Ticket>,
+
+    #[account(
+        mut,
+        close = sender,
+        seeds = [PENDING_TRANSFER_SEED, ticket.key().as_ref()],
+        bump
+    )]
+    pub pending_transfer: Account<'info, PendingTransfer>,
+
+    /// CHECK: The sender recorded on the pending transfer; receives the
+    /// escrowed fee and rent back.
+    #[account(mut, address = pending_transfer.from)]
+    pub sender: AccountInfo<'info>,
+
+    pub authority: Signer<'info>,
 }
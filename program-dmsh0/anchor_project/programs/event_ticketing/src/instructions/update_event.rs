@@ -0,0 +1,107 @@
+use super::migrate::grow_account;
+use crate::constants::{
+    EVENT_METADATA_SEED, MAX_METADATA_URI_LEN, MAX_UPDATED_DATE_LEN, MAX_UPDATED_NAME_LEN,
+};
+use crate::errors::EventTicketingError;
+use crate::rbac::{has_permission, Delegations, Permission};
+use crate::state::{CoOrganizer, Event, EventMetadata};
+use anchor_lang::prelude::*;
+
+/// Replaces the event's name, date and/or metadata URI. Unlike creation,
+/// lengths are not capped by the original allocation: the metadata account
+/// is reallocated to fit, with the event authority paying the extra rent, up
+/// to `MAX_UPDATED_NAME_LEN`, `MAX_UPDATED_DATE_LEN` and
+/// `MAX_METADATA_URI_LEN`.
+pub fn update_event(
+    ctx: Context<UpdateEvent>,
+    name: Option<String>,
+    date: Option<String>,
+    metadata_uri: Option<String>,
+) -> Result<()> {
+    has_permission(
+        &*ctx.accounts.event.load()?,
+        &ctx.accounts.event_authority.key(),
+        Permission::Update,
+        &Delegations {
+            co_organizer: ctx.accounts.co_organizer.as_deref(),
+            ..Default::default()
+        },
+    )?;
+
+    if let Some(name) = &name {
+        require!(!name.is_empty(), EventTicketingError::InvalidEventName);
+        require!(
+            name.len() <= MAX_UPDATED_NAME_LEN,
+            EventTicketingError::NameTooLong
+        );
+    }
+    if let Some(date) = &date {
+        require!(
+            date.len() <= MAX_UPDATED_DATE_LEN,
+            EventTicketingError::DateTooLong
+        );
+    }
+    if let Some(metadata_uri) = &metadata_uri {
+        require!(
+            metadata_uri.len() <= MAX_METADATA_URI_LEN,
+            EventTicketingError::MetadataUriTooLong
+        );
+    }
+
+    let space = ctx.accounts.metadata.space_after_update(
+        name.as_deref(),
+        date.as_deref(),
+        metadata_uri.as_deref(),
+        ctx.accounts.metadata.to_account_info().data_len(),
+    );
+    grow_account(
+        &ctx.accounts.metadata.to_account_info(),
+        &ctx.accounts.event_authority,
+        &ctx.accounts.system_program,
+        space,
+    )?;
+
+    let metadata = &mut ctx.accounts.metadata;
+
+    if let Some(name) = name {
+        metadata.name = name;
+    }
+    if let Some(date) = date {
+        metadata.date = date;
+    }
+    if let Some(metadata_uri) = metadata_uri {
+        metadata.metadata_uri = metadata_uri;
+    }
+
+    msg!(
+        "Event {} updated: {} on {}",
+        ctx.accounts.event.load()?.event_id,
+        metadata.name,
+        metadata.date
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateEvent<'info> {
+    pub event: AccountLoader<'info, Event>,
+
+    /// Co-organizer record when `event_authority` is a teammate allowed to
+    /// update the event.
+    #[account(constraint = co_organizer.event == event.key() @ EventTicketingError::UnauthorizedEventAuthority)]
+    pub co_organizer: Option<Account<'info, CoOrganizer>>,
+
+    #[account(
+        mut,
+        seeds = [EVENT_METADATA_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub metadata: Account<'info, EventMetadata>,
+
+    /// The event authority, or a co-organizer allowed to update the event.
+    #[account(mut)]
+    pub event_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,37 @@
+use crate::constants::{CONFIG_SEED, ORGANIZER_SEED};
+use crate::errors::EventTicketingError;
+use crate::state::{Config, OrganizerRegistry};
+use anchor_lang::prelude::*;
+
+pub fn verify_organizer(ctx: Context<VerifyOrganizer>) -> Result<()> {
+    let organizer_registry = &mut ctx.accounts.organizer_registry;
+
+    organizer_registry.verified = true;
+
+    msg!(
+        "Organizer {} verified by admin {}",
+        organizer_registry.organizer,
+        ctx.accounts.admin.key()
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VerifyOrganizer<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump,
+        has_one = admin @ EventTicketingError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [ORGANIZER_SEED, organizer_registry.organizer.as_ref()],
+        bump
+    )]
+    pub organizer_registry: Account<'info, OrganizerRegistry>,
+
+    pub admin: Signer<'info>,
+}
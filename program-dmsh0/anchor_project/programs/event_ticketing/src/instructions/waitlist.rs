@@ -0,0 +1,325 @@
+use crate::attestation::require_no_attestor;
+use crate::constants::*;
+use crate::errors::EventTicketingError;
+use crate::events::{emit_cpi_event, TicketMinted};
+use crate::payment::require_sol_currency;
+use crate::pyth;
+use crate::sale_hook::require_no_sale_hook;
+use crate::sales_stats::record_daily_sales;
+use crate::state::{Event, SalesStats, Ticket, TransactionLog, VaultActivity, WaitlistEntry};
+use crate::token_gate::require_no_token_gate;
+use crate::transaction_log::record_vault_activity;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Joins the back of a sold-out event's waitlist, escrowing the price a
+/// ticket sells for right now in the entry account until a seat frees up.
+pub fn join_waitlist(ctx: Context<JoinWaitlist>) -> Result<()> {
+    let deposit = ctx.accounts.sale_price()?;
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    require!(!event.canceled(), EventTicketingError::EventCanceled);
+    require!(!event.is_finalized(), EventTicketingError::EventFinalized);
+    require!(!event.sales_paused(), EventTicketingError::SalesPaused);
+    require!(event.available() == 0, EventTicketingError::EventNotSoldOut);
+    require_sol_currency(event)?;
+    require_no_sale_hook(event)?;
+    require_no_attestor(event)?;
+    require_no_token_gate(event)?;
+    event.require_numbered_tickets()?;
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.wallet.to_account_info(),
+                to: ctx.accounts.entry.to_account_info(),
+            },
+        ),
+        deposit,
+    )?;
+
+    let entry = &mut ctx.accounts.entry;
+
+    entry.event = event_key;
+    entry.wallet = ctx.accounts.wallet.key();
+    entry.position = event.waitlist_len;
+    entry.deposit = deposit;
+
+    event.waitlist_len += 1;
+
+    msg!(
+        "{} joined the waitlist for event {} at position {}",
+        entry.wallet,
+        event.event_id,
+        entry.position
+    );
+
+    Ok(())
+}
+
+/// Converts the deposit of the next waitlist entrant into a ticket once a
+/// refund has freed a seat. Anyone can crank this.
+pub fn claim_from_waitlist(ctx: Context<ClaimFromWaitlist>) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event.load_mut()?;
+    let entry = &ctx.accounts.entry;
+
+    require!(!event.canceled(), EventTicketingError::EventCanceled);
+    require!(!event.is_finalized(), EventTicketingError::EventFinalized);
+    require!(!event.sales_paused(), EventTicketingError::SalesPaused);
+    require!(event.freed_seats > 0, EventTicketingError::NoFreedSeats);
+
+    // Move the escrowed deposit into the vault; the entry's rent goes back
+    // to the entrant when it is closed.
+    entry.sub_lamports(entry.deposit)?;
+    ctx.accounts.vault.add_lamports(entry.deposit)?;
+
+    // The refunded ticket keeps its id, so the freed seat is reissued as a
+    // new one at the end of the sequence, without growing `supply`.
+    event.freed_seats -= 1;
+    event.reissued_seats += 1;
+    event.waitlist_next += 1;
+
+    let ticket_id = event.take_ticket_id()?;
+
+    ctx.accounts.ticket.issue(
+        entry.wallet,
+        event_key,
+        ticket_id,
+        entry.deposit,
+        Clock::get()?.unix_timestamp,
+    );
+
+    event.sold += 1;
+    let fees = event.record_sale(entry.deposit, 0)?;
+    record_daily_sales(
+        event,
+        ctx.accounts.sales_stats.as_deref_mut(),
+        1,
+        entry.deposit,
+        Clock::get()?.unix_timestamp,
+    )?;
+    record_vault_activity(
+        event,
+        ctx.accounts.transaction_log.as_ref(),
+        VaultActivity::Mint,
+        entry.wallet,
+        entry.deposit,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    emit_cpi_event(
+        &ctx.accounts.event_cpi_authority,
+        &TicketMinted {
+            event: event_key,
+            ticket: ctx.accounts.ticket.key(),
+            ticket_id,
+            owner: entry.wallet,
+            price: entry.deposit,
+            fees,
+        },
+    )?;
+
+    msg!(
+        "Ticket #{} issued for event {} to waitlist position {}",
+        ticket_id,
+        event.event_id,
+        entry.position
+    );
+
+    Ok(())
+}
+
+/// Leaves the waitlist before being served. Closing the entry returns the
+/// escrowed deposit along with its rent.
+pub fn leave_waitlist(ctx: Context<LeaveWaitlist>) -> Result<()> {
+    let entry = &ctx.accounts.entry;
+
+    msg!(
+        "{} left the waitlist for event {} at position {}, {} lamports returned",
+        entry.wallet,
+        ctx.accounts.event.load()?.event_id,
+        entry.position,
+        entry.deposit
+    );
+
+    Ok(())
+}
+
+/// Moves the front of the waitlist past an entrant who has left, so the
+/// entrants behind them can be served. Anyone can crank this.
+pub fn skip_waitlist_entry(ctx: Context<SkipWaitlistEntry>) -> Result<()> {
+    let event = &mut ctx.accounts.event.load_mut()?;
+
+    require!(
+        event.waitlist_next < event.waitlist_len,
+        EventTicketingError::WaitlistEmpty
+    );
+    require!(
+        ctx.accounts.entry.data_is_empty(),
+        EventTicketingError::WaitlistEntryActive
+    );
+
+    msg!(
+        "Waitlist position {} of event {} skipped",
+        event.waitlist_next,
+        event.event_id
+    );
+
+    event.waitlist_next += 1;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct JoinWaitlist<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        init,
+        payer = wallet,
+        space = WaitlistEntry::SPACE,
+        seeds = [
+            WAITLIST_SEED,
+            event.key().as_ref(),
+            &event.load()?.waitlist_len.to_le_bytes()
+        ],
+        bump
+    )]
+    pub entry: Account<'info, WaitlistEntry>,
+
+    /// CHECK: Pyth SOL/USD `PriceUpdateV2`; required for USD-priced events and
+    /// verified in `pyth::sol_usd_price`.
+    pub price_update: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl JoinWaitlist<'_> {
+    /// Lamport price a ticket sells for right now; see `pyth::sale_price`.
+    pub fn sale_price(&self) -> Result<u64> {
+        pyth::sale_price(
+            &*self.event.load()?,
+            self.price_update.as_deref(),
+            Clock::get()?.unix_timestamp,
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct LeaveWaitlist<'info> {
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        close = wallet,
+        has_one = wallet,
+        seeds = [
+            WAITLIST_SEED,
+            event.key().as_ref(),
+            &entry.position.to_le_bytes()
+        ],
+        bump
+    )]
+    pub entry: Account<'info, WaitlistEntry>,
+
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SkipWaitlistEntry<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    /// CHECK: The entry at the front of the waitlist; must have been closed
+    /// by `leave_waitlist`. Verified by seeds.
+    #[account(
+        seeds = [
+            WAITLIST_SEED,
+            event.key().as_ref(),
+            &event.load()?.waitlist_next.to_le_bytes()
+        ],
+        bump
+    )]
+    pub entry: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimFromWaitlist<'info> {
+    #[account(mut)]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        close = wallet,
+        seeds = [
+            WAITLIST_SEED,
+            event.key().as_ref(),
+            &event.load()?.waitlist_next.to_le_bytes()
+        ],
+        bump
+    )]
+    pub entry: Account<'info, WaitlistEntry>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Ticket::SPACE,
+        seeds = [
+            TICKET_SEED,
+            event.key().as_ref(),
+            &event.load()?.next_ticket_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub ticket: Account<'info, Ticket>,
+
+    /// CHECK: This is the vault PDA that holds event funds. Verified by seeds.
+    #[account(
+        mut,
+        seeds = [
+            VAULT_SEED,
+            event.key().as_ref()
+        ],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: Entrant receiving the entry's rent back; must match the entry.
+    #[account(mut, address = entry.wallet)]
+    pub wallet: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Daily sales buckets; required when the event keeps them.
+    #[account(
+        mut,
+        constraint = event.load()?.sales_stats() == Some(sales_stats.key()) @ EventTicketingError::InvalidSalesStats
+    )]
+    pub sales_stats: Option<Account<'info, SalesStats>>,
+
+    /// Vault activity log; required when the event keeps one.
+    #[account(
+        mut,
+        constraint = event.load()?.transaction_log() == Some(transaction_log.key()) @ EventTicketingError::InvalidTransactionLog
+    )]
+    pub transaction_log: Option<AccountLoader<'info, TransactionLog>>,
+
+    /// CHECK: Signs the event self-CPI. Verified by seeds.
+    #[account(seeds = [EVENT_CPI_AUTHORITY_SEED], bump)]
+    pub event_cpi_authority: AccountInfo<'info>,
+
+    /// CHECK: This program, invoked by the event self-CPI.
+    #[account(address = crate::ID)]
+    pub program: AccountInfo<'info>,
+}
@@ -0,0 +1,199 @@
+use crate::constants::*;
+use crate::errors::EventTicketingError;
+use crate::payment::{pay_out_tokens, require_vault_token_account};
+use crate::rbac::{has_permission, Delegations, Permission};
+use crate::state::{
+    CoOrganizer, Event, EventAuthorityConfig, PayoutConfig, TransactionLog, VaultActivity,
+};
+use crate::transaction_log::record_vault_activity;
+use crate::vault::{available_funds, require_vault_funds};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{Token, TokenAccount};
+
+pub fn withdraw_proceeds(ctx: Context<WithdrawProceeds>, amount: u64) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+    let event = &ctx.accounts.event.load()?;
+    let payout_config = ctx.accounts.payout_config.as_mut();
+
+    has_permission(
+        event,
+        &ctx.accounts.event_authority.key(),
+        Permission::Withdraw,
+        &Delegations {
+            authority_config: ctx.accounts.authority_config.as_deref(),
+            cosigners: ctx.remaining_accounts,
+            co_organizer: ctx.accounts.co_organizer.as_deref(),
+            ..Default::default()
+        },
+    )?;
+
+    require!(
+        !event.withdrawals_frozen(),
+        EventTicketingError::WithdrawalsFrozen
+    );
+
+    // Deposits are owed back to attendees until finalization forfeits the
+    // no-shows' ones.
+    require!(
+        event.attendance_deposit == 0 || event.is_finalized(),
+        EventTicketingError::DepositsHeld
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        !event.refunds_claimable(now),
+        EventTicketingError::ProceedsFrozen
+    );
+    // A tranche schedule takes the place of the payout timelock.
+    if event.payout_config().is_none() {
+        require!(
+            event.payouts_unlocked(now),
+            EventTicketingError::PayoutTimelocked
+        );
+    }
+    if event.currency().is_some() {
+        let (Some(vault_token_account), Some(destination_token_account), Some(token_program)) = (
+            &ctx.accounts.vault_token_account,
+            &ctx.accounts.destination_token_account,
+            &ctx.accounts.token_program,
+        ) else {
+            return err!(EventTicketingError::CurrencyAccountsMissing);
+        };
+        require_vault_token_account(event, &ctx.accounts.vault.key(), vault_token_account)?;
+        apply_payout_tranches(
+            event,
+            payout_config,
+            vault_token_account.amount,
+            amount,
+            now,
+        )?;
+        pay_out_tokens(
+            vault_token_account,
+            destination_token_account,
+            &ctx.accounts.vault,
+            ctx.bumps.vault,
+            event_key,
+            token_program,
+            amount,
+        )?;
+    } else {
+        require_vault_funds(&ctx.accounts.vault, amount)?;
+        apply_payout_tranches(
+            event,
+            payout_config,
+            available_funds(&ctx.accounts.vault)?,
+            amount,
+            now,
+        )?;
+
+        let seeds = &[VAULT_SEED, event_key.as_ref(), &[ctx.bumps.vault]];
+        let signer_seeds = &[&seeds[..]];
+
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+    }
+
+    record_vault_activity(
+        event,
+        ctx.accounts.transaction_log.as_ref(),
+        VaultActivity::Withdraw,
+        ctx.accounts.event_authority.key(),
+        amount,
+        now,
+    )?;
+
+    msg!(
+        "Withdrew {} lamports from event {} vault to {}",
+        amount,
+        event.event_id,
+        ctx.accounts.destination.key()
+    );
+
+    Ok(())
+}
+
+/// Checks a withdrawal of `amount` out of the `held` proceeds against the
+/// event's payout tranches, if it has any.
+fn apply_payout_tranches(
+    event: &Event,
+    payout_config: Option<&mut Account<PayoutConfig>>,
+    held: u64,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    if event.payout_config().is_none() {
+        return Ok(());
+    }
+    let payout_config = payout_config.ok_or(EventTicketingError::InvalidPayoutConfig)?;
+    payout_config.record_withdrawal(event, held, amount, now)
+}
+
+#[derive(Accounts)]
+pub struct WithdrawProceeds<'info> {
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(constraint = authority_config.event == event.key())]
+    pub authority_config: Option<Account<'info, EventAuthorityConfig>>,
+
+    /// Co-organizer record when `event_authority` is a teammate allowed to
+    /// withdraw. Proceeds still go to the event authority.
+    #[account(constraint = co_organizer.event == event.key() @ EventTicketingError::UnauthorizedEventAuthority)]
+    pub co_organizer: Option<Account<'info, CoOrganizer>>,
+
+    /// CHECK: This is the vault PDA that holds event funds. Verified by seeds.
+    #[account(
+        mut,
+        seeds = [
+            VAULT_SEED,
+            event.key().as_ref()
+        ],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: Proceeds always go to the event authority's wallet.
+    #[account(mut, address = event.load()?.event_authority)]
+    pub destination: AccountInfo<'info>,
+
+    /// Vault's token account for SPL-currency events.
+    #[account(mut)]
+    pub vault_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Event authority's token account receiving SPL-currency proceeds.
+    #[account(
+        mut,
+        constraint = destination_token_account.owner == event.load()?.event_authority @ EventTicketingError::UnauthorizedEventAuthority
+    )]
+    pub destination_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// Tranche schedule; required when the event has one.
+    #[account(
+        mut,
+        constraint = event.load()?.payout_config() == Some(payout_config.key()) @ EventTicketingError::InvalidPayoutConfig
+    )]
+    pub payout_config: Option<Account<'info, PayoutConfig>>,
+
+    /// Vault activity log; required when the event keeps one.
+    #[account(
+        mut,
+        constraint = event.load()?.transaction_log() == Some(transaction_log.key()) @ EventTicketingError::InvalidTransactionLog
+    )]
+    pub transaction_log: Option<AccountLoader<'info, TransactionLog>>,
+
+    /// The event authority, or a co-organizer allowed to withdraw.
+    pub event_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
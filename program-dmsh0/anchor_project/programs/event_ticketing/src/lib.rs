@@ -1,22 +1,56 @@
+// The IDL instructions `#[program]` generates resize accounts with the
+// deprecated `AccountInfo::realloc`, outside any item we can annotate.
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
 
 declare_id!("5wkLPJVMaiemo3Nn5QdAgdifjZig3DWUR9pxAGAeCXZJ");
 
+pub mod attestation;
+pub mod authority;
+#[cfg(feature = "client")]
+pub mod client;
 pub mod constants;
+pub mod ed25519;
 pub mod errors;
+pub mod events;
 pub mod instructions;
+pub mod merkle;
+pub mod payment;
+pub mod pda;
+pub mod pyth;
+pub mod randomness;
+pub mod rbac;
+pub mod sale_hook;
+pub mod sales_stats;
 pub mod state;
+pub mod token_gate;
+pub mod transaction_log;
+pub mod vault;
 
 use instructions::*;
+use state::{PayoutTranche, SeatLayout};
 
 #[program]
 pub mod event_ticketing {
     use super::*;
 
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        max_unverified_supply: u32,
+    ) -> Result<()> {
+        instructions::initialize_config(ctx, max_unverified_supply)
+    }
+
     pub fn register_organizer(ctx: Context<RegisterOrganizer>) -> Result<()> {
         instructions::register_organizer(ctx)
     }
 
+    pub fn verify_organizer(ctx: Context<VerifyOrganizer>) -> Result<()> {
+        instructions::verify_organizer(ctx)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_event(
         ctx: Context<InitializeEvent>,
         event_id: u32,
@@ -24,27 +58,681 @@ pub mod event_ticketing {
         supply: u32,
         name: String,
         date: String,
+        transferable: bool,
+        seat_layout: Option<SeatLayout>,
+        one_per_wallet: bool,
+        max_total_supply: Option<u32>,
     ) -> Result<()> {
-        instructions::initialize_event(ctx, event_id, price, supply, name, date)
+        instructions::initialize_event(
+            ctx,
+            event_id,
+            price,
+            supply,
+            name,
+            date,
+            transferable,
+            seat_layout,
+            one_per_wallet,
+            max_total_supply,
+        )
     }
 
-    pub fn mint_ticket(ctx: Context<MintTicket>) -> Result<()> {
+    pub fn mint_ticket<'info>(ctx: Context<'_, '_, 'info, 'info, MintTicket<'info>>) -> Result<()> {
         instructions::mint_ticket(ctx)
     }
 
-    pub fn transfer_ticket(ctx: Context<TransferTicket>) -> Result<()> {
-        instructions::transfer_ticket(ctx)
+    pub fn mint_ticket_to<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MintTicket<'info>>,
+        recipient: Pubkey,
+    ) -> Result<()> {
+        instructions::mint_ticket_to(ctx, recipient)
+    }
+
+    pub fn mint_ticket_with_amount<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MintTicket<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::mint_ticket_with_amount(ctx, amount)
+    }
+
+    pub fn close_receipt(ctx: Context<CloseReceipt>) -> Result<()> {
+        instructions::close_receipt(ctx)
+    }
+
+    pub fn mint_allowlisted(ctx: Context<MintTicket>, proof: Vec<[u8; 32]>) -> Result<()> {
+        instructions::mint_allowlisted(ctx, proof)
+    }
+
+    pub fn mint_tickets<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MintTickets<'info>>,
+        count: u8,
+    ) -> Result<()> {
+        instructions::mint_tickets(ctx, count)
+    }
+
+    pub fn mint_for_group<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MintTickets<'info>>,
+    ) -> Result<()> {
+        instructions::mint_for_group(ctx)
+    }
+
+    pub fn approve_transfer(ctx: Context<ApproveTransfer>) -> Result<()> {
+        instructions::approve_transfer(ctx)
+    }
+
+    pub fn accept_transfer(ctx: Context<AcceptTransfer>) -> Result<()> {
+        instructions::accept_transfer(ctx)
+    }
+
+    pub fn cancel_transfer(ctx: Context<CancelTransfer>) -> Result<()> {
+        instructions::cancel_transfer(ctx)
+    }
+
+    pub fn approve_operator(ctx: Context<ApproveOperator>) -> Result<()> {
+        instructions::approve_operator(ctx)
+    }
+
+    pub fn revoke_operator(ctx: Context<RevokeOperator>) -> Result<()> {
+        instructions::revoke_operator(ctx)
+    }
+
+    pub fn operator_transfer(ctx: Context<OperatorTransfer>) -> Result<()> {
+        instructions::operator_transfer(ctx)
+    }
+
+    pub fn lend_ticket(ctx: Context<LendTicket>, until: i64) -> Result<()> {
+        instructions::lend_ticket(ctx, until)
+    }
+
+    pub fn reclaim_ticket(ctx: Context<ReclaimTicket>) -> Result<()> {
+        instructions::reclaim_ticket(ctx)
+    }
+
+    pub fn list_ticket(ctx: Context<ListTicket>, price: u64) -> Result<()> {
+        instructions::list_ticket(ctx, price)
+    }
+
+    pub fn delist_ticket(ctx: Context<DelistTicket>) -> Result<()> {
+        instructions::delist_ticket(ctx)
+    }
+
+    pub fn buy_listed_ticket(ctx: Context<BuyListedTicket>, max_price: u64) -> Result<()> {
+        instructions::buy_listed_ticket(ctx, max_price)
+    }
+
+    pub fn set_approved_venues(ctx: Context<SetApprovedVenues>, venues: Vec<Pubkey>) -> Result<()> {
+        instructions::set_approved_venues(ctx, venues)
+    }
+
+    pub fn set_max_resale_multiplier(
+        ctx: Context<SetMaxResaleMultiplier>,
+        max_resale_multiplier_bps: u16,
+    ) -> Result<()> {
+        instructions::set_max_resale_multiplier(ctx, max_resale_multiplier_bps)
+    }
+
+    pub fn set_transfer_fee(
+        ctx: Context<SetTransferFee>,
+        transfer_fee_lamports: u64,
+    ) -> Result<()> {
+        instructions::set_transfer_fee(ctx, transfer_fee_lamports)
+    }
+
+    pub fn set_resale_split(
+        ctx: Context<SetResaleSplit>,
+        seller_bps: u16,
+        organizer_bps: u16,
+        protocol_bps: u16,
+    ) -> Result<()> {
+        instructions::set_resale_split(ctx, seller_bps, organizer_bps, protocol_bps)
+    }
+
+    pub fn freeze_transfers(ctx: Context<FreezeTransfers>) -> Result<()> {
+        instructions::freeze_transfers(ctx)
+    }
+
+    pub fn unfreeze_transfers(ctx: Context<FreezeTransfers>) -> Result<()> {
+        instructions::unfreeze_transfers(ctx)
+    }
+
+    pub fn check_in(ctx: Context<CheckIn>, scan_nonce: u64) -> Result<()> {
+        instructions::check_in(ctx, scan_nonce)
+    }
+
+    pub fn check_in_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CheckInBatch<'info>>,
+        scan_nonces: Vec<u64>,
+    ) -> Result<()> {
+        instructions::check_in_batch(ctx, scan_nonces)
+    }
+
+    pub fn check_in_with_voucher(ctx: Context<CheckInWithVoucher>, nonce: u64) -> Result<()> {
+        instructions::check_in_with_voucher(ctx, nonce)
+    }
+
+    pub fn set_doors_window(
+        ctx: Context<SetDoorsWindow>,
+        doors_open: i64,
+        doors_close: i64,
+    ) -> Result<()> {
+        instructions::set_doors_window(ctx, doors_open, doors_close)
+    }
+
+    pub fn set_max_check_ins(ctx: Context<SetMaxCheckIns>, max_check_ins: u16) -> Result<()> {
+        instructions::set_max_check_ins(ctx, max_check_ins)
+    }
+
+    pub fn add_staff(ctx: Context<AddStaff>, staff: Pubkey) -> Result<()> {
+        instructions::add_staff(ctx, staff)
+    }
+
+    pub fn remove_staff(ctx: Context<RemoveStaff>) -> Result<()> {
+        instructions::remove_staff(ctx)
+    }
+
+    pub fn add_co_organizer(
+        ctx: Context<AddCoOrganizer>,
+        member: Pubkey,
+        permissions: u8,
+    ) -> Result<()> {
+        instructions::add_co_organizer(ctx, member, permissions)
     }
 
-    pub fn check_in(ctx: Context<CheckIn>) -> Result<()> {
-        instructions::check_in(ctx)
+    pub fn remove_co_organizer(ctx: Context<RemoveCoOrganizer>) -> Result<()> {
+        instructions::remove_co_organizer(ctx)
     }
 
     pub fn refund(ctx: Context<Refund>) -> Result<()> {
         instructions::refund(ctx)
     }
 
+    pub fn refund_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefundBatch<'info>>,
+    ) -> Result<()> {
+        instructions::refund_batch(ctx)
+    }
+
     pub fn cancel_event(ctx: Context<CancelEvent>) -> Result<()> {
         instructions::cancel_event(ctx)
     }
+
+    pub fn set_event_authority(
+        ctx: Context<SetEventAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::set_event_authority(ctx, new_authority)
+    }
+
+    pub fn accept_event_authority(ctx: Context<AcceptEventAuthority>) -> Result<()> {
+        instructions::accept_event_authority(ctx)
+    }
+
+    pub fn set_event_multisig(
+        ctx: Context<SetEventMultisig>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::set_event_multisig(ctx, signers, threshold)
+    }
+
+    pub fn withdraw_proceeds(ctx: Context<WithdrawProceeds>, amount: u64) -> Result<()> {
+        instructions::withdraw_proceeds(ctx, amount)
+    }
+
+    pub fn set_max_per_wallet(ctx: Context<SetMaxPerWallet>, max_per_wallet: u32) -> Result<()> {
+        instructions::set_max_per_wallet(ctx, max_per_wallet)
+    }
+
+    pub fn set_allowlist_root(
+        ctx: Context<SetAllowlistRoot>,
+        allowlist_root: [u8; 32],
+    ) -> Result<()> {
+        instructions::set_allowlist_root(ctx, allowlist_root)
+    }
+
+    pub fn create_promo(
+        ctx: Context<CreatePromo>,
+        code: String,
+        discount_bps: u16,
+        max_uses: u32,
+    ) -> Result<()> {
+        instructions::create_promo(ctx, code, discount_bps, max_uses)
+    }
+
+    pub fn mint_with_promo(ctx: Context<MintWithPromo>, code: String) -> Result<()> {
+        instructions::mint_with_promo(ctx, code)
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        instructions::register_referrer(ctx)
+    }
+
+    pub fn set_referral_bps(ctx: Context<SetReferralBps>, referral_bps: u16) -> Result<()> {
+        instructions::set_referral_bps(ctx, referral_bps)
+    }
+
+    pub fn issue_comp_ticket(
+        ctx: Context<IssueCompTicket>,
+        recipient: Pubkey,
+        attributes: Vec<u8>,
+    ) -> Result<()> {
+        instructions::issue_comp_ticket(ctx, recipient, attributes)
+    }
+
+    pub fn set_ticket_attributes(
+        ctx: Context<SetTicketAttributes>,
+        attributes: Vec<u8>,
+    ) -> Result<()> {
+        instructions::set_ticket_attributes(ctx, attributes)
+    }
+
+    pub fn join_waitlist(ctx: Context<JoinWaitlist>) -> Result<()> {
+        instructions::join_waitlist(ctx)
+    }
+
+    pub fn claim_from_waitlist(ctx: Context<ClaimFromWaitlist>) -> Result<()> {
+        instructions::claim_from_waitlist(ctx)
+    }
+
+    pub fn leave_waitlist(ctx: Context<LeaveWaitlist>) -> Result<()> {
+        instructions::leave_waitlist(ctx)
+    }
+
+    pub fn skip_waitlist_entry(ctx: Context<SkipWaitlistEntry>) -> Result<()> {
+        instructions::skip_waitlist_entry(ctx)
+    }
+
+    pub fn reserve_ticket(ctx: Context<ReserveTicket>) -> Result<()> {
+        instructions::reserve_ticket(ctx)
+    }
+
+    pub fn finalize_reservation(ctx: Context<FinalizeReservation>) -> Result<()> {
+        instructions::finalize_reservation(ctx)
+    }
+
+    pub fn purge_reservation(ctx: Context<PurgeReservation>) -> Result<()> {
+        instructions::purge_reservation(ctx)
+    }
+
+    pub fn purge_expired<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PurgeExpired<'info>>,
+    ) -> Result<()> {
+        instructions::purge_expired(ctx)
+    }
+
+    pub fn set_dutch_auction(
+        ctx: Context<SetDutchAuction>,
+        start_price: u64,
+        floor_price: u64,
+        auction_start: i64,
+        auction_end: i64,
+    ) -> Result<()> {
+        instructions::set_dutch_auction(ctx, start_price, floor_price, auction_start, auction_end)
+    }
+
+    pub fn set_price_curve(
+        ctx: Context<SetPriceCurve>,
+        step_lamports: u64,
+        step_size: u32,
+    ) -> Result<()> {
+        instructions::set_price_curve(ctx, step_lamports, step_size)
+    }
+
+    pub fn create_auction(
+        ctx: Context<CreateAuction>,
+        seats: u32,
+        min_bid: u64,
+        close_at: i64,
+        reveal_close_at: i64,
+    ) -> Result<()> {
+        instructions::create_auction(ctx, seats, min_bid, close_at, reveal_close_at)
+    }
+
+    pub fn place_bid(ctx: Context<PlaceBid>, commitment: [u8; 32], deposit: u64) -> Result<()> {
+        instructions::place_bid(ctx, commitment, deposit)
+    }
+
+    pub fn withdraw_bid(ctx: Context<WithdrawBid>) -> Result<()> {
+        instructions::withdraw_bid(ctx)
+    }
+
+    pub fn reveal_bid(ctx: Context<RevealBid>, amount: u64, salt: [u8; 32]) -> Result<()> {
+        instructions::reveal_bid(ctx, amount, salt)
+    }
+
+    pub fn close_auction(ctx: Context<CloseAuction>) -> Result<()> {
+        instructions::close_auction(ctx)
+    }
+
+    pub fn settle_bid(ctx: Context<SettleBid>) -> Result<()> {
+        instructions::settle_bid(ctx)
+    }
+
+    pub fn create_raffle(ctx: Context<CreateRaffle>, seats: u32, close_at: i64) -> Result<()> {
+        instructions::create_raffle(ctx, seats, close_at)
+    }
+
+    pub fn enter_raffle(ctx: Context<EnterRaffle>) -> Result<()> {
+        instructions::enter_raffle(ctx)
+    }
+
+    pub fn commit_raffle(ctx: Context<CommitRaffle>) -> Result<()> {
+        instructions::commit_raffle(ctx)
+    }
+
+    pub fn draw_raffle(ctx: Context<DrawRaffle>) -> Result<()> {
+        instructions::draw_raffle(ctx)
+    }
+
+    pub fn cancel_raffle(ctx: Context<CancelRaffle>) -> Result<()> {
+        instructions::cancel_raffle(ctx)
+    }
+
+    pub fn settle_raffle_entry(ctx: Context<SettleRaffleEntry>) -> Result<()> {
+        instructions::settle_raffle_entry(ctx)
+    }
+
+    pub fn create_purchase_queue(
+        ctx: Context<CreatePurchaseQueue>,
+        seats: u32,
+        commit_close_at: i64,
+        reveal_close_at: i64,
+    ) -> Result<()> {
+        instructions::create_purchase_queue(ctx, seats, commit_close_at, reveal_close_at)
+    }
+
+    pub fn commit_purchase(ctx: Context<CommitPurchase>, commitment: [u8; 32]) -> Result<()> {
+        instructions::commit_purchase(ctx, commitment)
+    }
+
+    pub fn close_purchase_queue(ctx: Context<ClosePurchaseQueue>) -> Result<()> {
+        instructions::close_purchase_queue(ctx)
+    }
+
+    pub fn reveal_purchase(ctx: Context<RevealPurchase>, nonce: [u8; 32]) -> Result<()> {
+        instructions::reveal_purchase(ctx, nonce)
+    }
+
+    pub fn reclaim_purchase(ctx: Context<ReclaimPurchase>) -> Result<()> {
+        instructions::reclaim_purchase(ctx)
+    }
+
+    pub fn set_usd_price(ctx: Context<SetUsdPrice>, usd_price_cents: u64) -> Result<()> {
+        instructions::set_usd_price(ctx, usd_price_cents)
+    }
+
+    pub fn set_sale_hook(ctx: Context<SetSaleHook>, hook_program: Option<Pubkey>) -> Result<()> {
+        instructions::set_sale_hook(ctx, hook_program)
+    }
+
+    pub fn set_attestor(ctx: Context<SetAttestor>, attestor: Option<Pubkey>) -> Result<()> {
+        instructions::set_attestor(ctx, attestor)
+    }
+
+    pub fn set_token_gate(
+        ctx: Context<SetTokenGate>,
+        gate_mint: Option<Pubkey>,
+        min_amount: u64,
+        collection: bool,
+    ) -> Result<()> {
+        instructions::set_token_gate(ctx, gate_mint, min_amount, collection)
+    }
+
+    pub fn set_attendance_deposit(ctx: Context<SetAttendanceDeposit>, deposit: u64) -> Result<()> {
+        instructions::set_attendance_deposit(ctx, deposit)
+    }
+
+    pub fn set_pay_what_you_want(ctx: Context<SetPayWhatYouWant>, enabled: bool) -> Result<()> {
+        instructions::set_pay_what_you_want(ctx, enabled)
+    }
+
+    pub fn issue_attestation(
+        ctx: Context<IssueAttestation>,
+        subject: Pubkey,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::issue_attestation(ctx, subject, expires_at)
+    }
+
+    pub fn revoke_attestation(ctx: Context<RevokeAttestation>) -> Result<()> {
+        instructions::revoke_attestation(ctx)
+    }
+
+    pub fn mint_ticket_with_seat(
+        ctx: Context<MintTicketWithSeat>,
+        section: u16,
+        row: u16,
+        number: u16,
+    ) -> Result<()> {
+        instructions::mint_ticket_with_seat(ctx, section, row, number)
+    }
+
+    pub fn create_series(
+        ctx: Context<CreateSeries>,
+        series_id: u32,
+        name: String,
+        price: u64,
+        supply: u32,
+        transferable: bool,
+    ) -> Result<()> {
+        instructions::create_series(ctx, series_id, name, price, supply, transferable)
+    }
+
+    pub fn create_series_event(
+        ctx: Context<CreateSeriesEvent>,
+        event_id: u32,
+        date: String,
+    ) -> Result<()> {
+        instructions::create_series_event(ctx, event_id, date)
+    }
+
+    pub fn set_pass_price(ctx: Context<SetPassPrice>, pass_price: u64) -> Result<()> {
+        instructions::set_pass_price(ctx, pass_price)
+    }
+
+    pub fn buy_pass(ctx: Context<BuyPass>) -> Result<()> {
+        instructions::buy_pass(ctx)
+    }
+
+    pub fn check_in_with_pass(ctx: Context<CheckInWithPass>) -> Result<()> {
+        instructions::check_in_with_pass(ctx)
+    }
+
+    pub fn create_tier(
+        ctx: Context<CreateTier>,
+        tier_id: u8,
+        price: u64,
+        supply: u32,
+    ) -> Result<()> {
+        instructions::create_tier(ctx, tier_id, price, supply)
+    }
+
+    pub fn upgrade_ticket(ctx: Context<ChangeTier>) -> Result<()> {
+        instructions::upgrade_ticket(ctx)
+    }
+
+    pub fn downgrade_ticket(ctx: Context<ChangeTier>) -> Result<()> {
+        instructions::downgrade_ticket(ctx)
+    }
+
+    pub fn set_tier_currency(
+        ctx: Context<SetTierCurrency>,
+        price: u64,
+        feed_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::set_tier_currency(ctx, price, feed_id)
+    }
+
+    pub fn mint_ticket_with_token(ctx: Context<MintTicketWithToken>) -> Result<()> {
+        instructions::mint_ticket_with_token(ctx)
+    }
+
+    pub fn upgrade_ticket_with_token(ctx: Context<ChangeTierWithToken>) -> Result<()> {
+        instructions::upgrade_ticket_with_token(ctx)
+    }
+
+    pub fn refund_token_payment(ctx: Context<RefundTokenPayment>) -> Result<()> {
+        instructions::refund_token_payment(ctx)
+    }
+
+    pub fn withdraw_tier_currency(ctx: Context<WithdrawTierCurrency>, amount: u64) -> Result<()> {
+        instructions::withdraw_tier_currency(ctx, amount)
+    }
+
+    pub fn set_poap_mint(ctx: Context<SetPoapMint>) -> Result<()> {
+        instructions::set_poap_mint(ctx)
+    }
+
+    pub fn open_loyalty_account(ctx: Context<OpenLoyaltyAccount>, organizer: Pubkey) -> Result<()> {
+        instructions::open_loyalty_account(ctx, organizer)
+    }
+
+    pub fn mint_with_voucher(ctx: Context<MintTicket>, price: u64, expires_at: i64) -> Result<()> {
+        instructions::mint_with_voucher(ctx, price, expires_at)
+    }
+
+    pub fn redeem_points(ctx: Context<MintTicket>, points: u64) -> Result<()> {
+        instructions::redeem_points(ctx, points)
+    }
+
+    pub fn set_payout_schedule(
+        ctx: Context<SetPayoutSchedule>,
+        end_time: i64,
+        payout_timelock_secs: i64,
+    ) -> Result<()> {
+        instructions::set_payout_schedule(ctx, end_time, payout_timelock_secs)
+    }
+
+    pub fn set_payout_tranches(
+        ctx: Context<SetPayoutTranches>,
+        tranches: Vec<PayoutTranche>,
+    ) -> Result<()> {
+        instructions::set_payout_tranches(ctx, tranches)
+    }
+
+    pub fn enable_sales_stats(ctx: Context<EnableSalesStats>) -> Result<()> {
+        instructions::enable_sales_stats(ctx)
+    }
+
+    pub fn enable_transaction_log(ctx: Context<EnableTransactionLog>) -> Result<()> {
+        instructions::enable_transaction_log(ctx)
+    }
+
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        instructions::claim_refund(ctx)
+    }
+
+    pub fn cancel_ticket(ctx: Context<CancelTicket>) -> Result<()> {
+        instructions::cancel_ticket(ctx)
+    }
+
+    pub fn set_dispute_window(
+        ctx: Context<SetDisputeWindow>,
+        dispute_window_secs: i64,
+    ) -> Result<()> {
+        instructions::set_dispute_window(ctx, dispute_window_secs)
+    }
+
+    pub fn finalize_event(ctx: Context<FinalizeEvent>) -> Result<()> {
+        instructions::finalize_event(ctx)
+    }
+
+    pub fn pause_sales(ctx: Context<SetSalesPaused>) -> Result<()> {
+        instructions::pause_sales(ctx)
+    }
+
+    pub fn resume_sales(ctx: Context<SetSalesPaused>) -> Result<()> {
+        instructions::resume_sales(ctx)
+    }
+
+    pub fn revoke_ticket(ctx: Context<RevokeTicket>) -> Result<()> {
+        instructions::revoke_ticket(ctx)
+    }
+
+    pub fn set_refund_policy(ctx: Context<SetRefundPolicy>, refund_deadline: i64) -> Result<()> {
+        instructions::set_refund_policy(ctx, refund_deadline)
+    }
+
+    pub fn set_cancellation_policy(
+        ctx: Context<SetCancellationPolicy>,
+        cutoff: i64,
+        fee_bps: u16,
+    ) -> Result<()> {
+        instructions::set_cancellation_policy(ctx, cutoff, fee_bps)
+    }
+
+    pub fn set_metadata_uri(ctx: Context<SetMetadataUri>, metadata_uri: String) -> Result<()> {
+        instructions::set_metadata_uri(ctx, metadata_uri)
+    }
+
+    pub fn update_event(
+        ctx: Context<UpdateEvent>,
+        name: Option<String>,
+        date: Option<String>,
+        metadata_uri: Option<String>,
+    ) -> Result<()> {
+        instructions::update_event(ctx, name, date, metadata_uri)
+    }
+
+    pub fn migrate_ticket(ctx: Context<MigrateTicket>) -> Result<()> {
+        instructions::migrate_ticket(ctx)
+    }
+
+    pub fn migrate_event(ctx: Context<MigrateAccount>) -> Result<()> {
+        instructions::migrate_event(ctx)
+    }
+
+    pub fn migrate_legacy_event(ctx: Context<MigrateLegacyEvent>) -> Result<()> {
+        instructions::migrate_legacy_event(ctx)
+    }
+
+    pub fn migrate_organizer(ctx: Context<MigrateAccount>) -> Result<()> {
+        instructions::migrate_organizer(ctx)
+    }
+
+    pub fn decrease_supply(ctx: Context<ChangeSupply>, new_supply: u32) -> Result<()> {
+        instructions::decrease_supply(ctx, new_supply)
+    }
+
+    pub fn increase_supply(ctx: Context<ChangeSupply>, new_supply: u32) -> Result<()> {
+        instructions::increase_supply(ctx, new_supply)
+    }
+
+    pub fn emergency_cancel(ctx: Context<EmergencyCancel>) -> Result<()> {
+        instructions::emergency_cancel(ctx)
+    }
+
+    pub fn set_organizer_bond(ctx: Context<SetOrganizerBond>, bond_lamports: u64) -> Result<()> {
+        instructions::set_organizer_bond(ctx, bond_lamports)
+    }
+
+    pub fn post_bond(ctx: Context<PostBond>) -> Result<()> {
+        instructions::post_bond(ctx)
+    }
+
+    pub fn release_bond(ctx: Context<ReleaseBond>) -> Result<()> {
+        instructions::release_bond(ctx)
+    }
+
+    pub fn release_event_bond(ctx: Context<ReleaseEventBond>) -> Result<()> {
+        instructions::release_event_bond(ctx)
+    }
+
+    pub fn slash_organizer(ctx: Context<SlashOrganizer>, amount: u64) -> Result<()> {
+        instructions::slash_organizer(ctx, amount)
+    }
+
+    pub fn create_session(
+        ctx: Context<CreateSession>,
+        session_id: u16,
+        start_time: i64,
+        end_time: i64,
+        capacity: u32,
+    ) -> Result<()> {
+        instructions::create_session(ctx, session_id, start_time, end_time, capacity)
+    }
+
+    pub fn check_in_session(ctx: Context<CheckInSession>) -> Result<()> {
+        instructions::check_in_session(ctx)
+    }
 }
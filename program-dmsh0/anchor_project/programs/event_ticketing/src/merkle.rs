@@ -0,0 +1,19 @@
+use anchor_lang::solana_program::hash::hashv;
+
+/// Leaf committed to by an allowlist merkle tree for `buyer`.
+pub fn allowlist_leaf(buyer: &[u8; 32]) -> [u8; 32] {
+    hashv(&[buyer]).to_bytes()
+}
+
+/// Walks `proof` from `leaf` up to the root. Each pair is hashed in sorted
+/// order, so proofs don't need to carry left/right position flags.
+pub fn verify_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            hashv(&[&node, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &node]).to_bytes()
+        }
+    });
+    computed == root
+}
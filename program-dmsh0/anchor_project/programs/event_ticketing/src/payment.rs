@@ -0,0 +1,83 @@
+use crate::constants::VAULT_SEED;
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+/// Checks `vault_token_account` holds the event's currency on behalf of its
+/// vault PDA. Organizers create it (usually the vault's ATA) before selling.
+pub fn require_vault_token_account(
+    event: &Event,
+    vault: &Pubkey,
+    vault_token_account: &TokenAccount,
+) -> Result<()> {
+    require!(
+        event.currency() == Some(vault_token_account.mint) && vault_token_account.owner == *vault,
+        EventTicketingError::InvalidVaultTokenAccount
+    );
+    Ok(())
+}
+
+/// Rejects SPL-currency events on paths that only settle in lamports.
+pub fn require_sol_currency(event: &Event) -> Result<()> {
+    require!(
+        event.currency().is_none(),
+        EventTicketingError::CurrencyNotSupported
+    );
+    Ok(())
+}
+
+/// Moves `amount` of the event currency from `from` into the vault's token
+/// account; `authority` must have signed.
+pub fn collect_tokens<'info>(
+    from: &Account<'info, TokenAccount>,
+    vault_token_account: &Account<'info, TokenAccount>,
+    authority: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    token::transfer(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Transfer {
+                from: from.to_account_info(),
+                to: vault_token_account.to_account_info(),
+                authority: authority.clone(),
+            },
+        ),
+        amount,
+    )
+}
+
+/// Pays `amount` of the event currency out of the vault's token account,
+/// signed by the vault PDA.
+pub fn pay_out_tokens<'info>(
+    vault_token_account: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    vault: &AccountInfo<'info>,
+    vault_bump: u8,
+    event: Pubkey,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    require!(
+        amount <= vault_token_account.amount,
+        EventTicketingError::InsufficientVaultFunds
+    );
+
+    let seeds = &[VAULT_SEED, event.as_ref(), &[vault_bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: vault_token_account.to_account_info(),
+                to: to.to_account_info(),
+                authority: vault.clone(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )
+}
@@ -0,0 +1,96 @@
+use crate::constants::{
+    BUYER_RECORD_SEED, CONFIG_SEED, EVENT_CPI_AUTHORITY_SEED, EVENT_METADATA_SEED, EVENT_SEED,
+    OPERATOR_APPROVAL_SEED, ORGANIZER_SEED, PENDING_TRANSFER_SEED, RECEIPT_SEED, REFUND_POOL_SEED,
+    REGISTRY_PAGE_SEED, REGISTRY_SEED, TICKET_SEED, VAULT_SEED,
+};
+use anchor_lang::prelude::*;
+
+/// Address and bump of the event created by `event_authority` with `event_id`.
+pub fn derive_event_pda(event_authority: &Pubkey, event_id: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            EVENT_SEED,
+            event_authority.as_ref(),
+            &event_id.to_le_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+/// Address and bump of ticket `ticket_id` of `event`. The next ticket sold by
+/// `mint_ticket` is `derive_ticket_pda(event, event.next_ticket_id)`.
+pub fn derive_ticket_pda(event: &Pubkey, ticket_id: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[TICKET_SEED, event.as_ref(), &ticket_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// Address and bump of `owner`'s ticket for a one-per-wallet `event`.
+pub fn derive_wallet_ticket_pda(event: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TICKET_SEED, event.as_ref(), owner.as_ref()], &crate::ID)
+}
+
+/// Address and bump of the vault holding `event`'s funds.
+pub fn derive_vault_pda(event: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_SEED, event.as_ref()], &crate::ID)
+}
+
+/// Address and bump of `event`'s `RefundPool`, created when it is canceled.
+pub fn derive_refund_pool_pda(event: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REFUND_POOL_SEED, event.as_ref()], &crate::ID)
+}
+
+/// Address and bump of the purchase `Receipt` of `ticket`.
+pub fn derive_receipt_pda(ticket: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RECEIPT_SEED, ticket.as_ref()], &crate::ID)
+}
+
+/// Address and bump of `event`'s `EventMetadata`.
+pub fn derive_event_metadata_pda(event: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EVENT_METADATA_SEED, event.as_ref()], &crate::ID)
+}
+
+/// Address and bump of the global `Config`.
+pub fn derive_config_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED], &crate::ID)
+}
+
+/// Address and bump of `organizer`'s `OrganizerRegistry`.
+pub fn derive_organizer_pda(organizer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ORGANIZER_SEED, organizer.as_ref()], &crate::ID)
+}
+
+/// Address and bump of the `EventRegistry` head.
+pub fn derive_registry_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REGISTRY_SEED], &crate::ID)
+}
+
+/// Address and bump of registry page `page`. New events go to page
+/// `EventRegistry::current_page`.
+pub fn derive_registry_page_pda(page: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REGISTRY_PAGE_SEED, &page.to_le_bytes()], &crate::ID)
+}
+
+/// Address and bump of `buyer`'s purchase record for `event`.
+pub fn derive_buyer_record_pda(event: &Pubkey, buyer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[BUYER_RECORD_SEED, event.as_ref(), buyer.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// Address and bump of the pending transfer of `ticket`, if one was approved.
+pub fn derive_pending_transfer_pda(ticket: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PENDING_TRANSFER_SEED, ticket.as_ref()], &crate::ID)
+}
+
+/// Address and bump of the operator approval of `ticket`, if one was granted.
+pub fn derive_operator_approval_pda(ticket: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[OPERATOR_APPROVAL_SEED, ticket.as_ref()], &crate::ID)
+}
+
+/// Address and bump of the PDA signing event self-CPIs.
+pub fn derive_event_cpi_authority_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EVENT_CPI_AUTHORITY_SEED], &crate::ID)
+}
@@ -0,0 +1,152 @@
+use crate::constants::BPS_DENOMINATOR;
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::pubkey;
+
+/// Pyth Solana Receiver program, owner of `PriceUpdateV2` accounts.
+pub const PYTH_RECEIVER_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LyFJ");
+
+/// Pyth price feed id for SOL/USD.
+pub const SOL_USD_FEED_ID: [u8; 32] = [
+    0xef, 0x0d, 0x8b, 0x6f, 0xda, 0x2c, 0xeb, 0xa4, 0x1d, 0xa1, 0x5d, 0x40, 0x95, 0xd1, 0xda, 0x39,
+    0x2a, 0x0d, 0x2f, 0x8e, 0xd0, 0xc6, 0xc7, 0xbc, 0x0f, 0x4c, 0xfa, 0xc8, 0xc2, 0x80, 0xb5, 0x6d,
+];
+
+/// Oldest price update accepted at purchase time.
+pub const MAX_PRICE_AGE_SECS: i64 = 60;
+
+// Lamports per SOL divided by cents per dollar.
+const CENTS_TO_LAMPORTS_SCALE: u128 = 10_000_000;
+
+// Layout of a `PriceUpdateV2` account after the 8-byte discriminator:
+// [write_authority: 32, verification_level: enum (1 byte, +1 for Partial),
+//  feed_id: 32, price: i64, conf: u64, exponent: i32, publish_time: i64, ...]
+const VERIFICATION_OFFSET: usize = 8 + 32;
+const VERIFICATION_FULL: u8 = 1;
+const MESSAGE_LEN: usize = 32 + 8 + 8 + 4 + 8;
+
+/// Reads a fully verified, fresh SOL/USD price from a Pyth `PriceUpdateV2`
+/// account. Returns `(price, exponent)`.
+pub fn sol_usd_price(price_update: &AccountInfo, now: i64) -> Result<(i64, i32)> {
+    feed_price(price_update, &SOL_USD_FEED_ID, now)
+}
+
+/// Reads a fully verified, fresh price for `feed_id` from a Pyth
+/// `PriceUpdateV2` account. Returns `(price, exponent)`.
+pub fn feed_price(price_update: &AccountInfo, feed_id: &[u8; 32], now: i64) -> Result<(i64, i32)> {
+    require_keys_eq!(
+        *price_update.owner,
+        PYTH_RECEIVER_ID,
+        EventTicketingError::InvalidPriceFeed
+    );
+
+    let data = price_update.try_borrow_data()?;
+    let discriminator = hash(b"account:PriceUpdateV2").to_bytes();
+    require!(
+        data.len() > VERIFICATION_OFFSET + MESSAGE_LEN && data[..8] == discriminator[..8],
+        EventTicketingError::InvalidPriceFeed
+    );
+    require!(
+        data[VERIFICATION_OFFSET] == VERIFICATION_FULL,
+        EventTicketingError::InvalidPriceFeed
+    );
+
+    let message = &data[VERIFICATION_OFFSET + 1..VERIFICATION_OFFSET + 1 + MESSAGE_LEN];
+    require!(
+        message[..32] == feed_id[..],
+        EventTicketingError::InvalidPriceFeed
+    );
+
+    let price = i64::from_le_bytes(message[32..40].try_into().unwrap());
+    let exponent = i32::from_le_bytes(message[48..52].try_into().unwrap());
+    let publish_time = i64::from_le_bytes(message[52..60].try_into().unwrap());
+
+    require!(price > 0, EventTicketingError::InvalidPriceFeed);
+    require!(
+        now - publish_time <= MAX_PRICE_AGE_SECS,
+        EventTicketingError::StalePrice
+    );
+
+    Ok((price, exponent))
+}
+
+/// Lamport price of the event's next ticket: converted from `usd_price_cents`
+/// through the Pyth feed for USD-priced events, otherwise the event's
+/// lamport pricing.
+pub fn sale_price(event: &Event, price_update: Option<&AccountInfo>, now: i64) -> Result<u64> {
+    if event.usd_price_cents == 0 {
+        return Ok(event.current_price(now));
+    }
+    cents_in_lamports(event.usd_price_cents, price_update, now)
+}
+
+/// Lamport price of `cents` at the current SOL/USD rate read from
+/// `price_update`, which must be supplied.
+pub fn cents_in_lamports(cents: u64, price_update: Option<&AccountInfo>, now: i64) -> Result<u64> {
+    let price_update = price_update.ok_or(EventTicketingError::MissingPriceFeed)?;
+    let (price, exponent) = sol_usd_price(price_update, now)?;
+    usd_cents_to_lamports(cents, price, exponent)
+}
+
+/// Converts a USD amount in cents to lamports at `price * 10^exponent` USD/SOL.
+pub fn usd_cents_to_lamports(cents: u64, price: i64, exponent: i32) -> Result<u64> {
+    let scale = 10u128
+        .checked_pow(exponent.unsigned_abs())
+        .ok_or(EventTicketingError::Overflow)?;
+    let (numerator, denominator) = if exponent <= 0 {
+        (
+            cents as u128 * CENTS_TO_LAMPORTS_SCALE * scale,
+            price as u128,
+        )
+    } else {
+        (
+            cents as u128 * CENTS_TO_LAMPORTS_SCALE,
+            price as u128 * scale,
+        )
+    };
+
+    u64::try_from(numerator / denominator).map_err(|_| error!(EventTicketingError::Overflow))
+}
+
+/// Checks `a` and `b` are worth the same in USD to within
+/// `max_deviation_bps` of `b`. Each side is `(amount, decimals, price,
+/// exponent)`: an amount in base units of a token with `decimals`, priced at
+/// `price * 10^exponent` USD per whole token.
+pub fn require_equivalent(
+    a: (u64, u8, i64, i32),
+    b: (u64, u8, i64, i32),
+    max_deviation_bps: u64,
+) -> Result<()> {
+    // Value of each side as `mantissa * 10^scale` USD.
+    let value = |(amount, decimals, price, exponent): (u64, u8, i64, i32)| {
+        (amount as u128 * price as u128, exponent - decimals as i32)
+    };
+    let (mut a_value, a_scale) = value(a);
+    let (mut b_value, b_scale) = value(b);
+
+    let shift = 10u128
+        .checked_pow(a_scale.abs_diff(b_scale))
+        .ok_or(EventTicketingError::Overflow)?;
+    if a_scale > b_scale {
+        a_value = a_value
+            .checked_mul(shift)
+            .ok_or(EventTicketingError::Overflow)?;
+    } else {
+        b_value = b_value
+            .checked_mul(shift)
+            .ok_or(EventTicketingError::Overflow)?;
+    }
+
+    let deviation = a_value
+        .abs_diff(b_value)
+        .checked_mul(BPS_DENOMINATOR as u128)
+        .ok_or(EventTicketingError::Overflow)?;
+    require!(
+        deviation <= b_value * max_deviation_bps as u128,
+        EventTicketingError::PriceNotEquivalent
+    );
+
+    Ok(())
+}
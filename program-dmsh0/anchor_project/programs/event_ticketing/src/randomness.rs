@@ -0,0 +1,76 @@
+use crate::errors::EventTicketingError;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::pubkey;
+
+/// Switchboard On-Demand program, owner of randomness accounts.
+pub const SWITCHBOARD_ON_DEMAND_ID: Pubkey = pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+
+// Layout of Switchboard's `RandomnessAccountData` after the 8-byte discriminator:
+// [authority: 32, queue: 32, seed_slothash: 32, seed_slot: u64,
+//  oracle: 32, reveal_slot: u64, value: 32, ...]
+const SEED_SLOT_OFFSET: usize = 8 + 32 + 32 + 32;
+const REVEAL_SLOT_OFFSET: usize = SEED_SLOT_OFFSET + 8 + 32;
+const VALUE_OFFSET: usize = REVEAL_SLOT_OFFSET + 8;
+const VALUE_LEN: usize = 32;
+
+fn randomness_data<'a>(randomness: &'a AccountInfo) -> Result<std::cell::Ref<'a, &'a mut [u8]>> {
+    require_keys_eq!(
+        *randomness.owner,
+        SWITCHBOARD_ON_DEMAND_ID,
+        EventTicketingError::InvalidRandomnessAccount
+    );
+
+    let data = randomness.try_borrow_data()?;
+    let discriminator = hash(b"account:RandomnessAccountData").to_bytes();
+    require!(
+        data.len() >= VALUE_OFFSET + VALUE_LEN && data[..8] == discriminator[..8],
+        EventTicketingError::InvalidRandomnessAccount
+    );
+
+    Ok(data)
+}
+
+fn read_u64(data: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(data[at..at + 8].try_into().unwrap())
+}
+
+/// Checks that `randomness` was committed in the previous slot and has not
+/// been revealed yet, so nobody can know its value at commit time. Returns
+/// the seed slot, which the caller stores to pin this exact commit.
+pub fn require_fresh_commit(randomness: &AccountInfo, clock: &Clock) -> Result<u64> {
+    let data = randomness_data(randomness)?;
+    let seed_slot = read_u64(&data, SEED_SLOT_OFFSET);
+
+    require!(
+        seed_slot == clock.slot.saturating_sub(1),
+        EventTicketingError::RandomnessExpired
+    );
+    require!(
+        read_u64(&data, REVEAL_SLOT_OFFSET) == 0,
+        EventTicketingError::RandomnessAlreadyRevealed
+    );
+
+    Ok(seed_slot)
+}
+
+/// Returns the revealed value of `randomness`. Like Switchboard's own
+/// `get_value`, it must be consumed in the slot it was revealed. The account
+/// must still hold the commit made at `seed_slot`; Switchboard lets its
+/// authority re-commit, which would otherwise allow rerolling the value.
+pub fn revealed_value(randomness: &AccountInfo, clock: &Clock, seed_slot: u64) -> Result<[u8; 32]> {
+    let data = randomness_data(randomness)?;
+
+    require!(
+        read_u64(&data, SEED_SLOT_OFFSET) == seed_slot,
+        EventTicketingError::RandomnessReseeded
+    );
+    require!(
+        read_u64(&data, REVEAL_SLOT_OFFSET) == clock.slot,
+        EventTicketingError::RandomnessNotResolved
+    );
+
+    let mut value = [0u8; VALUE_LEN];
+    value.copy_from_slice(&data[VALUE_OFFSET..VALUE_OFFSET + VALUE_LEN]);
+    Ok(value)
+}
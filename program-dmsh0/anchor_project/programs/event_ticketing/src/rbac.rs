@@ -0,0 +1,117 @@
+//! Role-based access control for event management. Each management action
+//! needs a `Permission`, which `has_permission` checks against the roles the
+//! signer holds on the event:
+//!
+//! - `Role::Admin`: the event authority, or a quorum of its signers on
+//!   multisig events; holds every permission.
+//! - `Role::Finance`: teammates trusted with money, i.e. refunds and
+//!   withdrawals.
+//! - `Role::Staff`: door workers delegated via `add_staff`; check tickets in.
+//!
+//! Co-organizers hold any mix of the delegable permissions, with Finance and
+//! Staff as the usual presets for `add_co_organizer`.
+
+use crate::authority::require_event_authority;
+use crate::errors::EventTicketingError;
+use crate::state::{CoOrganizer, Event, EventAuthorityConfig, Staff};
+use anchor_lang::prelude::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Permission {
+    CheckIn,
+    Refund,
+    Update,
+    Withdraw,
+    Cancel,
+}
+
+impl Permission {
+    /// `CoOrganizer::permissions` bit delegating this permission; `None` for
+    /// actions only the admin may take.
+    pub fn bit(self) -> Option<u8> {
+        match self {
+            Permission::CheckIn => Some(CoOrganizer::CAN_CHECK_IN),
+            Permission::Refund => Some(CoOrganizer::CAN_REFUND),
+            Permission::Update => Some(CoOrganizer::CAN_UPDATE),
+            Permission::Withdraw => Some(CoOrganizer::CAN_WITHDRAW),
+            Permission::Cancel => None,
+        }
+    }
+
+    /// Whether the admin needs a multisig quorum rather than the event
+    /// authority's signature alone. Day-to-day actions never did.
+    fn needs_quorum(self) -> bool {
+        !matches!(self, Permission::CheckIn | Permission::Update)
+    }
+
+    fn denied(self) -> EventTicketingError {
+        match self {
+            Permission::CheckIn => EventTicketingError::UnauthorizedCheckIn,
+            _ => EventTicketingError::UnauthorizedEventAuthority,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    Admin,
+    Finance,
+    Staff,
+}
+
+impl Role {
+    /// Delegable permissions of the role, as `CoOrganizer::permissions` bits.
+    pub const fn permissions(self) -> u8 {
+        match self {
+            Role::Admin => CoOrganizer::ALL_PERMISSIONS,
+            Role::Finance => CoOrganizer::CAN_REFUND | CoOrganizer::CAN_WITHDRAW,
+            Role::Staff => CoOrganizer::CAN_CHECK_IN,
+        }
+    }
+}
+
+/// Accounts an instruction was given that can prove the signer's role. Each
+/// record's event is checked by the instruction's constraints.
+#[derive(Default)]
+pub struct Delegations<'a, 'info> {
+    /// Multisig signer set, for the admin of multisig events.
+    pub authority_config: Option<&'a EventAuthorityConfig>,
+    /// Extra approving signers counted towards the multisig quorum.
+    pub cosigners: &'a [AccountInfo<'info>],
+    pub staff: Option<&'a Staff>,
+    pub co_organizer: Option<&'a CoOrganizer>,
+}
+
+/// Checks `signer` holds `permission` on `event`, through a delegation
+/// record or as the admin.
+pub fn has_permission(
+    event: &Event,
+    signer: &Pubkey,
+    permission: Permission,
+    delegations: &Delegations,
+) -> Result<()> {
+    let delegated = permission.bit().is_some_and(|bit| {
+        let is_staff = delegations
+            .staff
+            .is_some_and(|staff| staff.staff == *signer && Role::Staff.permissions() & bit != 0);
+        let is_co_organizer = delegations
+            .co_organizer
+            .is_some_and(|co_organizer| co_organizer.member == *signer && co_organizer.can(bit));
+        is_staff || is_co_organizer
+    });
+    if delegated {
+        return Ok(());
+    }
+
+    if !permission.needs_quorum() {
+        require!(*signer == event.event_authority, permission.denied());
+        return Ok(());
+    }
+
+    require_event_authority(
+        event,
+        signer,
+        delegations.authority_config,
+        delegations.cosigners,
+    )
+}
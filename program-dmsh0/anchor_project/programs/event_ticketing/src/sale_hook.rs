@@ -0,0 +1,86 @@
+//! Gate-check hooks. An organizer can point `Event::hook_program` at a
+//! program of their own that decides who may buy (DAO membership, geo
+//! attestations, ...). `mint_ticket` and `mint_ticket_to` call it before
+//! every sale; the sale fails if the hook returns an error.
+//!
+//! The hook is invoked with the instruction Anchor generates for
+//! `check_sale(owner: Pubkey, price: u64)`:
+//!
+//! - data: `CHECK_SALE_DISCRIMINATOR || owner || price (u64 LE)`
+//! - accounts: the event (read-only), the buyer (read-only, signer), then
+//!   every account passed in the mint's `remaining_accounts`, as given.
+//!
+//! Other sale paths cannot run the hook, so they are closed for hooked events.
+
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+
+/// `sha256("global:check_sale")[..8]`, Anchor's discriminator for a
+/// `check_sale` instruction.
+pub const CHECK_SALE_DISCRIMINATOR: [u8; 8] = [209, 3, 130, 79, 180, 111, 1, 31];
+
+/// Rejects hooked events on sale paths that do not call the hook.
+pub fn require_no_sale_hook(event: &Event) -> Result<()> {
+    require!(
+        event.hook_program().is_none(),
+        EventTicketingError::SaleHookNotSupported
+    );
+    Ok(())
+}
+
+/// Asks the event's hook program whether `owner` may get a ticket for
+/// `price`. Does nothing for events without a hook.
+pub fn run_sale_hook<'info>(
+    event: &AccountLoader<'info, Event>,
+    hook_program: Option<&UncheckedAccount<'info>>,
+    buyer: &Signer<'info>,
+    hook_accounts: &[AccountInfo<'info>],
+    owner: Pubkey,
+    price: u64,
+) -> Result<()> {
+    let Some(hook) = event.load()?.hook_program() else {
+        return Ok(());
+    };
+    let hook_program = hook_program.ok_or(EventTicketingError::SaleHookMissing)?;
+    require_keys_eq!(
+        hook_program.key(),
+        hook,
+        EventTicketingError::InvalidSaleHook
+    );
+
+    let mut data = Vec::with_capacity(8 + 32 + 8);
+    data.extend_from_slice(&CHECK_SALE_DISCRIMINATOR);
+    data.extend_from_slice(owner.as_ref());
+    data.extend_from_slice(&price.to_le_bytes());
+
+    let mut metas = vec![
+        AccountMeta::new_readonly(event.key(), false),
+        AccountMeta::new_readonly(buyer.key(), true),
+    ];
+    let mut infos = vec![event.to_account_info(), buyer.to_account_info()];
+    for account in hook_accounts {
+        metas.push(if account.is_writable {
+            AccountMeta::new(account.key(), account.is_signer)
+        } else {
+            AccountMeta::new_readonly(account.key(), account.is_signer)
+        });
+        infos.push(account.clone());
+    }
+    infos.push(hook_program.to_account_info());
+
+    invoke(
+        &Instruction {
+            program_id: hook,
+            accounts: metas,
+            data,
+        },
+        &infos,
+    )?;
+
+    msg!("Sale hook {} approved ticket for {}", hook, owner);
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+//! Daily sales analytics. Events that opted in with `enable_sales_stats`
+//! keep a `SalesStats` account, which every instruction issuing or refunding
+//! tickets takes as `sales_stats` and updates through these helpers.
+
+use crate::errors::EventTicketingError;
+use crate::state::{Event, SalesStats};
+use anchor_lang::prelude::*;
+
+/// Adds `count` tickets sold for `amount` in total to today's bucket. Does
+/// nothing for events without stats.
+pub fn record_daily_sales(
+    event: &Event,
+    sales_stats: Option<&mut SalesStats>,
+    count: u32,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    let Some(today) = stats_for(event, sales_stats)?.map(|stats| stats.today(now)) else {
+        return Ok(());
+    };
+    today.sold = today
+        .sold
+        .checked_add(count)
+        .ok_or(EventTicketingError::Overflow)?;
+    today.gross_sales = today
+        .gross_sales
+        .checked_add(amount)
+        .ok_or(EventTicketingError::Overflow)?;
+    Ok(())
+}
+
+/// Adds `count` tickets refunded for `amount` in total to today's bucket.
+/// Does nothing for events without stats.
+pub fn record_daily_refunds(
+    event: &Event,
+    sales_stats: Option<&mut SalesStats>,
+    count: u32,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    let Some(today) = stats_for(event, sales_stats)?.map(|stats| stats.today(now)) else {
+        return Ok(());
+    };
+    today.refunded = today
+        .refunded
+        .checked_add(count)
+        .ok_or(EventTicketingError::Overflow)?;
+    today.refunds = today
+        .refunds
+        .checked_add(amount)
+        .ok_or(EventTicketingError::Overflow)?;
+    Ok(())
+}
+
+/// The event's stats account, which must be passed once it keeps one. The
+/// account's address is checked by the instruction's constraints.
+fn stats_for<'a>(
+    event: &Event,
+    sales_stats: Option<&'a mut SalesStats>,
+) -> Result<Option<&'a mut SalesStats>> {
+    if event.sales_stats().is_none() {
+        return Ok(None);
+    }
+    sales_stats
+        .map(Some)
+        .ok_or(error!(EventTicketingError::InvalidSalesStats))
+}
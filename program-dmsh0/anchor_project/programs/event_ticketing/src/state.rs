@@ -1,21 +1,520 @@
+use crate::constants::{
+    BPS_DENOMINATOR, DEFAULT_DISPUTE_WINDOW_SECS, DEFAULT_PAYOUT_TIMELOCK_SECS, EVENTS_PER_PAGE,
+    EVENT_DISCRIMINATOR, EVENT_VERSION, LAMPORTS_PER_LOYALTY_POINT, MAX_APPROVED_VENUES,
+    MAX_AUCTION_SEATS, MAX_DATE_LEN, MAX_METADATA_URI_LEN, MAX_MULTISIG_SIGNERS, MAX_NAME_LEN,
+    MAX_PAYOUT_TRANCHES, MAX_PROMO_CODE_LEN, MAX_SEATS, MAX_TICKET_ATTRIBUTES_LEN,
+    SALES_STATS_DAYS, SECONDS_PER_DAY, TICKET_VERSION, TRANSACTION_LOG_LEN,
+};
+use crate::errors::EventTicketingError;
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use bytemuck::Zeroable;
 
-#[account]
+/// Variable-length text lives in the event's `EventMetadata`, so the fields
+/// sit at fixed offsets usable as `getProgramAccounts` memcmp filters.
+///
+/// Zero-copy so mint and check-in paths read the event in place instead of
+/// deserializing and copying the whole account. Fields are grouped by
+/// alignment, widest first, so the `repr(C)` layout has no implicit padding;
+/// flags are `u8`s and unset keys are `Pubkey::default()`, read back through
+/// the methods of the same name.
+#[account(zero_copy, discriminator = &EVENT_DISCRIMINATOR)]
 pub struct Event {
     pub event_authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub allowlist_root: [u8; 32],
+    pub seat_map: Pubkey,
+    pub series: Pubkey,
+    pub poap_mint: Pubkey,
+    pub bond_organizer: Pubkey,
+    pub currency: Pubkey,
+    /// Program `mint_ticket` calls before each sale; see `sale_hook`.
+    pub hook_program: Pubkey,
+    /// Key whose `Attestation` (KYC, age check) a ticket owner needs before
+    /// buying; see `attestation`.
+    pub attestor: Pubkey,
+    /// Token buyers must hold, or NFT collection with `gate_collection`;
+    /// see `token_gate`.
+    pub gate_mint: Pubkey,
+    /// Tranche schedule replacing the payout timelock; see `PayoutConfig`.
+    pub payout_config: Pubkey,
+    /// Marketplaces that must co-sign `list_ticket` and `buy_listed_ticket`,
+    /// of which the first `approved_venue_count` are set; none allows resale
+    /// anywhere. See `set_approved_venues`.
+    pub approved_venues: [Pubkey; MAX_APPROVED_VENUES],
+    /// Daily sales buckets kept for dashboards; see `enable_sales_stats`.
+    pub sales_stats: Pubkey,
+    /// Log of recent vault movements; see `enable_transaction_log`.
+    pub transaction_log: Pubkey,
     pub price: u64,
+    pub doors_open: i64,
+    pub doors_close: i64,
+    pub transfer_fee_lamports: u64,
+    pub auction_start_price: u64,
+    pub auction_floor_price: u64,
+    pub auction_start: i64,
+    pub auction_end: i64,
+    pub curve_step_lamports: u64,
+    pub usd_price_cents: u64,
+    pub end_time: i64,
+    pub payout_timelock_secs: i64,
+    pub finalized_at: i64,
+    pub dispute_window_secs: i64,
+    pub final_revenue: u64,
+    pub canceled_at: i64,
+    pub refund_deadline: i64,
+    pub gate_min_amount: u64,
+    /// Refundable lamports a free event charges per ticket instead of a
+    /// price, returned at first check-in; see `set_attendance_deposit`.
+    pub attendance_deposit: u64,
+    /// Total paid above the price through `mint_ticket_with_amount`.
+    pub donations: u64,
+    /// Revenue totals for reconciliation, in the event's currency; see
+    /// `record_sale` and `record_transfer`. Sales before layout v9 are not
+    /// included.
+    pub gross_sales: u64,
+    pub referral_fees: u64,
+    pub protocol_fees: u64,
+    pub organizer_net: u64,
+    /// Transfer fees are always lamports.
+    pub transfer_fees: u64,
+    pub resale_volume: u64,
+    /// Holders may return unused tickets with `cancel_ticket` until this
+    /// time, less `cancellation_fee_bps` of what they paid; zero disables
+    /// it. See `set_cancellation_policy`.
+    pub cancellation_cutoff: i64,
+    /// Organizer and protocol shares of resales so far, in lamports.
+    pub resale_organizer_fees: u64,
+    pub resale_protocol_fees: u64,
     pub supply: u32,
     pub sold: u32,
-    pub canceled: bool,
     pub event_id: u32,
-    pub name: String,
-    pub date: String,
+    pub max_per_wallet: u32,
+    pub waitlist_len: u32,
+    pub waitlist_next: u32,
+    pub freed_seats: u32,
+    pub reserved: u32,
+    pub curve_step_size: u32,
+    pub checked_in_count: u32,
+    pub refunded_count: u32,
+    pub no_show_count: u32,
+    /// Id of the next ticket issued, and the seed of its Ticket PDA. Unlike
+    /// `sold` it only ever counts up, so ticket ids are never reused.
+    pub next_ticket_id: u32,
+    /// Ceiling fixed at creation that `increase_supply` can never raise
+    /// `supply` past.
+    pub max_total_supply: u32,
+    /// Seats returned by refunds and issued again, through the waitlist or
+    /// back on sale after `cancel_ticket`. They count towards `sold` a second
+    /// time, so they extend the capacity `supply` leaves for sale without
+    /// changing `supply` itself.
+    pub reissued_seats: u32,
+    pub max_check_ins: u16,
+    pub max_resale_multiplier_bps: u16,
+    pub referral_bps: u16,
+    pub cancellation_fee_bps: u16,
+    /// How a resale price is split between seller, organizer (paid into the
+    /// vault) and protocol (paid to the config admin); sums to 100%. See
+    /// `set_resale_split`.
+    pub resale_seller_bps: u16,
+    pub resale_organizer_bps: u16,
+    pub resale_protocol_bps: u16,
+    pub canceled: u8,
+    pub multisig_enabled: u8,
+    pub transferable: u8,
+    pub transfers_frozen: u8,
+    pub sales_paused: u8,
+    pub withdrawals_frozen: u8,
+    /// Layout version; see `EVENT_VERSION` and `migrate_event`.
+    pub version: u8,
+    /// Strict anti-scalping mode: Ticket PDAs are seeded by the buyer's
+    /// wallet instead of the ticket id, so each wallet can buy one ticket.
+    pub one_per_wallet: u8,
+    pub gate_collection: u8,
+    /// Buyers may pay above the price with `mint_ticket_with_amount`.
+    pub pay_what_you_want: u8,
+    pub approved_venue_count: u8,
+    pub _padding: [u8; 3],
+}
+
+/// `Pubkey::default()` marks an unset key in zero-copy accounts.
+fn key_if_set(key: Pubkey) -> Option<Pubkey> {
+    (key != Pubkey::default()).then_some(key)
 }
 
 impl Event {
-    pub fn space(max_name_len: usize, max_date_len: usize) -> usize {
-        8 + 32 + 8 + 4 + 4 + 1 + 4 + 4 + max_name_len + 4 + max_date_len
+    /// Account byte offsets, discriminator included, for memcmp filters.
+    pub const AUTHORITY_OFFSET: usize = 8;
+    pub const CANCELED_OFFSET: usize = 8 + std::mem::offset_of!(Event, canceled);
+
+    pub const SPACE: usize =
+        8 + 32 * 14 + 32 * MAX_APPROVED_VENUES + 8 * 29 + 4 * 15 + 2 * 7 + 11 + 3;
+
+    /// Resets every field to its default for a freshly created event. Price,
+    /// supply and transferability are left for the caller to set.
+    pub fn init(&mut self, event_authority: Pubkey, event_id: u32) {
+        *self = Zeroable::zeroed();
+        self.event_authority = event_authority;
+        self.event_id = event_id;
+        self.max_check_ins = 1;
+        self.transferable = 1;
+        self.payout_timelock_secs = DEFAULT_PAYOUT_TIMELOCK_SECS;
+        self.dispute_window_secs = DEFAULT_DISPUTE_WINDOW_SECS;
+        self.version = EVENT_VERSION;
+        self.resale_seller_bps = BPS_DENOMINATOR as u16;
+    }
+
+    pub fn canceled(&self) -> bool {
+        self.canceled != 0
+    }
+
+    pub fn multisig_enabled(&self) -> bool {
+        self.multisig_enabled != 0
+    }
+
+    pub fn transferable(&self) -> bool {
+        self.transferable != 0
+    }
+
+    pub fn transfers_frozen(&self) -> bool {
+        self.transfers_frozen != 0
+    }
+
+    pub fn sales_paused(&self) -> bool {
+        self.sales_paused != 0
+    }
+
+    pub fn withdrawals_frozen(&self) -> bool {
+        self.withdrawals_frozen != 0
+    }
+
+    pub fn one_per_wallet(&self) -> bool {
+        self.one_per_wallet != 0
+    }
+
+    pub fn gate_collection(&self) -> bool {
+        self.gate_collection != 0
+    }
+
+    pub fn pay_what_you_want(&self) -> bool {
+        self.pay_what_you_want != 0
+    }
+
+    pub fn pending_authority(&self) -> Option<Pubkey> {
+        key_if_set(self.pending_authority)
+    }
+
+    pub fn seat_map(&self) -> Option<Pubkey> {
+        key_if_set(self.seat_map)
+    }
+
+    pub fn series(&self) -> Option<Pubkey> {
+        key_if_set(self.series)
+    }
+
+    pub fn poap_mint(&self) -> Option<Pubkey> {
+        key_if_set(self.poap_mint)
+    }
+
+    pub fn bond_organizer(&self) -> Option<Pubkey> {
+        key_if_set(self.bond_organizer)
+    }
+
+    pub fn currency(&self) -> Option<Pubkey> {
+        key_if_set(self.currency)
+    }
+
+    pub fn hook_program(&self) -> Option<Pubkey> {
+        key_if_set(self.hook_program)
+    }
+
+    pub fn attestor(&self) -> Option<Pubkey> {
+        key_if_set(self.attestor)
+    }
+
+    pub fn gate_mint(&self) -> Option<Pubkey> {
+        key_if_set(self.gate_mint)
+    }
+
+    pub fn payout_config(&self) -> Option<Pubkey> {
+        key_if_set(self.payout_config)
+    }
+
+    pub fn sales_stats(&self) -> Option<Pubkey> {
+        key_if_set(self.sales_stats)
+    }
+
+    pub fn transaction_log(&self) -> Option<Pubkey> {
+        key_if_set(self.transaction_log)
+    }
+
+    /// Last seed of the Ticket PDA `buyer` gets from the next sale: the
+    /// buyer's key on one-per-wallet events, otherwise `next_ticket_id`.
+    pub fn ticket_seed(&self, buyer: &Pubkey) -> Vec<u8> {
+        if self.one_per_wallet() {
+            buyer.to_bytes().to_vec()
+        } else {
+            self.next_ticket_id.to_le_bytes().to_vec()
+        }
+    }
+
+    /// Rejects one-per-wallet events on paths that derive Ticket PDAs from
+    /// ticket ids or issue tickets to someone other than the buyer.
+    pub fn require_numbered_tickets(&self) -> Result<()> {
+        require!(
+            !self.one_per_wallet(),
+            EventTicketingError::OnePerWalletNotSupported
+        );
+        Ok(())
+    }
+
+    /// Adds a sale at `price`, `referral_fee` of which went to a referrer, to
+    /// the revenue totals and returns its fee breakdown.
+    pub fn record_sale(&mut self, price: u64, referral_fee: u64) -> Result<SaleFees> {
+        let fees = SaleFees {
+            referral_fee,
+            // The program takes no cut of sales.
+            protocol_fee: 0,
+            organizer_net: price
+                .checked_sub(referral_fee)
+                .ok_or(EventTicketingError::Overflow)?,
+        };
+
+        self.gross_sales = self
+            .gross_sales
+            .checked_add(price)
+            .ok_or(EventTicketingError::Overflow)?;
+        self.referral_fees = self
+            .referral_fees
+            .checked_add(fees.referral_fee)
+            .ok_or(EventTicketingError::Overflow)?;
+        self.protocol_fees = self
+            .protocol_fees
+            .checked_add(fees.protocol_fee)
+            .ok_or(EventTicketingError::Overflow)?;
+        self.organizer_net = self
+            .organizer_net
+            .checked_add(fees.organizer_net)
+            .ok_or(EventTicketingError::Overflow)?;
+
+        Ok(fees)
+    }
+
+    /// Adds a transfer's fee, and its price for resales, to the totals.
+    pub fn record_transfer(&mut self, transfer_fee: u64, resale_price: u64) -> Result<()> {
+        self.transfer_fees = self
+            .transfer_fees
+            .checked_add(transfer_fee)
+            .ok_or(EventTicketingError::Overflow)?;
+        self.resale_volume = self
+            .resale_volume
+            .checked_add(resale_price)
+            .ok_or(EventTicketingError::Overflow)?;
+        Ok(())
+    }
+
+    /// Splits a listing's price per the resale split it was listed under,
+    /// adding the organizer and protocol shares to the totals. Rounding goes
+    /// to the seller.
+    pub fn record_resale_split(&mut self, listing: &Listing) -> Result<ResaleSplit> {
+        let share =
+            |bps: u16| (listing.price as u128 * bps as u128 / BPS_DENOMINATOR as u128) as u64;
+        let organizer = share(listing.organizer_bps);
+        let protocol = share(listing.protocol_bps);
+        let split = ResaleSplit {
+            seller: listing.price - organizer - protocol,
+            organizer,
+            protocol,
+        };
+
+        self.resale_organizer_fees = self
+            .resale_organizer_fees
+            .checked_add(organizer)
+            .ok_or(EventTicketingError::Overflow)?;
+        self.resale_protocol_fees = self
+            .resale_protocol_fees
+            .checked_add(protocol)
+            .ok_or(EventTicketingError::Overflow)?;
+
+        Ok(split)
+    }
+
+    /// Hands out the next ticket id.
+    pub fn take_ticket_id(&mut self) -> Result<u32> {
+        let ticket_id = self.next_ticket_id;
+        self.next_ticket_id = ticket_id
+            .checked_add(1)
+            .ok_or(EventTicketingError::Overflow)?;
+        Ok(ticket_id)
+    }
+
+    /// Checks resale is routed through an approved venue, when the event
+    /// restricts where its tickets are resold.
+    pub fn require_approved_venue(&self, venue: Option<Pubkey>) -> Result<()> {
+        let approved = &self.approved_venues[..self.approved_venue_count as usize];
+        if approved.is_empty() {
+            return Ok(());
+        }
+        require!(
+            venue.is_some_and(|venue| approved.contains(&venue)),
+            EventTicketingError::VenueNotApproved
+        );
+        Ok(())
+    }
+
+    /// A zero `max_resale_multiplier_bps` leaves resale prices uncapped.
+    pub fn max_resale_price(&self) -> Option<u64> {
+        if self.max_resale_multiplier_bps == 0 {
+            return None;
+        }
+        let cap =
+            self.price as u128 * self.max_resale_multiplier_bps as u128 / BPS_DENOMINATOR as u128;
+        Some(cap.min(u64::MAX as u128) as u64)
+    }
+
+    /// Price of the next ticket at `now`.
+    pub fn current_price(&self, now: i64) -> u64 {
+        self.price_at(now, self.sold)
+    }
+
+    /// Price of the ticket sold after `sold` others. While a dutch auction is
+    /// configured (`auction_end != 0`) it decays linearly from
+    /// `auction_start_price` to `auction_floor_price`. Otherwise it is `price`,
+    /// raised by `curve_step_lamports` every `curve_step_size` tickets sold.
+    /// Free events with an attendance deposit charge just the deposit.
+    pub fn price_at(&self, now: i64, sold: u32) -> u64 {
+        if self.attendance_deposit > 0 {
+            return self.attendance_deposit;
+        }
+        if self.auction_end == 0 {
+            if self.curve_step_size == 0 {
+                return self.price;
+            }
+            let steps = (sold / self.curve_step_size) as u64;
+            return self
+                .price
+                .saturating_add(steps.saturating_mul(self.curve_step_lamports));
+        }
+        if now <= self.auction_start {
+            return self.auction_start_price;
+        }
+        if now >= self.auction_end {
+            return self.auction_floor_price;
+        }
+
+        let drop = (self.auction_start_price - self.auction_floor_price) as u128;
+        let elapsed = (now - self.auction_start) as u128;
+        let duration = (self.auction_end - self.auction_start) as u128;
+        self.auction_start_price - (drop * elapsed / duration) as u64
+    }
+
+    /// Seats neither sold nor held by an open reservation.
+    pub fn available(&self) -> u32 {
+        (self.supply + self.reissued_seats).saturating_sub(self.sold + self.reserved)
+    }
+
+    /// Part of `price` kept when its ticket is returned with `cancel_ticket`.
+    pub fn cancellation_fee(&self, price: u64) -> u64 {
+        (price as u128 * self.cancellation_fee_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+
+    /// Portion of `price` owed to a referrer; zero when referrals are off.
+    pub fn referral_cut(&self, price: u64) -> u64 {
+        (price as u128 * self.referral_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+
+    /// An all-zero `allowlist_root` means sales are open to everyone.
+    pub fn is_presale(&self) -> bool {
+        self.allowlist_root != [0; 32]
+    }
+
+    /// A zero `doors_close` means the check-in window has no upper bound.
+    pub fn doors_are_open(&self, now: i64) -> bool {
+        now >= self.doors_open && (self.doors_close == 0 || now <= self.doors_close)
+    }
+
+    /// Proceeds stay locked until the event is finalized and
+    /// `payout_timelock_secs` have passed since it ended, leaving attendees
+    /// time to dispute. Events without an end time can never be finalized,
+    /// so their proceeds stay locked.
+    pub fn payouts_unlocked(&self, now: i64) -> bool {
+        self.end_time != 0
+            && self.is_finalized()
+            && now >= self.end_time.saturating_add(self.payout_timelock_secs)
+    }
+
+    /// Events without an `end_time` are never considered ended.
+    pub fn has_ended(&self, now: i64) -> bool {
+        self.end_time != 0 && now >= self.end_time
+    }
+
+    /// Events without a doors window are never considered started.
+    pub fn has_started(&self, now: i64) -> bool {
+        self.doors_open != 0 && now >= self.doors_open
+    }
+
+    /// Organizer refunds are allowed until `refund_deadline`; zero means the
+    /// event has no refund policy.
+    pub fn refund_window_open(&self, now: i64) -> bool {
+        now <= self.refund_deadline
+    }
+
+    /// Sold tickets that were neither checked in nor refunded. Pass check-ins
+    /// count as attendance without a sale, hence the saturation.
+    pub fn no_shows(&self) -> u32 {
+        self.sold
+            .saturating_sub(self.checked_in_count)
+            .saturating_sub(self.refunded_count)
+    }
+
+    /// Rejects events with an attendance deposit on check-in paths that
+    /// cannot return it.
+    pub fn require_no_attendance_deposit(&self) -> Result<()> {
+        require!(
+            self.attendance_deposit == 0,
+            EventTicketingError::DepositNotSupported
+        );
+        Ok(())
+    }
+
+    pub fn is_finalized(&self) -> bool {
+        self.finalized_at != 0
+    }
+
+    /// Waitlist entries can no longer be claimed once the event is canceled
+    /// or finalized.
+    pub fn waitlist_closed(&self) -> bool {
+        self.canceled() || self.is_finalized()
     }
+
+    /// Holders may refund themselves once the event is canceled, or when the
+    /// organizer has not finalized it within `dispute_window_secs` of its end.
+    pub fn refunds_claimable(&self, now: i64) -> bool {
+        if self.canceled() {
+            return true;
+        }
+        !self.is_finalized()
+            && self.end_time != 0
+            && now > self.end_time.saturating_add(self.dispute_window_secs)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Seat {
+    pub section: u16,
+    pub row: u16,
+    pub number: u16,
+}
+
+impl Seat {
+    pub const SPACE: usize = 2 + 2 + 2;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SeatLayout {
+    pub sections: u16,
+    pub rows: u16,
+    pub seats_per_row: u16,
 }
 
 #[account]
@@ -23,20 +522,1120 @@ pub struct Ticket {
     pub owner: Pubkey,
     pub event: Pubkey,
     pub ticket_id: u32,
-    pub is_used: bool,
+    pub checked_in_count: u16,
+    pub last_check_in_day: i64,
     pub refunded: bool,
+    pub checked_in_at: i64,
+    pub checked_in_by: Pubkey,
+    pub comped: bool,
+    pub seat: Option<Seat>,
+    pub tier: Option<u8>,
+    pub revoked: bool,
+    pub version: u8,
+    pub purchased_at: i64,
+    pub purchase_price: u64,
+    /// Original owner while the ticket is lent out; `owner` is the borrower.
+    pub lender: Option<Pubkey>,
+    pub lent_until: i64,
+    /// Part of `purchase_price` paid above the event price.
+    pub donation: u64,
+    /// Organizer-defined data such as seat notes or a meal preference code;
+    /// see `set_ticket_attributes`.
+    pub attributes: Vec<u8>,
+    /// Highest scan nonce accepted at check-in; each scan must present a
+    /// larger one, so replayed QR payloads and duplicated NFC reads fail.
+    pub scan_nonce: u64,
+    /// Part of the price paid in a tier's listed SPL currency rather than in
+    /// lamports; `purchase_price` only counts the lamports. Returned by
+    /// `refund_token_payment` once the ticket is refunded.
+    pub token_payment: Option<TokenPayment>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TokenPayment {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+impl TokenPayment {
+    pub const SPACE: usize = 32 + 8;
 }
 
 impl Ticket {
-    pub const SPACE: usize = 8 + 32 + 32 + 4 + 1 + 1;
+    /// Size of tickets issued before `version`, `purchased_at` and
+    /// `purchase_price` were added; see `migrate_ticket`.
+    pub const V1_SPACE: usize =
+        8 + 32 + 32 + 4 + 2 + 8 + 1 + 8 + 32 + 1 + (1 + Seat::SPACE) + (1 + 1) + 1;
+    /// Size of tickets issued before lending was added.
+    pub const V2_SPACE: usize = Self::V1_SPACE + 1 + 8 + 8;
+    /// Size of tickets issued before donations were added.
+    pub const V3_SPACE: usize = Self::V2_SPACE + (1 + 32) + 8;
+    /// Size of tickets issued before attributes were added.
+    pub const V4_SPACE: usize = Self::V3_SPACE + 8;
+    /// Size of tickets issued before scan nonces were added.
+    pub const V5_SPACE: usize = Self::V4_SPACE + (4 + MAX_TICKET_ATTRIBUTES_LEN);
+    /// Size of tickets issued before token payments were added.
+    pub const V6_SPACE: usize = Self::V5_SPACE + 8;
+    pub const SPACE: usize = Self::V6_SPACE + (1 + TokenPayment::SPACE);
+
+    /// Initializes a freshly created ticket account.
+    pub fn issue(
+        &mut self,
+        owner: Pubkey,
+        event: Pubkey,
+        ticket_id: u32,
+        purchase_price: u64,
+        purchased_at: i64,
+    ) {
+        self.owner = owner;
+        self.event = event;
+        self.ticket_id = ticket_id;
+        self.checked_in_count = 0;
+        self.last_check_in_day = 0;
+        self.refunded = false;
+        self.checked_in_at = 0;
+        self.checked_in_by = Pubkey::default();
+        self.comped = false;
+        self.seat = None;
+        self.tier = None;
+        self.revoked = false;
+        self.version = TICKET_VERSION;
+        self.purchased_at = purchased_at;
+        self.purchase_price = purchase_price;
+        self.lender = None;
+        self.lent_until = 0;
+        self.donation = 0;
+        self.attributes = Vec::new();
+        self.scan_nonce = 0;
+        self.token_payment = None;
+    }
+
+    /// Adds `amount` of `mint` to the ticket's token payment. A ticket can
+    /// only be paid in one token.
+    pub fn record_token_payment(&mut self, mint: Pubkey, amount: u64) -> Result<()> {
+        let paid = match self.token_payment {
+            Some(payment) => {
+                require_keys_eq!(
+                    payment.mint,
+                    mint,
+                    EventTicketingError::TicketCurrencyMismatch
+                );
+                payment.amount
+            }
+            None => 0,
+        };
+        self.token_payment = Some(TokenPayment {
+            mint,
+            amount: paid
+                .checked_add(amount)
+                .ok_or(EventTicketingError::Overflow)?,
+        });
+        Ok(())
+    }
+
+    pub fn is_used(&self) -> bool {
+        self.checked_in_count > 0
+    }
+
+    /// Replaces the ticket's attributes, bounded by `MAX_TICKET_ATTRIBUTES_LEN`.
+    pub fn set_attributes(&mut self, attributes: Vec<u8>) -> Result<()> {
+        require!(
+            attributes.len() <= MAX_TICKET_ATTRIBUTES_LEN,
+            EventTicketingError::AttributesTooLong
+        );
+        self.attributes = attributes;
+        Ok(())
+    }
+
+    pub fn is_lent(&self) -> bool {
+        self.lender.is_some()
+    }
+
+    /// Wallet refunds are paid to: the lender while the ticket is lent out,
+    /// otherwise the owner.
+    pub fn refund_recipient(&self) -> Pubkey {
+        self.lender.unwrap_or(self.owner)
+    }
+
+    /// Admits the ticket once more, allowing at most one entry per UTC day
+    /// and `event.max_check_ins` entries in total. Records when and by whom
+    /// the latest entry was made.
+    pub fn record_check_in(
+        &mut self,
+        event: &mut Event,
+        operator: Pubkey,
+        scan_nonce: u64,
+        now: i64,
+    ) -> Result<()> {
+        // A consumed ticket can no longer be refunded, so canceled events
+        // must not admit anyone.
+        require!(!event.canceled(), EventTicketingError::EventCanceled);
+        require!(!event.is_finalized(), EventTicketingError::EventFinalized);
+        require!(!self.revoked, EventTicketingError::TicketRevoked);
+        require!(!self.refunded, EventTicketingError::AlreadyRefunded);
+        // The borrower's eligibility ends at the loan deadline, even before
+        // the ticket is reclaimed.
+        require!(
+            !self.is_lent() || now < self.lent_until,
+            EventTicketingError::LoanExpired
+        );
+        require!(
+            scan_nonce > self.scan_nonce,
+            EventTicketingError::ScanReplayed
+        );
+        require!(
+            self.checked_in_count < event.max_check_ins,
+            EventTicketingError::AlreadyCheckedIn
+        );
+
+        let today = now.div_euclid(SECONDS_PER_DAY);
+        require!(
+            !self.is_used() || self.last_check_in_day != today,
+            EventTicketingError::AlreadyCheckedInToday
+        );
+
+        if self.checked_in_count == 0 {
+            event.checked_in_count += 1;
+        }
+        self.checked_in_count += 1;
+        self.last_check_in_day = today;
+        self.checked_in_at = now;
+        self.checked_in_by = operator;
+        self.scan_nonce = scan_nonce;
+
+        Ok(())
+    }
 }
 
 #[account]
 pub struct OrganizerRegistry {
     pub organizer: Pubkey,
     pub registered_at: i64,
+    pub verified: bool,
+    /// Lamports locked in the organizer's bond PDA.
+    pub bond: u64,
+    /// Bonded events that have not finalized yet; the bond is released only
+    /// once this is back to zero.
+    pub open_events: u32,
+    /// Layout version; see `ORGANIZER_VERSION` and `migrate_organizer`.
+    pub version: u8,
 }
 
 impl OrganizerRegistry {
-    pub const SPACE: usize = 8 + 32 + 8;
+    pub const SPACE: usize = 8 + 32 + 8 + 1 + 8 + 4 + 1;
+}
+
+#[account]
+pub struct EventAuthorityConfig {
+    pub event: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+impl EventAuthorityConfig {
+    pub const SPACE: usize = 8 + 32 + 4 + 32 * MAX_MULTISIG_SIGNERS + 1;
+}
+
+#[account]
+pub struct Staff {
+    pub event: Pubkey,
+    pub staff: Pubkey,
+    pub added_at: i64,
+}
+
+impl Staff {
+    pub const SPACE: usize = 8 + 32 + 32 + 8;
+}
+
+/// A teammate sharing management of an event, limited to the actions in
+/// `permissions`; see `add_co_organizer`.
+#[account]
+pub struct CoOrganizer {
+    pub event: Pubkey,
+    pub member: Pubkey,
+    pub permissions: u8,
+    pub added_at: i64,
+}
+
+impl CoOrganizer {
+    pub const SPACE: usize = 8 + 32 + 32 + 1 + 8;
+
+    pub const CAN_CHECK_IN: u8 = 1 << 0;
+    pub const CAN_REFUND: u8 = 1 << 1;
+    pub const CAN_UPDATE: u8 = 1 << 2;
+    pub const CAN_WITHDRAW: u8 = 1 << 3;
+    pub const ALL_PERMISSIONS: u8 =
+        Self::CAN_CHECK_IN | Self::CAN_REFUND | Self::CAN_UPDATE | Self::CAN_WITHDRAW;
+
+    pub fn can(&self, permission: u8) -> bool {
+        self.permissions & permission == permission
+    }
+}
+
+#[account]
+pub struct Listing {
+    pub ticket: Pubkey,
+    pub event: Pubkey,
+    pub seller: Pubkey,
+    pub price: u64,
+    pub listed_at: i64,
+    /// Resale split in force when the ticket was listed, which the sale
+    /// pays out; the seller gets the rest.
+    pub organizer_bps: u16,
+    pub protocol_bps: u16,
+}
+
+impl Listing {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 2 + 2;
+}
+
+/// A transfer offered by `from` and waiting for `to` to accept it. `fee` is
+/// escrowed in the account on top of its rent.
+#[account]
+pub struct PendingTransfer {
+    pub ticket: Pubkey,
+    pub event: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub fee: u64,
+    pub created_at: i64,
+}
+
+impl PendingTransfer {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 32 + 8 + 8;
+}
+
+/// Allows `operator` to transfer `ticket` once while `owner` still holds it.
+#[account]
+pub struct OperatorApproval {
+    pub ticket: Pubkey,
+    pub owner: Pubkey,
+    pub operator: Pubkey,
+    pub approved_at: i64,
+}
+
+impl OperatorApproval {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8;
+}
+
+#[account]
+pub struct BuyerRecord {
+    pub event: Pubkey,
+    pub buyer: Pubkey,
+    pub purchased: u32,
+}
+
+impl BuyerRecord {
+    pub const SPACE: usize = 8 + 32 + 32 + 4;
+
+    /// Counts `count` more purchases by `buyer`, enforcing the event's
+    /// per-wallet limit (zero means unlimited).
+    pub fn record_purchase(
+        &mut self,
+        event: &Event,
+        event_key: Pubkey,
+        buyer: Pubkey,
+        count: u32,
+    ) -> Result<()> {
+        self.event = event_key;
+        self.buyer = buyer;
+        self.purchased = self
+            .purchased
+            .checked_add(count)
+            .ok_or(EventTicketingError::Overflow)?;
+
+        require!(
+            event.max_per_wallet == 0 || self.purchased <= event.max_per_wallet,
+            EventTicketingError::WalletLimitReached
+        );
+
+        Ok(())
+    }
+}
+
+#[account]
+pub struct Config {
+    pub admin: Pubkey,
+    pub max_unverified_supply: u32,
+    /// Bond organizers lock at registration; zero disables bonding.
+    pub organizer_bond_lamports: u64,
+}
+
+impl Config {
+    pub const SPACE: usize = 8 + 32 + 4 + 8;
+}
+
+/// Display data for an event, kept out of `Event` so the core account has a
+/// fixed layout.
+#[account]
+pub struct EventMetadata {
+    pub event: Pubkey,
+    pub name: String,
+    pub date: String,
+    pub metadata_uri: String,
+}
+
+impl EventMetadata {
+    /// Allocation made at creation; `update_event` grows it as needed.
+    pub const SPACE: usize = Self::space(MAX_NAME_LEN, MAX_DATE_LEN, MAX_METADATA_URI_LEN);
+
+    pub const fn space(name_len: usize, date_len: usize, metadata_uri_len: usize) -> usize {
+        8 + 32 + 4 + name_len + 4 + date_len + 4 + metadata_uri_len
+    }
+
+    /// Size needed after replacing the given fields; never less than the
+    /// current allocation, so updates only ever grow the account.
+    pub fn space_after_update(
+        &self,
+        name: Option<&str>,
+        date: Option<&str>,
+        metadata_uri: Option<&str>,
+        current_len: usize,
+    ) -> usize {
+        Self::space(
+            name.map_or(self.name.len(), str::len),
+            date.map_or(self.date.len(), str::len),
+            metadata_uri.map_or(self.metadata_uri.len(), str::len),
+        )
+        .max(current_len)
+    }
+}
+
+/// Global index of every event, split into fixed-size pages so clients can
+/// enumerate events without scanning program accounts.
+#[account]
+pub struct EventRegistry {
+    pub event_count: u64,
+}
+
+impl EventRegistry {
+    pub const SPACE: usize = 8 + 8;
+
+    /// Page the next registered event is appended to.
+    pub fn current_page(&self) -> u64 {
+        self.event_count / EVENTS_PER_PAGE
+    }
+
+    pub fn record(&mut self, page: &mut EventRegistryPage, event: Pubkey) {
+        if page.events.is_empty() {
+            page.page = self.current_page();
+        }
+        page.events.push(event);
+        self.event_count += 1;
+    }
+}
+
+#[account]
+pub struct EventRegistryPage {
+    pub page: u64,
+    pub events: Vec<Pubkey>,
+}
+
+impl EventRegistryPage {
+    pub const SPACE: usize = 8 + 8 + 4 + 32 * EVENTS_PER_PAGE as usize;
+}
+
+#[account]
+pub struct Promo {
+    pub event: Pubkey,
+    pub code: String,
+    pub discount_bps: u16,
+    pub max_uses: u32,
+    pub uses: u32,
+}
+
+impl Promo {
+    pub const SPACE: usize = 8 + 32 + 4 + MAX_PROMO_CODE_LEN + 2 + 4 + 4;
+
+    /// Consumes one use of the code and returns the discounted `price`.
+    pub fn redeem(&mut self, price: u64) -> Result<u64> {
+        require!(
+            self.uses < self.max_uses,
+            EventTicketingError::PromoExhausted
+        );
+        self.uses += 1;
+
+        let discount = price as u128 * self.discount_bps as u128 / BPS_DENOMINATOR as u128;
+        Ok(price - discount as u64)
+    }
+}
+
+#[account]
+pub struct Referrer {
+    pub event: Pubkey,
+    pub referrer: Pubkey,
+    pub referrals: u32,
+    pub earned: u64,
+}
+
+impl Referrer {
+    pub const SPACE: usize = 8 + 32 + 32 + 4 + 8;
+}
+
+#[account]
+pub struct WaitlistEntry {
+    pub event: Pubkey,
+    pub wallet: Pubkey,
+    pub position: u32,
+    pub deposit: u64,
+}
+
+impl WaitlistEntry {
+    pub const SPACE: usize = 8 + 32 + 32 + 4 + 8;
+}
+
+#[account]
+pub struct Reservation {
+    pub event: Pubkey,
+    pub buyer: Pubkey,
+    pub expires_at_slot: u64,
+    /// Ticket price escrowed in this account while the seat is held;
+    /// returned to the buyer when the hold is finalized or purged.
+    pub deposit: u64,
+}
+
+impl Reservation {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 8;
+}
+
+#[account]
+pub struct Auction {
+    pub event: Pubkey,
+    pub seats: u32,
+    pub min_bid: u64,
+    pub close_at: i64,
+    pub reveal_close_at: i64,
+    pub bid_count: u32,
+    pub settled: bool,
+    /// Best revealed bids, highest first (earliest bid wins ties). Never
+    /// longer than `seats`.
+    pub leaders: Vec<AuctionLeader>,
+}
+
+impl Auction {
+    pub const SPACE: usize =
+        8 + 32 + 4 + 8 + 8 + 8 + 4 + 1 + 4 + MAX_AUCTION_SEATS * AuctionLeader::SPACE;
+
+    /// Ranks a revealed bid, dropping the lowest leader once every seat is
+    /// spoken for.
+    pub fn record_reveal(&mut self, bid: Pubkey, amount: u64, placed_at: i64) {
+        let rank = self
+            .leaders
+            .iter()
+            .position(|leader| {
+                amount > leader.amount || (amount == leader.amount && placed_at < leader.placed_at)
+            })
+            .unwrap_or(self.leaders.len());
+        if rank < self.seats as usize {
+            self.leaders.insert(
+                rank,
+                AuctionLeader {
+                    bid,
+                    amount,
+                    placed_at,
+                },
+            );
+            self.leaders.truncate(self.seats as usize);
+        }
+    }
+
+    pub fn is_winner(&self, bid: &Pubkey) -> bool {
+        self.leaders.iter().any(|leader| leader.bid == *bid)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AuctionLeader {
+    pub bid: Pubkey,
+    pub amount: u64,
+    pub placed_at: i64,
+}
+
+impl AuctionLeader {
+    pub const SPACE: usize = 32 + 8 + 8;
+}
+
+#[account]
+pub struct Bid {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub commitment: [u8; 32],
+    pub deposit: u64,
+    pub amount: u64,
+    pub placed_at: i64,
+    pub revealed: bool,
+}
+
+impl Bid {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 1;
+
+    /// Bids are sealed as `hash(bidder || amount || salt)`; the deposit
+    /// escrowed alongside only bounds the amount.
+    pub fn hash(bidder: &Pubkey, amount: u64, salt: &[u8; 32]) -> [u8; 32] {
+        hashv(&[bidder.as_ref(), &amount.to_le_bytes(), salt]).to_bytes()
+    }
+
+    pub fn matches(&self, amount: u64, salt: &[u8; 32]) -> bool {
+        Self::hash(&self.bidder, amount, salt) == self.commitment
+    }
+}
+
+#[account]
+pub struct Raffle {
+    pub event: Pubkey,
+    pub seats: u32,
+    pub close_at: i64,
+    pub entry_count: u32,
+    pub randomness: Option<Pubkey>,
+    /// Seed slot of the randomness commit; the draw must reveal that commit.
+    pub seed_slot: u64,
+    pub drawn: bool,
+    pub seed: [u8; 32],
+    pub canceled: bool,
+}
+
+impl Raffle {
+    pub const SPACE: usize = 8 + 32 + 4 + 8 + 4 + (1 + 32) + 8 + 1 + 32 + 1;
+
+    pub fn winners(&self) -> u32 {
+        self.seats.min(self.entry_count)
+    }
+
+    /// Winners are the entries whose position the drawn `seed` shuffles into
+    /// the first `winners()` places, so every set of entries is equally
+    /// likely to win, whatever order they entered in.
+    pub fn is_winner(&self, position: u32) -> bool {
+        self.shuffled_position(position) < self.winners()
+    }
+
+    /// Keyed permutation of `[0, entry_count)`: a four-round Feistel network
+    /// over the smallest even bit width that covers the entries, cycle-walked
+    /// back into range.
+    fn shuffled_position(&self, position: u32) -> u32 {
+        let half_bits =
+            (u32::BITS - self.entry_count.saturating_sub(1).leading_zeros()).div_ceil(2);
+        let mask = (1u64 << half_bits) - 1;
+
+        let mut value = position as u64;
+        loop {
+            let (mut left, mut right) = (value >> half_bits, value & mask);
+            for round in 0u8..4 {
+                let digest = hashv(&[&self.seed, &[round], &right.to_le_bytes()]).to_bytes();
+                let mix = u64::from_le_bytes(digest[..8].try_into().unwrap());
+                (left, right) = (right, left ^ (mix & mask));
+            }
+            value = (left << half_bits) | right;
+            if value < self.entry_count as u64 {
+                return value as u32;
+            }
+        }
+    }
+}
+
+#[account]
+pub struct RaffleEntry {
+    pub raffle: Pubkey,
+    pub entrant: Pubkey,
+    pub position: u32,
+    pub deposit: u64,
+}
+
+impl RaffleEntry {
+    pub const SPACE: usize = 8 + 32 + 32 + 4 + 8;
+}
+
+/// Two-phase on-sale: buyers commit during `[created, commit_close_at)` and
+/// reveal during `[commit_close_at, reveal_close_at)`. The first `seats`
+/// commitments get a ticket, whatever order they are revealed in.
+#[account]
+pub struct PurchaseQueue {
+    pub event: Pubkey,
+    pub seats: u32,
+    pub commit_close_at: i64,
+    pub reveal_close_at: i64,
+    pub commit_count: u32,
+    pub closed: bool,
+}
+
+impl PurchaseQueue {
+    pub const SPACE: usize = 8 + 32 + 4 + 8 + 8 + 4 + 1;
+
+    pub fn winners(&self) -> u32 {
+        self.seats.min(self.commit_count)
+    }
+
+    pub fn is_winner(&self, position: u32) -> bool {
+        position < self.winners()
+    }
+}
+
+#[account]
+pub struct PurchaseCommitment {
+    pub queue: Pubkey,
+    pub buyer: Pubkey,
+    pub commitment: [u8; 32],
+    pub position: u32,
+    pub deposit: u64,
+}
+
+impl PurchaseCommitment {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 4 + 8;
+
+    /// Commitments are `hash(buyer || nonce)`, so a commitment copied from a
+    /// pending transaction can't be revealed by anyone else.
+    pub fn hash(buyer: &Pubkey, nonce: &[u8; 32]) -> [u8; 32] {
+        hashv(&[buyer.as_ref(), nonce]).to_bytes()
+    }
+
+    pub fn matches(&self, nonce: &[u8; 32]) -> bool {
+        Self::hash(&self.buyer, nonce) == self.commitment
+    }
+}
+
+#[account]
+pub struct SeatClaim {
+    pub event: Pubkey,
+    pub ticket: Pubkey,
+    pub seat: Seat,
+}
+
+impl SeatClaim {
+    pub const SPACE: usize = 8 + 32 + 32 + Seat::SPACE;
+}
+
+/// Availability bitset over every seat of a seated venue, one bit per seat.
+#[account(zero_copy)]
+pub struct SeatMap {
+    pub event: Pubkey,
+    pub sections: u16,
+    pub rows: u16,
+    pub seats_per_row: u16,
+    pub _padding: [u8; 2],
+    pub taken: [u8; MAX_SEATS / 8],
+}
+
+impl SeatMap {
+    pub const SPACE: usize = 8 + 32 + 2 + 2 + 2 + 2 + MAX_SEATS / 8;
+
+    fn bit(&self, seat: &Seat) -> Result<(usize, u8)> {
+        require!(
+            seat.section < self.sections
+                && seat.row < self.rows
+                && seat.number < self.seats_per_row,
+            EventTicketingError::InvalidSeat
+        );
+        let index = (seat.section as usize * self.rows as usize + seat.row as usize)
+            * self.seats_per_row as usize
+            + seat.number as usize;
+        Ok((index / 8, 1 << (index % 8)))
+    }
+
+    pub fn take(&mut self, seat: &Seat) -> Result<()> {
+        let (byte, mask) = self.bit(seat)?;
+        require!(self.taken[byte] & mask == 0, EventTicketingError::SeatTaken);
+        self.taken[byte] |= mask;
+        Ok(())
+    }
+
+    pub fn release(&mut self, seat: &Seat) -> Result<()> {
+        let (byte, mask) = self.bit(seat)?;
+        self.taken[byte] &= !mask;
+        Ok(())
+    }
+}
+
+/// Ring buffer of an event's latest vault movements, so light clients can
+/// audit the vault without an indexer.
+#[account(zero_copy)]
+pub struct TransactionLog {
+    pub event: Pubkey,
+    /// Movements recorded so far; the latest is at `(count - 1) % TRANSACTION_LOG_LEN`.
+    pub count: u64,
+    pub entries: [VaultEntry; TRANSACTION_LOG_LEN],
+}
+
+impl TransactionLog {
+    pub const SPACE: usize = 8 + 32 + 8 + VaultEntry::SPACE * TRANSACTION_LOG_LEN;
+
+    pub fn push(&mut self, entry: VaultEntry) {
+        self.entries[(self.count % TRANSACTION_LOG_LEN as u64) as usize] = entry;
+        self.count += 1;
+    }
+}
+
+#[zero_copy]
+pub struct VaultEntry {
+    /// Wallet the lamports came from or went to.
+    pub actor: Pubkey,
+    /// In lamports, or units of the event's `currency`; for the tier
+    /// currency kinds, units of the mint paid in.
+    pub amount: u64,
+    pub at: i64,
+    /// A `VaultActivity`.
+    pub kind: u8,
+    pub _padding: [u8; 7],
+}
+
+impl VaultEntry {
+    pub const SPACE: usize = 32 + 8 + 8 + 1 + 7;
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum VaultActivity {
+    Mint = 1,
+    Refund = 2,
+    Withdraw = 3,
+    /// Price difference paid in by `upgrade_ticket`.
+    Upgrade = 4,
+    /// Price difference paid back by `downgrade_ticket`.
+    Downgrade = 5,
+    /// Vault balance moved to the refund pool when the event is canceled.
+    Sweep = 6,
+    /// Organizer bond paid in by `slash_organizer`.
+    Slash = 7,
+    /// Transfer fees and the organizer share of resales.
+    Fee = 8,
+    /// Tier currency tokens paid in for a ticket or an upgrade.
+    TierCurrencyPayment = 9,
+    /// Tier currency tokens refunded or withdrawn.
+    TierCurrencyPayout = 10,
+}
+
+#[account]
+pub struct EventSeries {
+    pub organizer: Pubkey,
+    pub series_id: u32,
+    pub name: String,
+    pub price: u64,
+    pub supply: u32,
+    pub transferable: bool,
+    pub occurrences: u32,
+    pub pass_price: u64,
+}
+
+impl EventSeries {
+    pub fn space(max_name_len: usize) -> usize {
+        8 + 32 + 4 + 4 + max_name_len + 8 + 4 + 1 + 4 + 8
+    }
+}
+
+#[account]
+pub struct Pass {
+    pub series: Pubkey,
+    pub holder: Pubkey,
+    pub purchased_at: i64,
+}
+
+impl Pass {
+    pub const SPACE: usize = 8 + 32 + 32 + 8;
+}
+
+#[account]
+pub struct PassCheckIn {
+    pub pass: Pubkey,
+    pub event: Pubkey,
+    pub checked_in_at: i64,
+    pub checked_in_by: Pubkey,
+}
+
+impl PassCheckIn {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 32;
+}
+
+/// Lamports set aside for holder refunds when an event is canceled. The
+/// vault's funds move here on cancellation so refunds no longer depend on
+/// the organizer; holders draw from it with `claim_refund`.
+#[account]
+pub struct RefundPool {
+    pub event: Pubkey,
+    /// Total lamports moved in from the vault.
+    pub deposited: u64,
+    /// Total lamports paid out as refunds.
+    pub claimed: u64,
+}
+
+impl RefundPool {
+    pub const SPACE: usize = 8 + 32 + 8 + 8;
+}
+
+/// Proof, issued by `attestor`, that `subject` passed its identity or age
+/// checks. Shared by every event that trusts the same attestor.
+#[account]
+pub struct Attestation {
+    pub attestor: Pubkey,
+    pub subject: Pubkey,
+    pub issued_at: i64,
+    /// Zero means the attestation does not expire.
+    pub expires_at: i64,
+}
+
+impl Attestation {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 8;
+
+    pub fn is_valid_at(&self, now: i64) -> bool {
+        self.expires_at == 0 || now < self.expires_at
+    }
+}
+
+/// A time slot within an event (a talk, a parallel track) with its own
+/// check-in window and room capacity.
+#[account]
+pub struct Session {
+    pub event: Pubkey,
+    pub session_id: u16,
+    pub start_time: i64,
+    pub end_time: i64,
+    /// Maximum attendees; zero means unlimited.
+    pub capacity: u32,
+    /// Tickets admitted so far, counted against `capacity`.
+    pub checked_in: u32,
+}
+
+impl Session {
+    pub const SPACE: usize = 8 + 32 + 2 + 8 + 8 + 4 + 4;
+
+    pub fn is_full(&self) -> bool {
+        self.capacity != 0 && self.checked_in >= self.capacity
+    }
+}
+
+#[account]
+pub struct SessionCheckIn {
+    pub session: Pubkey,
+    pub ticket: Pubkey,
+    pub checked_in_at: i64,
+    pub checked_in_by: Pubkey,
+}
+
+impl SessionCheckIn {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 32;
+}
+
+/// Optional ticket class above general admission (e.g. VIP). Tickets start
+/// in general admission at the event price and can move between tiers.
+#[account]
+pub struct Tier {
+    pub event: Pubkey,
+    pub tier_id: u8,
+    pub price: u64,
+    pub supply: u32,
+    pub sold: u32,
+}
+
+impl Tier {
+    pub const SPACE: usize = 8 + 32 + 1 + 8 + 4 + 4;
+}
+
+/// An SPL mint a tier also accepts, priced in that token. `feed_id` is the
+/// Pyth USD feed for the mint, used to check `price` still matches the tier's
+/// lamport price when buyers pay with it.
+#[account]
+pub struct TierCurrency {
+    pub tier: Pubkey,
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub price: u64,
+    pub feed_id: [u8; 32],
+}
+
+impl TierCurrency {
+    pub const SPACE: usize = 8 + 32 + 32 + 1 + 8 + 32;
+}
+
+/// Points an attendee has earned with one organizer across all of their events.
+#[account]
+pub struct LoyaltyAccount {
+    pub organizer: Pubkey,
+    pub attendee: Pubkey,
+    pub points: u64,
+    pub lifetime_points: u64,
+}
+
+impl LoyaltyAccount {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 8;
+
+    pub fn accrue(&mut self, points: u64) {
+        self.points = self.points.saturating_add(points);
+        self.lifetime_points = self.lifetime_points.saturating_add(points);
+    }
+
+    /// Spends `points` against `price` and returns the discounted price.
+    /// The discount never exceeds the price.
+    pub fn redeem(&mut self, points: u64, price: u64) -> Result<u64> {
+        require!(
+            points <= self.points,
+            EventTicketingError::InsufficientLoyaltyPoints
+        );
+        self.points -= points;
+
+        let discount = points.saturating_mul(LAMPORTS_PER_LOYALTY_POINT);
+        Ok(price.saturating_sub(discount))
+    }
+}
+
+/// When a payout tranche unlocks.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PayoutMilestone {
+    /// As soon as tickets sell.
+    SaleStart,
+    /// From a unix timestamp on.
+    At(i64),
+    /// Once the event is finalized and its payout timelock has passed.
+    Finalized,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PayoutTranche {
+    pub milestone: PayoutMilestone,
+    /// Share of the event's proceeds this tranche releases.
+    pub bps: u16,
+}
+
+impl PayoutTranche {
+    pub const SPACE: usize = (1 + 8) + 2;
+
+    pub fn is_unlocked(&self, event: &Event, now: i64) -> bool {
+        match self.milestone {
+            PayoutMilestone::SaleStart => true,
+            PayoutMilestone::At(timestamp) => now >= timestamp,
+            PayoutMilestone::Finalized => event.is_finalized() && event.payouts_unlocked(now),
+        }
+    }
+}
+
+/// Installment schedule for an event's proceeds, e.g. 30% from sale start
+/// and 70% after finalization. While an event has one, `withdraw_proceeds`
+/// caps the organizer at the unlocked share of everything sold so far
+/// instead of applying the payout timelock.
+#[account]
+pub struct PayoutConfig {
+    pub event: Pubkey,
+    /// Tranche shares add up to `BPS_DENOMINATOR`.
+    pub tranches: Vec<PayoutTranche>,
+    /// Proceeds withdrawn so far.
+    pub withdrawn: u64,
+}
+
+impl PayoutConfig {
+    pub const SPACE: usize = 8 + 32 + 4 + MAX_PAYOUT_TRANCHES * PayoutTranche::SPACE + 8;
+
+    /// Records a withdrawal of `amount` out of `held` proceeds still in the
+    /// vault, failing if it goes beyond the tranches unlocked at `now`.
+    pub fn record_withdrawal(
+        &mut self,
+        event: &Event,
+        held: u64,
+        amount: u64,
+        now: i64,
+    ) -> Result<()> {
+        let unlocked_bps: u64 = self
+            .tranches
+            .iter()
+            .filter(|tranche| tranche.is_unlocked(event, now))
+            .map(|tranche| tranche.bps as u64)
+            .sum();
+        let total = self.withdrawn as u128 + held as u128;
+        let limit = total * unlocked_bps as u128 / BPS_DENOMINATOR as u128;
+
+        let withdrawn = self
+            .withdrawn
+            .checked_add(amount)
+            .ok_or(EventTicketingError::Overflow)?;
+        require!(
+            withdrawn as u128 <= limit,
+            EventTicketingError::PayoutTrancheLocked
+        );
+        self.withdrawn = withdrawn;
+
+        Ok(())
+    }
+}
+
+/// How the price of a sale was split.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct SaleFees {
+    pub referral_fee: u64,
+    pub protocol_fee: u64,
+    /// What reached the event vault.
+    pub organizer_net: u64,
+}
+
+impl SaleFees {
+    pub const SPACE: usize = 8 + 8 + 8;
+}
+
+/// Lamports each party gets from a resale; see `Event::record_resale_split`.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct ResaleSplit {
+    pub seller: u64,
+    pub organizer: u64,
+    pub protocol: u64,
+}
+
+/// Sales and refunds of one UTC day.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct DailySales {
+    /// Days since the Unix epoch.
+    pub day: i64,
+    pub sold: u32,
+    pub refunded: u32,
+    /// Sale and refund amounts, in the event's currency.
+    pub gross_sales: u64,
+    pub refunds: u64,
+}
+
+impl DailySales {
+    pub const SPACE: usize = 8 + 4 + 4 + 8 + 8;
+}
+
+/// Per-day sales counters of one event, so dashboards can chart sales
+/// velocity from chain state. `days` is a ring indexed by day number, so it
+/// holds the last `SALES_STATS_DAYS` days with activity at most.
+#[account]
+pub struct SalesStats {
+    pub event: Pubkey,
+    pub days: [DailySales; SALES_STATS_DAYS],
+}
+
+impl SalesStats {
+    pub const SPACE: usize = 8 + 32 + DailySales::SPACE * SALES_STATS_DAYS;
+
+    /// Bucket of the day containing `now`, cleared first if it still holds
+    /// an older day.
+    pub fn today(&mut self, now: i64) -> &mut DailySales {
+        let day = now.div_euclid(SECONDS_PER_DAY);
+        let bucket = &mut self.days[day.rem_euclid(SALES_STATS_DAYS as i64) as usize];
+        if bucket.day != day {
+            *bucket = DailySales {
+                day,
+                ..Default::default()
+            };
+        }
+        bucket
+    }
+}
+
+/// Invoice for one ticket purchase, kept for the buyer's accounting and for
+/// disputes. The payer can close it with `close_receipt` to reclaim its rent.
+#[account]
+pub struct Receipt {
+    pub ticket: Pubkey,
+    pub event: Pubkey,
+    /// Wallet that paid, which may differ from the ticket owner.
+    pub payer: Pubkey,
+    pub owner: Pubkey,
+    /// Total paid, in lamports or units of `currency`.
+    pub amount: u64,
+    /// How `amount` was split.
+    pub fees: SaleFees,
+    /// Part of `amount` paid above the price with `mint_ticket_with_amount`.
+    pub donation: u64,
+    /// SPL mint the amounts are in; `None` for lamports.
+    pub currency: Option<Pubkey>,
+    pub issued_at: i64,
+}
+
+impl Receipt {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 32 + 8 + SaleFees::SPACE + 8 + (1 + 32) + 8;
 }
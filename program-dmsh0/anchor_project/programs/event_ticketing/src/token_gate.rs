@@ -0,0 +1,172 @@
+//! Token-gated sales for fan-club presales. An organizer can set
+//! `Event::gate_mint` so every ticket sold through `mint_ticket` and its
+//! variants needs the buyer to show a token account they own, passed as the
+//! mint's `gate_token_account`:
+//!
+//! - balance gates: an account of `gate_mint` holding at least
+//!   `gate_min_amount` tokens;
+//! - collection gates (`gate_collection`): an NFT whose Metaplex metadata,
+//!   passed as `gate_metadata`, is a verified member of the `gate_mint`
+//!   collection.
+//!
+//! Sale paths that cannot check a proof per buyer are closed for gated events.
+
+use crate::errors::EventTicketingError;
+use crate::state::Event;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey;
+use anchor_spl::token::TokenAccount;
+
+/// Metaplex Token Metadata program, owner of NFT metadata accounts.
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey =
+    pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+const METADATA_SEED: &[u8] = b"metadata";
+const METADATA_V1_KEY: u8 = 4;
+// Creator: address (32), verified (1), share (1).
+const CREATOR_LEN: usize = 34;
+
+/// Rejects token-gated events on sale paths that do not check a proof.
+pub fn require_no_token_gate(event: &Event) -> Result<()> {
+    require!(
+        event.gate_mint().is_none(),
+        EventTicketingError::TokenGateNotSupported
+    );
+    Ok(())
+}
+
+/// Checks `buyer` proved they hold the event's gate token. Does nothing for
+/// events without a gate.
+pub fn verify_token_gate(
+    event: &Event,
+    gate_token_account: Option<&TokenAccount>,
+    gate_metadata: Option<&AccountInfo>,
+    buyer: &Pubkey,
+) -> Result<()> {
+    let Some(gate_mint) = event.gate_mint() else {
+        return Ok(());
+    };
+    let token_account = gate_token_account.ok_or(EventTicketingError::TokenGateProofMissing)?;
+
+    require_keys_eq!(
+        token_account.owner,
+        *buyer,
+        EventTicketingError::InvalidTokenGateAccount
+    );
+    require!(
+        token_account.amount >= event.gate_min_amount,
+        EventTicketingError::TokenGateBalanceTooLow
+    );
+
+    if event.gate_collection() {
+        let metadata = gate_metadata.ok_or(EventTicketingError::TokenGateProofMissing)?;
+        let collection = verified_collection(metadata, &token_account.mint)?;
+        require_keys_eq!(
+            collection,
+            gate_mint,
+            EventTicketingError::NotInGateCollection
+        );
+    } else {
+        require_keys_eq!(
+            token_account.mint,
+            gate_mint,
+            EventTicketingError::InvalidTokenGateAccount
+        );
+    }
+
+    Ok(())
+}
+
+/// Reads the verified collection of `mint` from its Metaplex metadata account.
+fn verified_collection(metadata: &AccountInfo, mint: &Pubkey) -> Result<Pubkey> {
+    require_keys_eq!(
+        *metadata.owner,
+        TOKEN_METADATA_PROGRAM_ID,
+        EventTicketingError::InvalidGateMetadata
+    );
+    let (expected, _) = Pubkey::find_program_address(
+        &[
+            METADATA_SEED,
+            TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            mint.as_ref(),
+        ],
+        &TOKEN_METADATA_PROGRAM_ID,
+    );
+    require_keys_eq!(
+        metadata.key(),
+        expected,
+        EventTicketingError::InvalidGateMetadata
+    );
+
+    // Borsh layout of `Metadata`: key, update_authority, mint, name, symbol,
+    // uri, seller_fee_basis_points, creators, primary_sale_happened,
+    // is_mutable, edition_nonce, token_standard, collection, ...
+    let data = metadata.try_borrow_data()?;
+    let mut reader = BorshReader {
+        data: &data,
+        offset: 0,
+    };
+
+    require!(
+        reader.u8()? == METADATA_V1_KEY,
+        EventTicketingError::InvalidGateMetadata
+    );
+    reader.take(32)?;
+    require!(
+        reader.take(32)? == mint.as_ref(),
+        EventTicketingError::InvalidGateMetadata
+    );
+    for _ in 0..3 {
+        let len = reader.u32()? as usize;
+        reader.take(len)?;
+    }
+    reader.take(2)?;
+    if reader.u8()? == 1 {
+        let creators = reader.u32()? as usize;
+        reader.take(
+            creators
+                .checked_mul(CREATOR_LEN)
+                .ok_or(EventTicketingError::InvalidGateMetadata)?,
+        )?;
+    }
+    reader.take(2)?;
+    for _ in 0..2 {
+        if reader.u8()? == 1 {
+            reader.take(1)?;
+        }
+    }
+
+    require!(reader.u8()? == 1, EventTicketingError::NotInGateCollection);
+    let verified = reader.u8()? == 1;
+    let collection = Pubkey::try_from(reader.take(32)?)
+        .map_err(|_| error!(EventTicketingError::InvalidGateMetadata))?;
+    require!(verified, EventTicketingError::NotInGateCollection);
+
+    Ok(collection)
+}
+
+struct BorshReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> BorshReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or(EventTicketingError::InvalidGateMetadata)?;
+        let bytes = &self.data[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+}
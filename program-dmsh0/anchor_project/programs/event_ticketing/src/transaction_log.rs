@@ -0,0 +1,36 @@
+//! On-chain vault activity log. Events that opted in with
+//! `enable_transaction_log` keep a `TransactionLog` of their latest vault
+//! movements (see `VaultActivity`), which every instruction moving vault
+//! funds takes as `transaction_log` and appends to through
+//! `record_vault_activity`.
+
+use crate::errors::EventTicketingError;
+use crate::state::{Event, TransactionLog, VaultActivity, VaultEntry};
+use anchor_lang::prelude::*;
+
+/// Appends a movement of `amount` by `actor` to the event's log. Does
+/// nothing for events without one.
+pub fn record_vault_activity(
+    event: &Event,
+    transaction_log: Option<&AccountLoader<TransactionLog>>,
+    kind: VaultActivity,
+    actor: Pubkey,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    if event.transaction_log().is_none() {
+        return Ok(());
+    }
+    // The account's address is checked by the instruction's constraints.
+    let transaction_log =
+        transaction_log.ok_or(error!(EventTicketingError::InvalidTransactionLog))?;
+
+    transaction_log.load_mut()?.push(VaultEntry {
+        actor,
+        amount,
+        at: now,
+        kind: kind as u8,
+        _padding: [0; 7],
+    });
+    Ok(())
+}
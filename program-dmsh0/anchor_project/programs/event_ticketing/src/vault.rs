@@ -0,0 +1,133 @@
+use crate::constants::VAULT_SEED;
+use crate::errors::EventTicketingError;
+use crate::state::RefundPool;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Lamports a data-less vault must keep to stay rent-exempt.
+pub fn vault_reserve() -> Result<u64> {
+    Ok(Rent::get()?.minimum_balance(0))
+}
+
+/// Seeds a new event's vault with its rent-exempt reserve, so every lamport
+/// above the reserve is event funds.
+pub fn fund_vault_reserve<'info>(
+    payer: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let shortfall = vault_reserve()?.saturating_sub(vault.lamports());
+    if shortfall == 0 {
+        return Ok(());
+    }
+
+    system_program::transfer(
+        CpiContext::new(
+            system_program.clone(),
+            system_program::Transfer {
+                from: payer.clone(),
+                to: vault.clone(),
+            },
+        ),
+        shortfall,
+    )
+}
+
+/// Event funds held by the vault, excluding its rent-exempt reserve.
+pub fn available_funds(vault: &AccountInfo) -> Result<u64> {
+    Ok(vault.lamports().saturating_sub(vault_reserve()?))
+}
+
+/// Checks the vault can pay out `amount` without dipping into its reserve.
+pub fn require_vault_funds(vault: &AccountInfo, amount: u64) -> Result<()> {
+    require!(
+        amount <= available_funds(vault)?,
+        EventTicketingError::InsufficientVaultFunds
+    );
+    Ok(())
+}
+
+/// Moves all of the vault's event funds into the event's refund pool and
+/// returns the amount moved.
+pub fn sweep_to_refund_pool<'info>(
+    vault: &AccountInfo<'info>,
+    vault_bump: u8,
+    refund_pool: &mut Account<'info, RefundPool>,
+    system_program: &AccountInfo<'info>,
+) -> Result<u64> {
+    let amount = available_funds(vault)?;
+    if amount == 0 {
+        return Ok(0);
+    }
+
+    let event_key = refund_pool.event;
+    let seeds = &[VAULT_SEED, event_key.as_ref(), &[vault_bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Transfer {
+                from: vault.clone(),
+                to: refund_pool.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    refund_pool.deposited = refund_pool
+        .deposited
+        .checked_add(amount)
+        .ok_or(EventTicketingError::Overflow)?;
+
+    Ok(amount)
+}
+
+/// Pays a lamport refund of `amount` to `recipient`, out of the refund pool
+/// when one is passed and out of the vault otherwise. Events canceled before
+/// refund pools existed, and bonds slashed after cancellation, are paid from
+/// the vault.
+pub fn pay_refund<'info>(
+    vault: &AccountInfo<'info>,
+    vault_bump: u8,
+    event: Pubkey,
+    refund_pool: Option<&mut Account<'info, RefundPool>>,
+    recipient: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if let Some(refund_pool) = refund_pool {
+        let reserve = Rent::get()?.minimum_balance(RefundPool::SPACE);
+        let available = refund_pool.get_lamports().saturating_sub(reserve);
+        require!(
+            amount <= available,
+            EventTicketingError::InsufficientRefundPool
+        );
+
+        refund_pool.sub_lamports(amount)?;
+        recipient.add_lamports(amount)?;
+        refund_pool.claimed = refund_pool
+            .claimed
+            .checked_add(amount)
+            .ok_or(EventTicketingError::Overflow)?;
+        return Ok(());
+    }
+
+    require_vault_funds(vault, amount)?;
+
+    let seeds = &[VAULT_SEED, event.as_ref(), &[vault_bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Transfer {
+                from: vault.clone(),
+                to: recipient.clone(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )
+}
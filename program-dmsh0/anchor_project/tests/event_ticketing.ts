@@ -13,8 +13,19 @@ import {
   Keypair,
   SystemProgram,
   LAMPORTS_PER_SOL,
+  BPF_LOADER_UPGRADEABLE_PROGRAM_ID,
+  Ed25519Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
 } from "@solana/web3.js";
+import {
+  createMint,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
 import { expect } from "chai";
+import { createHash } from "crypto";
 
 // ============================================================================
 // TEST SUITE SETUP
@@ -39,6 +50,7 @@ describe("event_ticketing", () => {
   const ticketSupply = 3; // Only 3 tickets available
   const eventName = "Bitcoin Conference 2024";
   const eventDate = "2024-12-31T19:00:00Z";
+  const maxUnverifiedSupply = 100; // Larger events need a verified organizer
 
   // ============================================================================
   // HELPER FUNCTIONS
@@ -66,6 +78,17 @@ describe("event_ticketing", () => {
     );
   }
 
+  /**
+   * Derives the EventMetadata PDA address (name, date, metadata URI)
+   * Seeds: ["event_metadata", event_pda]
+   */
+  function getEventMetadataPda(eventPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("event_metadata"), eventPda.toBuffer()],
+      program.programId
+    );
+  }
+
   /**
    * Derives the Ticket PDA address
    * Seeds: ["ticket", event_pda, ticket_id]
@@ -94,6 +117,123 @@ describe("event_ticketing", () => {
     );
   }
 
+  /**
+   * Derives the RefundPool PDA address that holds a canceled event's funds
+   */
+  function getRefundPoolPda(eventPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("refund_pool"), eventPda.toBuffer()],
+      program.programId
+    );
+  }
+
+  /**
+   * Derives the PendingTransfer PDA address of a ticket
+   * Seeds: ["pending_transfer", ticket_pda]
+   */
+  function getPendingTransferPda(ticketPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("pending_transfer"), ticketPda.toBuffer()],
+      program.programId
+    );
+  }
+
+  /**
+   * Derives the global Config PDA address
+   * Seeds: ["config"]
+   */
+  function getConfigPda(): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      program.programId
+    );
+  }
+
+  /**
+   * Derives the EventRegistry page the next created event is appended to
+   * Seeds: ["registry_page", page_index (u64 LE)]
+   */
+  async function getRegistryPagePda(): Promise<PublicKey> {
+    const [registryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("registry")],
+      program.programId
+    );
+    const registry = await program.account.eventRegistry.fetchNullable(
+      registryPda
+    );
+    const eventCount = registry ? registry.eventCount.toNumber() : 0;
+
+    const pageBuffer = Buffer.alloc(8);
+    pageBuffer.writeBigUInt64LE(BigInt(Math.floor(eventCount / 100)));
+
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("registry_page"), pageBuffer],
+      program.programId
+    )[0];
+  }
+
+  /**
+   * Opens the organizer refund window for a day so `refund` is allowed
+   * on a live (non-canceled) event
+   */
+  async function openRefundWindow(eventPda: PublicKey) {
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods
+      .setRefundPolicy(new anchor.BN(now + 86_400))
+      .accounts({
+        event: eventPda,
+        eventAuthority: eventAuthority.publicKey,
+      })
+      .signers([eventAuthority])
+      .rpc();
+  }
+
+  /**
+   * Transfers a ticket in both steps: the owner approves, the recipient accepts
+   */
+  async function transferTicket(
+    eventPda: PublicKey,
+    ticketPda: PublicKey,
+    owner: Keypair,
+    recipient: Keypair
+  ) {
+    await program.methods
+      .approveTransfer()
+      .accounts({
+        event: eventPda,
+        ticket: ticketPda,
+        pendingTransfer: getPendingTransferPda(ticketPda)[0],
+        recipient: recipient.publicKey,
+        owner: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+
+    return program.methods
+      .acceptTransfer()
+      .accounts({
+        event: eventPda,
+        ticket: ticketPda,
+        pendingTransfer: getPendingTransferPda(ticketPda)[0],
+        vault: getVaultPda(eventPda)[0],
+        sender: owner.publicKey,
+        recipient: recipient.publicKey,
+      })
+      .signers([recipient])
+      .rpc();
+  }
+
+  /**
+   * Scan nonces only need to increase per ticket, so one counter serves
+   * every check-in in the suite
+   */
+  let lastScanNonce = 0;
+  function nextScanNonce() {
+    lastScanNonce += 1;
+    return new anchor.BN(lastScanNonce);
+  }
+
   /**
    * Airdrops SOL to an account for testing
    * Needed because test accounts start with 0 SOL
@@ -116,6 +256,23 @@ describe("event_ticketing", () => {
     await airdrop(buyer1.publicKey, 10);
     await airdrop(buyer2.publicKey, 10);
     await airdrop(buyer3.publicKey, 10);
+
+    // The provider wallet deployed the program, so it is the upgrade
+    // authority and becomes the config admin
+    const [programDataPda] = PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+    );
+    await program.methods
+      .initializeConfig(maxUnverifiedSupply)
+      .accounts({
+        config: getConfigPda()[0],
+        admin: provider.wallet.publicKey,
+        program: program.programId,
+        programData: programDataPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
   });
 
   // ============================================================================
@@ -181,9 +338,7 @@ describe("event_ticketing", () => {
         // If we get here, the test should fail
         expect.fail("Should have thrown an error");
       } catch (error) {
-        // Expected to fail because account already exists
-        expect(error).to.exist;
-        console.log("Expected error: Organizer already registered");
+        expect(error.toString()).to.include("OrganizerAlreadyRegistered");
       }
     });
 
@@ -213,6 +368,112 @@ describe("event_ticketing", () => {
     });
   });
 
+  // ============================================================================
+  // TEST GROUP: verify_organizer instruction
+  // ============================================================================
+  describe("verify_organizer", () => {
+    function getOrganizerPda(organizer: PublicKey): [PublicKey, number] {
+      return PublicKey.findProgramAddressSync(
+        [Buffer.from("organizer"), organizer.toBuffer()],
+        program.programId
+      );
+    }
+
+    // UNHAPPY PATH: Unverified organizer cannot create a large event
+    it("Fails to create a large event as an unverified organizer", async () => {
+      const [largeEventPda] = getEventPda(eventAuthority.publicKey, 200);
+
+      try {
+        await program.methods
+          .initializeEvent(
+            200,
+            ticketPrice,
+            maxUnverifiedSupply + 1,
+            "Stadium Show",
+            eventDate,
+            true,
+            null,
+            false,
+            null
+          )
+          .accounts({
+            event: largeEventPda,
+            registryPage: await getRegistryPagePda(),
+            organizerRegistry: getOrganizerPda(eventAuthority.publicKey)[0],
+            eventAuthority: eventAuthority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([eventAuthority])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("OrganizerNotVerified");
+      }
+    });
+
+    // UNHAPPY PATH: Only the config admin can verify organizers
+    it("Fails to verify an organizer as non-admin", async () => {
+      try {
+        await program.methods
+          .verifyOrganizer()
+          .accounts({
+            config: getConfigPda()[0],
+            organizerRegistry: getOrganizerPda(eventAuthority.publicKey)[0],
+            admin: buyer1.publicKey,
+          })
+          .signers([buyer1])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedAdmin");
+      }
+    });
+
+    // HAPPY PATH: Admin verifies the organizer, unlocking large events
+    it("Successfully verifies an organizer", async () => {
+      const [organizerPda] = getOrganizerPda(eventAuthority.publicKey);
+
+      await program.methods
+        .verifyOrganizer()
+        .accounts({
+          config: getConfigPda()[0],
+          organizerRegistry: organizerPda,
+          admin: provider.wallet.publicKey,
+        })
+        .rpc();
+
+      const organizerAccount = await program.account.organizerRegistry.fetch(
+        organizerPda
+      );
+      expect(organizerAccount.verified).to.be.true;
+
+      const [largeEventPda] = getEventPda(eventAuthority.publicKey, 200);
+      await program.methods
+        .initializeEvent(
+          200,
+          ticketPrice,
+          maxUnverifiedSupply + 1,
+          "Stadium Show",
+          eventDate,
+          true,
+          null,
+          false,
+          null
+        )
+        .accounts({
+          event: largeEventPda,
+          registryPage: await getRegistryPagePda(),
+          organizerRegistry: organizerPda,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+    });
+  });
+
   // ============================================================================
   // TEST GROUP: initialize_event instruction
   // ============================================================================
@@ -229,10 +490,15 @@ describe("event_ticketing", () => {
           ticketPrice,
           ticketSupply,
           eventName,
-          eventDate
+          eventDate,
+          true,
+          null,
+          false,
+          null
         )
         .accounts({
           event: eventPda,
+          registryPage: await getRegistryPagePda(),
           eventAuthority: eventAuthority.publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -251,10 +517,16 @@ describe("event_ticketing", () => {
       expect(eventAccount.price.toNumber()).to.equal(ticketPrice.toNumber());
       expect(eventAccount.supply).to.equal(ticketSupply);
       expect(eventAccount.sold).to.equal(0); // No tickets sold yet
-      expect(eventAccount.canceled).to.be.false; // Event is active
+      expect(eventAccount.canceled).to.equal(0); // Event is active
       expect(eventAccount.eventId).to.equal(eventId);
-      expect(eventAccount.name).to.equal(eventName);
-      expect(eventAccount.date).to.equal(eventDate);
+
+      // Name and date live in the event's metadata account
+      const metadataAccount = await program.account.eventMetadata.fetch(
+        getEventMetadataPda(eventPda)[0]
+      );
+      expect(metadataAccount.event.toString()).to.equal(eventPda.toString());
+      expect(metadataAccount.name).to.equal(eventName);
+      expect(metadataAccount.date).to.equal(eventDate);
     });
 
     // UNHAPPY PATH: Try to create duplicate event
@@ -269,10 +541,15 @@ describe("event_ticketing", () => {
             ticketPrice,
             ticketSupply,
             "Duplicate Event",
-            eventDate
+            eventDate,
+            true,
+            null,
+            false,
+            null
           )
           .accounts({
             event: eventPda,
+            registryPage: await getRegistryPagePda(),
             eventAuthority: eventAuthority.publicKey,
             systemProgram: SystemProgram.programId,
           })
@@ -302,10 +579,15 @@ describe("event_ticketing", () => {
             ticketPrice,
             ticketSupply,
             tooLongName,
-            eventDate
+            eventDate,
+            true,
+            null,
+            false,
+            null
           )
           .accounts({
             event: eventPda,
+            registryPage: await getRegistryPagePda(),
             eventAuthority: eventAuthority.publicKey,
             systemProgram: SystemProgram.programId,
           })
@@ -341,6 +623,7 @@ describe("event_ticketing", () => {
           ticket: ticketPda,
           vault: vaultPda,
           buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
           systemProgram: SystemProgram.programId,
         })
         .signers([buyer1]) // buyer1 must sign to pay
@@ -357,12 +640,13 @@ describe("event_ticketing", () => {
       );
       expect(ticketAccount.event.toString()).to.equal(eventPda.toString());
       expect(ticketAccount.ticketId).to.equal(0);
-      expect(ticketAccount.isUsed).to.be.false;
+      expect(ticketAccount.checkedInCount).to.equal(0);
       expect(ticketAccount.refunded).to.be.false;
 
-      // Verify the event's sold counter was incremented
+      // Verify the event's sold counter and next ticket id were incremented
       const eventAccount = await program.account.event.fetch(eventPda);
       expect(eventAccount.sold).to.equal(1);
+      expect(eventAccount.nextTicketId).to.equal(1);
 
       // Verify payment was transferred to vault
       const vaultBalanceAfter = await provider.connection.getBalance(vaultPda);
@@ -384,6 +668,7 @@ describe("event_ticketing", () => {
           ticket: ticketPda,
           vault: vaultPda,
           buyer: buyer2.publicKey,
+          payer: buyer2.publicKey,
           systemProgram: SystemProgram.programId,
         })
         .signers([buyer2])
@@ -407,6 +692,7 @@ describe("event_ticketing", () => {
           ticket: ticketPda,
           vault: vaultPda,
           buyer: buyer3.publicKey,
+          payer: buyer3.publicKey,
           systemProgram: SystemProgram.programId,
         })
         .signers([buyer3])
@@ -435,6 +721,7 @@ describe("event_ticketing", () => {
             ticket: ticketPda,
             vault: vaultPda,
             buyer: buyer4.publicKey,
+            payer: buyer4.publicKey,
             systemProgram: SystemProgram.programId,
           })
           .signers([buyer4])
@@ -463,10 +750,15 @@ describe("event_ticketing", () => {
           ticketPrice,
           5,
           "Canceled Event",
-          eventDate
+          eventDate,
+          true,
+          null,
+          false,
+          null
         )
         .accounts({
           event: canceledEventPda,
+          registryPage: await getRegistryPagePda(),
           eventAuthority: eventAuthority.publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -485,7 +777,7 @@ describe("event_ticketing", () => {
 
       // Verify the event is canceled
       const eventAccount = await program.account.event.fetch(canceledEventPda);
-      expect(eventAccount.canceled).to.be.true;
+      expect(eventAccount.canceled).to.equal(1);
 
       // Now try to mint a ticket for the canceled event
       const [ticketPda] = getTicketPda(canceledEventPda, 0);
@@ -502,6 +794,7 @@ describe("event_ticketing", () => {
             ticket: ticketPda,
             vault: vaultPda,
             buyer: testBuyer.publicKey,
+            payer: testBuyer.publicKey,
             systemProgram: SystemProgram.programId,
           })
           .signers([testBuyer])
@@ -517,7 +810,7 @@ describe("event_ticketing", () => {
   });
 
   // ============================================================================
-  // TEST GROUP: transfer_ticket instruction
+  // TEST GROUP: approve_transfer / accept_transfer instructions
   // ============================================================================
   describe("transfer_ticket", () => {
     // HAPPY PATH: Successfully transfer a ticket
@@ -529,15 +822,7 @@ describe("event_ticketing", () => {
       const recipient = Keypair.generate();
 
       // Transfer ticket from buyer1 to recipient
-      const tx = await program.methods
-        .transferTicket()
-        .accounts({
-          ticket: ticketPda,
-          currentOwner: buyer1.publicKey,
-          newOwner: recipient.publicKey,
-        })
-        .signers([buyer1]) // buyer1 must sign to authorize transfer
-        .rpc();
+      const tx = await transferTicket(eventPda, ticketPda, buyer1, recipient);
 
       console.log("Ticket transferred:", tx);
 
@@ -558,11 +843,14 @@ describe("event_ticketing", () => {
 
       try {
         await program.methods
-          .transferTicket()
+          .approveTransfer()
           .accounts({
+            event: eventPda,
             ticket: ticketPda,
-            currentOwner: buyer3.publicKey, // Wrong owner!
-            newOwner: recipient.publicKey,
+            pendingTransfer: getPendingTransferPda(ticketPda)[0],
+            recipient: recipient.publicKey,
+            owner: buyer3.publicKey,
+            systemProgram: SystemProgram.programId,
           })
           .signers([buyer3])
           .rpc();
@@ -581,11 +869,11 @@ describe("event_ticketing", () => {
 
       // First, check in the ticket (mark as used)
       await program.methods
-        .checkIn()
+        .checkIn(nextScanNonce())
         .accounts({
           event: eventPda,
           ticket: ticketPda,
-          eventAuthority: eventAuthority.publicKey,
+          operator: eventAuthority.publicKey,
         })
         .signers([eventAuthority])
         .rpc();
@@ -595,11 +883,14 @@ describe("event_ticketing", () => {
 
       try {
         await program.methods
-          .transferTicket()
+          .approveTransfer()
           .accounts({
+            event: eventPda,
             ticket: ticketPda,
-            currentOwner: buyer2.publicKey,
-            newOwner: recipient.publicKey,
+            pendingTransfer: getPendingTransferPda(ticketPda)[0],
+            recipient: recipient.publicKey,
+            owner: buyer2.publicKey,
+            systemProgram: SystemProgram.programId,
           })
           .signers([buyer2])
           .rpc();
@@ -623,11 +914,11 @@ describe("event_ticketing", () => {
 
       // Check in the ticket
       const tx = await program.methods
-        .checkIn()
+        .checkIn(nextScanNonce())
         .accounts({
           event: eventPda,
           ticket: ticketPda,
-          eventAuthority: eventAuthority.publicKey,
+          operator: eventAuthority.publicKey,
         })
         .signers([eventAuthority]) // Only event authority can check in
         .rpc();
@@ -636,7 +927,7 @@ describe("event_ticketing", () => {
 
       // Fetch the ticket and verify it's marked as used
       const ticketAccount = await program.account.ticket.fetch(ticketPda);
-      expect(ticketAccount.isUsed).to.be.true;
+      expect(ticketAccount.checkedInCount).to.equal(1);
     });
 
     // UNHAPPY PATH: Try to check in twice
@@ -646,11 +937,11 @@ describe("event_ticketing", () => {
 
       try {
         await program.methods
-          .checkIn()
+          .checkIn(nextScanNonce())
           .accounts({
             event: eventPda,
             ticket: ticketPda,
-            eventAuthority: eventAuthority.publicKey,
+            operator: eventAuthority.publicKey,
           })
           .signers([eventAuthority])
           .rpc();
@@ -677,10 +968,15 @@ describe("event_ticketing", () => {
           ticketPrice,
           5,
           "Unauthorized Test Event",
-          eventDate
+          eventDate,
+          true,
+          null,
+          false,
+          null
         )
         .accounts({
           event: unauthorizedEventPda,
+          registryPage: await getRegistryPagePda(),
           eventAuthority: eventAuthority.publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -698,6 +994,7 @@ describe("event_ticketing", () => {
           ticket: ticketPda,
           vault: vaultPda,
           buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
           systemProgram: SystemProgram.programId,
         })
         .signers([buyer1])
@@ -706,11 +1003,11 @@ describe("event_ticketing", () => {
       // Try to check in with buyer1 (not the event authority)
       try {
         await program.methods
-          .checkIn()
+          .checkIn(nextScanNonce())
           .accounts({
             event: unauthorizedEventPda,
             ticket: ticketPda,
-            eventAuthority: buyer1.publicKey, // Wrong authority!
+            operator: buyer1.publicKey, // Wrong authority!
           })
           .signers([buyer1])
           .rpc();
@@ -744,10 +1041,15 @@ describe("event_ticketing", () => {
           ticketPrice,
           5,
           "Event to be Canceled",
-          eventDate
+          eventDate,
+          true,
+          null,
+          false,
+          null
         )
         .accounts({
           event: cancelTestEventPda,
+          registryPage: await getRegistryPagePda(),
           eventAuthority: eventAuthority.publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -770,7 +1072,7 @@ describe("event_ticketing", () => {
       const eventAccount = await program.account.event.fetch(
         cancelTestEventPda
       );
-      expect(eventAccount.canceled).to.be.true;
+      expect(eventAccount.canceled).to.equal(1);
     });
 
     // UNHAPPY PATH: Try to cancel event as non-authority
@@ -787,10 +1089,15 @@ describe("event_ticketing", () => {
           ticketPrice,
           5,
           "Another Event",
-          eventDate
+          eventDate,
+          true,
+          null,
+          false,
+          null
         )
         .accounts({
           event: anotherEventPda,
+          registryPage: await getRegistryPagePda(),
           eventAuthority: eventAuthority.publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -836,10 +1143,15 @@ describe("event_ticketing", () => {
           ticketPrice,
           5,
           "Refundable Event",
-          eventDate
+          eventDate,
+          true,
+          null,
+          false,
+          null
         )
         .accounts({
           event: refundEventPda,
+          registryPage: await getRegistryPagePda(),
           eventAuthority: eventAuthority.publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -857,6 +1169,7 @@ describe("event_ticketing", () => {
           ticket: refundTicketPda,
           vault: refundVaultPda,
           buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
           systemProgram: SystemProgram.programId,
         })
         .signers([buyer1])
@@ -869,6 +1182,8 @@ describe("event_ticketing", () => {
         buyer1.publicKey
       );
 
+      await openRefundWindow(refundEventPda);
+
       await program.methods
         .refund()
         .accounts({
@@ -908,10 +1223,15 @@ describe("event_ticketing", () => {
           ticketPrice,
           5,
           "Self Refund Test Event",
-          eventDate
+          eventDate,
+          true,
+          null,
+          false,
+          null
         )
         .accounts({
           event: selfRefundEventPda,
+          registryPage: await getRegistryPagePda(),
           eventAuthority: eventAuthority.publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -928,6 +1248,7 @@ describe("event_ticketing", () => {
           ticket: selfRefundTicketPda,
           vault: selfRefundVaultPda,
           buyer: buyer2.publicKey,
+          payer: buyer2.publicKey,
           systemProgram: SystemProgram.programId,
         })
         .signers([buyer2])
@@ -969,10 +1290,15 @@ describe("event_ticketing", () => {
           ticketPrice,
           5,
           "Used Ticket Event",
-          eventDate
+          eventDate,
+          true,
+          null,
+          false,
+          null
         )
         .accounts({
           event: usedEventPda,
+          registryPage: await getRegistryPagePda(),
           eventAuthority: eventAuthority.publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -989,6 +1315,7 @@ describe("event_ticketing", () => {
           ticket: usedTicketPda,
           vault: usedVaultPda,
           buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
           systemProgram: SystemProgram.programId,
         })
         .signers([buyer1])
@@ -996,11 +1323,11 @@ describe("event_ticketing", () => {
 
       // Check in the ticket (mark as used)
       await program.methods
-        .checkIn()
+        .checkIn(nextScanNonce())
         .accounts({
           event: usedEventPda,
           ticket: usedTicketPda,
-          eventAuthority: eventAuthority.publicKey,
+          operator: eventAuthority.publicKey,
         })
         .signers([eventAuthority])
         .rpc();
@@ -1026,6 +1353,8709 @@ describe("event_ticketing", () => {
         expect(error.toString()).to.include("CannotRefundUsedTicket");
       }
     });
+
+    // UNHAPPY PATH: Refund cannot be routed to someone other than the owner
+    it("Fails to refund to a wallet that does not own the ticket", async () => {
+      const [selfRefundEventPda] = getEventPda(eventAuthority.publicKey, 102);
+      const [selfRefundTicketPda] = getTicketPda(selfRefundEventPda, 0);
+      const [selfRefundVaultPda] = getVaultPda(selfRefundEventPda);
+
+      try {
+        await program.methods
+          .refund()
+          .accounts({
+            event: selfRefundEventPda,
+            ticket: selfRefundTicketPda,
+            vault: selfRefundVaultPda,
+            ticketOwner: buyer3.publicKey, // Ticket belongs to buyer2
+            eventAuthority: eventAuthority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([eventAuthority])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidRefundRecipient");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: set_event_authority / accept_event_authority instructions
+  // ============================================================================
+  describe("set_event_authority", () => {
+    const handoverEventId = 110;
+    let handoverEventPda: PublicKey;
+
+    before(async () => {
+      [handoverEventPda] = getEventPda(eventAuthority.publicKey, handoverEventId);
+
+      await program.methods
+        .initializeEvent(
+          handoverEventId,
+          ticketPrice,
+          5,
+          "Handover Event",
+          eventDate,
+          true,
+          null,
+          false,
+          null
+        )
+        .accounts({
+          event: handoverEventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // HAPPY PATH: Authority proposes a new authority
+    it("Successfully proposes a new event authority", async () => {
+      await program.methods
+        .setEventAuthority(buyer3.publicKey)
+        .accounts({
+          event: handoverEventPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const eventAccount = await program.account.event.fetch(handoverEventPda);
+      expect(eventAccount.pendingAuthority.toString()).to.equal(
+        buyer3.publicKey.toString()
+      );
+      // Nothing changes until the new authority accepts
+      expect(eventAccount.eventAuthority.toString()).to.equal(
+        eventAuthority.publicKey.toString()
+      );
+    });
+
+    // UNHAPPY PATH: Someone other than the pending authority tries to accept
+    it("Fails to accept as a non-pending wallet", async () => {
+      try {
+        await program.methods
+          .acceptEventAuthority()
+          .accounts({
+            event: handoverEventPda,
+            newAuthority: buyer2.publicKey,
+          })
+          .signers([buyer2])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("NotPendingAuthority");
+      }
+    });
+
+    // HAPPY PATH: Pending authority accepts the handover
+    it("Successfully accepts the event authority", async () => {
+      await program.methods
+        .acceptEventAuthority()
+        .accounts({
+          event: handoverEventPda,
+          newAuthority: buyer3.publicKey,
+        })
+        .signers([buyer3])
+        .rpc();
+
+      const eventAccount = await program.account.event.fetch(handoverEventPda);
+      expect(eventAccount.eventAuthority.toString()).to.equal(
+        buyer3.publicKey.toString()
+      );
+      expect(eventAccount.pendingAuthority.equals(PublicKey.default)).to.be.true;
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: set_event_multisig / withdraw_proceeds instructions
+  // ============================================================================
+  describe("event multisig", () => {
+    const multisigEventId = 120;
+    let multisigEventPda: PublicKey;
+    let authorityConfigPda: PublicKey;
+
+    before(async () => {
+      [multisigEventPda] = getEventPda(eventAuthority.publicKey, multisigEventId);
+      [authorityConfigPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("authority_config"), multisigEventPda.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initializeEvent(
+          multisigEventId,
+          ticketPrice,
+          5,
+          "Multisig Event",
+          eventDate,
+          true,
+          null,
+          false,
+          null
+        )
+        .accounts({
+          event: multisigEventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      // Sell one ticket so the vault has proceeds to withdraw
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: multisigEventPda,
+          ticket: getTicketPda(multisigEventPda, 0)[0],
+          vault: getVaultPda(multisigEventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+    });
+
+    // UNHAPPY PATH: Proceeds stay locked while the event has no end time
+    it("Fails to withdraw proceeds before the event has ended", async () => {
+      try {
+        await program.methods
+          .withdrawProceeds(new anchor.BN(0.01 * LAMPORTS_PER_SOL))
+          .accounts({
+            event: multisigEventPda,
+            authorityConfig: null,
+            vault: getVaultPda(multisigEventPda)[0],
+            destination: eventAuthority.publicKey,
+            eventAuthority: eventAuthority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("PayoutTimelocked");
+      }
+    });
+
+    // HAPPY PATH: Configure a 2-of-3 multisig
+    it("Successfully configures a 2-of-3 multisig", async () => {
+      await program.methods
+        .setEventMultisig(
+          [eventAuthority.publicKey, buyer2.publicKey, buyer3.publicKey],
+          2
+        )
+        .accounts({
+          event: multisigEventPda,
+          authorityConfig: authorityConfigPda,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const eventAccount = await program.account.event.fetch(multisigEventPda);
+      expect(eventAccount.multisigEnabled).to.equal(1);
+    });
+
+    // UNHAPPY PATH: A single signer is no longer enough
+    it("Fails to cancel with only one signer", async () => {
+      try {
+        await program.methods
+          .cancelEvent()
+          .accounts({
+            event: multisigEventPda,
+            authorityConfig: authorityConfigPda,
+            eventAuthority: eventAuthority.publicKey,
+          })
+          .signers([eventAuthority])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InsufficientSigners");
+      }
+    });
+
+    // UNHAPPY PATH: Money settings need the multisig too
+    it("Fails to change the transfer fee with only one signer", async () => {
+      try {
+        await program.methods
+          .setTransferFee(new anchor.BN(1_000_000))
+          .accounts({
+            event: multisigEventPda,
+            authorityConfig: authorityConfigPda,
+            eventAuthority: eventAuthority.publicKey,
+          })
+          .signers([eventAuthority])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InsufficientSigners");
+      }
+    });
+
+    // HAPPY PATH: Two configured signers change the transfer fee together
+    it("Successfully changes the transfer fee with two signers", async () => {
+      await program.methods
+        .setTransferFee(new anchor.BN(1_000_000))
+        .accounts({
+          event: multisigEventPda,
+          authorityConfig: authorityConfigPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .remainingAccounts([
+          { pubkey: buyer2.publicKey, isSigner: true, isWritable: false },
+        ])
+        .signers([eventAuthority, buyer2])
+        .rpc();
+
+      const eventAccount = await program.account.event.fetch(multisigEventPda);
+      expect(eventAccount.transferFeeLamports.toNumber()).to.equal(1_000_000);
+    });
+
+    // HAPPY PATH: Two configured signers cancel together
+    it("Successfully cancels with two signers", async () => {
+      await program.methods
+        .cancelEvent()
+        .accounts({
+          event: multisigEventPda,
+          authorityConfig: authorityConfigPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .remainingAccounts([
+          { pubkey: buyer2.publicKey, isSigner: true, isWritable: false },
+        ])
+        .signers([eventAuthority, buyer2])
+        .rpc();
+
+      const eventAccount = await program.account.event.fetch(multisigEventPda);
+      expect(eventAccount.canceled).to.equal(1);
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: add_staff / remove_staff instructions
+  // ============================================================================
+  describe("staff delegation", () => {
+    const staffEventId = 130;
+    const doorWorker = Keypair.generate();
+    let staffEventPda: PublicKey;
+    let staffPda: PublicKey;
+
+    before(async () => {
+      [staffEventPda] = getEventPda(eventAuthority.publicKey, staffEventId);
+      [staffPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("staff"),
+          staffEventPda.toBuffer(),
+          doorWorker.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .initializeEvent(staffEventId, ticketPrice, 5, "Staffed Event", eventDate, true, null, false, null)
+        .accounts({
+          event: staffEventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      for (const ticketId of [0, 1]) {
+        await program.methods
+          .mintTicket()
+          .accounts({
+            event: staffEventPda,
+            ticket: getTicketPda(staffEventPda, ticketId)[0],
+            vault: getVaultPda(staffEventPda)[0],
+            buyer: buyer1.publicKey,
+            payer: buyer1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+      }
+    });
+
+    // HAPPY PATH: Authority delegates check-in to a door worker
+    it("Successfully adds staff and lets them check in", async () => {
+      await program.methods
+        .addStaff(doorWorker.publicKey)
+        .accounts({
+          event: staffEventPda,
+          staffAccount: staffPda,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const [ticketPda] = getTicketPda(staffEventPda, 0);
+      await program.methods
+        .checkIn(nextScanNonce())
+        .accounts({
+          event: staffEventPda,
+          ticket: ticketPda,
+          staff: staffPda,
+          operator: doorWorker.publicKey,
+        })
+        .signers([doorWorker])
+        .rpc();
+
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      expect(ticketAccount.checkedInCount).to.equal(1);
+      // The door worker is recorded as the operator who admitted the holder
+      expect(ticketAccount.checkedInBy.toString()).to.equal(
+        doorWorker.publicKey.toString()
+      );
+      expect(ticketAccount.checkedInAt.toNumber()).to.be.greaterThan(0);
+    });
+
+    // UNHAPPY PATH: Removed staff can no longer check in
+    it("Fails to check in after staff is removed", async () => {
+      await program.methods
+        .removeStaff()
+        .accounts({
+          event: staffEventPda,
+          staffAccount: staffPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      try {
+        await program.methods
+          .checkIn(nextScanNonce())
+          .accounts({
+            event: staffEventPda,
+            ticket: getTicketPda(staffEventPda, 1)[0],
+            staff: null,
+            operator: doorWorker.publicKey,
+          })
+          .signers([doorWorker])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedCheckIn");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: check_in_batch instruction
+  // ============================================================================
+  describe("check_in_batch", () => {
+    const batchEventId = 140;
+    let batchEventPda: PublicKey;
+
+    before(async () => {
+      [batchEventPda] = getEventPda(eventAuthority.publicKey, batchEventId);
+
+      await program.methods
+        .initializeEvent(batchEventId, ticketPrice, 5, "Batch Event", eventDate, true, null, false, null)
+        .accounts({
+          event: batchEventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      for (const ticketId of [0, 1, 2]) {
+        await program.methods
+          .mintTicket()
+          .accounts({
+            event: batchEventPda,
+            ticket: getTicketPda(batchEventPda, ticketId)[0],
+            vault: getVaultPda(batchEventPda)[0],
+            buyer: buyer2.publicKey,
+            payer: buyer2.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer2])
+          .rpc();
+      }
+    });
+
+    // HAPPY PATH: Check in several tickets in one transaction
+    it("Successfully checks in a batch of tickets", async () => {
+      const ticketPdas = [0, 1].map((id) => getTicketPda(batchEventPda, id)[0]);
+
+      await program.methods
+        .checkInBatch(ticketPdas.map(() => nextScanNonce()))
+        .accounts({
+          event: batchEventPda,
+          staff: null,
+          operator: eventAuthority.publicKey,
+        })
+        .remainingAccounts(
+          ticketPdas.map((pubkey) => ({
+            pubkey,
+            isSigner: false,
+            isWritable: true,
+          }))
+        )
+        .signers([eventAuthority])
+        .rpc();
+
+      for (const ticketPda of ticketPdas) {
+        const ticketAccount = await program.account.ticket.fetch(ticketPda);
+        expect(ticketAccount.checkedInCount).to.equal(1);
+      }
+    });
+
+    // UNHAPPY PATH: One already-used ticket fails the whole batch
+    it("Fails the batch if any ticket is already used", async () => {
+      const ticketPdas = [2, 0].map((id) => getTicketPda(batchEventPda, id)[0]);
+
+      try {
+        await program.methods
+          .checkInBatch(ticketPdas.map(() => nextScanNonce()))
+          .accounts({
+            event: batchEventPda,
+            staff: null,
+            operator: eventAuthority.publicKey,
+          })
+          .remainingAccounts(
+            ticketPdas.map((pubkey) => ({
+              pubkey,
+              isSigner: false,
+              isWritable: true,
+            }))
+          )
+          .signers([eventAuthority])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("AlreadyCheckedIn");
+      }
+
+      // The valid ticket in the failed batch was not consumed
+      const ticketAccount = await program.account.ticket.fetch(ticketPdas[0]);
+      expect(ticketAccount.checkedInCount).to.equal(0);
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: check_in_with_voucher instruction
+  // ============================================================================
+  describe("check_in_with_voucher", () => {
+    const voucherEventId = 150;
+    let voucherEventPda: PublicKey;
+
+    /**
+     * Builds the voucher message the ticket owner signs:
+     * event (32 bytes) || ticket_id (u32 LE) || nonce (u64 LE)
+     */
+    function voucherMessage(
+      eventPda: PublicKey,
+      ticketId: number,
+      nonce: number
+    ): Buffer {
+      const message = Buffer.alloc(44);
+      eventPda.toBuffer().copy(message, 0);
+      message.writeUInt32LE(ticketId, 32);
+      message.writeBigUInt64LE(BigInt(nonce), 36);
+      return message;
+    }
+
+    before(async () => {
+      [voucherEventPda] = getEventPda(eventAuthority.publicKey, voucherEventId);
+
+      await program.methods
+        .initializeEvent(voucherEventId, ticketPrice, 5, "QR Event", eventDate, true, null, false, null)
+        .accounts({
+          event: voucherEventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      for (const ticketId of [0, 1]) {
+        await program.methods
+          .mintTicket()
+          .accounts({
+            event: voucherEventPda,
+            ticket: getTicketPda(voucherEventPda, ticketId)[0],
+            vault: getVaultPda(voucherEventPda)[0],
+            buyer: buyer1.publicKey,
+            payer: buyer1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+      }
+    });
+
+    // HAPPY PATH: Owner-signed voucher checks the ticket in
+    it("Successfully checks in with an owner-signed voucher", async () => {
+      const [ticketPda] = getTicketPda(voucherEventPda, 0);
+      const verifyIx = Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: buyer1.secretKey,
+        message: voucherMessage(voucherEventPda, 0, 1),
+      });
+
+      await program.methods
+        .checkInWithVoucher(new anchor.BN(1))
+        .accounts({
+          event: voucherEventPda,
+          ticket: ticketPda,
+          staff: null,
+          operator: eventAuthority.publicKey,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .preInstructions([verifyIx])
+        .signers([eventAuthority])
+        .rpc();
+
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      expect(ticketAccount.checkedInCount).to.equal(1);
+    });
+
+    // UNHAPPY PATH: Voucher signed by someone other than the owner
+    it("Fails with a voucher not signed by the ticket owner", async () => {
+      const verifyIx = Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: buyer2.secretKey,
+        message: voucherMessage(voucherEventPda, 1, 1),
+      });
+
+      try {
+        await program.methods
+          .checkInWithVoucher(new anchor.BN(1))
+          .accounts({
+            event: voucherEventPda,
+            ticket: getTicketPda(voucherEventPda, 1)[0],
+            staff: null,
+            operator: eventAuthority.publicKey,
+            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          })
+          .preInstructions([verifyIx])
+          .signers([eventAuthority])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidVoucher");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: set_doors_window instruction
+  // ============================================================================
+  describe("set_doors_window", () => {
+    const doorsEventId = 160;
+    let doorsEventPda: PublicKey;
+
+    before(async () => {
+      [doorsEventPda] = getEventPda(eventAuthority.publicKey, doorsEventId);
+
+      await program.methods
+        .initializeEvent(doorsEventId, ticketPrice, 5, "Doors Event", eventDate, true, null, false, null)
+        .accounts({
+          event: doorsEventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: doorsEventPda,
+          ticket: getTicketPda(doorsEventPda, 0)[0],
+          vault: getVaultPda(doorsEventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+    });
+
+    // UNHAPPY PATH: Check-in before doors open is rejected
+    it("Fails to check in before doors open", async () => {
+      const now = Math.floor(Date.now() / 1000);
+
+      await program.methods
+        .setDoorsWindow(new anchor.BN(now + 3600), new anchor.BN(now + 7200))
+        .accounts({
+          event: doorsEventPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      try {
+        await program.methods
+          .checkIn(nextScanNonce())
+          .accounts({
+            event: doorsEventPda,
+            ticket: getTicketPda(doorsEventPda, 0)[0],
+            staff: null,
+            operator: eventAuthority.publicKey,
+          })
+          .signers([eventAuthority])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("DoorsClosed");
+      }
+    });
+
+    // UNHAPPY PATH: Doors must open before they close
+    it("Fails to set an inverted doors window", async () => {
+      try {
+        await program.methods
+          .setDoorsWindow(new anchor.BN(2000), new anchor.BN(1000))
+          .accounts({
+            event: doorsEventPda,
+            eventAuthority: eventAuthority.publicKey,
+          })
+          .signers([eventAuthority])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidDoorsWindow");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: set_max_check_ins instruction (multi-day passes)
+  // ============================================================================
+  describe("set_max_check_ins", () => {
+    const festivalEventId = 170;
+    let festivalEventPda: PublicKey;
+    let festivalTicketPda: PublicKey;
+
+    before(async () => {
+      [festivalEventPda] = getEventPda(eventAuthority.publicKey, festivalEventId);
+      [festivalTicketPda] = getTicketPda(festivalEventPda, 0);
+
+      await program.methods
+        .initializeEvent(festivalEventId, ticketPrice, 5, "Festival", eventDate, true, null, false, null)
+        .accounts({
+          event: festivalEventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .setMaxCheckIns(3)
+        .accounts({
+          event: festivalEventPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: festivalEventPda,
+          ticket: festivalTicketPda,
+          vault: getVaultPda(festivalEventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+    });
+
+    // HAPPY PATH: First day's scan is accepted
+    it("Successfully checks in a festival pass", async () => {
+      await program.methods
+        .checkIn(nextScanNonce())
+        .accounts({
+          event: festivalEventPda,
+          ticket: festivalTicketPda,
+          staff: null,
+          operator: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const ticketAccount = await program.account.ticket.fetch(festivalTicketPda);
+      expect(ticketAccount.checkedInCount).to.equal(1);
+    });
+
+    // UNHAPPY PATH: A second scan on the same day is rejected
+    it("Fails to check in twice on the same day", async () => {
+      try {
+        await program.methods
+          .checkIn(nextScanNonce())
+          .accounts({
+            event: festivalEventPda,
+            ticket: festivalTicketPda,
+            staff: null,
+            operator: eventAuthority.publicKey,
+          })
+          .signers([eventAuthority])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("AlreadyCheckedInToday");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: soulbound (non-transferable) events
+  // ============================================================================
+  describe("soulbound tickets", () => {
+    // UNHAPPY PATH: Tickets of a non-transferable event cannot move
+    it("Fails to transfer a ticket of a non-transferable event", async () => {
+      const soulboundEventId = 180;
+      const [eventPda] = getEventPda(eventAuthority.publicKey, soulboundEventId);
+      const [ticketPda] = getTicketPda(eventPda, 0);
+
+      await program.methods
+        .initializeEvent(
+          soulboundEventId,
+          ticketPrice,
+          5,
+          "ID-Bound Event",
+          eventDate,
+          false,
+          null,
+          false,
+          null
+        )
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      try {
+        await program.methods
+          .approveTransfer()
+          .accounts({
+            event: eventPda,
+            ticket: ticketPda,
+            pendingTransfer: getPendingTransferPda(ticketPda)[0],
+            recipient: buyer2.publicKey,
+            owner: buyer1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("TicketNotTransferable");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: freeze_transfers / unfreeze_transfers instructions
+  // ============================================================================
+  describe("freeze_transfers", () => {
+    const freezeEventId = 190;
+    let eventPda: PublicKey;
+    let ticketPda: PublicKey;
+
+    before(async () => {
+      [eventPda] = getEventPda(eventAuthority.publicKey, freezeEventId);
+      [ticketPda] = getTicketPda(eventPda, 0);
+
+      await program.methods
+        .initializeEvent(
+          freezeEventId,
+          ticketPrice,
+          5,
+          "Freezable Event",
+          eventDate,
+          true,
+          null,
+          false,
+          null
+        )
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      await program.methods
+        .freezeTransfers()
+        .accounts({ event: eventPda, eventAuthority: eventAuthority.publicKey })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // UNHAPPY PATH: Transfers fail while frozen
+    it("Fails to transfer while transfers are frozen", async () => {
+      try {
+        await program.methods
+          .approveTransfer()
+          .accounts({
+            event: eventPda,
+            ticket: ticketPda,
+            pendingTransfer: getPendingTransferPda(ticketPda)[0],
+            recipient: buyer2.publicKey,
+            owner: buyer1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("TransfersFrozen");
+      }
+    });
+
+    // UNHAPPY PATH: Listings are transfers too and fail while frozen
+    it("Fails to list while transfers are frozen", async () => {
+      try {
+        await program.methods
+          .listTicket(ticketPrice)
+          .accounts({
+            event: eventPda,
+            ticket: ticketPda,
+            listing: PublicKey.findProgramAddressSync(
+              [Buffer.from("listing"), ticketPda.toBuffer()],
+              program.programId
+            )[0],
+            seller: buyer1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("TransfersFrozen");
+      }
+    });
+
+    // HAPPY PATH: Transfers work again once unfrozen
+    it("Successfully transfers after unfreezing", async () => {
+      await program.methods
+        .unfreezeTransfers()
+        .accounts({ event: eventPda, eventAuthority: eventAuthority.publicKey })
+        .signers([eventAuthority])
+        .rpc();
+
+      await transferTicket(eventPda, ticketPda, buyer1, buyer2);
+
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      expect(ticketAccount.owner.toString()).to.equal(
+        buyer2.publicKey.toString()
+      );
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: set_transfer_fee instruction
+  // ============================================================================
+  describe("set_transfer_fee", () => {
+    // HAPPY PATH: Transfer fee is charged to the sender and lands in the vault
+    it("Successfully charges the transfer fee into the vault", async () => {
+      const feeEventId = 191;
+      const transferFee = new anchor.BN(0.01 * LAMPORTS_PER_SOL);
+      const [eventPda] = getEventPda(eventAuthority.publicKey, feeEventId);
+      const [ticketPda] = getTicketPda(eventPda, 0);
+      const [vaultPda] = getVaultPda(eventPda);
+
+      await program.methods
+        .initializeEvent(feeEventId, ticketPrice, 5, "Fee Event", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .setTransferFee(transferFee)
+        .accounts({ event: eventPda, eventAuthority: eventAuthority.publicKey })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const vaultBalanceBefore = await provider.connection.getBalance(vaultPda);
+
+      await transferTicket(eventPda, ticketPda, buyer1, buyer2);
+
+      const vaultBalanceAfter = await provider.connection.getBalance(vaultPda);
+      expect(vaultBalanceAfter - vaultBalanceBefore).to.equal(
+        transferFee.toNumber()
+      );
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: list_ticket / delist_ticket / buy_listed_ticket instructions
+  // ============================================================================
+  describe("resale marketplace", () => {
+    const resaleEventId = 200;
+    const askPrice = new anchor.BN(0.2 * LAMPORTS_PER_SOL);
+    let eventPda: PublicKey;
+    let ticketPda: PublicKey;
+    let listingPda: PublicKey;
+
+    before(async () => {
+      [eventPda] = getEventPda(eventAuthority.publicKey, resaleEventId);
+      [ticketPda] = getTicketPda(eventPda, 0);
+      [listingPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("listing"), ticketPda.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initializeEvent(
+          resaleEventId,
+          ticketPrice,
+          5,
+          "Resale Event",
+          eventDate,
+          true,
+          null,
+          false,
+          null
+        )
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+    });
+
+    // HAPPY PATH: Owner lists and delists a ticket
+    it("Successfully lists and delists a ticket", async () => {
+      await program.methods
+        .listTicket(askPrice)
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          listing: listingPda,
+          seller: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const listingAccount = await program.account.listing.fetch(listingPda);
+      expect(listingAccount.price.toNumber()).to.equal(askPrice.toNumber());
+
+      await program.methods
+        .delistTicket()
+        .accounts({
+          ticket: ticketPda,
+          listing: listingPda,
+          seller: buyer1.publicKey,
+          authority: buyer1.publicKey,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const closed = await provider.connection.getAccountInfo(listingPda);
+      expect(closed).to.be.null;
+    });
+
+    // HAPPY PATH: Buyer pays the seller and receives the ticket atomically
+    it("Successfully buys a listed ticket", async () => {
+      await program.methods
+        .listTicket(askPrice)
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          listing: listingPda,
+          seller: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const sellerBalanceBefore = await provider.connection.getBalance(
+        buyer1.publicKey
+      );
+
+      await program.methods
+        .buyListedTicket(askPrice)
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          listing: listingPda,
+          vault: getVaultPda(eventPda)[0],
+          seller: buyer1.publicKey,
+          buyer: buyer2.publicKey,
+          config: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer2])
+        .rpc();
+
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      expect(ticketAccount.owner.toString()).to.equal(
+        buyer2.publicKey.toString()
+      );
+
+      // Seller receives the asking price plus the listing rent back
+      const sellerBalanceAfter = await provider.connection.getBalance(
+        buyer1.publicKey
+      );
+      expect(sellerBalanceAfter - sellerBalanceBefore).to.be.at.least(
+        askPrice.toNumber()
+      );
+    });
+
+    // UNHAPPY PATH: The buyer's maximum protects against a raised relisting
+    it("Fails to buy a listing priced above the buyer's maximum", async () => {
+      await program.methods
+        .listTicket(askPrice.muln(2))
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          listing: listingPda,
+          seller: buyer2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer2])
+        .rpc();
+
+      try {
+        await program.methods
+          .buyListedTicket(askPrice)
+          .accounts({
+            event: eventPda,
+            ticket: ticketPda,
+            listing: listingPda,
+            vault: getVaultPda(eventPda)[0],
+            seller: buyer2.publicKey,
+            buyer: buyer3.publicKey,
+            config: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer3])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("ListingPriceAboveMax");
+      }
+
+      await program.methods
+        .delistTicket()
+        .accounts({
+          ticket: ticketPda,
+          listing: listingPda,
+          seller: buyer2.publicKey,
+          authority: buyer2.publicKey,
+        })
+        .signers([buyer2])
+        .rpc();
+    });
+
+    // UNHAPPY PATH: Only the owner can list
+    it("Fails to list someone else's ticket", async () => {
+      try {
+        await program.methods
+          .listTicket(askPrice)
+          .accounts({
+            event: eventPda,
+            ticket: ticketPda,
+            listing: listingPda,
+            seller: buyer3.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer3])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedListing");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: set_max_resale_multiplier instruction
+  // ============================================================================
+  describe("set_max_resale_multiplier", () => {
+    // UNHAPPY PATH: Listing above 110% of face value is rejected
+    it("Fails to list above the resale cap", async () => {
+      const cappedEventId = 210;
+      const [eventPda] = getEventPda(eventAuthority.publicKey, cappedEventId);
+      const [ticketPda] = getTicketPda(eventPda, 0);
+      const [listingPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("listing"), ticketPda.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initializeEvent(cappedEventId, ticketPrice, 5, "Capped", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .setMaxResaleMultiplier(11_000)
+        .accounts({ event: eventPda, eventAuthority: eventAuthority.publicKey })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      try {
+        await program.methods
+          .listTicket(ticketPrice.muln(2))
+          .accounts({
+            event: eventPda,
+            ticket: ticketPda,
+            listing: listingPda,
+            seller: buyer1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("ResalePriceTooHigh");
+      }
+    });
+
+    // UNHAPPY PATH: Listings made before the cap was lowered can't be bought
+    it("Fails to buy a listing priced above a lowered resale cap", async () => {
+      const loweredCapEventId = 950;
+      const [eventPda] = getEventPda(
+        eventAuthority.publicKey,
+        loweredCapEventId
+      );
+      const [ticketPda] = getTicketPda(eventPda, 0);
+      const [listingPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("listing"), ticketPda.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initializeEvent(loweredCapEventId, ticketPrice, 5, "Lowered Cap", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      await program.methods
+        .listTicket(ticketPrice.muln(2))
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          listing: listingPda,
+          seller: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      await program.methods
+        .setMaxResaleMultiplier(11_000)
+        .accounts({ event: eventPda, eventAuthority: eventAuthority.publicKey })
+        .signers([eventAuthority])
+        .rpc();
+
+      try {
+        await program.methods
+          .buyListedTicket(ticketPrice.muln(2))
+          .accounts({
+            event: eventPda,
+            ticket: ticketPda,
+            listing: listingPda,
+            vault: getVaultPda(eventPda)[0],
+            seller: buyer1.publicKey,
+            buyer: buyer2.publicKey,
+            config: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer2])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("ResalePriceTooHigh");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: mint_ticket_to instruction (gift minting)
+  // ============================================================================
+  describe("mint_ticket_to", () => {
+    // HAPPY PATH: Buyer pays, friend owns the ticket
+    it("Successfully mints a ticket for a different recipient", async () => {
+      const giftEventId = 220;
+      const friend = Keypair.generate();
+      const [eventPda] = getEventPda(eventAuthority.publicKey, giftEventId);
+      const [ticketPda] = getTicketPda(eventPda, 0);
+
+      await program.methods
+        .initializeEvent(giftEventId, ticketPrice, 5, "Gift Event", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicketTo(friend.publicKey)
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      expect(ticketAccount.owner.toString()).to.equal(
+        friend.publicKey.toString()
+      );
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: mint_tickets instruction (bulk mint)
+  // ============================================================================
+  describe("mint_tickets", () => {
+    const bulkEventId = 230;
+    let eventPda: PublicKey;
+
+    before(async () => {
+      [eventPda] = getEventPda(eventAuthority.publicKey, bulkEventId);
+
+      await program.methods
+        .initializeEvent(bulkEventId, ticketPrice, 4, "Bulk Event", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // HAPPY PATH: Buy three seats in one instruction
+    it("Successfully mints several tickets at once", async () => {
+      const [vaultPda] = getVaultPda(eventPda);
+      const ticketPdas = [0, 1, 2].map((id) => getTicketPda(eventPda, id)[0]);
+      const vaultBalanceBefore = await provider.connection.getBalance(vaultPda);
+
+      await program.methods
+        .mintTickets(3)
+        .accounts({
+          event: eventPda,
+          vault: vaultPda,
+          buyer: buyer2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+          ticketPdas.map((pubkey) => ({
+            pubkey,
+            isSigner: false,
+            isWritable: true,
+          }))
+        )
+        .signers([buyer2])
+        .rpc();
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.sold).to.equal(3);
+
+      const vaultBalanceAfter = await provider.connection.getBalance(vaultPda);
+      expect(vaultBalanceAfter - vaultBalanceBefore).to.equal(
+        ticketPrice.toNumber() * 3
+      );
+
+      for (const [id, ticketPda] of ticketPdas.entries()) {
+        const ticketAccount = await program.account.ticket.fetch(ticketPda);
+        expect(ticketAccount.ticketId).to.equal(id);
+        expect(ticketAccount.owner.toString()).to.equal(
+          buyer2.publicKey.toString()
+        );
+      }
+    });
+
+    // UNHAPPY PATH: Asking for more tickets than remain
+    it("Fails to bulk mint beyond remaining supply", async () => {
+      const ticketPdas = [3, 4].map((id) => getTicketPda(eventPda, id)[0]);
+
+      try {
+        await program.methods
+          .mintTickets(2)
+          .accounts({
+            event: eventPda,
+            vault: getVaultPda(eventPda)[0],
+            buyer: buyer2.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(
+            ticketPdas.map((pubkey) => ({
+              pubkey,
+              isSigner: false,
+              isWritable: true,
+            }))
+          )
+          .signers([buyer2])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("EventSoldOut");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: mint_for_group instruction
+  // ============================================================================
+  describe("mint_for_group", () => {
+    // HAPPY PATH: One payer buys a ticket for each colleague
+    it("Successfully mints one ticket per group member", async () => {
+      const groupEventId = 240;
+      const colleagues = [Keypair.generate(), Keypair.generate()];
+      const [eventPda] = getEventPda(eventAuthority.publicKey, groupEventId);
+
+      await program.methods
+        .initializeEvent(groupEventId, ticketPrice, 5, "Offsite", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintForGroup()
+        .accounts({
+          event: eventPda,
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer3.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+          colleagues.flatMap((colleague, id) => [
+            { pubkey: colleague.publicKey, isSigner: false, isWritable: false },
+            {
+              pubkey: getTicketPda(eventPda, id)[0],
+              isSigner: false,
+              isWritable: true,
+            },
+          ])
+        )
+        .signers([buyer3])
+        .rpc();
+
+      for (const [id, colleague] of colleagues.entries()) {
+        const ticketAccount = await program.account.ticket.fetch(
+          getTicketPda(eventPda, id)[0]
+        );
+        expect(ticketAccount.owner.toString()).to.equal(
+          colleague.publicKey.toString()
+        );
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: set_max_per_wallet instruction
+  // ============================================================================
+  describe("set_max_per_wallet", () => {
+    // UNHAPPY PATH: Second purchase exceeds a 1-per-wallet limit
+    it("Fails to buy beyond the per-wallet limit", async () => {
+      const limitedEventId = 250;
+      const [eventPda] = getEventPda(eventAuthority.publicKey, limitedEventId);
+      const [vaultPda] = getVaultPda(eventPda);
+
+      await program.methods
+        .initializeEvent(limitedEventId, ticketPrice, 5, "Limited", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .setMaxPerWallet(1)
+        .accounts({ event: eventPda, eventAuthority: eventAuthority.publicKey })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, 0)[0],
+          vault: vaultPda,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      try {
+        await program.methods
+          .mintTicket()
+          .accounts({
+            event: eventPda,
+            ticket: getTicketPda(eventPda, 1)[0],
+            vault: vaultPda,
+            buyer: buyer1.publicKey,
+            payer: buyer1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("WalletLimitReached");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: mint_allowlisted instruction
+  // ============================================================================
+  describe("mint_allowlisted", () => {
+    const presaleEventId = 260;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, presaleEventId);
+    const [vaultPda] = getVaultPda(eventPda);
+
+    const sha256 = (...parts: Buffer[]) =>
+      createHash("sha256").update(Buffer.concat(parts)).digest();
+    // Pairs are hashed in sorted order, matching the program's verifier
+    const hashPair = (a: Buffer, b: Buffer) =>
+      Buffer.compare(a, b) <= 0 ? sha256(a, b) : sha256(b, a);
+
+    // Two-leaf allowlist: buyer1 and buyer2
+    const leaf1 = sha256(buyer1.publicKey.toBuffer());
+    const leaf2 = sha256(buyer2.publicKey.toBuffer());
+    const root = hashPair(leaf1, leaf2);
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(presaleEventId, ticketPrice, 5, "Presale", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .setAllowlistRoot([...root])
+        .accounts({ event: eventPda, eventAuthority: eventAuthority.publicKey })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // HAPPY PATH: Allowlisted buyer mints with a valid proof
+    it("Successfully mints with a valid merkle proof", async () => {
+      await program.methods
+        .mintAllowlisted([[...leaf2]])
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, 0)[0],
+          vault: vaultPda,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const ticketAccount = await program.account.ticket.fetch(
+        getTicketPda(eventPda, 0)[0]
+      );
+      expect(ticketAccount.owner.toString()).to.equal(
+        buyer1.publicKey.toString()
+      );
+    });
+
+    // UNHAPPY PATH: Buyer not on the allowlist reuses someone else's proof
+    it("Fails to mint when the buyer is not allowlisted", async () => {
+      try {
+        await program.methods
+          .mintAllowlisted([[...leaf2]])
+          .accounts({
+            event: eventPda,
+            ticket: getTicketPda(eventPda, 1)[0],
+            vault: vaultPda,
+            buyer: buyer3.publicKey,
+            payer: buyer3.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer3])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("NotAllowlisted");
+      }
+    });
+
+    // UNHAPPY PATH: Public mint is closed while the presale is active
+    it("Fails to mint publicly during the presale", async () => {
+      try {
+        await program.methods
+          .mintTicket()
+          .accounts({
+            event: eventPda,
+            ticket: getTicketPda(eventPda, 1)[0],
+            vault: vaultPda,
+            buyer: buyer3.publicKey,
+            payer: buyer3.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer3])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("PresaleActive");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: create_promo / mint_with_promo instructions
+  // ============================================================================
+  describe("promo codes", () => {
+    const promoEventId = 270;
+    const code = "EARLYBIRD";
+    const [eventPda] = getEventPda(eventAuthority.publicKey, promoEventId);
+    const [vaultPda] = getVaultPda(eventPda);
+    const [promoPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("promo"), eventPda.toBuffer(), Buffer.from(code)],
+      program.programId
+    );
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(promoEventId, ticketPrice, 5, "Promo Night", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      // 25% off, single use
+      await program.methods
+        .createPromo(code, 2500, 1)
+        .accounts({
+          event: eventPda,
+          promo: promoPda,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // HAPPY PATH: Buyer pays the discounted price
+    it("Successfully mints at the discounted price", async () => {
+      const vaultBefore = await provider.connection.getBalance(vaultPda);
+
+      await program.methods
+        .mintWithPromo(code)
+        .accounts({
+          sale: {
+            event: eventPda,
+            ticket: getTicketPda(eventPda, 0)[0],
+            vault: vaultPda,
+            buyer: buyer1.publicKey,
+            payer: buyer1.publicKey,
+            systemProgram: SystemProgram.programId,
+          },
+          promo: promoPda,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const vaultAfter = await provider.connection.getBalance(vaultPda);
+      expect(vaultAfter - vaultBefore).to.equal(
+        ticketPrice.toNumber() * 0.75
+      );
+
+      const promoAccount = await program.account.promo.fetch(promoPda);
+      expect(promoAccount.uses).to.equal(1);
+    });
+
+    // UNHAPPY PATH: Code has no uses left
+    it("Fails to redeem an exhausted promo code", async () => {
+      try {
+        await program.methods
+          .mintWithPromo(code)
+          .accounts({
+            sale: {
+              event: eventPda,
+              ticket: getTicketPda(eventPda, 1)[0],
+              vault: vaultPda,
+              buyer: buyer2.publicKey,
+              payer: buyer2.publicKey,
+              systemProgram: SystemProgram.programId,
+            },
+            promo: promoPda,
+          })
+          .signers([buyer2])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("PromoExhausted");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: register_referrer / set_referral_bps instructions
+  // ============================================================================
+  describe("referrals", () => {
+    // HAPPY PATH: Referrer receives their cut of the ticket price
+    it("Successfully pays the referrer a cut of the sale", async () => {
+      const referralEventId = 280;
+      const [eventPda] = getEventPda(eventAuthority.publicKey, referralEventId);
+      const [vaultPda] = getVaultPda(eventPda);
+      const [referrerPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("referrer"), eventPda.toBuffer(), buyer2.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initializeEvent(referralEventId, ticketPrice, 5, "Affiliates", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      // 10% referral cut
+      await program.methods
+        .setReferralBps(1000)
+        .accounts({ event: eventPda, eventAuthority: eventAuthority.publicKey })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .registerReferrer()
+        .accounts({
+          event: eventPda,
+          referrer: referrerPda,
+          wallet: buyer2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer2])
+        .rpc();
+
+      const referrerBefore = await provider.connection.getBalance(
+        buyer2.publicKey
+      );
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, 0)[0],
+          vault: vaultPda,
+          referrer: referrerPda,
+          referrerWallet: buyer2.publicKey,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const referrerAfter = await provider.connection.getBalance(
+        buyer2.publicKey
+      );
+      expect(referrerAfter - referrerBefore).to.equal(
+        ticketPrice.toNumber() / 10
+      );
+
+      const referrerAccount = await program.account.referrer.fetch(referrerPda);
+      expect(referrerAccount.referrals).to.equal(1);
+      expect(referrerAccount.earned.toNumber()).to.equal(
+        ticketPrice.toNumber() / 10
+      );
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: issue_comp_ticket instruction
+  // ============================================================================
+  describe("issue_comp_ticket", () => {
+    const compEventId = 290;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, compEventId);
+    const [compTicketPda] = getTicketPda(eventPda, 0);
+
+    // HAPPY PATH: Organizer issues a free ticket to a guest
+    it("Successfully issues a comp ticket without payment", async () => {
+      await program.methods
+        .initializeEvent(compEventId, ticketPrice, 5, "Guest List", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .issueCompTicket(buyer1.publicKey, Buffer.from([]))
+        .accounts({
+          event: eventPda,
+          ticket: compTicketPda,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const ticketAccount = await program.account.ticket.fetch(compTicketPda);
+      expect(ticketAccount.owner.toString()).to.equal(
+        buyer1.publicKey.toString()
+      );
+      expect(ticketAccount.comped).to.be.true;
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.sold).to.equal(1);
+    });
+
+    // UNHAPPY PATH: Comp tickets are excluded from refunds
+    it("Fails to refund a comp ticket", async () => {
+      try {
+        await program.methods
+          .refund()
+          .accounts({
+            event: eventPda,
+            ticket: compTicketPda,
+            vault: getVaultPda(eventPda)[0],
+            ticketOwner: buyer1.publicKey,
+            eventAuthority: eventAuthority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([eventAuthority])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("CompTicketNotRefundable");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: join_waitlist / claim_from_waitlist instructions
+  // ============================================================================
+  describe("waitlist", () => {
+    const waitlistEventId = 300;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, waitlistEventId);
+    const [vaultPda] = getVaultPda(eventPda);
+    const getEntryPda = (position: number) =>
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("waitlist"),
+          eventPda.toBuffer(),
+          new anchor.BN(position).toArrayLike(Buffer, "le", 4),
+        ],
+        program.programId
+      )[0];
+
+    before(async () => {
+      // Single-seat event that sells out immediately
+      await program.methods
+        .initializeEvent(waitlistEventId, ticketPrice, 1, "Tiny Venue", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, 0)[0],
+          vault: vaultPda,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+    });
+
+    // UNHAPPY PATH: No seat has been freed yet
+    it("Fails to claim before a refund frees a seat", async () => {
+      await program.methods
+        .joinWaitlist()
+        .accounts({
+          event: eventPda,
+          entry: getEntryPda(0),
+          wallet: buyer2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer2])
+        .rpc();
+
+      try {
+        await program.methods
+          .claimFromWaitlist()
+          .accounts({
+            event: eventPda,
+            entry: getEntryPda(0),
+            ticket: getTicketPda(eventPda, 1)[0],
+            vault: vaultPda,
+            wallet: buyer2.publicKey,
+            payer: eventAuthority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([eventAuthority])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("NoFreedSeats");
+      }
+    });
+
+    // HAPPY PATH: A refund frees the seat and the first entrant gets it
+    it("Successfully backfills a refunded seat from the waitlist", async () => {
+      await openRefundWindow(eventPda);
+
+      await program.methods
+        .refund()
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, 0)[0],
+          vault: vaultPda,
+          ticketOwner: buyer1.publicKey,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .claimFromWaitlist()
+        .accounts({
+          event: eventPda,
+          entry: getEntryPda(0),
+          ticket: getTicketPda(eventPda, 1)[0],
+          vault: vaultPda,
+          wallet: buyer2.publicKey,
+          payer: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const ticketAccount = await program.account.ticket.fetch(
+        getTicketPda(eventPda, 1)[0]
+      );
+      expect(ticketAccount.owner.toString()).to.equal(
+        buyer2.publicKey.toString()
+      );
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.freedSeats).to.equal(0);
+      expect(eventAccount.waitlistNext).to.equal(1);
+      // The freed seat is reused rather than added to the supply
+      expect(eventAccount.supply).to.equal(1);
+      expect(eventAccount.reissuedSeats).to.equal(1);
+    });
+
+    // UNHAPPY PATH: The front of the queue can't be skipped while waiting
+    it("Fails to skip an entrant who is still waiting", async () => {
+      await program.methods
+        .joinWaitlist()
+        .accounts({
+          event: eventPda,
+          entry: getEntryPda(1),
+          wallet: buyer3.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer3])
+        .rpc();
+
+      try {
+        await program.methods
+          .skipWaitlistEntry()
+          .accounts({ event: eventPda, entry: getEntryPda(1) })
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("WaitlistEntryActive");
+      }
+    });
+
+    // HAPPY PATH: Leaving returns the deposit and the queue moves past it
+    it("Successfully leaves the waitlist with the deposit back", async () => {
+      const balanceBefore = await provider.connection.getBalance(
+        buyer3.publicKey
+      );
+
+      await program.methods
+        .leaveWaitlist()
+        .accounts({
+          event: eventPda,
+          entry: getEntryPda(1),
+          wallet: buyer3.publicKey,
+        })
+        .signers([buyer3])
+        .rpc();
+
+      const balanceAfter = await provider.connection.getBalance(
+        buyer3.publicKey
+      );
+      expect(balanceAfter - balanceBefore).to.be.greaterThan(
+        ticketPrice.toNumber()
+      );
+      expect(await provider.connection.getAccountInfo(getEntryPda(1))).to.be
+        .null;
+
+      await program.methods
+        .skipWaitlistEntry()
+        .accounts({ event: eventPda, entry: getEntryPda(1) })
+        .rpc();
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.waitlistNext).to.equal(2);
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: reserve_ticket / finalize_reservation / purge_reservation
+  // ============================================================================
+  describe("reservations", () => {
+    const reservationEventId = 310;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, reservationEventId);
+    const getReservationPda = (buyer: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("reservation"), eventPda.toBuffer(), buyer.toBuffer()],
+        program.programId
+      )[0];
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(reservationEventId, ticketPrice, 1, "Checkout", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .reserveTicket()
+        .accounts({
+          event: eventPda,
+          reservation: getReservationPda(buyer1.publicKey),
+          buyer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+    });
+
+    // HAPPY PATH: Holding a seat escrows its price
+    it("Successfully escrows the ticket price in a reservation", async () => {
+      const reservation = await program.account.reservation.fetch(
+        getReservationPda(buyer1.publicKey)
+      );
+      expect(reservation.deposit.toNumber()).to.equal(ticketPrice.toNumber());
+
+      const rent = await provider.connection.getMinimumBalanceForRentExemption(
+        8 + 32 + 32 + 8 + 8
+      );
+      expect(
+        await provider.connection.getBalance(getReservationPda(buyer1.publicKey))
+      ).to.equal(rent + ticketPrice.toNumber());
+    });
+
+    // UNHAPPY PATH: The only seat is held, so public minting fails
+    it("Fails to mint a seat held by a reservation", async () => {
+      try {
+        await program.methods
+          .mintTicket()
+          .accounts({
+            event: eventPda,
+            ticket: getTicketPda(eventPda, 0)[0],
+            vault: getVaultPda(eventPda)[0],
+            buyer: buyer2.publicKey,
+            payer: buyer2.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer2])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("EventSoldOut");
+      }
+    });
+
+    // UNHAPPY PATH: Live reservations cannot be purged
+    it("Fails to purge a reservation before it expires", async () => {
+      try {
+        await program.methods
+          .purgeReservation()
+          .accounts({
+            event: eventPda,
+            reservation: getReservationPda(buyer1.publicKey),
+            buyer: buyer1.publicKey,
+          })
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("ReservationNotExpired");
+      }
+    });
+
+    // HAPPY PATH: Buyer completes payment for the held seat
+    it("Successfully finalizes a reservation", async () => {
+      await program.methods
+        .finalizeReservation()
+        .accounts({
+          sale: {
+            event: eventPda,
+            ticket: getTicketPda(eventPda, 0)[0],
+            vault: getVaultPda(eventPda)[0],
+            buyer: buyer1.publicKey,
+            payer: buyer1.publicKey,
+            systemProgram: SystemProgram.programId,
+          },
+          reservation: getReservationPda(buyer1.publicKey),
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.sold).to.equal(1);
+      expect(eventAccount.reserved).to.equal(0);
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: set_dutch_auction instruction
+  // ============================================================================
+  describe("set_dutch_auction", () => {
+    const auctionEventId = 320;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, auctionEventId);
+    const [vaultPda] = getVaultPda(eventPda);
+    const startPrice = new anchor.BN(0.2 * LAMPORTS_PER_SOL);
+    const floorPrice = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(auctionEventId, ticketPrice, 5, "Drop", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // UNHAPPY PATH: Floor above the start price
+    it("Fails to set an auction whose floor exceeds the start price", async () => {
+      const now = Math.floor(Date.now() / 1000);
+
+      try {
+        await program.methods
+          .setDutchAuction(floorPrice, startPrice, new anchor.BN(now), new anchor.BN(now + 3600))
+          .accounts({ event: eventPda, eventAuthority: eventAuthority.publicKey })
+          .signers([eventAuthority])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidAuction");
+      }
+    });
+
+    // HAPPY PATH: Once the window has passed, tickets sell at the floor price
+    it("Successfully charges the floor price after the auction window", async () => {
+      const now = Math.floor(Date.now() / 1000);
+
+      await program.methods
+        .setDutchAuction(
+          startPrice,
+          floorPrice,
+          new anchor.BN(now - 7200),
+          new anchor.BN(now - 3600)
+        )
+        .accounts({ event: eventPda, eventAuthority: eventAuthority.publicKey })
+        .signers([eventAuthority])
+        .rpc();
+
+      const vaultBefore = await provider.connection.getBalance(vaultPda);
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, 0)[0],
+          vault: vaultPda,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const vaultAfter = await provider.connection.getBalance(vaultPda);
+      expect(vaultAfter - vaultBefore).to.equal(floorPrice.toNumber());
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: set_price_curve instruction
+  // ============================================================================
+  describe("set_price_curve", () => {
+    // HAPPY PATH: Price steps up after every ticket sold
+    it("Successfully raises the price as tickets sell", async () => {
+      const curveEventId = 330;
+      const step = new anchor.BN(0.01 * LAMPORTS_PER_SOL);
+      const [eventPda] = getEventPda(eventAuthority.publicKey, curveEventId);
+      const [vaultPda] = getVaultPda(eventPda);
+
+      await program.methods
+        .initializeEvent(curveEventId, ticketPrice, 5, "Early Bird", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      // +0.01 SOL after every ticket
+      await program.methods
+        .setPriceCurve(step, 1)
+        .accounts({ event: eventPda, eventAuthority: eventAuthority.publicKey })
+        .signers([eventAuthority])
+        .rpc();
+
+      const paid: number[] = [];
+      for (const [id, buyer] of [buyer1, buyer2].entries()) {
+        const vaultBefore = await provider.connection.getBalance(vaultPda);
+
+        await program.methods
+          .mintTicket()
+          .accounts({
+            event: eventPda,
+            ticket: getTicketPda(eventPda, id)[0],
+            vault: vaultPda,
+            buyer: buyer.publicKey,
+            payer: buyer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer])
+          .rpc();
+
+        paid.push((await provider.connection.getBalance(vaultPda)) - vaultBefore);
+      }
+
+      expect(paid[0]).to.equal(ticketPrice.toNumber());
+      expect(paid[1]).to.equal(ticketPrice.add(step).toNumber());
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: create_auction / place_bid / reveal_bid / settle_bid instructions
+  // ============================================================================
+  describe("auctions", () => {
+    const auctionEventId = 340;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, auctionEventId);
+    const [vaultPda] = getVaultPda(eventPda);
+    const [auctionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("auction"), eventPda.toBuffer()],
+      program.programId
+    );
+    const getBidPda = (bidder: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("bid"), auctionPda.toBuffer(), bidder.toBuffer()],
+        program.programId
+      )[0];
+    const salt = Buffer.alloc(32, 5);
+    const sealBid = (bidder: PublicKey, amount: number) =>
+      Array.from(
+        createHash("sha256")
+          .update(
+            Buffer.concat([bidder.toBuffer(), new anchor.BN(amount).toArrayLike(Buffer, "le", 8), salt])
+          )
+          .digest()
+      );
+    // [bidder, sealed amount, escrowed deposit]
+    const bids: [Keypair, number, number][] = [
+      [buyer1, 0.3 * LAMPORTS_PER_SOL, 0.4 * LAMPORTS_PER_SOL],
+      [buyer2, 0.2 * LAMPORTS_PER_SOL, 0.2 * LAMPORTS_PER_SOL],
+    ];
+
+    // HAPPY PATH: Sealed bids are escrowed with their deposit
+    it("Successfully places sealed bids", async () => {
+      await program.methods
+        .initializeEvent(auctionEventId, ticketPrice, 5, "VIP Box", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const now = Math.floor(Date.now() / 1000);
+      await program.methods
+        .createAuction(1, ticketPrice, new anchor.BN(now + 3), new anchor.BN(now + 8))
+        .accounts({
+          event: eventPda,
+          auction: auctionPda,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      for (const [bidder, amount, deposit] of bids) {
+        await program.methods
+          .placeBid(sealBid(bidder.publicKey, amount), new anchor.BN(deposit))
+          .accounts({
+            event: eventPda,
+            auction: auctionPda,
+            bid: getBidPda(bidder.publicKey),
+            bidder: bidder.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([bidder])
+          .rpc();
+      }
+
+      const bidAccount = await program.account.bid.fetch(getBidPda(buyer1.publicKey));
+      expect(bidAccount.deposit.toNumber()).to.equal(0.4 * LAMPORTS_PER_SOL);
+      expect(bidAccount.revealed).to.be.false;
+    });
+
+    // UNHAPPY PATH: Revealing an amount other than the sealed one
+    it("Fails to reveal a bid that doesn't match its commitment", async () => {
+      // Wait for bidding to close
+      await new Promise((resolve) => setTimeout(resolve, 4000));
+
+      try {
+        await program.methods
+          .revealBid(new anchor.BN(0.4 * LAMPORTS_PER_SOL), Array.from(salt))
+          .accounts({
+            auction: auctionPda,
+            bid: getBidPda(buyer1.publicKey),
+            bidder: buyer1.publicKey,
+          })
+          .signers([buyer1])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidBidReveal");
+      }
+    });
+
+    // HAPPY PATH: Highest revealed bid wins the seat, the other bidder is refunded
+    it("Successfully awards the seat to the highest revealed bid", async () => {
+      for (const [bidder, amount] of bids) {
+        await program.methods
+          .revealBid(new anchor.BN(amount), Array.from(salt))
+          .accounts({
+            auction: auctionPda,
+            bid: getBidPda(bidder.publicKey),
+            bidder: bidder.publicKey,
+          })
+          .signers([bidder])
+          .rpc();
+      }
+
+      // Wait for reveals to close
+      await new Promise((resolve) => setTimeout(resolve, 5000));
+
+      await program.methods
+        .closeAuction()
+        .accounts({ event: eventPda, auction: auctionPda })
+        .rpc();
+
+      const vaultBefore = await provider.connection.getBalance(vaultPda);
+      const loserBefore = await provider.connection.getBalance(buyer2.publicKey);
+      for (const [bidder] of bids) {
+        await program.methods
+          .settleBid()
+          .accounts({
+            event: eventPda,
+            auction: auctionPda,
+            bid: getBidPda(bidder.publicKey),
+            bidder: bidder.publicKey,
+            ticket: bidder === buyer1 ? getTicketPda(eventPda, 0)[0] : null,
+            vault: vaultPda,
+            payer: eventAuthority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([eventAuthority])
+          .rpc();
+      }
+
+      const ticketAccount = await program.account.ticket.fetch(
+        getTicketPda(eventPda, 0)[0]
+      );
+      expect(ticketAccount.owner.toString()).to.equal(
+        buyer1.publicKey.toString()
+      );
+
+      // The winner pays its revealed bid, not its deposit
+      const vaultAfter = await provider.connection.getBalance(vaultPda);
+      expect(vaultAfter - vaultBefore).to.equal(0.3 * LAMPORTS_PER_SOL);
+
+      // Losing bid account is closed and refunded
+      const losingBid = await provider.connection.getAccountInfo(
+        getBidPda(buyer2.publicKey)
+      );
+      expect(losingBid).to.be.null;
+      const loserAfter = await provider.connection.getBalance(buyer2.publicKey);
+      expect(loserAfter - loserBefore).to.be.greaterThan(0.2 * LAMPORTS_PER_SOL);
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.sold).to.equal(1);
+      expect(eventAccount.reserved).to.equal(0);
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: raffle instructions
+  // ============================================================================
+  describe("raffles", () => {
+    const raffleEventId = 350;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, raffleEventId);
+    const [rafflePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("raffle"), eventPda.toBuffer()],
+      program.programId
+    );
+    const getEntryPda = (entrant: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("raffle_entry"), rafflePda.toBuffer(), entrant.toBuffer()],
+        program.programId
+      )[0];
+
+    // HAPPY PATH: Entrants escrow the ticket price while seats are held
+    it("Successfully enters a raffle", async () => {
+      await program.methods
+        .initializeEvent(raffleEventId, ticketPrice, 5, "Hype Drop", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .createRaffle(2, new anchor.BN(Math.floor(Date.now() / 1000) + 2))
+        .accounts({
+          event: eventPda,
+          raffle: rafflePda,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .enterRaffle()
+        .accounts({
+          event: eventPda,
+          raffle: rafflePda,
+          entry: getEntryPda(buyer1.publicKey),
+          entrant: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const raffleAccount = await program.account.raffle.fetch(rafflePda);
+      expect(raffleAccount.entryCount).to.equal(1);
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.reserved).to.equal(2);
+    });
+
+    // UNHAPPY PATH: Only Switchboard randomness accounts can be committed
+    it("Fails to commit a non-Switchboard randomness account", async () => {
+      // Wait for entries to close
+      await new Promise((resolve) => setTimeout(resolve, 3000));
+
+      try {
+        await program.methods
+          .commitRaffle()
+          .accounts({
+            event: eventPda,
+            raffle: rafflePda,
+            randomness: buyer2.publicKey,
+            eventAuthority: eventAuthority.publicKey,
+          })
+          .signers([eventAuthority])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidRandomnessAccount");
+      }
+    });
+
+    // HAPPY PATH: Canceling the event calls off the raffle and refunds entries
+    it("Successfully cancels the raffle of a canceled event and refunds its entry", async () => {
+      await program.methods
+        .cancelEvent()
+        .accounts({
+          event: eventPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .cancelRaffle()
+        .accounts({ event: eventPda, raffle: rafflePda })
+        .rpc();
+
+      const entrantBefore = await provider.connection.getBalance(buyer1.publicKey);
+      await program.methods
+        .settleRaffleEntry()
+        .accounts({
+          event: eventPda,
+          raffle: rafflePda,
+          entry: getEntryPda(buyer1.publicKey),
+          entrant: buyer1.publicKey,
+          ticket: getTicketPda(eventPda, 0)[0],
+          vault: getVaultPda(eventPda)[0],
+          payer: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const entrantAfter = await provider.connection.getBalance(buyer1.publicKey);
+      expect(entrantAfter - entrantBefore).to.be.greaterThan(ticketPrice.toNumber());
+
+      const raffleAccount = await program.account.raffle.fetch(rafflePda);
+      expect(raffleAccount.canceled).to.be.true;
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.reserved).to.equal(0);
+    });
+
+    // UNHAPPY PATH: Entering the raffle of a canceled event
+    it("Fails to enter the raffle of a canceled event", async () => {
+      try {
+        await program.methods
+          .enterRaffle()
+          .accounts({
+            event: eventPda,
+            raffle: rafflePda,
+            entry: getEntryPda(buyer2.publicKey),
+            entrant: buyer2.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer2])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("EventCanceled");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: set_usd_price instruction
+  // ============================================================================
+  describe("set_usd_price", () => {
+    const usdEventId = 360;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, usdEventId);
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(usdEventId, ticketPrice, 5, "Dollar Show", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      // $25.00 per ticket
+      await program.methods
+        .setUsdPrice(new anchor.BN(2500))
+        .accounts({ event: eventPda, eventAuthority: eventAuthority.publicKey })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // UNHAPPY PATH: USD-priced events need a Pyth price update
+    it("Fails to mint without a price update", async () => {
+      try {
+        await program.methods
+          .mintTicket()
+          .accounts({
+            event: eventPda,
+            ticket: getTicketPda(eventPda, 0)[0],
+            vault: getVaultPda(eventPda)[0],
+            buyer: buyer1.publicKey,
+            payer: buyer1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("MissingPriceFeed");
+      }
+    });
+
+    // UNHAPPY PATH: Arbitrary accounts are rejected as price feeds
+    it("Fails to mint with a non-Pyth price account", async () => {
+      try {
+        await program.methods
+          .mintTicket()
+          .accounts({
+            event: eventPda,
+            ticket: getTicketPda(eventPda, 0)[0],
+            vault: getVaultPda(eventPda)[0],
+            priceUpdate: buyer2.publicKey,
+            buyer: buyer1.publicKey,
+            payer: buyer1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidPriceFeed");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: mint_ticket_with_seat instruction
+  // ============================================================================
+  describe("mint_ticket_with_seat", () => {
+    const seatedEventId = 370;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, seatedEventId);
+    const getSeatClaimPda = (section: number, row: number, number: number) =>
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("seat"),
+          eventPda.toBuffer(),
+          new anchor.BN(section).toArrayLike(Buffer, "le", 2),
+          new anchor.BN(row).toArrayLike(Buffer, "le", 2),
+          new anchor.BN(number).toArrayLike(Buffer, "le", 2),
+        ],
+        program.programId
+      )[0];
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(seatedEventId, ticketPrice, 5, "Theater", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // HAPPY PATH: Buyer picks a seat
+    it("Successfully mints a ticket for a specific seat", async () => {
+      await program.methods
+        .mintTicketWithSeat(1, 4, 12)
+        .accounts({
+          sale: {
+            event: eventPda,
+            ticket: getTicketPda(eventPda, 0)[0],
+            vault: getVaultPda(eventPda)[0],
+            buyer: buyer1.publicKey,
+            payer: buyer1.publicKey,
+            systemProgram: SystemProgram.programId,
+          },
+          seatClaim: getSeatClaimPda(1, 4, 12),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const ticketAccount = await program.account.ticket.fetch(
+        getTicketPda(eventPda, 0)[0]
+      );
+      expect(ticketAccount.seat).to.deep.equal({ section: 1, row: 4, number: 12 });
+    });
+
+    // UNHAPPY PATH: The same seat cannot be sold twice
+    it("Fails to mint an already taken seat", async () => {
+      try {
+        await program.methods
+          .mintTicketWithSeat(1, 4, 12)
+          .accounts({
+            sale: {
+              event: eventPda,
+              ticket: getTicketPda(eventPda, 1)[0],
+              vault: getVaultPda(eventPda)[0],
+              buyer: buyer2.publicKey,
+              payer: buyer2.publicKey,
+              systemProgram: SystemProgram.programId,
+            },
+            seatClaim: getSeatClaimPda(1, 4, 12),
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer2])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("already in use");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: seat map (zero-copy availability bitset)
+  // ============================================================================
+  describe("seat map", () => {
+    const seatMapEventId = 380;
+    const seatMap = Keypair.generate();
+    const [eventPda] = getEventPda(eventAuthority.publicKey, seatMapEventId);
+    const [vaultPda] = getVaultPda(eventPda);
+    const [seatClaimPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("seat"),
+        eventPda.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 2),
+        new anchor.BN(1).toArrayLike(Buffer, "le", 2),
+        new anchor.BN(3).toArrayLike(Buffer, "le", 2),
+      ],
+      program.programId
+    );
+    // Seat (section 0, row 1, number 3) in a 1 x 2 x 10 venue is bit 13
+    const seatIsTaken = async () => {
+      const map = await program.account.seatMap.fetch(seatMap.publicKey);
+      return (map.taken[1] & (1 << 5)) !== 0;
+    };
+
+    before(async () => {
+      const seatMapSize = program.account.seatMap.size;
+
+      await program.methods
+        .initializeEvent(seatMapEventId, ticketPrice, 20, "Arena", eventDate, true, {
+          sections: 1,
+          rows: 2,
+          seatsPerRow: 10,
+        }, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          seatMap: seatMap.publicKey,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .preInstructions([
+          SystemProgram.createAccount({
+            fromPubkey: eventAuthority.publicKey,
+            newAccountPubkey: seatMap.publicKey,
+            space: seatMapSize,
+            lamports:
+              await provider.connection.getMinimumBalanceForRentExemption(
+                seatMapSize
+              ),
+            programId: program.programId,
+          }),
+        ])
+        .signers([eventAuthority, seatMap])
+        .rpc();
+    });
+
+    // HAPPY PATH: Minting a seat marks it taken in the map
+    it("Successfully marks a sold seat in the seat map", async () => {
+      await program.methods
+        .mintTicketWithSeat(0, 1, 3)
+        .accounts({
+          sale: {
+            event: eventPda,
+            ticket: getTicketPda(eventPda, 0)[0],
+            vault: vaultPda,
+            buyer: buyer1.publicKey,
+            payer: buyer1.publicKey,
+            systemProgram: SystemProgram.programId,
+          },
+          seatClaim: seatClaimPda,
+          seatMap: seatMap.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      expect(await seatIsTaken()).to.be.true;
+    });
+
+    // UNHAPPY PATH: Refunding a seated ticket without closing its seat claim
+    it("Fails to refund a seated ticket without its seat claim", async () => {
+      await openRefundWindow(eventPda);
+
+      try {
+        await program.methods
+          .refund()
+          .accounts({
+            event: eventPda,
+            ticket: getTicketPda(eventPda, 0)[0],
+            vault: vaultPda,
+            seatMap: seatMap.publicKey,
+            seatClaim: null,
+            ticketOwner: buyer1.publicKey,
+            eventAuthority: eventAuthority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([eventAuthority])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("MissingSeatClaim");
+      }
+      expect(await seatIsTaken()).to.be.true;
+    });
+
+    // HAPPY PATH: Refunding the ticket releases the seat
+    it("Successfully releases the seat on refund", async () => {
+      await openRefundWindow(eventPda);
+
+      await program.methods
+        .refund()
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, 0)[0],
+          vault: vaultPda,
+          seatMap: seatMap.publicKey,
+          seatClaim: seatClaimPda,
+          ticketOwner: buyer1.publicKey,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      expect(await seatIsTaken()).to.be.false;
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: create_series / create_series_event instructions
+  // ============================================================================
+  describe("event series", () => {
+    // HAPPY PATH: Weekly show occurrences inherit the series defaults
+    it("Successfully creates an occurrence from series defaults", async () => {
+      const seriesId = 1;
+      const occurrenceEventId = 390;
+      const [seriesPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("series"),
+          eventAuthority.publicKey.toBuffer(),
+          new anchor.BN(seriesId).toArrayLike(Buffer, "le", 4),
+        ],
+        program.programId
+      );
+      const [eventPda] = getEventPda(eventAuthority.publicKey, occurrenceEventId);
+
+      await program.methods
+        .createSeries(seriesId, "Open Mic", ticketPrice, 40, true)
+        .accounts({
+          series: seriesPda,
+          organizer: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .createSeriesEvent(occurrenceEventId, "2025-07-04")
+        .accounts({
+          registryPage: await getRegistryPagePda(),
+          series: seriesPda,
+          event: eventPda,
+          organizer: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      const metadataAccount = await program.account.eventMetadata.fetch(
+        getEventMetadataPda(eventPda)[0]
+      );
+      expect(metadataAccount.name).to.equal("Open Mic");
+      expect(eventAccount.supply).to.equal(40);
+      expect(eventAccount.price.toString()).to.equal(ticketPrice.toString());
+      expect(eventAccount.series.toString()).to.equal(seriesPda.toString());
+
+      const seriesAccount = await program.account.eventSeries.fetch(seriesPda);
+      expect(seriesAccount.occurrences).to.equal(1);
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: set_pass_price / buy_pass / check_in_with_pass instructions
+  // ============================================================================
+  describe("season passes", () => {
+    const seriesId = 2;
+    const passEventId = 400;
+    const [seriesPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("series"),
+        eventAuthority.publicKey.toBuffer(),
+        new anchor.BN(seriesId).toArrayLike(Buffer, "le", 4),
+      ],
+      program.programId
+    );
+    const [eventPda] = getEventPda(eventAuthority.publicKey, passEventId);
+    const [passPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pass"), seriesPda.toBuffer(), buyer1.publicKey.toBuffer()],
+      program.programId
+    );
+    const [passCheckInPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pass_check_in"), passPda.toBuffer(), eventPda.toBuffer()],
+      program.programId
+    );
+
+    before(async () => {
+      await program.methods
+        .createSeries(seriesId, "Jazz Season", ticketPrice, 40, true)
+        .accounts({
+          series: seriesPda,
+          organizer: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .createSeriesEvent(passEventId, "2025-09-01")
+        .accounts({
+          registryPage: await getRegistryPagePda(),
+          series: seriesPda,
+          event: eventPda,
+          organizer: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .setPassPrice(new anchor.BN(LAMPORTS_PER_SOL))
+        .accounts({ series: seriesPda, organizer: eventAuthority.publicKey })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // HAPPY PATH: Pass holder is admitted to a series event
+    it("Successfully checks in a pass holder", async () => {
+      await program.methods
+        .buyPass()
+        .accounts({
+          series: seriesPda,
+          pass: passPda,
+          organizer: eventAuthority.publicKey,
+          holder: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      await program.methods
+        .checkInWithPass()
+        .accounts({
+          event: eventPda,
+          pass: passPda,
+          passCheckIn: passCheckInPda,
+          operator: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const record = await program.account.passCheckIn.fetch(passCheckInPda);
+      expect(record.event.toString()).to.equal(eventPda.toString());
+    });
+
+    // UNHAPPY PATH: A pass admits its holder once per event
+    it("Fails to check in the same pass twice at one event", async () => {
+      try {
+        await program.methods
+          .checkInWithPass()
+          .accounts({
+            event: eventPda,
+            pass: passPda,
+            passCheckIn: passCheckInPda,
+            operator: eventAuthority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([eventAuthority])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("already in use");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: create_tier / upgrade_ticket instructions
+  // ============================================================================
+  describe("ticket tiers", () => {
+    const tierEventId = 410;
+    const vipPrice = new anchor.BN(0.3 * LAMPORTS_PER_SOL);
+    const [eventPda] = getEventPda(eventAuthority.publicKey, tierEventId);
+    const [vaultPda] = getVaultPda(eventPda);
+    const [vipTierPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("tier"), eventPda.toBuffer(), Buffer.from([1])],
+      program.programId
+    );
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(tierEventId, ticketPrice, 5, "Gala", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .createTier(1, vipPrice, 1)
+        .accounts({
+          event: eventPda,
+          tier: vipTierPda,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      for (const [id, buyer] of [buyer1, buyer2].entries()) {
+        await program.methods
+          .mintTicket()
+          .accounts({
+            event: eventPda,
+            ticket: getTicketPda(eventPda, id)[0],
+            vault: vaultPda,
+            buyer: buyer.publicKey,
+            payer: buyer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer])
+          .rpc();
+      }
+    });
+
+    // HAPPY PATH: GA ticket upgraded to VIP for the price difference
+    it("Successfully upgrades a ticket, charging the difference", async () => {
+      const vaultBefore = await provider.connection.getBalance(vaultPda);
+
+      await program.methods
+        .upgradeTicket()
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, 0)[0],
+          toTier: vipTierPda,
+          vault: vaultPda,
+          owner: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const vaultAfter = await provider.connection.getBalance(vaultPda);
+      expect(vaultAfter - vaultBefore).to.equal(
+        vipPrice.sub(ticketPrice).toNumber()
+      );
+
+      const ticketAccount = await program.account.ticket.fetch(
+        getTicketPda(eventPda, 0)[0]
+      );
+      expect(ticketAccount.tier).to.equal(1);
+    });
+
+    // UNHAPPY PATH: VIP tier has a single seat
+    it("Fails to upgrade into a sold-out tier", async () => {
+      try {
+        await program.methods
+          .upgradeTicket()
+          .accounts({
+            event: eventPda,
+            ticket: getTicketPda(eventPda, 1)[0],
+            toTier: vipTierPda,
+            vault: vaultPda,
+            owner: buyer2.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer2])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("TierSoldOut");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: downgrade_ticket instruction
+  // ============================================================================
+  describe("downgrade_ticket", () => {
+    // HAPPY PATH: VIP ticket moved back to GA is refunded the difference
+    it("Successfully downgrades a ticket with a partial refund", async () => {
+      const downgradeEventId = 420;
+      const vipPrice = new anchor.BN(0.3 * LAMPORTS_PER_SOL);
+      const [eventPda] = getEventPda(eventAuthority.publicKey, downgradeEventId);
+      const [vaultPda] = getVaultPda(eventPda);
+      const [ticketPda] = getTicketPda(eventPda, 0);
+      const [vipTierPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("tier"), eventPda.toBuffer(), Buffer.from([1])],
+        program.programId
+      );
+
+      await program.methods
+        .initializeEvent(downgradeEventId, ticketPrice, 5, "Gala II", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .createTier(1, vipPrice, 2)
+        .accounts({
+          event: eventPda,
+          tier: vipTierPda,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      await program.methods
+        .upgradeTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          toTier: vipTierPda,
+          vault: vaultPda,
+          owner: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const vaultBefore = await provider.connection.getBalance(vaultPda);
+
+      // Back to general admission: no target tier
+      await program.methods
+        .downgradeTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          fromTier: vipTierPda,
+          vault: vaultPda,
+          owner: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const vaultAfter = await provider.connection.getBalance(vaultPda);
+      expect(vaultBefore - vaultAfter).to.equal(
+        vipPrice.sub(ticketPrice).toNumber()
+      );
+
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      expect(ticketAccount.tier).to.be.null;
+
+      const tierAccount = await program.account.tier.fetch(vipTierPda);
+      expect(tierAccount.sold).to.equal(0);
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: attendance collectible minted at check-in
+  // ============================================================================
+  describe("set_poap_mint", () => {
+    const poapEventId = 430;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, poapEventId);
+    const [vaultPda] = getVaultPda(eventPda);
+    const [ticketPda] = getTicketPda(eventPda, 0);
+    const [poapAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("poap"), eventPda.toBuffer()],
+      program.programId
+    );
+    let poapMint: PublicKey;
+
+    // HAPPY PATH: First check-in mints one collectible to the ticket owner
+    it("Successfully mints an attendance collectible on check-in", async () => {
+      await program.methods
+        .initializeEvent(poapEventId, ticketPrice, 5, "Meetup", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      poapMint = await createMint(
+        provider.connection,
+        eventAuthority,
+        poapAuthority,
+        null,
+        0
+      );
+
+      await program.methods
+        .setPoapMint()
+        .accounts({
+          event: eventPda,
+          poapMint,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const attendeeAccount = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        buyer1,
+        poapMint,
+        buyer1.publicKey
+      );
+
+      await program.methods
+        .checkIn(nextScanNonce())
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          poapMint,
+          poapAuthority,
+          attendeePoapAccount: attendeeAccount.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          operator: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const balance = await getAccount(provider.connection, attendeeAccount.address);
+      expect(Number(balance.amount)).to.equal(1);
+    });
+
+    // UNHAPPY PATH: Check-in without the collectible accounts is rejected
+    it("Fails to check in without the attendance collectible accounts", async () => {
+      const [secondTicketPda] = getTicketPda(eventPda, 1);
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: secondTicketPda,
+          vault: vaultPda,
+          buyer: buyer2.publicKey,
+          payer: buyer2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer2])
+        .rpc();
+
+      try {
+        await program.methods
+          .checkIn(nextScanNonce())
+          .accounts({
+            event: eventPda,
+            ticket: secondTicketPda,
+            operator: eventAuthority.publicKey,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("PoapAccountsMissing");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: loyalty points
+  // ============================================================================
+  describe("loyalty points", () => {
+    const loyaltyEventId = 440;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, loyaltyEventId);
+    const [vaultPda] = getVaultPda(eventPda);
+    const [loyaltyPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("loyalty"),
+        eventAuthority.publicKey.toBuffer(),
+        buyer3.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+
+    // HAPPY PATH: Points accrue on mint and check-in
+    it("Successfully accrues points on mint and check-in", async () => {
+      await program.methods
+        .initializeEvent(loyaltyEventId, ticketPrice, 5, "Club Night", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .openLoyaltyAccount(eventAuthority.publicKey)
+        .accounts({
+          loyalty: loyaltyPda,
+          attendee: buyer3.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer3])
+        .rpc();
+
+      const [ticketPda] = getTicketPda(eventPda, 0);
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          loyalty: loyaltyPda,
+          buyer: buyer3.publicKey,
+          payer: buyer3.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer3])
+        .rpc();
+
+      await program.methods
+        .checkIn(nextScanNonce())
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          loyalty: loyaltyPda,
+          operator: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const loyalty = await program.account.loyaltyAccount.fetch(loyaltyPda);
+      expect(loyalty.points.toNumber()).to.equal(15);
+      expect(loyalty.lifetimePoints.toNumber()).to.equal(15);
+    });
+
+    // HAPPY PATH: Redeemed points discount the next purchase
+    it("Successfully redeems points for a discounted ticket", async () => {
+      const [ticketPda] = getTicketPda(eventPda, 1);
+      const vaultBefore = await provider.connection.getBalance(vaultPda);
+
+      await program.methods
+        .redeemPoints(new anchor.BN(10))
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          loyalty: loyaltyPda,
+          buyer: buyer3.publicKey,
+          payer: buyer3.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer3])
+        .rpc();
+
+      const vaultAfter = await provider.connection.getBalance(vaultPda);
+      expect(vaultAfter - vaultBefore).to.equal(
+        ticketPrice.toNumber() - 10 * 100_000
+      );
+
+      const loyalty = await program.account.loyaltyAccount.fetch(loyaltyPda);
+      expect(loyalty.points.toNumber()).to.equal(15);
+      expect(loyalty.lifetimePoints.toNumber()).to.equal(25);
+    });
+
+    // UNHAPPY PATH: Cannot redeem more points than the balance
+    it("Fails to redeem more points than available", async () => {
+      const [ticketPda] = getTicketPda(eventPda, 2);
+
+      try {
+        await program.methods
+          .redeemPoints(new anchor.BN(1_000))
+          .accounts({
+            event: eventPda,
+            ticket: ticketPda,
+            vault: vaultPda,
+            loyalty: loyaltyPda,
+            buyer: buyer3.publicKey,
+            payer: buyer3.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer3])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("InsufficientLoyaltyPoints");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: set_payout_schedule instruction
+  // ============================================================================
+  describe("set_payout_schedule", () => {
+    const lockedEventId = 450;
+    const unlockedEventId = 451;
+    const timelock = new anchor.BN(3 * 86_400);
+
+    const createEndedEvent = async (eventId: number, endedSecsAgo: number) => {
+      const [eventPda] = getEventPda(eventAuthority.publicKey, eventId);
+      const [vaultPda] = getVaultPda(eventPda);
+
+      await program.methods
+        .initializeEvent(eventId, ticketPrice, 5, "Payout Test", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const now = Math.floor(Date.now() / 1000);
+      await program.methods
+        .setPayoutSchedule(new anchor.BN(now - endedSecsAgo), timelock)
+        .accounts({
+          event: eventPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, 0)[0],
+          vault: vaultPda,
+          buyer: buyer2.publicKey,
+          payer: buyer2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer2])
+        .rpc();
+
+      return { eventPda, vaultPda };
+    };
+
+    // HAPPY PATH: Proceeds can be withdrawn once the timelock has passed
+    it("Successfully withdraws after the payout timelock", async () => {
+      const { eventPda, vaultPda } = await createEndedEvent(unlockedEventId, 4 * 86_400);
+
+      await program.methods
+        .finalizeEvent()
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          vault: vaultPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .withdrawProceeds(ticketPrice)
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          vault: vaultPda,
+          destination: eventAuthority.publicKey,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.payoutTimelockSecs.toNumber()).to.equal(timelock.toNumber());
+    });
+
+    // UNHAPPY PATH: Withdrawing during the timelock is rejected
+    it("Fails to withdraw before the payout timelock expires", async () => {
+      const { eventPda, vaultPda } = await createEndedEvent(lockedEventId, 3600);
+
+      try {
+        await program.methods
+          .withdrawProceeds(ticketPrice)
+          .accounts({
+            event: eventPda,
+            authorityConfig: null,
+            vault: vaultPda,
+            destination: eventAuthority.publicKey,
+            eventAuthority: eventAuthority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("PayoutTimelocked");
+      }
+    });
+
+    // UNHAPPY PATH: The timelock cannot be shortened after tickets are sold
+    it("Fails to shorten the payout timelock after sales", async () => {
+      const [eventPda] = getEventPda(eventAuthority.publicKey, lockedEventId);
+      const eventAccount = await program.account.event.fetch(eventPda);
+
+      try {
+        await program.methods
+          .setPayoutSchedule(eventAccount.endTime, new anchor.BN(0))
+          .accounts({
+            event: eventPda,
+            eventAuthority: eventAuthority.publicKey,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("PayoutScheduleLocked");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: claim_refund instruction (dispute window)
+  // ============================================================================
+  describe("claim_refund", () => {
+    const lapsedEventId = 460;
+    const recentEventId = 461;
+
+    const createEndedEventWithTicket = async (eventId: number, endedSecsAgo: number) => {
+      const [eventPda] = getEventPda(eventAuthority.publicKey, eventId);
+      const [vaultPda] = getVaultPda(eventPda);
+      const [ticketPda] = getTicketPda(eventPda, 0);
+
+      await program.methods
+        .initializeEvent(eventId, ticketPrice, 5, "Dispute Test", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const now = Math.floor(Date.now() / 1000);
+      await program.methods
+        .setPayoutSchedule(new anchor.BN(now - endedSecsAgo), new anchor.BN(3 * 86_400))
+        .accounts({
+          event: eventPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      return { eventPda, vaultPda, ticketPda };
+    };
+
+    // HAPPY PATH: Holder refunds themselves once the dispute window lapses
+    it("Successfully claims a refund after the dispute window lapses", async () => {
+      const { eventPda, vaultPda, ticketPda } = await createEndedEventWithTicket(
+        lapsedEventId,
+        10 * 86_400
+      );
+
+      const vaultBefore = await provider.connection.getBalance(vaultPda);
+
+      await program.methods
+        .claimRefund()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          holder: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const vaultAfter = await provider.connection.getBalance(vaultPda);
+      expect(vaultBefore - vaultAfter).to.equal(ticketPrice.toNumber());
+
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      expect(ticketAccount.refunded).to.be.true;
+    });
+
+    // UNHAPPY PATH: Organizer cannot withdraw while refunds are claimable
+    it("Fails to withdraw proceeds while refunds are claimable", async () => {
+      const [eventPda] = getEventPda(eventAuthority.publicKey, lapsedEventId);
+      const [vaultPda] = getVaultPda(eventPda);
+
+      try {
+        await program.methods
+          .withdrawProceeds(new anchor.BN(1))
+          .accounts({
+            event: eventPda,
+            authorityConfig: null,
+            vault: vaultPda,
+            destination: eventAuthority.publicKey,
+            eventAuthority: eventAuthority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("ProceedsFrozen");
+      }
+    });
+
+    // UNHAPPY PATH: Refunds cannot be claimed during the dispute window
+    it("Fails to claim a refund before the dispute window lapses", async () => {
+      const { eventPda, vaultPda, ticketPda } = await createEndedEventWithTicket(
+        recentEventId,
+        3600
+      );
+
+      try {
+        await program.methods
+          .claimRefund()
+          .accounts({
+            event: eventPda,
+            ticket: ticketPda,
+            vault: vaultPda,
+            holder: buyer1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("RefundsNotClaimable");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: finalize_event instruction
+  // ============================================================================
+  describe("finalize_event", () => {
+    const finalizedEventId = 470;
+    const futureEventId = 471;
+    const unscheduledEventId = 472;
+
+    // HAPPY PATH: Finalizing records attendance and revenue
+    it("Successfully finalizes an ended event", async () => {
+      const [eventPda] = getEventPda(eventAuthority.publicKey, finalizedEventId);
+      const [vaultPda] = getVaultPda(eventPda);
+      const [ticketPda] = getTicketPda(eventPda, 0);
+
+      await program.methods
+        .initializeEvent(finalizedEventId, ticketPrice, 5, "Finale", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      // A second ticket that is never checked in
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, 1)[0],
+          vault: vaultPda,
+          buyer: buyer2.publicKey,
+          payer: buyer2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer2])
+        .rpc();
+
+      await program.methods
+        .checkIn(nextScanNonce())
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          operator: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const now = Math.floor(Date.now() / 1000);
+      await program.methods
+        .setPayoutSchedule(new anchor.BN(now - 60), new anchor.BN(3 * 86_400))
+        .accounts({
+          event: eventPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .finalizeEvent()
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          vault: vaultPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.finalizedAt.toNumber()).to.be.greaterThan(0);
+      expect(eventAccount.checkedInCount).to.equal(1);
+      expect(eventAccount.noShowCount).to.equal(1);
+      const vaultReserve =
+        await provider.connection.getMinimumBalanceForRentExemption(0);
+      expect(eventAccount.finalRevenue.toNumber()).to.equal(
+        (await provider.connection.getBalance(vaultPda)) - vaultReserve
+      );
+    });
+
+    // UNHAPPY PATH: No sales once the event is finalized
+    it("Fails to mint a ticket for a finalized event", async () => {
+      const [eventPda] = getEventPda(eventAuthority.publicKey, finalizedEventId);
+      const [vaultPda] = getVaultPda(eventPda);
+
+      try {
+        await program.methods
+          .mintTicket()
+          .accounts({
+            event: eventPda,
+            ticket: getTicketPda(eventPda, 1)[0],
+            vault: vaultPda,
+            buyer: buyer2.publicKey,
+            payer: buyer2.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer2])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("EventFinalized");
+      }
+    });
+
+    // UNHAPPY PATH: An event cannot be finalized before it ends
+    it("Fails to finalize an event that has not ended", async () => {
+      const [eventPda] = getEventPda(eventAuthority.publicKey, futureEventId);
+      const [vaultPda] = getVaultPda(eventPda);
+
+      await program.methods
+        .initializeEvent(futureEventId, ticketPrice, 5, "Upcoming", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const now = Math.floor(Date.now() / 1000);
+      await program.methods
+        .setPayoutSchedule(new anchor.BN(now + 86_400), new anchor.BN(3 * 86_400))
+        .accounts({
+          event: eventPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      try {
+        await program.methods
+          .finalizeEvent()
+          .accounts({
+            event: eventPda,
+            authorityConfig: null,
+            vault: vaultPda,
+            eventAuthority: eventAuthority.publicKey,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("EventNotEnded");
+      }
+    });
+
+    // UNHAPPY PATH: An event without an end time can never be finalized
+    it("Fails to finalize an event without an end time", async () => {
+      const [eventPda] = getEventPda(eventAuthority.publicKey, unscheduledEventId);
+
+      await program.methods
+        .initializeEvent(unscheduledEventId, ticketPrice, 5, "Unscheduled", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      try {
+        await program.methods
+          .finalizeEvent()
+          .accounts({
+            event: eventPda,
+            authorityConfig: null,
+            vault: getVaultPda(eventPda)[0],
+            eventAuthority: eventAuthority.publicKey,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("EventNotEnded");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: pause_sales / resume_sales instructions
+  // ============================================================================
+  describe("pause_sales", () => {
+    const pausedEventId = 480;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, pausedEventId);
+    const [vaultPda] = getVaultPda(eventPda);
+    const [ticketPda] = getTicketPda(eventPda, 0);
+
+    // UNHAPPY PATH: Minting is rejected while sales are paused
+    it("Fails to mint while sales are paused", async () => {
+      await program.methods
+        .initializeEvent(pausedEventId, ticketPrice, 5, "Box Office Hold", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .pauseSales()
+        .accounts({
+          event: eventPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      try {
+        await program.methods
+          .mintTicket()
+          .accounts({
+            event: eventPda,
+            ticket: ticketPda,
+            vault: vaultPda,
+            buyer: buyer1.publicKey,
+            payer: buyer1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("SalesPaused");
+      }
+    });
+
+    // HAPPY PATH: Minting works again after sales resume
+    it("Successfully mints after sales resume", async () => {
+      await program.methods
+        .resumeSales()
+        .accounts({
+          event: eventPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.salesPaused).to.equal(0);
+      expect(eventAccount.sold).to.equal(1);
+    });
+
+    // UNHAPPY PATH: Only the event authority can pause sales
+    it("Fails to pause sales as a non-authority", async () => {
+      try {
+        await program.methods
+          .pauseSales()
+          .accounts({
+            event: eventPda,
+            eventAuthority: buyer1.publicKey,
+          })
+          .signers([buyer1])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedEventAuthority");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: revoke_ticket instruction
+  // ============================================================================
+  describe("revoke_ticket", () => {
+    const revokeEventId = 490;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, revokeEventId);
+    const [vaultPda] = getVaultPda(eventPda);
+    const [ticketPda] = getTicketPda(eventPda, 0);
+
+    // HAPPY PATH: Revoking refunds the holder and invalidates the ticket
+    it("Successfully revokes a ticket with a refund", async () => {
+      await program.methods
+        .initializeEvent(revokeEventId, ticketPrice, 5, "Chargeback", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          buyer: buyer2.publicKey,
+          payer: buyer2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer2])
+        .rpc();
+
+      const holderBefore = await provider.connection.getBalance(buyer2.publicKey);
+
+      await program.methods
+        .revokeTicket()
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          ticket: ticketPda,
+          vault: vaultPda,
+          ticketOwner: buyer2.publicKey,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const holderAfter = await provider.connection.getBalance(buyer2.publicKey);
+      expect(holderAfter - holderBefore).to.equal(ticketPrice.toNumber());
+
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      expect(ticketAccount.revoked).to.be.true;
+      expect(ticketAccount.refunded).to.be.true;
+    });
+
+    // UNHAPPY PATH: A revoked ticket cannot be checked in
+    it("Fails to check in a revoked ticket", async () => {
+      try {
+        await program.methods
+          .checkIn(nextScanNonce())
+          .accounts({
+            event: eventPda,
+            ticket: ticketPda,
+            operator: eventAuthority.publicKey,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("TicketRevoked");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: cancel_event state machine
+  // ============================================================================
+  describe("cancel_event state machine", () => {
+    const cancelOnceEventId = 500;
+    const startedEventId = 501;
+
+    const createEvent = async (eventId: number) => {
+      const [eventPda] = getEventPda(eventAuthority.publicKey, eventId);
+      await program.methods
+        .initializeEvent(eventId, ticketPrice, 5, "Cancel Rules", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+      return eventPda;
+    };
+
+    const cancel = (eventPda: PublicKey) =>
+      program.methods
+        .cancelEvent()
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+    // HAPPY PATH: Cancellation is timestamped
+    it("Successfully records when the event was canceled", async () => {
+      const eventPda = await createEvent(cancelOnceEventId);
+
+      await cancel(eventPda);
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.canceled).to.equal(1);
+      expect(eventAccount.canceledAt.toNumber()).to.be.greaterThan(0);
+    });
+
+    // UNHAPPY PATH: An event cannot be canceled twice
+    it("Fails to cancel an already canceled event", async () => {
+      const [eventPda] = getEventPda(eventAuthority.publicKey, cancelOnceEventId);
+
+      try {
+        await cancel(eventPda);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("EventCanceled");
+      }
+    });
+
+    // UNHAPPY PATH: An event cannot be canceled once doors have opened
+    it("Fails to cancel an event that has started", async () => {
+      const eventPda = await createEvent(startedEventId);
+      const now = Math.floor(Date.now() / 1000);
+
+      await program.methods
+        .setDoorsWindow(new anchor.BN(now - 3600), new anchor.BN(now + 3600))
+        .accounts({
+          event: eventPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      try {
+        await cancel(eventPda);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("EventAlreadyStarted");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: Event reconciliation counters
+  // ============================================================================
+  describe("event counters", () => {
+    // HAPPY PATH: Refunds and check-ins are tallied on the event
+    it("Successfully tracks refunded and checked-in tickets", async () => {
+      const countersEventId = 510;
+      const [eventPda] = getEventPda(eventAuthority.publicKey, countersEventId);
+      const [vaultPda] = getVaultPda(eventPda);
+      const [firstTicketPda] = getTicketPda(eventPda, 0);
+      const [secondTicketPda] = getTicketPda(eventPda, 1);
+
+      await program.methods
+        .initializeEvent(countersEventId, ticketPrice, 5, "Counters", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      for (const ticket of [firstTicketPda, secondTicketPda]) {
+        await program.methods
+          .mintTicket()
+          .accounts({
+            event: eventPda,
+            ticket,
+            vault: vaultPda,
+            buyer: buyer1.publicKey,
+            payer: buyer1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+      }
+
+      await program.methods
+        .checkIn(nextScanNonce())
+        .accounts({
+          event: eventPda,
+          ticket: firstTicketPda,
+          operator: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await openRefundWindow(eventPda);
+
+      await program.methods
+        .refund()
+        .accounts({
+          event: eventPda,
+          ticket: secondTicketPda,
+          vault: vaultPda,
+          ticketOwner: buyer1.publicKey,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.sold).to.equal(2);
+      expect(eventAccount.checkedInCount).to.equal(1);
+      expect(eventAccount.refundedCount).to.equal(1);
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: refund gating
+  // ============================================================================
+  describe("refund gating", () => {
+    const gatedEventId = 520;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, gatedEventId);
+    const [vaultPda] = getVaultPda(eventPda);
+    const [ticketPda] = getTicketPda(eventPda, 0);
+
+    const refundTicket = (refundPool: PublicKey | null = null) =>
+      program.methods
+        .refund()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          refundPool,
+          ticketOwner: buyer1.publicKey,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+    // UNHAPPY PATH: No refunds on a live event without a refund policy
+    it("Fails to refund without cancellation or a refund window", async () => {
+      await program.methods
+        .initializeEvent(gatedEventId, ticketPrice, 5, "No Refunds", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      try {
+        await refundTicket();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("RefundNotAllowed");
+      }
+    });
+
+    // UNHAPPY PATH: The refund deadline cannot be pulled in after sales
+    it("Fails to shorten the refund window after sales", async () => {
+      try {
+        await program.methods
+          .setRefundPolicy(new anchor.BN(-1))
+          .accounts({
+            event: eventPda,
+            eventAuthority: eventAuthority.publicKey,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("RefundPolicyLocked");
+      }
+    });
+
+    // HAPPY PATH: Refunds are allowed once the event is canceled
+    it("Successfully refunds after cancellation", async () => {
+      await program.methods
+        .cancelEvent()
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const { purchasePrice } = await program.account.ticket.fetch(ticketPda);
+      const balanceBefore = await provider.connection.getBalance(buyer1.publicKey);
+
+      await refundTicket(getRefundPoolPda(eventPda)[0]);
+
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      expect(ticketAccount.refunded).to.be.true;
+      expect(await provider.connection.getBalance(buyer1.publicKey)).to.equal(
+        balanceBefore + purchasePrice.toNumber()
+      );
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: vault rent-exempt reserve
+  // ============================================================================
+  describe("vault reserve", () => {
+    const reserveEventId = 530;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, reserveEventId);
+    const [vaultPda] = getVaultPda(eventPda);
+
+    // HAPPY PATH: New events start with a rent-exempt vault
+    it("Successfully funds the vault reserve at event creation", async () => {
+      await program.methods
+        .initializeEvent(reserveEventId, ticketPrice, 5, "Reserve", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          vault: vaultPda,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const reserve = await provider.connection.getMinimumBalanceForRentExemption(0);
+      expect(await provider.connection.getBalance(vaultPda)).to.equal(reserve);
+    });
+
+    // UNHAPPY PATH: Withdrawals cannot dip into the reserve
+    it("Fails to withdraw more than the funds above the reserve", async () => {
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, 0)[0],
+          vault: vaultPda,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const now = Math.floor(Date.now() / 1000);
+      await program.methods
+        .setPayoutSchedule(new anchor.BN(now - 60), new anchor.BN(0))
+        .accounts({
+          event: eventPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+      await program.methods
+        .finalizeEvent()
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          vault: vaultPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      try {
+        await program.methods
+          .withdrawProceeds(ticketPrice.addn(1))
+          .accounts({
+            event: eventPda,
+            authorityConfig: null,
+            vault: vaultPda,
+            destination: eventAuthority.publicKey,
+            eventAuthority: eventAuthority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("InsufficientVaultFunds");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: set_metadata_uri instruction
+  // ============================================================================
+  describe("set_metadata_uri", () => {
+    const metadataEventId = 540;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, metadataEventId);
+
+    // HAPPY PATH: Organizer links off-chain event metadata
+    it("Successfully sets the metadata URI", async () => {
+      const uri = "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+
+      await program.methods
+        .initializeEvent(metadataEventId, ticketPrice, 5, "Rich Event", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .setMetadataUri(uri)
+        .accounts({
+          event: eventPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const metadataAccount = await program.account.eventMetadata.fetch(
+        getEventMetadataPda(eventPda)[0]
+      );
+      expect(metadataAccount.metadataUri).to.equal(uri);
+    });
+
+    // UNHAPPY PATH: Overlong URIs are rejected
+    it("Fails to set a metadata URI over 200 characters", async () => {
+      try {
+        await program.methods
+          .setMetadataUri("ar://" + "a".repeat(200))
+          .accounts({
+            event: eventPda,
+            eventAuthority: eventAuthority.publicKey,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("MetadataUriTooLong");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: EventRegistry
+  // ============================================================================
+  describe("event registry", () => {
+    // HAPPY PATH: New events are appended to the current registry page
+    it("Successfully registers a new event in the registry", async () => {
+      const registryEventId = 550;
+      const [eventPda] = getEventPda(eventAuthority.publicKey, registryEventId);
+      const [registryPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("registry")],
+        program.programId
+      );
+      const registryPage = await getRegistryPagePda();
+      const countBefore = (
+        await program.account.eventRegistry.fetch(registryPda)
+      ).eventCount.toNumber();
+
+      await program.methods
+        .initializeEvent(registryEventId, ticketPrice, 5, "Listed", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const registry = await program.account.eventRegistry.fetch(registryPda);
+      expect(registry.eventCount.toNumber()).to.equal(countBefore + 1);
+
+      const page = await program.account.eventRegistryPage.fetch(registryPage);
+      expect(page.page.toNumber()).to.equal(Math.floor(countBefore / 100));
+      expect(page.events[page.events.length - 1].toString()).to.equal(
+        eventPda.toString()
+      );
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: fixed-layout Event filters
+  // ============================================================================
+  describe("event memcmp filters", () => {
+    // HAPPY PATH: Events can be found by authority and canceled flag
+    it("Successfully filters events by authority and canceled flag", async () => {
+      const canceledEvents = await program.account.event.all([
+        { memcmp: { offset: 8, bytes: eventAuthority.publicKey.toBase58() } },
+        { memcmp: { offset: 890, bytes: anchor.utils.bytes.bs58.encode([1]) } },
+      ]);
+
+      expect(canceledEvents.length).to.be.greaterThan(0);
+      for (const { account } of canceledEvents) {
+        expect(account.eventAuthority.toString()).to.equal(
+          eventAuthority.publicKey.toString()
+        );
+        expect(account.canceled).to.equal(1);
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: Ticket v2 layout / migrate_ticket instruction
+  // ============================================================================
+  describe("ticket v2 layout", () => {
+    const layoutEventId = 560;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, layoutEventId);
+    const [ticketPda] = getTicketPda(eventPda, 0);
+
+    // HAPPY PATH: New tickets record what the buyer paid and when
+    it("Successfully records the purchase price on new tickets", async () => {
+      await program.methods
+        .initializeEvent(layoutEventId, ticketPrice, 5, "Layout", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const ticket = await program.account.ticket.fetch(ticketPda);
+      expect(ticket.version).to.equal(7);
+      expect(ticket.purchasePrice.toString()).to.equal(ticketPrice.toString());
+      expect(ticket.purchasedAt.toNumber()).to.be.greaterThan(0);
+    });
+
+    // UNHAPPY PATH: Tickets already on the current layout cannot be migrated
+    it("Fails to migrate a ticket that is already on the current layout", async () => {
+      try {
+        await program.methods
+          .migrateTicket()
+          .accounts({
+            event: eventPda,
+            ticket: ticketPda,
+            payer: buyer1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("TicketAlreadyMigrated");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: decrease_supply instruction
+  // ============================================================================
+  describe("decrease_supply", () => {
+    const supplyEventId = 570;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, supplyEventId);
+
+    const decreaseSupply = (newSupply: number) =>
+      program.methods
+        .decreaseSupply(newSupply)
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+    // HAPPY PATH: Organizer downsizes the venue
+    it("Successfully decreases supply", async () => {
+      await program.methods
+        .initializeEvent(supplyEventId, ticketPrice, 10, "Downsized", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, 0)[0],
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      await decreaseSupply(4);
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.supply).to.equal(4);
+    });
+
+    // UNHAPPY PATH: Supply cannot drop below tickets already sold
+    it("Fails to decrease supply below tickets sold", async () => {
+      try {
+        await decreaseSupply(0);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("SupplyBelowSold");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: emergency_cancel instruction
+  // ============================================================================
+  describe("emergency_cancel", () => {
+    const fraudEventId = 580;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, fraudEventId);
+
+    // UNHAPPY PATH: Only the config admin has the kill switch
+    it("Fails to emergency cancel as a non-admin", async () => {
+      await program.methods
+        .initializeEvent(fraudEventId, ticketPrice, 5, "Suspicious", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      try {
+        await program.methods
+          .emergencyCancel()
+          .accounts({
+            config: getConfigPda()[0],
+            event: eventPda,
+            admin: eventAuthority.publicKey,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedAdmin");
+      }
+    });
+
+    // HAPPY PATH: Admin cancels the event and freezes withdrawals
+    it("Successfully emergency cancels and freezes withdrawals", async () => {
+      await program.methods
+        .emergencyCancel()
+        .accounts({
+          config: getConfigPda()[0],
+          event: eventPda,
+          admin: provider.wallet.publicKey,
+        })
+        .rpc();
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.canceled).to.equal(1);
+      expect(eventAccount.withdrawalsFrozen).to.equal(1);
+
+      try {
+        await program.methods
+          .withdrawProceeds(new anchor.BN(0))
+          .accounts({
+            event: eventPda,
+            authorityConfig: null,
+            vault: getVaultPda(eventPda)[0],
+            destination: eventAuthority.publicKey,
+            eventAuthority: eventAuthority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("WithdrawalsFrozen");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: organizer bonds
+  // ============================================================================
+  describe("organizer bond", () => {
+    const bondedOrganizer = Keypair.generate();
+    const bondLamports = new anchor.BN(LAMPORTS_PER_SOL);
+    const [organizerPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("organizer"), bondedOrganizer.publicKey.toBuffer()],
+      program.programId
+    );
+    const [bondPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("bond"), bondedOrganizer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const setBond = (lamports: anchor.BN) =>
+      program.methods
+        .setOrganizerBond(lamports)
+        .accounts({
+          config: getConfigPda()[0],
+          admin: provider.wallet.publicKey,
+        })
+        .rpc();
+
+    before(async () => {
+      await airdrop(bondedOrganizer.publicKey, 5);
+      await setBond(bondLamports);
+    });
+
+    after(async () => {
+      await setBond(new anchor.BN(0));
+    });
+
+    // HAPPY PATH: Registration locks the configured bond
+    it("Successfully locks the bond at registration", async () => {
+      await program.methods
+        .registerOrganizer()
+        .accounts({
+          organizerRegistry: organizerPda,
+          bond: bondPda,
+          organizer: bondedOrganizer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bondedOrganizer])
+        .rpc();
+
+      const registry = await program.account.organizerRegistry.fetch(organizerPda);
+      expect(registry.bond.toString()).to.equal(bondLamports.toString());
+      expect(await provider.connection.getBalance(bondPda)).to.equal(
+        bondLamports.toNumber()
+      );
+    });
+
+    // UNHAPPY PATH: Unbonded organizers cannot create events
+    it("Fails to create an event without a bonded registry", async () => {
+      const [eventPda] = getEventPda(eventAuthority.publicKey, 590);
+
+      try {
+        await program.methods
+          .initializeEvent(590, ticketPrice, 5, "Unbonded", eventDate, true, null, false, null)
+          .accounts({
+            event: eventPda,
+            registryPage: await getRegistryPagePda(),
+            eventAuthority: eventAuthority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("OrganizerNotBonded");
+      }
+    });
+
+    // UNHAPPY PATH: The bond stays locked while a bonded event is open
+    it("Fails to release the bond while an event is open", async () => {
+      const [eventPda] = getEventPda(bondedOrganizer.publicKey, 591);
+
+      await program.methods
+        .initializeEvent(591, ticketPrice, 5, "Bonded", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          organizerRegistry: organizerPda,
+          eventAuthority: bondedOrganizer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bondedOrganizer])
+        .rpc();
+
+      try {
+        await program.methods
+          .releaseBond()
+          .accounts({
+            organizerRegistry: organizerPda,
+            bond: bondPda,
+            organizer: bondedOrganizer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([bondedOrganizer])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("BondLocked");
+      }
+    });
+
+    // UNHAPPY PATH: Only canceled events can drop their hold early
+    it("Fails to release the bond hold of an open event", async () => {
+      try {
+        await program.methods
+          .releaseEventBond()
+          .accounts({
+            event: getEventPda(bondedOrganizer.publicKey, 591)[0],
+            organizerRegistry: organizerPda,
+          })
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("BondLocked");
+      }
+    });
+
+    // HAPPY PATH: A canceled event with every holder refunded stops holding the bond
+    it("Successfully releases a canceled event's hold on the bond", async () => {
+      const [eventPda] = getEventPda(bondedOrganizer.publicKey, 592);
+
+      await program.methods
+        .initializeEvent(592, ticketPrice, 5, "Scrapped", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          organizerRegistry: organizerPda,
+          eventAuthority: bondedOrganizer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bondedOrganizer])
+        .rpc();
+      await program.methods
+        .cancelEvent()
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          eventAuthority: bondedOrganizer.publicKey,
+        })
+        .signers([bondedOrganizer])
+        .rpc();
+
+      await program.methods
+        .releaseEventBond()
+        .accounts({ event: eventPda, organizerRegistry: organizerPda })
+        .rpc();
+
+      const registry = await program.account.organizerRegistry.fetch(organizerPda);
+      expect(registry.openEvents).to.equal(1);
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.bondOrganizer.equals(PublicKey.default)).to.be.true;
+    });
+
+    // HAPPY PATH: Finalizing the event releases the bond
+    it("Successfully releases the bond after the event finalizes", async () => {
+      const [eventPda] = getEventPda(bondedOrganizer.publicKey, 591);
+
+      const now = Math.floor(Date.now() / 1000);
+      await program.methods
+        .setPayoutSchedule(new anchor.BN(now - 60), new anchor.BN(0))
+        .accounts({
+          event: eventPda,
+          eventAuthority: bondedOrganizer.publicKey,
+        })
+        .signers([bondedOrganizer])
+        .rpc();
+
+      await program.methods
+        .finalizeEvent()
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          vault: getVaultPda(eventPda)[0],
+          organizerRegistry: organizerPda,
+          eventAuthority: bondedOrganizer.publicKey,
+        })
+        .signers([bondedOrganizer])
+        .rpc();
+
+      await program.methods
+        .releaseBond()
+        .accounts({
+          organizerRegistry: organizerPda,
+          bond: bondPda,
+          organizer: bondedOrganizer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bondedOrganizer])
+        .rpc();
+
+      const registry = await program.account.organizerRegistry.fetch(organizerPda);
+      expect(registry.bond.toNumber()).to.equal(0);
+      expect(await provider.connection.getBalance(bondPda)).to.equal(0);
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: slash_organizer instruction
+  // ============================================================================
+  describe("slash_organizer", () => {
+    const rogueOrganizer = Keypair.generate();
+    const bondLamports = new anchor.BN(LAMPORTS_PER_SOL);
+    const [organizerPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("organizer"), rogueOrganizer.publicKey.toBuffer()],
+      program.programId
+    );
+    const [bondPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("bond"), rogueOrganizer.publicKey.toBuffer()],
+      program.programId
+    );
+    const [eventPda] = getEventPda(rogueOrganizer.publicKey, 600);
+    const [vaultPda] = getVaultPda(eventPda);
+
+    const setBond = (lamports: anchor.BN) =>
+      program.methods
+        .setOrganizerBond(lamports)
+        .accounts({
+          config: getConfigPda()[0],
+          admin: provider.wallet.publicKey,
+        })
+        .rpc();
+
+    const slash = (authority: Keypair | null) => {
+      const builder = program.methods.slashOrganizer(bondLamports).accounts({
+        config: getConfigPda()[0],
+        event: eventPda,
+        organizerRegistry: organizerPda,
+        bond: bondPda,
+        vault: vaultPda,
+        authority: authority ? authority.publicKey : provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      });
+      return authority ? builder.signers([authority]).rpc() : builder.rpc();
+    };
+
+    before(async () => {
+      await airdrop(rogueOrganizer.publicKey, 5);
+      await setBond(bondLamports);
+
+      await program.methods
+        .registerOrganizer()
+        .accounts({
+          organizerRegistry: organizerPda,
+          bond: bondPda,
+          organizer: rogueOrganizer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([rogueOrganizer])
+        .rpc();
+
+      await program.methods
+        .initializeEvent(600, ticketPrice, 5, "No-Show", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          organizerRegistry: organizerPda,
+          eventAuthority: rogueOrganizer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([rogueOrganizer])
+        .rpc();
+
+      await program.methods
+        .emergencyCancel()
+        .accounts({
+          config: getConfigPda()[0],
+          event: eventPda,
+          admin: provider.wallet.publicKey,
+        })
+        .rpc();
+    });
+
+    after(async () => {
+      await setBond(new anchor.BN(0));
+    });
+
+    // UNHAPPY PATH: Only the admin can slash a canceled event
+    it("Fails to slash a canceled event as a non-admin", async () => {
+      try {
+        await slash(buyer1);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedAdmin");
+      }
+    });
+
+    // HAPPY PATH: The bond moves into the vault to fund refunds
+    it("Successfully slashes the bond into the event vault", async () => {
+      const vaultBefore = await provider.connection.getBalance(vaultPda);
+
+      await slash(null);
+
+      expect(await provider.connection.getBalance(vaultPda)).to.equal(
+        vaultBefore + bondLamports.toNumber()
+      );
+      const registry = await program.account.organizerRegistry.fetch(organizerPda);
+      expect(registry.bond.toNumber()).to.equal(0);
+      expect(registry.openEvents).to.equal(0);
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.bondOrganizer.equals(PublicKey.default)).to.be.true;
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: check-in on canceled events
+  // ============================================================================
+  describe("check_in after cancellation", () => {
+    // UNHAPPY PATH: Tickets of a canceled event stay refundable
+    it("Fails to check in a ticket of a canceled event", async () => {
+      const [eventPda] = getEventPda(eventAuthority.publicKey, 610);
+      const [ticketPda] = getTicketPda(eventPda, 0);
+
+      await program.methods
+        .initializeEvent(610, ticketPrice, 5, "Called Off", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      await program.methods
+        .cancelEvent()
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      try {
+        await program.methods
+          .checkIn(nextScanNonce())
+          .accounts({
+            event: eventPda,
+            ticket: ticketPda,
+            operator: eventAuthority.publicKey,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("EventCanceled");
+      }
+
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      expect(ticketAccount.checkedInCount).to.equal(0);
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: refunds after the event has ended
+  // ============================================================================
+  describe("refund after end_time", () => {
+    // UNHAPPY PATH: The organizer cannot refund once the event is over
+    it("Fails to refund an un-canceled event after it ended", async () => {
+      const [eventPda] = getEventPda(eventAuthority.publicKey, 620);
+      const [vaultPda] = getVaultPda(eventPda);
+      const [ticketPda] = getTicketPda(eventPda, 0);
+
+      await program.methods
+        .initializeEvent(620, ticketPrice, 5, "Over", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const now = Math.floor(Date.now() / 1000);
+      await program.methods
+        .setPayoutSchedule(new anchor.BN(now - 60), new anchor.BN(3 * 86_400))
+        .accounts({
+          event: eventPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+      await openRefundWindow(eventPda);
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      try {
+        await program.methods
+          .refund()
+          .accounts({
+            event: eventPda,
+            ticket: ticketPda,
+            vault: vaultPda,
+            ticketOwner: buyer1.publicKey,
+            eventAuthority: eventAuthority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("EventEnded");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: SPL-currency events
+  // ============================================================================
+  describe("spl currency", () => {
+    const splEventId = 630;
+    const splPrice = new anchor.BN(5_000_000);
+    const [eventPda] = getEventPda(eventAuthority.publicKey, splEventId);
+    const [vaultPda] = getVaultPda(eventPda);
+    const [ticketPda] = getTicketPda(eventPda, 0);
+    let currencyMint: PublicKey;
+    let vaultTokenAccount: PublicKey;
+    let buyerTokenAccount: PublicKey;
+    let buyer2TokenAccount: PublicKey;
+
+    before(async () => {
+      currencyMint = await createMint(
+        provider.connection,
+        eventAuthority,
+        eventAuthority.publicKey,
+        null,
+        6
+      );
+
+      await program.methods
+        .initializeEvent(splEventId, splPrice, 5, "Stablecoin Gig", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          currencyMint,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      vaultTokenAccount = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          eventAuthority,
+          currencyMint,
+          vaultPda,
+          true
+        )
+      ).address;
+      buyerTokenAccount = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          eventAuthority,
+          currencyMint,
+          buyer1.publicKey
+        )
+      ).address;
+      await mintTo(
+        provider.connection,
+        eventAuthority,
+        currencyMint,
+        buyerTokenAccount,
+        eventAuthority,
+        splPrice.toNumber()
+      );
+    });
+
+    // UNHAPPY PATH: SPL events cannot be paid in lamports
+    it("Fails to buy an SPL ticket without token accounts", async () => {
+      try {
+        await program.methods
+          .mintTicket()
+          .accounts({
+            event: eventPda,
+            ticket: ticketPda,
+            vault: vaultPda,
+            buyer: buyer1.publicKey,
+            payer: buyer1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("CurrencyAccountsMissing");
+      }
+    });
+
+    // HAPPY PATH: The ticket is paid in the event's token
+    it("Successfully buys a ticket in the event currency", async () => {
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          buyerTokenAccount,
+          vaultTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.currency.toString()).to.equal(currencyMint.toString());
+      const vaultTokens = await getAccount(provider.connection, vaultTokenAccount);
+      expect(vaultTokens.amount.toString()).to.equal(splPrice.toString());
+    });
+
+    // HAPPY PATH: A second holder buys in tokens, to claim a refund later
+    it("Successfully buys a second ticket in the event currency", async () => {
+      buyer2TokenAccount = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          eventAuthority,
+          currencyMint,
+          buyer2.publicKey
+        )
+      ).address;
+      await mintTo(
+        provider.connection,
+        eventAuthority,
+        currencyMint,
+        buyer2TokenAccount,
+        eventAuthority,
+        splPrice.toNumber()
+      );
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, 1)[0],
+          vault: vaultPda,
+          buyerTokenAccount: buyer2TokenAccount,
+          vaultTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          buyer: buyer2.publicKey,
+          payer: buyer2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer2])
+        .rpc();
+
+      const buyerTokens = await getAccount(provider.connection, buyer2TokenAccount);
+      expect(buyerTokens.amount.toString()).to.equal("0");
+    });
+
+    // HAPPY PATH: Revoking an SPL ticket refunds its holder in tokens
+    it("Successfully revokes an SPL ticket with a token refund", async () => {
+      const [revokedTicketPda] = getTicketPda(eventPda, 2);
+      await mintTo(
+        provider.connection,
+        eventAuthority,
+        currencyMint,
+        buyer2TokenAccount,
+        eventAuthority,
+        splPrice.toNumber()
+      );
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: revokedTicketPda,
+          vault: vaultPda,
+          buyerTokenAccount: buyer2TokenAccount,
+          vaultTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          buyer: buyer2.publicKey,
+          payer: buyer2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer2])
+        .rpc();
+
+      await program.methods
+        .revokeTicket()
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          ticket: revokedTicketPda,
+          vault: vaultPda,
+          ticketOwner: buyer2.publicKey,
+          vaultTokenAccount,
+          ownerTokenAccount: buyer2TokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const buyerTokens = await getAccount(provider.connection, buyer2TokenAccount);
+      expect(buyerTokens.amount.toString()).to.equal(splPrice.toString());
+      const ticketAccount = await program.account.ticket.fetch(revokedTicketPda);
+      expect(ticketAccount.revoked).to.be.true;
+    });
+
+    // HAPPY PATH: Refunds are paid back in the same token
+    it("Successfully refunds an SPL ticket in tokens", async () => {
+      await program.methods
+        .cancelEvent()
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .refund()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          ticketOwner: buyer1.publicKey,
+          vaultTokenAccount,
+          ownerTokenAccount: buyerTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const buyerTokens = await getAccount(provider.connection, buyerTokenAccount);
+      expect(buyerTokens.amount.toString()).to.equal(splPrice.toString());
+    });
+
+    // HAPPY PATH: Holders claim their own refund in the same token
+    it("Successfully claims an SPL refund in tokens", async () => {
+      await program.methods
+        .claimRefund()
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, 1)[0],
+          vault: vaultPda,
+          holder: buyer2.publicKey,
+          vaultTokenAccount,
+          holderTokenAccount: buyer2TokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer2])
+        .rpc();
+
+      // On top of the revoked ticket's refund.
+      const buyerTokens = await getAccount(provider.connection, buyer2TokenAccount);
+      expect(buyerTokens.amount.toString()).to.equal(splPrice.muln(2).toString());
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: pending transfers
+  // ============================================================================
+  describe("pending transfers", () => {
+    const pendingEventId = 640;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, pendingEventId);
+    const [ticketPda] = getTicketPda(eventPda, 0);
+    const [pendingTransferPda] = getPendingTransferPda(ticketPda);
+    const recipient = Keypair.generate();
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(pendingEventId, ticketPrice, 5, "Pending Transfers", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      await program.methods
+        .approveTransfer()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          pendingTransfer: pendingTransferPda,
+          recipient: recipient.publicKey,
+          owner: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+    });
+
+    // HAPPY PATH: The offer is visible on-chain and ownership is unchanged
+    it("Records the pending transfer without moving the ticket", async () => {
+      const pending = await program.account.pendingTransfer.fetch(pendingTransferPda);
+      expect(pending.from.toString()).to.equal(buyer1.publicKey.toString());
+      expect(pending.to.toString()).to.equal(recipient.publicKey.toString());
+
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      expect(ticketAccount.owner.toString()).to.equal(buyer1.publicKey.toString());
+    });
+
+    // UNHAPPY PATH: Only the named recipient can accept
+    it("Fails to accept a transfer offered to someone else", async () => {
+      try {
+        await program.methods
+          .acceptTransfer()
+          .accounts({
+            event: eventPda,
+            ticket: ticketPda,
+            pendingTransfer: pendingTransferPda,
+            vault: getVaultPda(eventPda)[0],
+            sender: buyer1.publicKey,
+            recipient: buyer2.publicKey,
+          })
+          .signers([buyer2])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedTransfer");
+      }
+    });
+
+    // HAPPY PATH: The recipient declines and the offer is closed
+    it("Successfully declines a pending transfer", async () => {
+      await program.methods
+        .cancelTransfer()
+        .accounts({
+          ticket: ticketPda,
+          pendingTransfer: pendingTransferPda,
+          sender: buyer1.publicKey,
+          authority: recipient.publicKey,
+        })
+        .signers([recipient])
+        .rpc();
+
+      const pending = await program.account.pendingTransfer.fetchNullable(
+        pendingTransferPda
+      );
+      expect(pending).to.be.null;
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      expect(ticketAccount.owner.toString()).to.equal(buyer1.publicKey.toString());
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: approve_operator / operator_transfer instructions
+  // ============================================================================
+  describe("operator approvals", () => {
+    const operatorEventId = 650;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, operatorEventId);
+    const [ticketPda] = getTicketPda(eventPda, 0);
+    const [approvalPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("operator_approval"), ticketPda.toBuffer()],
+      program.programId
+    );
+    const marketplace = Keypair.generate();
+
+    const operatorTransfer = (operator: Keypair, newOwner: PublicKey) =>
+      program.methods
+        .operatorTransfer()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          approval: approvalPda,
+          vault: getVaultPda(eventPda)[0],
+          approver: buyer1.publicKey,
+          newOwner,
+          operator: operator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([operator])
+        .rpc();
+
+    before(async () => {
+      await airdrop(marketplace.publicKey, 1);
+
+      await program.methods
+        .initializeEvent(operatorEventId, ticketPrice, 5, "Resale Partner", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      await program.methods
+        .approveOperator()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          approval: approvalPda,
+          operator: marketplace.publicKey,
+          owner: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+    });
+
+    // UNHAPPY PATH: Only the approved operator can move the ticket
+    it("Fails to transfer with an unapproved operator", async () => {
+      try {
+        await operatorTransfer(buyer3, buyer3.publicKey);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedOperator");
+      }
+    });
+
+    // HAPPY PATH: The operator transfers the ticket and the approval is consumed
+    it("Successfully transfers a ticket as the approved operator", async () => {
+      await operatorTransfer(marketplace, buyer2.publicKey);
+
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      expect(ticketAccount.owner.toString()).to.equal(buyer2.publicKey.toString());
+      const approval = await program.account.operatorApproval.fetchNullable(
+        approvalPda
+      );
+      expect(approval).to.be.null;
+    });
+
+    // UNHAPPY PATH: The approval cannot be used twice
+    it("Fails to reuse a consumed approval", async () => {
+      try {
+        await operatorTransfer(marketplace, marketplace.publicKey);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("AccountNotInitialized");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: lend_ticket / reclaim_ticket instructions
+  // ============================================================================
+  describe("ticket lending", () => {
+    const lendingEventId = 660;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, lendingEventId);
+    const [ticketPda] = getTicketPda(eventPda, 0);
+
+    const lendTicket = (until: number) =>
+      program.methods
+        .lendTicket(new anchor.BN(until))
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          borrower: buyer2.publicKey,
+          owner: buyer1.publicKey,
+        })
+        .signers([buyer1])
+        .rpc();
+
+    const reclaimTicket = (authority: Keypair) =>
+      program.methods
+        .reclaimTicket()
+        .accounts({ ticket: ticketPda, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(lendingEventId, ticketPrice, 5, "Plus One", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+    });
+
+    // HAPPY PATH: The borrower holds the ticket until the deadline
+    it("Successfully lends a ticket", async () => {
+      await lendTicket(Math.floor(Date.now() / 1000) + 3600);
+
+      const ticket = await program.account.ticket.fetch(ticketPda);
+      expect(ticket.owner.toString()).to.equal(buyer2.publicKey.toString());
+      expect(ticket.lender.toString()).to.equal(buyer1.publicKey.toString());
+    });
+
+    // UNHAPPY PATH: The lender cannot take it back before the deadline
+    it("Fails to reclaim a ticket before the loan expires", async () => {
+      try {
+        await reclaimTicket(buyer1);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("LoanNotExpired");
+      }
+    });
+
+    // UNHAPPY PATH: The borrower cannot pass the ticket on
+    it("Fails to transfer a lent ticket", async () => {
+      try {
+        await program.methods
+          .approveTransfer()
+          .accounts({
+            event: eventPda,
+            ticket: ticketPda,
+            pendingTransfer: getPendingTransferPda(ticketPda)[0],
+            recipient: buyer3.publicKey,
+            owner: buyer2.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer2])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("TicketOnLoan");
+      }
+    });
+
+    // HAPPY PATH: The borrower returns the ticket early
+    it("Successfully returns a lent ticket early", async () => {
+      await reclaimTicket(buyer2);
+
+      const ticket = await program.account.ticket.fetch(ticketPda);
+      expect(ticket.owner.toString()).to.equal(buyer1.publicKey.toString());
+      expect(ticket.lender).to.be.null;
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: mint_with_voucher instruction
+  // ============================================================================
+  describe("mint_with_voucher", () => {
+    const lazyEventId = 670;
+    const voucherPrice = new anchor.BN(0.01 * LAMPORTS_PER_SOL);
+    const [eventPda] = getEventPda(eventAuthority.publicKey, lazyEventId);
+
+    /**
+     * Builds the sale voucher the organizer signs:
+     * event || buyer || price (u64 LE) || expires_at (i64 LE) || purchased (u32 LE)
+     */
+    function saleVoucherMessage(
+      buyer: PublicKey,
+      price: anchor.BN,
+      expiresAt: number,
+      purchased: number
+    ): Buffer {
+      const message = Buffer.alloc(84);
+      eventPda.toBuffer().copy(message, 0);
+      buyer.toBuffer().copy(message, 32);
+      message.writeBigUInt64LE(BigInt(price.toString()), 64);
+      message.writeBigInt64LE(BigInt(expiresAt), 72);
+      message.writeUInt32LE(purchased, 80);
+      return message;
+    }
+
+    const mintWithVoucher = async (ticketId: number, expiresAt: number, purchased: number) => {
+      const verifyIx = Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: eventAuthority.secretKey,
+        message: saleVoucherMessage(buyer1.publicKey, voucherPrice, expiresAt, purchased),
+      });
+
+      return program.methods
+        .mintWithVoucher(voucherPrice, new anchor.BN(expiresAt))
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, ticketId)[0],
+          vault: getVaultPda(eventPda)[0],
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .preInstructions([verifyIx])
+        .signers([buyer1])
+        .rpc();
+    };
+
+    const expiresAt = Math.floor(Date.now() / 1000) + 3600;
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(lazyEventId, ticketPrice, 5, "Invite Only", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // HAPPY PATH: The buyer pays the price named in the voucher
+    it("Successfully mints a ticket at the voucher price", async () => {
+      await mintWithVoucher(0, expiresAt, 0);
+
+      const ticket = await program.account.ticket.fetch(getTicketPda(eventPda, 0)[0]);
+      expect(ticket.owner.toString()).to.equal(buyer1.publicKey.toString());
+      expect(ticket.purchasePrice.toString()).to.equal(voucherPrice.toString());
+    });
+
+    // UNHAPPY PATH: A redeemed voucher cannot be replayed
+    it("Fails to redeem the same voucher twice", async () => {
+      try {
+        await mintWithVoucher(1, expiresAt, 0);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidVoucher");
+      }
+    });
+
+    // UNHAPPY PATH: Expired vouchers are rejected
+    it("Fails to mint with an expired voucher", async () => {
+      try {
+        await mintWithVoucher(1, Math.floor(Date.now() / 1000) - 60, 1);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("VoucherExpired");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: set_sale_hook instruction
+  // ============================================================================
+  describe("sale hooks", () => {
+    const hookEventId = 680;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, hookEventId);
+    const hookProgram = Keypair.generate().publicKey;
+
+    const setSaleHook = (hook: PublicKey | null) =>
+      program.methods
+        .setSaleHook(hook)
+        .accounts({ event: eventPda, eventAuthority: eventAuthority.publicKey })
+        .signers([eventAuthority])
+        .rpc();
+
+    const mintTicket = () =>
+      program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, 0)[0],
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(hookEventId, ticketPrice, 5, "Members Only", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await setSaleHook(hookProgram);
+    });
+
+    // UNHAPPY PATH: The hook program must be passed so it can be called
+    it("Fails to mint without the hook program", async () => {
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.hookProgram.toString()).to.equal(hookProgram.toString());
+
+      try {
+        await mintTicket();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("SaleHookMissing");
+      }
+    });
+
+    // UNHAPPY PATH: Sale paths that cannot call the hook are closed
+    it("Fails to batch-mint a hooked event", async () => {
+      try {
+        await program.methods
+          .mintTickets(1)
+          .accounts({
+            event: eventPda,
+            vault: getVaultPda(eventPda)[0],
+            buyer: buyer1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts([
+            { pubkey: getTicketPda(eventPda, 0)[0], isSigner: false, isWritable: true },
+          ])
+          .signers([buyer1])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("SaleHookNotSupported");
+      }
+    });
+
+    // HAPPY PATH: Clearing the hook reopens normal sales
+    it("Successfully mints after the hook is cleared", async () => {
+      await setSaleHook(null);
+      await mintTicket();
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.sold).to.equal(1);
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: self-CPI events
+  // ============================================================================
+  describe("cpi events", () => {
+    const cpiEventId = 690;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, cpiEventId);
+    const [ticketPda] = getTicketPda(eventPda, 0);
+
+    /**
+     * Decodes the events a transaction recorded through self-CPIs: inner
+     * instructions to this program whose data starts with the event tag
+     */
+    async function cpiEvents(signature: string) {
+      await provider.connection.confirmTransaction(signature, "confirmed");
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const keys = tx.transaction.message.getAccountKeys();
+
+      return tx.meta.innerInstructions
+        .flatMap((inner) => inner.instructions)
+        .filter((ix) => keys.get(ix.programIdIndex).equals(program.programId))
+        .map((ix) => {
+          const data = anchor.utils.bytes.bs58.decode(ix.data);
+          return program.coder.events.decode(
+            anchor.utils.bytes.base64.encode(Buffer.from(data.subarray(8)))
+          );
+        })
+        .filter((event) => event !== null);
+    }
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(cpiEventId, ticketPrice, 5, "Indexed", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // HAPPY PATH: Sales are recorded as TicketMinted inner instructions
+    it("Records TicketMinted through a self-CPI", async () => {
+      const signature = await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const [event] = await cpiEvents(signature);
+      expect(event.name).to.equal("ticketMinted");
+      expect(event.data.ticket.toString()).to.equal(ticketPda.toString());
+      expect(event.data.owner.toString()).to.equal(buyer1.publicKey.toString());
+    });
+
+    // HAPPY PATH: Refunds are recorded as TicketRefunded inner instructions
+    it("Records TicketRefunded through a self-CPI", async () => {
+      await program.methods
+        .cancelEvent()
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const signature = await program.methods
+        .refund()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: getVaultPda(eventPda)[0],
+          refundPool: getRefundPoolPda(eventPda)[0],
+          ticketOwner: buyer1.publicKey,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const [event] = await cpiEvents(signature);
+      expect(event.name).to.equal("ticketRefunded");
+      expect(event.data.amount.toString()).to.equal(ticketPrice.toString());
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: migrate_event / migrate_organizer instructions
+  // ============================================================================
+  describe("account versioning", () => {
+    const versionEventId = 700;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, versionEventId);
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(versionEventId, ticketPrice, 5, "Versioned", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // HAPPY PATH: New events are created on the current layout
+    it("Successfully stamps new events with the current version", async () => {
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.version).to.equal(18);
+    });
+
+    // UNHAPPY PATH: Current-layout events cannot be migrated again
+    it("Fails to migrate an event already on the current layout", async () => {
+      try {
+        await program.methods
+          .migrateEvent()
+          .accounts({
+            account: eventPda,
+            payer: buyer1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("AccountAlreadyMigrated");
+      }
+    });
+
+    // UNHAPPY PATH: Migration only accepts the matching account type
+    it("Fails to migrate an event as an organizer registry", async () => {
+      try {
+        await program.methods
+          .migrateOrganizer()
+          .accounts({
+            account: eventPda,
+            payer: buyer1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("AccountDiscriminatorMismatch");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: update_event instruction
+  // ============================================================================
+  describe("update_event", () => {
+    const updateEventId = 710;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, updateEventId);
+    const [metadataPda] = getEventMetadataPda(eventPda);
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(updateEventId, ticketPrice, 5, "Short Name", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // HAPPY PATH: A name longer than the original allocation grows the account
+    it("Successfully grows the metadata account for a longer name", async () => {
+      const longName = "The Annual Festival of Extremely Long Event Names, ".repeat(3);
+      const sizeBefore = (await provider.connection.getAccountInfo(metadataPda)).data.length;
+
+      await program.methods
+        .updateEvent(longName, null, null)
+        .accounts({
+          event: eventPda,
+          metadata: metadataPda,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const metadata = await program.account.eventMetadata.fetch(metadataPda);
+      expect(metadata.name).to.equal(longName);
+      expect(metadata.date).to.equal(eventDate);
+      const sizeAfter = (await provider.connection.getAccountInfo(metadataPda)).data.length;
+      expect(sizeAfter).to.be.greaterThan(sizeBefore);
+    });
+
+    // UNHAPPY PATH: Only the event authority can update
+    it("Fails to update someone else's event", async () => {
+      try {
+        await program.methods
+          .updateEvent("Hijacked", null, null)
+          .accounts({
+            event: eventPda,
+            metadata: metadataPda,
+            eventAuthority: buyer1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedEventAuthority");
+      }
+    });
+
+    // UNHAPPY PATH: Growing the account is still bounded
+    it("Fails to set a name beyond the update limit", async () => {
+      try {
+        await program.methods
+          .updateEvent("x".repeat(201), null, null)
+          .accounts({
+            event: eventPda,
+            metadata: metadataPda,
+            eventAuthority: eventAuthority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("NameTooLong");
+      }
+    });
+  });
+
+  describe("event sessions", () => {
+    const sessionEventId = 720;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, sessionEventId);
+    const [ticketPda] = getTicketPda(eventPda, 0);
+    const [secondTicketPda] = getTicketPda(eventPda, 1);
+
+    const getSessionPda = (sessionId: number) =>
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("session"),
+          eventPda.toBuffer(),
+          new anchor.BN(sessionId).toArrayLike(Buffer, "le", 2),
+        ],
+        program.programId
+      )[0];
+
+    const getSessionCheckInPda = (sessionPda: PublicKey, ticket = ticketPda) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("session_check_in"), sessionPda.toBuffer(), ticket.toBuffer()],
+        program.programId
+      )[0];
+
+    const createSession = (sessionId: number, start: number, end: number, capacity = 100) =>
+      program.methods
+        .createSession(sessionId, new anchor.BN(start), new anchor.BN(end), capacity)
+        .accounts({
+          event: eventPda,
+          session: getSessionPda(sessionId),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+    const checkInSession = (sessionId: number, ticket = ticketPda) =>
+      program.methods
+        .checkInSession()
+        .accounts({
+          event: eventPda,
+          ticket,
+          session: getSessionPda(sessionId),
+          sessionCheckIn: getSessionCheckInPda(getSessionPda(sessionId), ticket),
+          staff: null,
+          operator: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(sessionEventId, ticketPrice, 5, "Two Day Conference", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: secondTicketPda,
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer2.publicKey,
+          payer: buyer2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer2])
+        .rpc();
+
+      const now = Math.floor(Date.now() / 1000);
+      await createSession(1, now - 3600, now + 3600);
+      await createSession(2, now + 86400, now + 90000);
+      await createSession(3, now - 3600, now + 3600, 1);
+    });
+
+    // HAPPY PATH: A ticket is admitted to a running session
+    it("Successfully checks a ticket in to an open session", async () => {
+      await checkInSession(1);
+
+      const record = await program.account.sessionCheckIn.fetch(
+        getSessionCheckInPda(getSessionPda(1))
+      );
+      expect(record.ticket.toString()).to.equal(ticketPda.toString());
+      expect(record.checkedInBy.toString()).to.equal(eventAuthority.publicKey.toString());
+
+      const session = await program.account.session.fetch(getSessionPda(1));
+      expect(session.checkedIn).to.equal(1);
+    });
+
+    // UNHAPPY PATH: Each ticket enters a session once
+    it("Fails to check in to the same session twice", async () => {
+      try {
+        await checkInSession(1);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("already in use");
+      }
+    });
+
+    // UNHAPPY PATH: Sessions only admit during their window
+    it("Fails to check in to a session that has not started", async () => {
+      try {
+        await checkInSession(2);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("SessionNotOpen");
+      }
+    });
+
+    // UNHAPPY PATH: A full session turns away further tickets
+    it("Fails to check in once the session is at capacity", async () => {
+      await checkInSession(3);
+
+      try {
+        await checkInSession(3, secondTicketPda);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("SessionFull");
+      }
+    });
+  });
+
+  describe("refund_batch", () => {
+    const batchEventId = 730;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, batchEventId);
+    const holders = [buyer1, buyer2];
+    const ticketPdas = holders.map((_, id) => getTicketPda(eventPda, id)[0]);
+
+    const refundBatch = (accounts: PublicKey[]) =>
+      program.methods
+        .refundBatch()
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          vault: getVaultPda(eventPda)[0],
+          refundPool: getRefundPoolPda(eventPda)[0],
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+          accounts.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }))
+        )
+        .signers([eventAuthority])
+        .rpc();
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(batchEventId, ticketPrice, 5, "Rained Out", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      for (const [id, holder] of holders.entries()) {
+        await program.methods
+          .mintTicket()
+          .accounts({
+            event: eventPda,
+            ticket: ticketPdas[id],
+            vault: getVaultPda(eventPda)[0],
+            buyer: holder.publicKey,
+            payer: holder.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([holder])
+          .rpc();
+      }
+
+      await program.methods
+        .cancelEvent()
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // HAPPY PATH: Every ticket in the batch is refunded in one transaction
+    it("Successfully refunds several tickets at once", async () => {
+      const balanceBefore = await provider.connection.getBalance(buyer2.publicKey);
+
+      await refundBatch([
+        ticketPdas[0],
+        buyer1.publicKey,
+        ticketPdas[1],
+        buyer2.publicKey,
+      ]);
+
+      for (const ticketPda of ticketPdas) {
+        const ticket = await program.account.ticket.fetch(ticketPda);
+        expect(ticket.refunded).to.be.true;
+      }
+      const event = await program.account.event.fetch(eventPda);
+      expect(event.refundedCount).to.equal(2);
+      const balanceAfter = await provider.connection.getBalance(buyer2.publicKey);
+      expect(balanceAfter - balanceBefore).to.equal(ticketPrice.toNumber());
+    });
+
+    // UNHAPPY PATH: A ticket cannot be refunded twice
+    it("Fails to refund a batch containing an already refunded ticket", async () => {
+      try {
+        await refundBatch([ticketPdas[0], buyer1.publicKey]);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("AlreadyRefunded");
+      }
+    });
+
+    // UNHAPPY PATH: Every ticket needs its refund recipient alongside it
+    it("Fails with an unpaired ticket account", async () => {
+      try {
+        await refundBatch([ticketPdas[0]]);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("UnpairedRefundAccounts");
+      }
+    });
+  });
+
+  describe("refund pool", () => {
+    const poolEventId = 740;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, poolEventId);
+    const [vaultPda] = getVaultPda(eventPda);
+    const [refundPoolPda] = getRefundPoolPda(eventPda);
+    const [ticketPda] = getTicketPda(eventPda, 0);
+
+    const claimRefund = (refundPool: PublicKey) =>
+      program.methods
+        .claimRefund()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          refundPool,
+          holder: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(poolEventId, ticketPrice, 5, "Called Off", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+    });
+
+    // HAPPY PATH: Cancellation moves the vault's funds into the refund pool
+    it("Successfully moves vault funds into the refund pool on cancel", async () => {
+      const vaultBefore = await provider.connection.getBalance(vaultPda);
+
+      await program.methods
+        .cancelEvent()
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          refundPool: refundPoolPda,
+          vault: vaultPda,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const vaultAfter = await provider.connection.getBalance(vaultPda);
+      expect(vaultBefore - vaultAfter).to.equal(ticketPrice.toNumber());
+      const pool = await program.account.refundPool.fetch(refundPoolPda);
+      expect(pool.deposited.toString()).to.equal(ticketPrice.toString());
+    });
+
+    // UNHAPPY PATH: Another event's pool cannot pay this event's refunds
+    it("Fails to claim from another event's refund pool", async () => {
+      const [otherEventPda] = getEventPda(eventAuthority.publicKey, 730);
+
+      try {
+        await claimRefund(getRefundPoolPda(otherEventPda)[0]);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidRefundPool");
+      }
+    });
+
+    // HAPPY PATH: The holder claims their refund from the pool
+    it("Successfully claims a refund from the pool", async () => {
+      const balanceBefore = await provider.connection.getBalance(buyer1.publicKey);
+
+      await claimRefund(refundPoolPda);
+
+      const pool = await program.account.refundPool.fetch(refundPoolPda);
+      expect(pool.claimed.toString()).to.equal(ticketPrice.toString());
+      const ticket = await program.account.ticket.fetch(ticketPda);
+      expect(ticket.refunded).to.be.true;
+      expect(await provider.connection.getBalance(buyer1.publicKey)).to.be.greaterThan(
+        balanceBefore
+      );
+    });
+  });
+
+  describe("one-per-wallet events", () => {
+    const strictEventId = 750;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, strictEventId);
+
+    const getWalletTicketPda = (owner: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("ticket"), eventPda.toBuffer(), owner.toBuffer()],
+        program.programId
+      )[0];
+
+    const mintTicket = (buyer: Keypair) =>
+      program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: getWalletTicketPda(buyer.publicKey),
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer.publicKey,
+          payer: buyer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer])
+        .rpc();
+
+    // UNHAPPY PATH: Strict events cannot allow transfers
+    it("Fails to create a one-per-wallet event with transferable tickets", async () => {
+      try {
+        await program.methods
+          .initializeEvent(strictEventId, ticketPrice, 5, "No Scalpers", eventDate, true, null, true, null)
+          .accounts({
+            event: eventPda,
+            registryPage: await getRegistryPagePda(),
+            eventAuthority: eventAuthority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("OnePerWalletTransferable");
+      }
+    });
+
+    // HAPPY PATH: The ticket PDA is derived from the buyer's wallet
+    it("Successfully mints a ticket keyed by the buyer", async () => {
+      await program.methods
+        .initializeEvent(strictEventId, ticketPrice, 5, "No Scalpers", eventDate, false, null, true, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await mintTicket(buyer1);
+
+      const ticket = await program.account.ticket.fetch(getWalletTicketPda(buyer1.publicKey));
+      expect(ticket.owner.toString()).to.equal(buyer1.publicKey.toString());
+      expect(ticket.ticketId).to.equal(0);
+      const event = await program.account.event.fetch(eventPda);
+      expect(event.onePerWallet).to.equal(1);
+      expect(event.nextTicketId).to.equal(1);
+    });
+
+    // UNHAPPY PATH: A wallet can only ever buy one ticket
+    it("Fails to mint a second ticket for the same wallet", async () => {
+      try {
+        await mintTicket(buyer1);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("already in use");
+      }
+    });
+
+    // UNHAPPY PATH: Tickets cannot be bought on behalf of another wallet
+    it("Fails to mint a ticket for someone else", async () => {
+      try {
+        await program.methods
+          .mintTicketTo(buyer3.publicKey)
+          .accounts({
+            event: eventPda,
+            ticket: getWalletTicketPda(buyer2.publicKey),
+            vault: getVaultPda(eventPda)[0],
+            buyer: buyer2.publicKey,
+            payer: buyer2.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer2])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("OnePerWalletNotSupported");
+      }
+    });
+  });
+
+  describe("attestation gating", () => {
+    const gatedEventId = 760;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, gatedEventId);
+    const attestor = Keypair.generate();
+
+    const getAttestationPda = (subject: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("attestation"), attestor.publicKey.toBuffer(), subject.toBuffer()],
+        program.programId
+      )[0];
+
+    const mintTicket = (buyer: Keypair, ticketId: number, attestation: PublicKey | null) =>
+      program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, ticketId)[0],
+          vault: getVaultPda(eventPda)[0],
+          attestation,
+          buyer: buyer.publicKey,
+          payer: buyer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer])
+        .rpc();
+
+    before(async () => {
+      await airdrop(attestor.publicKey, 1);
+
+      await program.methods
+        .initializeEvent(gatedEventId, ticketPrice, 5, "21+ Night", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .setAttestor(attestor.publicKey)
+        .accounts({ event: eventPda, eventAuthority: eventAuthority.publicKey })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .issueAttestation(buyer1.publicKey, new anchor.BN(0))
+        .accounts({
+          attestation: getAttestationPda(buyer1.publicKey),
+          attestor: attestor.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([attestor])
+        .rpc();
+    });
+
+    // UNHAPPY PATH: Gated events need an attestation
+    it("Fails to mint without an attestation", async () => {
+      try {
+        await mintTicket(buyer1, 0, null);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("AttestationMissing");
+      }
+    });
+
+    // UNHAPPY PATH: An attestation only vouches for its own subject
+    it("Fails to mint with someone else's attestation", async () => {
+      try {
+        await mintTicket(buyer2, 0, getAttestationPda(buyer1.publicKey));
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidAttestation");
+      }
+    });
+
+    // HAPPY PATH: An attested buyer can mint
+    it("Successfully mints with a valid attestation", async () => {
+      await mintTicket(buyer1, 0, getAttestationPda(buyer1.publicKey));
+
+      const ticket = await program.account.ticket.fetch(getTicketPda(eventPda, 0)[0]);
+      expect(ticket.owner.toString()).to.equal(buyer1.publicKey.toString());
+    });
+  });
+
+  describe("token-gated sales", () => {
+    const gatedEventId = 770;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, gatedEventId);
+    let fanClubMint: PublicKey;
+    let memberTokenAccount: PublicKey;
+    let outsiderTokenAccount: PublicKey;
+
+    const mintTicket = (buyer: Keypair, gateTokenAccount: PublicKey | null) =>
+      program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, 0)[0],
+          vault: getVaultPda(eventPda)[0],
+          gateTokenAccount,
+          buyer: buyer.publicKey,
+          payer: buyer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer])
+        .rpc();
+
+    before(async () => {
+      fanClubMint = await createMint(
+        provider.connection,
+        eventAuthority,
+        eventAuthority.publicKey,
+        null,
+        0
+      );
+      memberTokenAccount = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          eventAuthority,
+          fanClubMint,
+          buyer1.publicKey
+        )
+      ).address;
+      outsiderTokenAccount = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          eventAuthority,
+          fanClubMint,
+          buyer2.publicKey
+        )
+      ).address;
+      await mintTo(
+        provider.connection,
+        eventAuthority,
+        fanClubMint,
+        memberTokenAccount,
+        eventAuthority,
+        10
+      );
+      await mintTo(
+        provider.connection,
+        eventAuthority,
+        fanClubMint,
+        outsiderTokenAccount,
+        eventAuthority,
+        1
+      );
+
+      await program.methods
+        .initializeEvent(gatedEventId, ticketPrice, 5, "Fan Club Presale", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .setTokenGate(fanClubMint, new anchor.BN(5), false)
+        .accounts({ event: eventPda, eventAuthority: eventAuthority.publicKey })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // UNHAPPY PATH: Gated events need a token account
+    it("Fails to mint without a gate token account", async () => {
+      try {
+        await mintTicket(buyer1, null);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("TokenGateProofMissing");
+      }
+    });
+
+    // UNHAPPY PATH: The buyer must hold enough of the gate token
+    it("Fails to mint below the minimum gate balance", async () => {
+      try {
+        await mintTicket(buyer2, outsiderTokenAccount);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("TokenGateBalanceTooLow");
+      }
+    });
+
+    // UNHAPPY PATH: The token account must be the buyer's own
+    it("Fails to mint with someone else's token account", async () => {
+      try {
+        await mintTicket(buyer2, memberTokenAccount);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidTokenGateAccount");
+      }
+    });
+
+    // HAPPY PATH: A fan club member can mint
+    it("Successfully mints holding the gate token", async () => {
+      await mintTicket(buyer1, memberTokenAccount);
+
+      const ticket = await program.account.ticket.fetch(getTicketPda(eventPda, 0)[0]);
+      expect(ticket.owner.toString()).to.equal(buyer1.publicKey.toString());
+    });
+  });
+
+  describe("free events with attendance deposits", () => {
+    const freeEventId = 780;
+    const deposit = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
+    const [eventPda] = getEventPda(eventAuthority.publicKey, freeEventId);
+    const [vaultPda] = getVaultPda(eventPda);
+    const [ticketPda] = getTicketPda(eventPda, 0);
+
+    const checkIn = (depositRecipient: PublicKey) =>
+      program.methods
+        .checkIn(nextScanNonce())
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          depositRecipient,
+          systemProgram: SystemProgram.programId,
+          operator: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(freeEventId, new anchor.BN(0), 5, "Free Meetup", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // HAPPY PATH: Free tickets cost just the deposit
+    it("Successfully charges the deposit for a free ticket", async () => {
+      await program.methods
+        .setAttendanceDeposit(deposit)
+        .accounts({ event: eventPda, eventAuthority: eventAuthority.publicKey })
+        .signers([eventAuthority])
+        .rpc();
+
+      const vaultBefore = await provider.connection.getBalance(vaultPda);
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const vaultAfter = await provider.connection.getBalance(vaultPda);
+      expect(vaultAfter - vaultBefore).to.equal(deposit.toNumber());
+    });
+
+    // UNHAPPY PATH: The deposit is fixed once tickets are out
+    it("Fails to change the deposit after a sale", async () => {
+      try {
+        await program.methods
+          .setAttendanceDeposit(new anchor.BN(0))
+          .accounts({ event: eventPda, eventAuthority: eventAuthority.publicKey })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("DepositLocked");
+      }
+    });
+
+    // UNHAPPY PATH: Deposits cannot be withdrawn before finalization
+    it("Fails to withdraw held deposits", async () => {
+      try {
+        await program.methods
+          .withdrawProceeds(deposit)
+          .accounts({
+            event: eventPda,
+            authorityConfig: null,
+            vault: vaultPda,
+            destination: eventAuthority.publicKey,
+            eventAuthority: eventAuthority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("DepositsHeld");
+      }
+    });
+
+    // UNHAPPY PATH: Only the holder gets the deposit back
+    it("Fails to return the deposit to someone else", async () => {
+      try {
+        await checkIn(buyer2.publicKey);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidDepositRecipient");
+      }
+    });
+
+    // HAPPY PATH: Checking in returns the deposit
+    it("Successfully returns the deposit at check-in", async () => {
+      const before = await provider.connection.getBalance(buyer1.publicKey);
+
+      await checkIn(buyer1.publicKey);
+
+      const after = await provider.connection.getBalance(buyer1.publicKey);
+      expect(after - before).to.equal(deposit.toNumber());
+    });
+  });
+
+  describe("pay-what-you-want pricing", () => {
+    const pwywEventId = 790;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, pwywEventId);
+    const [vaultPda] = getVaultPda(eventPda);
+
+    const mintWithAmount = (ticketId: number, amount: anchor.BN) =>
+      program.methods
+        .mintTicketWithAmount(amount)
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, ticketId)[0],
+          vault: vaultPda,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(pwywEventId, ticketPrice, 5, "Benefit Show", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // UNHAPPY PATH: The organizer has to opt in
+    it("Fails to pay what you want when disabled", async () => {
+      try {
+        await mintWithAmount(0, ticketPrice);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("PayWhatYouWantDisabled");
+      }
+    });
+
+    // UNHAPPY PATH: The ticket price is the floor
+    it("Fails to pay below the ticket price", async () => {
+      await program.methods
+        .setPayWhatYouWant(true)
+        .accounts({ event: eventPda, eventAuthority: eventAuthority.publicKey })
+        .signers([eventAuthority])
+        .rpc();
+
+      try {
+        await mintWithAmount(0, ticketPrice.subn(1));
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("AmountBelowFloor");
+      }
+    });
+
+    // HAPPY PATH: Paying above the price records a donation
+    it("Successfully records the excess as a donation", async () => {
+      const donation = new anchor.BN(0.02 * LAMPORTS_PER_SOL);
+      const vaultBefore = await provider.connection.getBalance(vaultPda);
+
+      await mintWithAmount(0, ticketPrice.add(donation));
+
+      const vaultAfter = await provider.connection.getBalance(vaultPda);
+      expect(vaultAfter - vaultBefore).to.equal(ticketPrice.add(donation).toNumber());
+
+      const ticket = await program.account.ticket.fetch(getTicketPda(eventPda, 0)[0]);
+      expect(ticket.donation.toNumber()).to.equal(donation.toNumber());
+      expect(ticket.purchasePrice.toNumber()).to.equal(ticketPrice.add(donation).toNumber());
+
+      const event = await program.account.event.fetch(eventPda);
+      expect(event.donations.toNumber()).to.equal(donation.toNumber());
+    });
+  });
+
+  describe("payout tranches", () => {
+    const trancheEventId = 800;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, trancheEventId);
+    const [vaultPda] = getVaultPda(eventPda);
+    const [payoutConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("payout_config"), eventPda.toBuffer()],
+      program.programId
+    );
+
+    const setTranches = (tranches: { milestone: any; bps: number }[]) =>
+      program.methods
+        .setPayoutTranches(tranches)
+        .accounts({
+          event: eventPda,
+          payoutConfig: payoutConfigPda,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+    const withdraw = (amount: anchor.BN) =>
+      program.methods
+        .withdrawProceeds(amount)
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          vault: vaultPda,
+          destination: eventAuthority.publicKey,
+          payoutConfig: payoutConfigPda,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(trancheEventId, ticketPrice, 5, "Installments", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // UNHAPPY PATH: Tranches must cover all proceeds
+    it("Fails to set tranches that do not add up to 100%", async () => {
+      try {
+        await setTranches([
+          { milestone: { saleStart: {} }, bps: 3000 },
+          { milestone: { finalized: {} }, bps: 6000 },
+        ]);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidPayoutTranches");
+      }
+    });
+
+    // HAPPY PATH: The sale-start tranche is available before the event
+    it("Successfully withdraws the unlocked tranche", async () => {
+      await setTranches([
+        { milestone: { saleStart: {} }, bps: 3000 },
+        { milestone: { finalized: {} }, bps: 7000 },
+      ]);
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, 0)[0],
+          vault: vaultPda,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      await withdraw(ticketPrice.muln(3).divn(10));
+
+      const payoutConfig = await program.account.payoutConfig.fetch(payoutConfigPda);
+      expect(payoutConfig.withdrawn.toNumber()).to.equal(ticketPrice.muln(3).divn(10).toNumber());
+    });
+
+    // UNHAPPY PATH: Later tranches stay locked until their milestone
+    it("Fails to withdraw beyond the unlocked tranches", async () => {
+      try {
+        await withdraw(new anchor.BN(1));
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("PayoutTrancheLocked");
+      }
+    });
+  });
+
+  describe("purchase receipts", () => {
+    const receiptEventId = 810;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, receiptEventId);
+    const [ticketPda] = getTicketPda(eventPda, 0);
+    const [receiptPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("receipt"), ticketPda.toBuffer()],
+      program.programId
+    );
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(receiptEventId, ticketPrice, 5, "Receipts", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // HAPPY PATH: Buying a ticket records a receipt
+    it("Successfully records a receipt for the purchase", async () => {
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          receipt: receiptPda,
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const receipt = await program.account.receipt.fetch(receiptPda);
+      expect(receipt.ticket.toString()).to.equal(ticketPda.toString());
+      expect(receipt.payer.toString()).to.equal(buyer1.publicKey.toString());
+      expect(receipt.amount.toNumber()).to.equal(ticketPrice.toNumber());
+      expect(receipt.fees.referralFee.toNumber()).to.equal(0);
+      expect(receipt.fees.organizerNet.toNumber()).to.equal(ticketPrice.toNumber());
+    });
+
+    // UNHAPPY PATH: Only the payer can close the receipt
+    it("Fails to close someone else's receipt", async () => {
+      try {
+        await program.methods
+          .closeReceipt()
+          .accounts({ receipt: receiptPda, payer: buyer2.publicKey })
+          .signers([buyer2])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedReceiptClose");
+      }
+    });
+
+    // HAPPY PATH: The payer reclaims the receipt rent
+    it("Successfully closes the receipt", async () => {
+      await program.methods
+        .closeReceipt()
+        .accounts({ receipt: receiptPda, payer: buyer1.publicKey })
+        .signers([buyer1])
+        .rpc();
+
+      const info = await provider.connection.getAccountInfo(receiptPda);
+      expect(info).to.be.null;
+
+      // The ticket is unaffected
+      const ticket = await program.account.ticket.fetch(ticketPda);
+      expect(ticket.owner.toString()).to.equal(buyer1.publicKey.toString());
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: set_tier_currency / upgrade_ticket_with_token instructions
+  // ============================================================================
+  describe("multi-currency tier prices", () => {
+    const currencyEventId = 820;
+    const vipPrice = new anchor.BN(0.3 * LAMPORTS_PER_SOL);
+    const [eventPda] = getEventPda(eventAuthority.publicKey, currencyEventId);
+    const [vaultPda] = getVaultPda(eventPda);
+    const [ticketPda] = getTicketPda(eventPda, 0);
+    const [vipTierPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("tier"), eventPda.toBuffer(), Buffer.from([1])],
+      program.programId
+    );
+    let stableMint: PublicKey;
+    let tierCurrencyPda: PublicKey;
+    let buyerTokenAccount: PublicKey;
+    let vaultTokenAccount: PublicKey;
+
+    before(async () => {
+      stableMint = await createMint(
+        provider.connection,
+        eventAuthority,
+        eventAuthority.publicKey,
+        null,
+        6
+      );
+      [tierCurrencyPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("tier_currency"), vipTierPda.toBuffer(), stableMint.toBuffer()],
+        program.programId
+      );
+      buyerTokenAccount = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          eventAuthority,
+          stableMint,
+          buyer1.publicKey
+        )
+      ).address;
+      vaultTokenAccount = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          eventAuthority,
+          stableMint,
+          vaultPda,
+          true
+        )
+      ).address;
+      await mintTo(
+        provider.connection,
+        eventAuthority,
+        stableMint,
+        buyerTokenAccount,
+        eventAuthority,
+        100_000_000
+      );
+
+      await program.methods
+        .initializeEvent(currencyEventId, ticketPrice, 5, "Gala III", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .createTier(1, vipPrice, 2)
+        .accounts({
+          event: eventPda,
+          tier: vipTierPda,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+    });
+
+    // HAPPY PATH: Organizer lists a token price for the VIP tier
+    it("Successfully lists a token price for a tier", async () => {
+      const feedId = Array(32).fill(7);
+
+      await program.methods
+        .setTierCurrency(new anchor.BN(45_000_000), feedId)
+        .accounts({
+          event: eventPda,
+          tier: vipTierPda,
+          mint: stableMint,
+          tierCurrency: tierCurrencyPda,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const tierCurrency = await program.account.tierCurrency.fetch(tierCurrencyPda);
+      expect(tierCurrency.tier.toString()).to.equal(vipTierPda.toString());
+      expect(tierCurrency.decimals).to.equal(6);
+      expect(tierCurrency.price.toNumber()).to.equal(45_000_000);
+      expect(tierCurrency.feedId).to.deep.equal(feedId);
+    });
+
+    // UNHAPPY PATH: Only the organizer can list currencies
+    it("Fails to list a token price as someone else", async () => {
+      try {
+        await program.methods
+          .setTierCurrency(new anchor.BN(1), Array(32).fill(0))
+          .accounts({
+            event: eventPda,
+            tier: vipTierPda,
+            mint: stableMint,
+            tierCurrency: tierCurrencyPda,
+            eventAuthority: buyer2.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer2])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedEventAuthority");
+      }
+    });
+
+    // UNHAPPY PATH: Token payments need a genuine oracle price
+    it("Fails to upgrade with a non-Pyth price account", async () => {
+      try {
+        await program.methods
+          .upgradeTicketWithToken()
+          .accounts({
+            change: {
+              event: eventPda,
+              ticket: ticketPda,
+              toTier: vipTierPda,
+              vault: vaultPda,
+              owner: buyer1.publicKey,
+              systemProgram: SystemProgram.programId,
+            },
+            tierCurrency: tierCurrencyPda,
+            solPriceUpdate: buyer2.publicKey,
+            tokenPriceUpdate: buyer2.publicKey,
+            ownerTokenAccount: buyerTokenAccount,
+            vaultTokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([buyer1])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidPriceFeed");
+      }
+
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      expect(ticketAccount.tier).to.be.null;
+    });
+
+    // UNHAPPY PATH: Token payments come back only once the ticket is refunded
+    it("Fails to refund a token payment of a live ticket", async () => {
+      try {
+        await program.methods
+          .refundTokenPayment()
+          .accounts({
+            event: eventPda,
+            ticket: ticketPda,
+            vault: vaultPda,
+            vaultTokenAccount,
+            recipientTokenAccount: buyerTokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("TicketNotRefunded");
+      }
+
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      expect(ticketAccount.tokenPayment).to.be.null;
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: set_approved_venues instruction
+  // ============================================================================
+  describe("approved resale venues", () => {
+    const venueEventId = 830;
+    const askPrice = new anchor.BN(0.2 * LAMPORTS_PER_SOL);
+    const venue = Keypair.generate();
+    const [eventPda] = getEventPda(eventAuthority.publicKey, venueEventId);
+    const [ticketPda] = getTicketPda(eventPda, 0);
+    const [listingPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("listing"), ticketPda.toBuffer()],
+      program.programId
+    );
+
+    const listTicket = (signers: Keypair[], venueKey: PublicKey | null) =>
+      program.methods
+        .listTicket(askPrice)
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          listing: listingPda,
+          seller: buyer1.publicKey,
+          venue: venueKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers(signers)
+        .rpc();
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(venueEventId, ticketPrice, 5, "Venue Resale", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      await program.methods
+        .setApprovedVenues([venue.publicKey])
+        .accounts({ event: eventPda, eventAuthority: eventAuthority.publicKey })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // UNHAPPY PATH: Listing outside an approved venue is refused
+    it("Fails to list without an approved venue", async () => {
+      try {
+        await listTicket([buyer1], null);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("VenueNotApproved");
+      }
+
+      const outsider = Keypair.generate();
+      try {
+        await listTicket([buyer1, outsider], outsider.publicKey);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("VenueNotApproved");
+      }
+    });
+
+    // HAPPY PATH: The approved venue routes both the listing and the sale
+    it("Successfully resells through an approved venue", async () => {
+      await listTicket([buyer1, venue], venue.publicKey);
+
+      await program.methods
+        .buyListedTicket(askPrice)
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          listing: listingPda,
+          vault: getVaultPda(eventPda)[0],
+          seller: buyer1.publicKey,
+          buyer: buyer2.publicKey,
+          config: null,
+          venue: venue.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer2, venue])
+        .rpc();
+
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      expect(ticketAccount.owner.toString()).to.equal(buyer2.publicKey.toString());
+    });
+
+    // UNHAPPY PATH: The whitelist is bounded
+    it("Fails to approve too many venues", async () => {
+      try {
+        await program.methods
+          .setApprovedVenues(
+            Array.from({ length: 5 }, () => Keypair.generate().publicKey)
+          )
+          .accounts({ event: eventPda, eventAuthority: eventAuthority.publicKey })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("TooManyVenues");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: add_co_organizer / remove_co_organizer instructions
+  // ============================================================================
+  describe("co-organizers", () => {
+    const teamEventId = 840;
+    const CAN_CHECK_IN = 1;
+    const teammate = Keypair.generate();
+    const [eventPda] = getEventPda(eventAuthority.publicKey, teamEventId);
+    const [vaultPda] = getVaultPda(eventPda);
+    const [ticketPda] = getTicketPda(eventPda, 0);
+    const [coOrganizerPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("co_organizer"), eventPda.toBuffer(), teammate.publicKey.toBuffer()],
+      program.programId
+    );
+
+    before(async () => {
+      await airdrop(teammate.publicKey, 1);
+
+      await program.methods
+        .initializeEvent(teamEventId, ticketPrice, 5, "Team Event", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, 1)[0],
+          vault: vaultPda,
+          buyer: buyer2.publicKey,
+          payer: buyer2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer2])
+        .rpc();
+    });
+
+    // UNHAPPY PATH: Unknown permission bits are refused
+    it("Fails to add a co-organizer with invalid permissions", async () => {
+      try {
+        await program.methods
+          .addCoOrganizer(teammate.publicKey, 0x10)
+          .accounts({
+            event: eventPda,
+            authorityConfig: null,
+            coOrganizer: coOrganizerPda,
+            eventAuthority: eventAuthority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidPermissions");
+      }
+    });
+
+    // HAPPY PATH: A co-organizer allowed to check in admits an attendee
+    it("Successfully checks in as a co-organizer", async () => {
+      await program.methods
+        .addCoOrganizer(teammate.publicKey, CAN_CHECK_IN)
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          coOrganizer: coOrganizerPda,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .checkIn(nextScanNonce())
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          coOrganizer: coOrganizerPda,
+          operator: teammate.publicKey,
+        })
+        .signers([teammate])
+        .rpc();
+
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      expect(ticketAccount.checkedInCount).to.equal(1);
+    });
+
+    // HAPPY PATH: The same permission covers batch check-ins
+    it("Successfully batch checks in as a co-organizer", async () => {
+      const [secondTicketPda] = getTicketPda(eventPda, 1);
+      await program.methods
+        .checkInBatch([nextScanNonce()])
+        .accounts({
+          event: eventPda,
+          staff: null,
+          coOrganizer: coOrganizerPda,
+          operator: teammate.publicKey,
+        })
+        .remainingAccounts([
+          { pubkey: secondTicketPda, isSigner: false, isWritable: true },
+        ])
+        .signers([teammate])
+        .rpc();
+
+      const ticketAccount = await program.account.ticket.fetch(secondTicketPda);
+      expect(ticketAccount.checkedInCount).to.equal(1);
+    });
+
+    // UNHAPPY PATH: Ticket attributes need the update permission
+    it("Fails to set ticket attributes without the update permission", async () => {
+      try {
+        await program.methods
+          .setTicketAttributes(Buffer.from([1]))
+          .accounts({
+            event: eventPda,
+            ticket: getTicketPda(eventPda, 1)[0],
+            coOrganizer: coOrganizerPda,
+            eventAuthority: teammate.publicKey,
+          })
+          .signers([teammate])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedEventAuthority");
+      }
+    });
+
+    // UNHAPPY PATH: Permissions are scoped; check-in rights do not allow withdrawals
+    it("Fails to withdraw without the withdraw permission", async () => {
+      try {
+        await program.methods
+          .withdrawProceeds(new anchor.BN(1))
+          .accounts({
+            event: eventPda,
+            authorityConfig: null,
+            coOrganizer: coOrganizerPda,
+            vault: vaultPda,
+            destination: eventAuthority.publicKey,
+            eventAuthority: teammate.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([teammate])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedEventAuthority");
+      }
+    });
+
+    // HAPPY PATH: Removing a co-organizer closes their role account
+    it("Successfully removes a co-organizer", async () => {
+      await program.methods
+        .removeCoOrganizer()
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          coOrganizer: coOrganizerPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const info = await provider.connection.getAccountInfo(coOrganizerPda);
+      expect(info).to.be.null;
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: set_ticket_attributes instruction
+  // ============================================================================
+  describe("ticket attributes", () => {
+    const attributesEventId = 850;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, attributesEventId);
+    const [compTicketPda] = getTicketPda(eventPda, 0);
+    const [soldTicketPda] = getTicketPda(eventPda, 1);
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(attributesEventId, ticketPrice, 5, "Gala Dinner", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // HAPPY PATH: Attributes written when a comp ticket is issued
+    it("Successfully issues a comp ticket with attributes", async () => {
+      await program.methods
+        .issueCompTicket(buyer1.publicKey, Buffer.from("table=4;meal=V"))
+        .accounts({
+          event: eventPda,
+          ticket: compTicketPda,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const ticketAccount = await program.account.ticket.fetch(compTicketPda);
+      expect(Buffer.from(ticketAccount.attributes).toString()).to.equal("table=4;meal=V");
+    });
+
+    // HAPPY PATH: Organizer annotates a sold ticket
+    it("Successfully sets attributes on a sold ticket", async () => {
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: soldTicketPda,
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer2.publicKey,
+          payer: buyer2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer2])
+        .rpc();
+
+      await program.methods
+        .setTicketAttributes(Buffer.from([1, 2, 3]))
+        .accounts({
+          event: eventPda,
+          ticket: soldTicketPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const ticketAccount = await program.account.ticket.fetch(soldTicketPda);
+      expect([...ticketAccount.attributes]).to.deep.equal([1, 2, 3]);
+    });
+
+    // UNHAPPY PATH: Attributes are bounded
+    it("Fails to set oversized attributes", async () => {
+      try {
+        await program.methods
+          .setTicketAttributes(Buffer.alloc(65))
+          .accounts({
+            event: eventPda,
+            ticket: soldTicketPda,
+            eventAuthority: eventAuthority.publicKey,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("AttributesTooLong");
+      }
+    });
+
+    // UNHAPPY PATH: Only the organizer writes attributes
+    it("Fails to set attributes as the ticket owner", async () => {
+      try {
+        await program.methods
+          .setTicketAttributes(Buffer.from([9]))
+          .accounts({
+            event: eventPda,
+            ticket: soldTicketPda,
+            eventAuthority: buyer2.publicKey,
+          })
+          .signers([buyer2])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedEventAuthority");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: scan nonce replay protection
+  // ============================================================================
+  describe("scan nonces", () => {
+    const scanEventId = 860;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, scanEventId);
+    const [ticketPda] = getTicketPda(eventPda, 0);
+    const [otherTicketPda] = getTicketPda(eventPda, 1);
+    let acceptedNonce: anchor.BN;
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(scanEventId, ticketPrice, 5, "Scan Event", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      for (const [ticket, buyer] of [
+        [ticketPda, buyer1],
+        [otherTicketPda, buyer2],
+      ] as [PublicKey, Keypair][]) {
+        await program.methods
+          .mintTicket()
+          .accounts({
+            event: eventPda,
+            ticket,
+            vault: getVaultPda(eventPda)[0],
+            buyer: buyer.publicKey,
+            payer: buyer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer])
+          .rpc();
+      }
+    });
+
+    // HAPPY PATH: A fresh scan admits and records its nonce
+    it("Successfully checks in with a fresh scan nonce", async () => {
+      acceptedNonce = nextScanNonce();
+
+      await program.methods
+        .checkIn(acceptedNonce)
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          operator: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      expect(ticketAccount.scanNonce.toString()).to.equal(acceptedNonce.toString());
+    });
+
+    // UNHAPPY PATH: Replaying the same scan is rejected
+    it("Fails to check in with a replayed scan nonce", async () => {
+      try {
+        await program.methods
+          .checkIn(acceptedNonce)
+          .accounts({
+            event: eventPda,
+            ticket: ticketPda,
+            operator: eventAuthority.publicKey,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("ScanReplayed");
+      }
+    });
+
+    // UNHAPPY PATH: Batches need one nonce per ticket
+    it("Fails a batch with missing scan nonces", async () => {
+      try {
+        await program.methods
+          .checkInBatch([])
+          .accounts({
+            event: eventPda,
+            staff: null,
+            operator: eventAuthority.publicKey,
+          })
+          .remainingAccounts([
+            { pubkey: otherTicketPda, isSigner: false, isWritable: true },
+          ])
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("ScanNonceCountMismatch");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: daily sales analytics
+  // ============================================================================
+  describe("sales stats", () => {
+    const statsEventId = 870;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, statsEventId);
+    const [salesStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("sales_stats"), eventPda.toBuffer()],
+      program.programId
+    );
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(statsEventId, ticketPrice, 5, "Stats Event", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .enableSalesStats()
+        .accounts({
+          event: eventPda,
+          salesStats: salesStatsPda,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // HAPPY PATH: A sale lands in today's bucket
+    it("Successfully records a sale in today's bucket", async () => {
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, 0)[0],
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          salesStats: salesStatsPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const stats = await program.account.salesStats.fetch(salesStatsPda);
+      const today = stats.days.find((day) => day.sold > 0);
+      expect(today.sold).to.equal(1);
+      expect(today.grossSales.toNumber()).to.equal(ticketPrice.toNumber());
+    });
+
+    // UNHAPPY PATH: Sales must pass the stats account once it exists
+    it("Fails to mint without the sales stats account", async () => {
+      try {
+        await program.methods
+          .mintTicket()
+          .accounts({
+            event: eventPda,
+            ticket: getTicketPda(eventPda, 1)[0],
+            vault: getVaultPda(eventPda)[0],
+            buyer: buyer2.publicKey,
+            payer: buyer2.publicKey,
+            salesStats: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer2])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidSalesStats");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: commit-reveal purchase queue
+  // ============================================================================
+  describe("purchase queue", () => {
+    const queueEventId = 880;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, queueEventId);
+    const [queuePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("purchase_queue"), eventPda.toBuffer()],
+      program.programId
+    );
+    const getCommitmentPda = (buyer: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("purchase_commitment"), queuePda.toBuffer(), buyer.toBuffer()],
+        program.programId
+      )[0];
+    const nonce = Buffer.alloc(32, 7);
+    const commitment = (buyer: PublicKey) =>
+      Array.from(createHash("sha256").update(Buffer.concat([buyer.toBuffer(), nonce])).digest());
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(queueEventId, ticketPrice, 5, "Queue Drop", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const now = Math.floor(Date.now() / 1000);
+      await program.methods
+        .createPurchaseQueue(2, new anchor.BN(now + 2), new anchor.BN(now + 600))
+        .accounts({
+          event: eventPda,
+          queue: queuePda,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      for (const buyer of [buyer1, buyer2]) {
+        await program.methods
+          .commitPurchase(commitment(buyer.publicKey))
+          .accounts({
+            event: eventPda,
+            queue: queuePda,
+            commitment: getCommitmentPda(buyer.publicKey),
+            buyer: buyer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer])
+          .rpc();
+      }
+
+      // Wait for commits to close
+      await new Promise((resolve) => setTimeout(resolve, 3000));
+
+      await program.methods
+        .closePurchaseQueue()
+        .accounts({ event: eventPda, queue: queuePda })
+        .rpc();
+    });
+
+    // UNHAPPY PATH: A reveal must match the buyer's commitment
+    it("Fails to reveal with the wrong nonce", async () => {
+      try {
+        await program.methods
+          .revealPurchase(Array.from(Buffer.alloc(32, 8)))
+          .accounts({
+            event: eventPda,
+            queue: queuePda,
+            commitment: getCommitmentPda(buyer2.publicKey),
+            ticket: getTicketPda(eventPda, 0)[0],
+            vault: getVaultPda(eventPda)[0],
+            buyer: buyer2.publicKey,
+            salesStats: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer2])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidPurchaseReveal");
+      }
+    });
+
+    // HAPPY PATH: Revealing issues the ticket held by the commitment
+    it("Successfully reveals a winning commitment", async () => {
+      // Reveal out of commitment order; both positions still win a seat
+      await program.methods
+        .revealPurchase(Array.from(nonce))
+        .accounts({
+          event: eventPda,
+          queue: queuePda,
+          commitment: getCommitmentPda(buyer2.publicKey),
+          ticket: getTicketPda(eventPda, 0)[0],
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer2.publicKey,
+          salesStats: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer2])
+        .rpc();
+
+      const ticketAccount = await program.account.ticket.fetch(getTicketPda(eventPda, 0)[0]);
+      expect(ticketAccount.owner.toString()).to.equal(buyer2.publicKey.toString());
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.sold).to.equal(1);
+      expect(eventAccount.reserved).to.equal(1);
+    });
+
+    // UNHAPPY PATH: Unrevealed commitments can't be reclaimed during the reveal window
+    it("Fails to reclaim before the reveal window closes", async () => {
+      try {
+        await program.methods
+          .reclaimPurchase()
+          .accounts({
+            event: eventPda,
+            queue: queuePda,
+            commitment: getCommitmentPda(buyer1.publicKey),
+            buyer: buyer1.publicKey,
+          })
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("PurchaseRevealOpen");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: sponsored minting
+  // ============================================================================
+  describe("sponsored minting", () => {
+    const sponsoredEventId = 890;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, sponsoredEventId);
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(sponsoredEventId, ticketPrice, 5, "Sponsored Event", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // HAPPY PATH: The sponsor pays rent, the buyer only the ticket price
+    it("Successfully mints with rent paid by a sponsor", async () => {
+      const buyerBefore = await provider.connection.getBalance(buyer1.publicKey);
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, 0)[0],
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1, eventAuthority])
+        .rpc();
+
+      const buyerAfter = await provider.connection.getBalance(buyer1.publicKey);
+      expect(buyerBefore - buyerAfter).to.equal(ticketPrice.toNumber());
+    });
+
+    // UNHAPPY PATH: The sponsor has to sign
+    it("Fails to mint when the sponsor does not sign", async () => {
+      try {
+        await program.methods
+          .mintTicket()
+          .accounts({
+            event: eventPda,
+            ticket: getTicketPda(eventPda, 1)[0],
+            vault: getVaultPda(eventPda)[0],
+            buyer: buyer2.publicKey,
+            payer: eventAuthority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer2])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("Signature verification failed");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: purge_expired instruction
+  // ============================================================================
+  describe("purge_expired", () => {
+    const purgeEventId = 900;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, purgeEventId);
+    const [entryPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("waitlist"),
+        eventPda.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 4),
+      ],
+      program.programId
+    );
+    const cranker = Keypair.generate();
+
+    before(async () => {
+      await airdrop(cranker.publicKey, LAMPORTS_PER_SOL);
+
+      await program.methods
+        .initializeEvent(purgeEventId, ticketPrice, 1, "Purge Event", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, 0)[0],
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      await program.methods
+        .joinWaitlist()
+        .accounts({
+          event: eventPda,
+          entry: entryPda,
+          wallet: buyer2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer2])
+        .rpc();
+    });
+
+    const purge = () =>
+      program.methods
+        .purgeExpired()
+        .accounts({ event: eventPda, cranker: cranker.publicKey })
+        .remainingAccounts([
+          { pubkey: entryPda, isSigner: false, isWritable: true },
+          { pubkey: buyer2.publicKey, isSigner: false, isWritable: true },
+        ])
+        .signers([cranker])
+        .rpc();
+
+    // UNHAPPY PATH: Waitlist entries stay while they can still be claimed
+    it("Fails to purge a waitlist entry of a live event", async () => {
+      try {
+        await purge();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("WaitlistNotClosed");
+      }
+    });
+
+    // HAPPY PATH: Canceling the event lets anyone purge the entry for its rent
+    it("Successfully purges a waitlist entry of a canceled event", async () => {
+      await program.methods
+        .cancelEvent()
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          refundPool: getRefundPoolPda(eventPda)[0],
+          vault: getVaultPda(eventPda)[0],
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const entryLamports = (await provider.connection.getAccountInfo(entryPda)).lamports;
+      const walletBefore = await provider.connection.getBalance(buyer2.publicKey);
+      const crankerBefore = await provider.connection.getBalance(cranker.publicKey);
+      await purge();
+      const walletAfter = await provider.connection.getBalance(buyer2.publicKey);
+      const crankerAfter = await provider.connection.getBalance(cranker.publicKey);
+
+      // The wallet gets its deposit back; the cranker keeps the rent
+      expect(walletAfter - walletBefore).to.equal(ticketPrice.toNumber());
+      expect(crankerAfter - crankerBefore).to.equal(entryLamports - ticketPrice.toNumber());
+      expect(await provider.connection.getAccountInfo(entryPda)).to.be.null;
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: cancel_ticket instruction
+  // ============================================================================
+  describe("cancel_ticket", () => {
+    const cancelTicketEventId = 910;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, cancelTicketEventId);
+    const [ticketPda] = getTicketPda(eventPda, 0);
+    const [vaultPda] = getVaultPda(eventPda);
+
+    const cancelTicket = () =>
+      program.methods
+        .cancelTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          seatMap: null,
+          seatClaim: null,
+          holder: buyer1.publicKey,
+          salesStats: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(cancelTicketEventId, ticketPrice, 1, "Flexible Event", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+    });
+
+    // UNHAPPY PATH: Events don't allow cancellation until the organizer opts in
+    it("Fails to cancel a ticket without a cancellation policy", async () => {
+      try {
+        await cancelTicket();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("CancellationClosed");
+      }
+    });
+
+    // HAPPY PATH: The holder gets the price back less the fee, and the seat returns to sale
+    it("Successfully cancels a ticket for a 10% fee", async () => {
+      await program.methods
+        .setCancellationPolicy(new anchor.BN(Math.floor(Date.now() / 1000) + 3600), 1000)
+        .accounts({
+          event: eventPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const vaultBefore = await provider.connection.getBalance(vaultPda);
+      await cancelTicket();
+      const vaultAfter = await provider.connection.getBalance(vaultPda);
+
+      expect(vaultBefore - vaultAfter).to.equal(ticketPrice.toNumber() * 0.9);
+
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      expect(ticketAccount.refunded).to.be.true;
+
+      // The seat goes back on sale as a reissue; the base supply is unchanged
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.supply).to.equal(1);
+      expect(eventAccount.reissuedSeats).to.equal(1);
+    });
+
+    // UNHAPPY PATH: A ticket can only be returned once
+    it("Fails to cancel a ticket twice", async () => {
+      try {
+        await cancelTicket();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("AlreadyRefunded");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: set_resale_split instruction
+  // ============================================================================
+  describe("set_resale_split", () => {
+    const splitEventId = 920;
+    const askPrice = new anchor.BN(0.2 * LAMPORTS_PER_SOL);
+    const [eventPda] = getEventPda(eventAuthority.publicKey, splitEventId);
+    const [ticketPda] = getTicketPda(eventPda, 0);
+    const [vaultPda] = getVaultPda(eventPda);
+    const [listingPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("listing"), ticketPda.toBuffer()],
+      program.programId
+    );
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(splitEventId, ticketPrice, 5, "Royalty Event", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          vault: vaultPda,
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+    });
+
+    // UNHAPPY PATH: The shares must cover exactly the whole price
+    it("Fails to set a split that doesn't add up to 100%", async () => {
+      try {
+        await program.methods
+          .setResaleSplit(9000, 500, 0)
+          .accounts({
+            event: eventPda,
+            eventAuthority: eventAuthority.publicKey,
+          })
+          .signers([eventAuthority])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidResaleSplit");
+      }
+    });
+
+    // HAPPY PATH: The organizer share of a resale, as listed, lands in the vault
+    it("Successfully pays the organizer share of a resale", async () => {
+      await program.methods
+        .setResaleSplit(9000, 1000, 0)
+        .accounts({
+          event: eventPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .listTicket(askPrice)
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          listing: listingPda,
+          seller: buyer1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const listing = await program.account.listing.fetch(listingPda);
+      expect(listing.organizerBps).to.equal(1000);
+
+      // Raising the organizer share afterwards doesn't touch the open listing
+      await program.methods
+        .setResaleSplit(5000, 5000, 0)
+        .accounts({
+          event: eventPda,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const vaultBefore = await provider.connection.getBalance(vaultPda);
+
+      await program.methods
+        .buyListedTicket(askPrice)
+        .accounts({
+          event: eventPda,
+          ticket: ticketPda,
+          listing: listingPda,
+          vault: vaultPda,
+          seller: buyer1.publicKey,
+          buyer: buyer2.publicKey,
+          config: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer2])
+        .rpc();
+
+      const vaultAfter = await provider.connection.getBalance(vaultPda);
+      expect(vaultAfter - vaultBefore).to.equal(askPrice.toNumber() / 10);
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.resaleOrganizerFees.toNumber()).to.equal(askPrice.toNumber() / 10);
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: vault activity log
+  // ============================================================================
+  describe("transaction log", () => {
+    const logEventId = 930;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, logEventId);
+    const [transactionLogPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("transaction_log"), eventPda.toBuffer()],
+      program.programId
+    );
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(logEventId, ticketPrice, 5, "Logged Event", eventDate, true, null, false, null)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      await program.methods
+        .enableTransactionLog()
+        .accounts({
+          event: eventPda,
+          transactionLog: transactionLogPda,
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // HAPPY PATH: A sale is appended to the log
+    it("Successfully logs a mint into the vault", async () => {
+      await program.methods
+        .mintTicket()
+        .accounts({
+          event: eventPda,
+          ticket: getTicketPda(eventPda, 0)[0],
+          vault: getVaultPda(eventPda)[0],
+          buyer: buyer1.publicKey,
+          payer: buyer1.publicKey,
+          transactionLog: transactionLogPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const log = await program.account.transactionLog.fetch(transactionLogPda);
+      expect(log.count.toNumber()).to.equal(1);
+      expect(log.entries[0].kind).to.equal(1);
+      expect(log.entries[0].actor.toString()).to.equal(buyer1.publicKey.toString());
+      expect(log.entries[0].amount.toNumber()).to.equal(ticketPrice.toNumber());
+    });
+
+    // UNHAPPY PATH: Vault movements must pass the log once it exists
+    it("Fails to mint without the transaction log", async () => {
+      try {
+        await program.methods
+          .mintTicket()
+          .accounts({
+            event: eventPda,
+            ticket: getTicketPda(eventPda, 1)[0],
+            vault: getVaultPda(eventPda)[0],
+            buyer: buyer2.publicKey,
+            payer: buyer2.publicKey,
+            transactionLog: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer2])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidTransactionLog");
+      }
+    });
+
+    // HAPPY PATH: Canceling logs the sweep of the vault into the refund pool
+    it("Successfully logs the vault sweep of a canceled event", async () => {
+      const [refundPoolPda] = getRefundPoolPda(eventPda);
+
+      await program.methods
+        .cancelEvent()
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          refundPool: refundPoolPda,
+          vault: getVaultPda(eventPda)[0],
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+          transactionLog: transactionLogPda,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+      const log = await program.account.transactionLog.fetch(transactionLogPda);
+      expect(log.count.toNumber()).to.equal(2);
+      expect(log.entries[1].kind).to.equal(6);
+      expect(log.entries[1].actor.toString()).to.equal(refundPoolPda.toString());
+      expect(log.entries[1].amount.toNumber()).to.equal(ticketPrice.toNumber());
+    });
+  });
+
+  // ============================================================================
+  // TEST GROUP: increase_supply instruction
+  // ============================================================================
+  describe("increase_supply", () => {
+    const growEventId = 940;
+    const [eventPda] = getEventPda(eventAuthority.publicKey, growEventId);
+
+    const increaseSupply = (newSupply: number) =>
+      program.methods
+        .increaseSupply(newSupply)
+        .accounts({
+          event: eventPda,
+          authorityConfig: null,
+          eventAuthority: eventAuthority.publicKey,
+        })
+        .signers([eventAuthority])
+        .rpc();
+
+    before(async () => {
+      await program.methods
+        .initializeEvent(growEventId, ticketPrice, 5, "Second Stage", eventDate, true, null, false, 8)
+        .accounts({
+          event: eventPda,
+          registryPage: await getRegistryPagePda(),
+          eventAuthority: eventAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eventAuthority])
+        .rpc();
+    });
+
+    // HAPPY PATH: Organizer opens more seats up to the ceiling
+    it("Successfully increases supply up to the max total supply", async () => {
+      await increaseSupply(8);
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      expect(eventAccount.supply).to.equal(8);
+      expect(eventAccount.maxTotalSupply).to.equal(8);
+    });
+
+    // UNHAPPY PATH: The ceiling fixed at creation cannot be exceeded
+    it("Fails to increase supply past the max total supply", async () => {
+      try {
+        await increaseSupply(9);
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.toString()).to.include("MaxTotalSupplyExceeded");
+      }
+    });
   });
 
   // ============================================================================
@@ -1042,7 +10072,7 @@ describe("event_ticketing", () => {
   //    - Happy: Mint last ticket
   //    - Unhappy: Sold out fails
   //
-  // ✅ transfer_ticket:
+  // ✅ approve_transfer / accept_transfer:
   //    - Happy: Transfer ticket successfully
   //    - Unhappy: Unauthorized transfer fails
   //    - Unhappy: Transfer used ticket fails
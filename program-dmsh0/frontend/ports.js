@@ -48,16 +48,48 @@ function getEventPda(programId, eventAuthority, eventId) {
   return pda;
 }
 
+/**
+ * Derives the EventMetadata PDA holding the event's name and date
+ * Seeds: ["event_metadata", event_pda]
+ */
+function getEventMetadataPda(programId, eventPda) {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("event_metadata"), new PublicKey(eventPda).toBuffer()],
+    programId,
+  );
+
+  return pda;
+}
+
 /**
  * Derives the Ticket PDA
  * Seeds: ["ticket", event_pda, ticket_id]
+ * One-per-wallet events use the buyer's pubkey in place of the ticket id
  */
-function getTicketPda(programId, eventPda, ticketId) {
-  const ticketIdBuffer = Buffer.alloc(4);
-  ticketIdBuffer.writeUInt32LE(ticketId);
+function getTicketPda(programId, eventPda, ticketIdOrBuyer) {
+  let ticketSeed;
+  if (typeof ticketIdOrBuyer === "number") {
+    ticketSeed = Buffer.alloc(4);
+    ticketSeed.writeUInt32LE(ticketIdOrBuyer);
+  } else {
+    ticketSeed = new PublicKey(ticketIdOrBuyer).toBuffer();
+  }
 
   const [pda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("ticket"), new PublicKey(eventPda).toBuffer(), ticketIdBuffer],
+    [Buffer.from("ticket"), new PublicKey(eventPda).toBuffer(), ticketSeed],
+    programId,
+  );
+
+  return pda;
+}
+
+/**
+ * Derives the PendingTransfer PDA created when an owner approves a transfer
+ * Seeds: ["pending_transfer", ticket_pda]
+ */
+function getPendingTransferPda(programId, ticketPda) {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("pending_transfer"), new PublicKey(ticketPda).toBuffer()],
     programId,
   );
 
@@ -77,6 +109,32 @@ function getVaultPda(programId, eventPda) {
   return pda;
 }
 
+/**
+ * Derives the EventRegistry page the next created event is appended to
+ * Seeds: ["registry_page", page_index (u64 LE)]
+ * Pages hold 100 events, so the index comes from the registry's event count
+ */
+async function getRegistryPagePda(programId) {
+  const [registryPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("registry")],
+    programId,
+  );
+  const registry = await program.account.eventRegistry.fetchNullable(
+    registryPda,
+  );
+  const eventCount = registry ? registry.eventCount.toNumber() : 0;
+
+  const pageBuffer = Buffer.alloc(8);
+  pageBuffer.writeBigUInt64LE(BigInt(Math.floor(eventCount / 100)));
+
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("registry_page"), pageBuffer],
+    programId,
+  );
+
+  return pda;
+}
+
 /**
  * Derives the Organizer Registry PDA
  * Seeds: ["organizer", wallet_pubkey]
@@ -235,7 +293,11 @@ async function loadEvent(app, data) {
     const eventPda = getEventPda(program.programId, eventAuthority, eventId);
 
     // Fetch the account data from the blockchain
+    // Name and date live in the event's metadata account
     const eventAccount = await program.account.event.fetch(eventPda);
+    const metadata = await program.account.eventMetadata.fetch(
+      getEventMetadataPda(program.programId, eventPda),
+    );
 
     // Convert to plain JavaScript object and send to Elm
     app.ports.eventLoaded.send({
@@ -246,10 +308,10 @@ async function loadEvent(app, data) {
         price: eventAccount.price.toNumber(),
         supply: eventAccount.supply,
         sold: eventAccount.sold,
-        canceled: eventAccount.canceled,
+        canceled: eventAccount.canceled !== 0,
         eventId: eventAccount.eventId,
-        name: eventAccount.name,
-        date: eventAccount.date,
+        name: metadata.name,
+        date: metadata.date,
       },
     });
   } catch (error) {
@@ -270,34 +332,23 @@ async function buyTicket(app, data) {
 
     const eventPda = new PublicKey(eventAddress);
 
-    // Fetch the event account data manually
-    const connection = provider.connection;
-    const accountInfo = await connection.getAccountInfo(eventPda);
-
-    if (!accountInfo) {
-      throw new Error("Event account not found");
-    }
-
-    // Decode manually like we do in loadAllEvents
-    const eventData = accountInfo.data;
-    const accountData = eventData.slice(8); // Skip discriminator
-
-    let offset = 0;
-    offset += 32; // skip event_authority
-    offset += 8; // skip price
-    offset += 4; // skip supply
+    const eventAccount = await program.account.event.fetch(eventPda);
 
-    const sold = accountData.readUInt32LE(offset);
-    const ticketId = sold; // Next ticket ID
+    // Ticket ids are never reused, so the next id is tracked separately
+    // from `sold`; one-per-wallet events key tickets by the buyer instead
+    const buyer = provider.wallet.publicKey;
+    const ticketSeed = eventAccount.onePerWallet
+      ? buyer
+      : eventAccount.nextTicketId;
 
     console.log("Event account info:", {
       eventAddress: eventPda.toString(),
-      sold: sold,
-      ticketId: ticketId,
+      sold: eventAccount.sold,
+      nextTicketId: eventAccount.nextTicketId,
     });
 
     // Derive PDAs
-    const ticketPda = getTicketPda(program.programId, eventPda, ticketId);
+    const ticketPda = getTicketPda(program.programId, eventPda, ticketSeed);
     const vaultPda = getVaultPda(program.programId, eventPda);
 
     console.log("Derived addresses:", {
@@ -312,7 +363,8 @@ async function buyTicket(app, data) {
         event: eventPda,
         ticket: ticketPda,
         vault: vaultPda,
-        buyer: provider.wallet.publicKey,
+        buyer: buyer,
+        payer: buyer,
         systemProgram: SystemProgram.programId,
       })
       .rpc();
@@ -352,77 +404,27 @@ async function loadMyTickets(app) {
       provider.wallet.publicKey.toString(),
     );
 
-    // Fetch all program accounts manually
-    const programId = program.programId;
-    const connection = provider.connection;
-    const accounts = await connection.getProgramAccounts(programId);
-
-    console.log(`Found ${accounts.length} total accounts`);
-
-    // Decode and filter Ticket accounts owned by the current wallet
-    const ticketsData = [];
-    for (const { pubkey, account } of accounts) {
-      try {
-        // Try to decode as Ticket account
-        const data = account.data;
-
-        // Skip if too small to be a Ticket (Ticket is ~78 bytes)
-        if (data.length < 70 || data.length > 100) continue;
-
-        // Decode the account data manually
-        const accountData = data.slice(8); // Skip discriminator
-        let offset = 0;
-
-        const owner = new PublicKey(accountData.slice(offset, offset + 32));
-        offset += 32;
-
-        const event = new PublicKey(accountData.slice(offset, offset + 32));
-        offset += 32;
-
-        const ticketId = accountData.readUInt32LE(offset);
-        offset += 4;
-
-        const isUsed = accountData[offset] === 1;
-        offset += 1;
-
-        const refunded = accountData[offset] === 1;
-
-        // For management page, we need all tickets, not just owned by current wallet
-        // So we'll load all tickets and let Elm filter them
-        {
-          // Fetch the event name for this ticket
-          let eventName = "Unknown Event";
-          try {
-            const eventAccountInfo = await connection.getAccountInfo(event);
-            if (eventAccountInfo) {
-              const eventData = eventAccountInfo.data.slice(8);
-              let eventOffset = 32 + 8 + 4 + 4 + 1 + 4; // skip to name
-              const nameLen = eventData.readUInt32LE(eventOffset);
-              eventOffset += 4;
-              eventName = eventData
-                .slice(eventOffset, eventOffset + nameLen)
-                .toString("utf8");
-            }
-          } catch (e) {
-            console.log("Could not fetch event name:", e.message);
-          }
-
-          ticketsData.push({
-            address: pubkey.toString(),
-            owner: owner.toString(),
-            event: event.toString(),
-            eventName: eventName,
-            ticketId: ticketId,
-            isUsed: isUsed,
-            refunded: refunded,
-          });
-        }
-      } catch (e) {
-        // Not a Ticket account, skip
-        continue;
-      }
+    // Fetch every Ticket account along with the event names
+    const tickets = await program.account.ticket.all();
+    const eventNames = new Map();
+    for (const { account } of await program.account.eventMetadata.all()) {
+      eventNames.set(account.event.toString(), account.name);
     }
 
+    console.log(`Found ${tickets.length} total tickets`);
+
+    // For management page, we need all tickets, not just owned by current wallet
+    // So we'll load all tickets and let Elm filter them
+    const ticketsData = tickets.map(({ publicKey, account }) => ({
+      address: publicKey.toString(),
+      owner: account.owner.toString(),
+      event: account.event.toString(),
+      eventName: eventNames.get(account.event.toString()) || "Unknown Event",
+      ticketId: account.ticketId,
+      isUsed: account.checkedInCount > 0,
+      refunded: account.refunded,
+    }));
+
     console.log(`Found ${ticketsData.length} tickets for this wallet`);
 
     app.ports.myTicketsLoaded.send({
@@ -441,6 +443,8 @@ async function loadMyTickets(app) {
 
 /**
  * Transfers a ticket to a new owner
+ * Transfers are two-step: this approves the recipient, who then accepts
+ * the ticket from their own wallet
  */
 async function transferTicket(app, data) {
   try {
@@ -449,13 +453,18 @@ async function transferTicket(app, data) {
     const ticketPda = new PublicKey(ticketAddress);
     const newOwnerPubkey = new PublicKey(newOwner);
 
-    // Call the transfer_ticket instruction
+    const ticketAccount = await program.account.ticket.fetch(ticketPda);
+
+    // Call the approve_transfer instruction
     const tx = await program.methods
-      .transferTicket()
+      .approveTransfer()
       .accounts({
+        event: ticketAccount.event,
         ticket: ticketPda,
-        currentOwner: provider.wallet.publicKey,
-        newOwner: newOwnerPubkey,
+        pendingTransfer: getPendingTransferPda(program.programId, ticketPda),
+        recipient: newOwnerPubkey,
+        owner: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
       })
       .rpc();
 
@@ -524,27 +533,20 @@ async function checkInTicket(app, data) {
 
     const ticketPda = new PublicKey(ticketAddress);
 
-    // Fetch the ticket account manually to get the event address
-    const connection = provider.connection;
-    const ticketAccountInfo = await connection.getAccountInfo(ticketPda);
+    // Fetch the ticket account to get the event address
+    const ticketAccount = await program.account.ticket.fetch(ticketPda);
 
-    if (!ticketAccountInfo) {
-      throw new Error("Ticket account not found");
-    }
-
-    // Decode manually to get event address
-    const ticketData = ticketAccountInfo.data.slice(8); // Skip discriminator
-    let offset = 0;
-    offset += 32; // skip owner
-    const eventPda = new PublicKey(ticketData.slice(offset, offset + 32));
+    // Each scan must carry a nonce above the ticket's last one, so the
+    // current time in milliseconds serves as a fresh nonce
+    const scanNonce = new anchor.BN(Date.now());
 
     // Call the check_in instruction
     const tx = await program.methods
-      .checkIn()
+      .checkIn(scanNonce)
       .accounts({
-        event: eventPda,
+        event: ticketAccount.event,
         ticket: ticketPda,
-        eventAuthority: provider.wallet.publicKey,
+        operator: provider.wallet.publicKey,
       })
       .rpc();
 
@@ -607,10 +609,22 @@ async function createEvent(app, data) {
     });
 
     // Call initialize_event instruction
+    // Transferable, unseated, unlimited per wallet, no lifetime supply cap
     const tx = await program.methods
-      .initializeEvent(eventIdNum, priceNum, supplyNum, name, date)
+      .initializeEvent(
+        eventIdNum,
+        priceNum,
+        supplyNum,
+        name,
+        date,
+        true,
+        null,
+        false,
+        null,
+      )
       .accounts({
         event: eventPda,
+        registryPage: await getRegistryPagePda(program.programId),
         eventAuthority: provider.wallet.publicKey,
         systemProgram: SystemProgram.programId,
       })
@@ -641,109 +655,29 @@ async function loadAllEvents(app) {
     }
 
     console.log("Loading all events from blockchain...");
-    console.log("Program account:", program.account);
-
-    // Since program.account is undefined, we need to fetch accounts manually
-    // Get all accounts owned by the program
-    const programId = program.programId;
-    const connection = provider.connection;
 
-    // Fetch all program accounts
-    const accounts = await connection.getProgramAccounts(programId);
-
-    console.log(`Found ${accounts.length} accounts`);
-
-    // Decode and filter Event accounts
-    const eventsData = [];
-    for (const { pubkey, account } of accounts) {
-      try {
-        // Try to decode as Event account
-        // Event discriminator is the first 8 bytes
-        const data = account.data;
-
-        console.log(`Account ${pubkey.toString()}: size=${data.length}`);
-
-        // Skip if size doesn't match Event (Events are ~149 bytes)
-        if (data.length < 140 || data.length > 200) {
-          console.log("  -> Size doesn't match Event");
-          continue;
-        }
-
-        // Decode the account data manually using borsh layout
-        console.log("  -> Attempting to decode as Event manually...");
-
-        // Skip the 8-byte discriminator
-        const accountData = data.slice(8);
-
-        // Manual decode based on Event struct
-        let offset = 0;
-        const eventAuthority = new PublicKey(
-          accountData.slice(offset, offset + 32),
-        );
-        offset += 32;
-
-        const price = new anchor.BN(
-          accountData.slice(offset, offset + 8),
-          "le",
-        );
-        offset += 8;
-
-        const supply = accountData.readUInt32LE(offset);
-        offset += 4;
-
-        const sold = accountData.readUInt32LE(offset);
-        offset += 4;
-
-        const canceled = accountData[offset] === 1;
-        offset += 1;
-
-        const eventId = accountData.readUInt32LE(offset);
-        offset += 4;
-
-        const nameLen = accountData.readUInt32LE(offset);
-        offset += 4;
-        const name = accountData
-          .slice(offset, offset + nameLen)
-          .toString("utf8");
-        offset += nameLen;
-
-        const dateLen = accountData.readUInt32LE(offset);
-        offset += 4;
-        const date = accountData
-          .slice(offset, offset + dateLen)
-          .toString("utf8");
-
-        const decoded = {
-          eventAuthority,
-          price,
-          supply,
-          sold,
-          canceled,
-          eventId,
-          name,
-          date,
-        };
-
-        console.log("  -> Successfully decoded Event:", decoded.name);
-
-        eventsData.push({
-          address: pubkey.toString(),
-          eventAuthority: decoded.eventAuthority.toString(),
-          price: decoded.price.toNumber(),
-          supply: decoded.supply,
-          sold: decoded.sold,
-          canceled: decoded.canceled,
-          eventId: decoded.eventId,
-          name: decoded.name,
-          date: decoded.date,
-        });
-      } catch (e) {
-        // Not an Event account, skip
-        console.log(`  -> Not an Event: ${e.message}`);
-        continue;
-      }
+    // Fetch every Event account along with its name and date
+    const events = await program.account.event.all();
+    const metadataByEvent = new Map();
+    for (const { account } of await program.account.eventMetadata.all()) {
+      metadataByEvent.set(account.event.toString(), account);
     }
 
+    const eventsData = events.map(({ publicKey, account }) => {
+      const metadata = metadataByEvent.get(publicKey.toString());
+      return {
+        address: publicKey.toString(),
+        eventAuthority: account.eventAuthority.toString(),
+        price: account.price.toNumber(),
+        supply: account.supply,
+        sold: account.sold,
+        canceled: account.canceled !== 0,
+        eventId: account.eventId,
+        name: metadata ? metadata.name : "",
+        date: metadata ? metadata.date : "",
+      };
+    });
+
     console.log(`Found ${eventsData.length} events`);
 
     app.ports.allEventsLoaded.send({